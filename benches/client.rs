@@ -0,0 +1,35 @@
+use bombay::client::{Client, RequestParameters};
+use bombay::mc::release::CatalogID;
+use bombay::testing::{fixtures, server};
+use criterion::{criterion_group, criterion_main, Criterion};
+
+/// Measure full requests against local fixtures.
+fn endpoint_overhead(c: &mut Criterion) {
+    // Serve fixtures locally, so requests measure the client's overhead
+    // rather than the network.
+    let url = server::serve(fixtures::ALL);
+    let mc = Client::new(url.clone(), format!("{url}/"));
+    let mut group = c.benchmark_group("endpoints");
+
+    group.bench_function("release/get_latest", |b| {
+        b.iter(|| mc.release().get_latest(None).unwrap())
+    });
+
+    group.bench_function("release/get_by_catalog_id", |b| {
+        let catalog_id = CatalogID("MCS1186".to_owned());
+        b.iter(|| mc.release().get_by_catalog_id(&catalog_id).unwrap())
+    });
+
+    group.bench_function("artist/get_all", |b| {
+        b.iter(|| {
+            mc.artist()
+                .get_all(Some(RequestParameters::from_search("whales".to_owned())))
+                .unwrap()
+        })
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, endpoint_overhead);
+criterion_main!(benches);
@@ -0,0 +1,80 @@
+use bombay::client::{Paginated, Wrapped};
+use bombay::mc::artist::{Artist, ArtistListItem};
+use bombay::mc::release::{AnyRelease, Track};
+use bombay::testing::fixtures;
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use serde::Deserialize;
+use serde_json::Value;
+
+/// Release and tracks response, as the client deserializes it.
+#[derive(Deserialize)]
+#[serde(rename_all = "PascalCase")]
+#[allow(dead_code)]
+struct ReleaseWithTracks {
+    release: AnyRelease,
+    tracks: Vec<Track>,
+}
+
+/// Build a page of tracks by repeating the track of the release fixture.
+fn tracks_json(count: usize) -> String {
+    let mut release: Value = serde_json::from_str(fixtures::RELEASE.body).unwrap();
+    let track = release["Tracks"][0].take();
+    serde_json::to_string(&vec![track; count]).unwrap()
+}
+
+/// Deserialize each fixture into the types the client uses for it.
+fn deserialize_fixtures(c: &mut Criterion) {
+    let mut group = c.benchmark_group("fixtures");
+
+    group.bench_function(fixtures::RELEASE.name, |b| {
+        b.iter(|| serde_json::from_str::<ReleaseWithTracks>(black_box(fixtures::RELEASE.body)))
+    });
+
+    group.bench_function(fixtures::LATEST_RELEASES.name, |b| {
+        b.iter(|| {
+            serde_json::from_str::<Paginated<AnyRelease>>(black_box(fixtures::LATEST_RELEASES.body))
+        })
+    });
+
+    group.bench_function(fixtures::ARTISTS.name, |b| {
+        b.iter(|| {
+            serde_json::from_str::<Wrapped<Paginated<Artist>>>(black_box(fixtures::ARTISTS.body))
+        })
+    });
+
+    // List items skip the heavy fields of the same payloads.
+    group.bench_function(format!("{}_slim", fixtures::ARTISTS.name), |b| {
+        b.iter(|| {
+            serde_json::from_str::<Wrapped<Paginated<ArtistListItem>>>(black_box(
+                fixtures::ARTISTS.body,
+            ))
+        })
+    });
+
+    group.finish();
+}
+
+/// Compare deserializing tracks directly with going through a `Value` first.
+fn deserialize_tracks(c: &mut Criterion) {
+    let mut group = c.benchmark_group("tracks");
+
+    for count in [10, 100] {
+        let json = tracks_json(count);
+
+        group.bench_with_input(BenchmarkId::new("direct", count), &json, |b, json| {
+            b.iter(|| serde_json::from_str::<Vec<Track>>(black_box(json)).unwrap())
+        });
+
+        group.bench_with_input(BenchmarkId::new("via_value", count), &json, |b, json| {
+            b.iter(|| {
+                let value = serde_json::from_str::<Value>(black_box(json)).unwrap();
+                serde_json::from_value::<Vec<Track>>(value).unwrap()
+            })
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, deserialize_fixtures, deserialize_tracks);
+criterion_main!(benches);
@@ -0,0 +1,147 @@
+use crate::client::auth::SignInOutcome;
+use crate::client::{Client, Error, SignedIn, SignedOut};
+use std::collections::HashMap;
+
+/// Sign-in that needs another step, like 2FA or a captcha, before the
+/// account can be added to an [`AccountManager`].
+///
+/// Call the outcome's callback with the client, then add the signed-in
+/// client with [`AccountManager::insert`].
+pub struct PendingSignIn {
+    pub email: String,
+    pub client: Client<SignedOut>,
+    pub outcome: SignInOutcome,
+}
+
+/// Signed-in clients for several accounts, keyed by email.
+///
+/// Each account gets its own client and agent, so cookie stores are never
+/// shared between accounts.
+///
+/// Example
+/// ```rust,no_run
+/// use bombay::accounts::AccountManager;
+///
+/// let mut accounts = AccountManager::new();
+/// let pending = accounts
+///     .sign_in("label@example.com".to_owned(), "password".to_owned())
+///     .expect("Could not sign in.");
+///
+/// if pending.is_none() {
+///     accounts.select("label@example.com").expect("Account was not added.");
+///
+///     if let Some(client) = accounts.selected() {
+///         println!("{:?}", client.user().get_info().map(|(_, user)| user.username));
+///     }
+/// }
+/// ```
+#[derive(Debug, Default)]
+pub struct AccountManager {
+    accounts: HashMap<String, Client<SignedIn>>,
+    selected: Option<String>,
+}
+
+impl AccountManager {
+    /// Create an account manager without accounts.
+    pub fn new() -> Self {
+        AccountManager::default()
+    }
+
+    /// Sign in to an account using a new client and add it.
+    ///
+    /// Returns the pending sign-in if another step is needed.
+    pub fn sign_in(
+        &mut self,
+        email: String,
+        password: String,
+    ) -> Result<Option<PendingSignIn>, Error> {
+        self.sign_in_with(Client::default(), email, password)
+    }
+
+    /// Sign in to an account using the given signed-out client and add it.
+    ///
+    /// Use this for clients with non-default API URLs. The client should not
+    /// have been used to sign in to another account.
+    pub fn sign_in_with(
+        &mut self,
+        mut client: Client<SignedOut>,
+        email: String,
+        password: String,
+    ) -> Result<Option<PendingSignIn>, Error> {
+        match client.sign_in(email.clone(), password)? {
+            SignInOutcome::Authenticated(signed_in) => {
+                self.insert(email, signed_in);
+                Ok(None)
+            }
+            outcome => Ok(Some(PendingSignIn {
+                email,
+                client,
+                outcome,
+            })),
+        }
+    }
+
+    /// Add a signed-in client for an account, replacing any existing one.
+    ///
+    /// The first account added is selected.
+    pub fn insert(&mut self, email: String, client: Client<SignedIn>) {
+        if self.selected.is_none() {
+            self.selected = Some(email.clone());
+        }
+        self.accounts.insert(email, client);
+    }
+
+    /// Remove an account, deselecting it if selected.
+    pub fn remove(&mut self, email: &str) -> Option<Client<SignedIn>> {
+        if self.selected.as_deref() == Some(email) {
+            self.selected = None;
+        }
+        self.accounts.remove(email)
+    }
+
+    /// Get the client of an account.
+    pub fn get(&self, email: &str) -> Option<&Client<SignedIn>> {
+        self.accounts.get(email)
+    }
+
+    /// Get the emails of all accounts.
+    pub fn emails(&self) -> impl Iterator<Item = &String> {
+        self.accounts.keys()
+    }
+
+    /// Select the account that [`AccountManager::selected`] returns.
+    pub fn select(&mut self, email: &str) -> Result<(), Error> {
+        if !self.accounts.contains_key(email) {
+            return Err(Error::NotFound("account"));
+        }
+        self.selected = Some(email.to_owned());
+        Ok(())
+    }
+
+    /// Get the client of the selected account.
+    pub fn selected(&self) -> Option<&Client<SignedIn>> {
+        self.selected
+            .as_deref()
+            .and_then(|email| self.accounts.get(email))
+    }
+
+    /// Get the email of the selected account.
+    pub fn selected_email(&self) -> Option<&str> {
+        self.selected.as_deref()
+    }
+
+    /// Get the session cookies of all accounts, keyed by email, to persist them.
+    pub fn sessions(&self) -> HashMap<String, String> {
+        self.accounts
+            .iter()
+            .filter_map(|(email, client)| Some((email.clone(), client.session_cookie()?)))
+            .collect()
+    }
+
+    /// Resume a persisted session of an account using a new client and add it.
+    pub fn restore(&mut self, email: String, cookie: String) -> Result<(), Error> {
+        let client = Client::default().restore_session(cookie)?;
+        self.insert(email, client);
+        Ok(())
+    }
+}
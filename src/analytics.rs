@@ -0,0 +1,254 @@
+use crate::mc::release::Release;
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+use std::collections::{BTreeMap, BTreeSet};
+
+/// Years of activity compared to tell an artist's trend, see [`Trend`].
+const TREND_YEARS: i32 = 2;
+
+/// Periods releases are grouped by over time.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Deserialize, Serialize)]
+pub enum Period {
+    /// Calendar years, like "2022".
+    #[default]
+    Year,
+    /// Calendar months, like "2022-09".
+    Month,
+}
+
+impl Period {
+    /// Get the period of a release, in the release's own time zone.
+    fn of(&self, release: &Release) -> String {
+        let released_at = release.released_at();
+        match self {
+            Period::Year => released_at.format("%Y").to_string(),
+            Period::Month => released_at.format("%Y-%m").to_string(),
+        }
+    }
+}
+
+/// Releases of a primary genre in a period, and their share of the period's
+/// releases with a genre.
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct GenreShare {
+    pub period: String,
+    pub genre: String,
+    pub releases: usize,
+    /// Share between 0 and 1.
+    pub share: f64,
+}
+
+/// Releases of a label in a year.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct LabelYear {
+    pub label: String,
+    pub year: i32,
+    pub releases: usize,
+}
+
+/// Direction of an artist's activity, comparing their releases of the last
+/// two years of the data to the two years before.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Deserialize, Serialize)]
+#[non_exhaustive]
+pub enum Trend {
+    Rising,
+    Steady,
+    Falling,
+    /// No releases in either of those periods, like an artist who stopped
+    /// releasing long before the latest release of the data.
+    Inactive,
+}
+
+/// Releases of an artist over the years, and where they are heading.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct ArtistActivity {
+    pub artist: String,
+    pub releases: usize,
+    pub first_year: i32,
+    pub last_year: i32,
+    /// Releases by year, for years with any.
+    pub releases_per_year: BTreeMap<i32, usize>,
+    pub trend: Trend,
+}
+
+/// Statistics of a catalog over time, like genre share and label output, so
+/// they can be charted or exported as JSON without a dataframe library.
+///
+/// Reports are made from releases already fetched, like all releases of the
+/// catalog, so their scope is the caller's to pick. Dates are taken in each
+/// release's own time zone, see [`Release::released_at`].
+///
+/// Example
+/// ```rust
+/// use bombay::analytics::{Period, Report};
+/// use bombay::mc::release::Release;
+///
+/// # fn example(releases: &[Release]) {
+/// let report = Report::new(releases, Period::Year);
+/// for share in &report.genre_share {
+///     println!("{} {}: {:.0}%", share.period, share.genre, share.share * 100.0);
+/// }
+/// let json = serde_json::to_string(&report).unwrap();
+/// # }
+/// ```
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct Report {
+    pub period: Period,
+    pub releases: usize,
+    pub genre_share: Vec<GenreShare>,
+    pub label_years: Vec<LabelYear>,
+    pub artist_activity: Vec<ArtistActivity>,
+}
+
+impl Report {
+    /// Create a report of releases, with genre share grouped by a period.
+    pub fn new(releases: &[Release], period: Period) -> Self {
+        Report {
+            period,
+            releases: releases.len(),
+            genre_share: genre_share(releases, period),
+            label_years: releases_per_label(releases),
+            artist_activity: artist_activity(releases),
+        }
+    }
+}
+
+/// Get the share of each primary genre in each period, ordered by period,
+/// then by releases, most first.
+///
+/// Releases without a primary genre are left out, of the shares too.
+pub fn genre_share(releases: &[Release], period: Period) -> Vec<GenreShare> {
+    let mut counts: BTreeMap<String, BTreeMap<String, usize>> = BTreeMap::new();
+    for release in releases {
+        if let Some(genre) = genre(release) {
+            *counts
+                .entry(period.of(release))
+                .or_default()
+                .entry(genre.to_owned())
+                .or_default() += 1;
+        }
+    }
+
+    let mut shares = Vec::new();
+    for (period, genres) in counts {
+        let total: usize = genres.values().sum();
+        let mut genres: Vec<(String, usize)> = genres.into_iter().collect();
+        genres.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        shares.extend(genres.into_iter().map(|(genre, releases)| GenreShare {
+            period: period.clone(),
+            genre,
+            releases,
+            share: releases as f64 / total as f64,
+        }));
+    }
+    shares
+}
+
+/// Get the releases of each label in each year, ordered by label, then year.
+///
+/// Releases without a label title are left out.
+pub fn releases_per_label(releases: &[Release]) -> Vec<LabelYear> {
+    let mut counts: BTreeMap<(String, i32), usize> = BTreeMap::new();
+    for release in releases {
+        if let Some(label) = release.brand_title.as_deref().map(str::trim) {
+            if !label.is_empty() {
+                *counts
+                    .entry((label.to_owned(), release.release_year()))
+                    .or_default() += 1;
+            }
+        }
+    }
+
+    counts
+        .into_iter()
+        .map(|((label, year), releases)| LabelYear {
+            label,
+            year,
+            releases,
+        })
+        .collect()
+}
+
+/// Get the activity of each artist credited on releases, ordered by
+/// releases, most first, then by name.
+///
+/// Releases without their artists count for their artists title, like
+/// "Whales feat. Dutch Melrose", as a whole.
+pub fn artist_activity(releases: &[Release]) -> Vec<ArtistActivity> {
+    let mut years: BTreeMap<String, BTreeMap<i32, usize>> = BTreeMap::new();
+    for release in releases {
+        let year = release.release_year();
+        for artist in artists(release)
+            .into_iter()
+            .filter(|artist| !artist.is_empty())
+        {
+            *years.entry(artist).or_default().entry(year).or_default() += 1;
+        }
+    }
+
+    let latest_year = releases.iter().map(Release::release_year).max();
+    let mut activity: Vec<ArtistActivity> = years
+        .into_iter()
+        .map(|(artist, releases_per_year)| {
+            let trend =
+                latest_year.map_or(Trend::Steady, |latest| trend(&releases_per_year, latest));
+            ArtistActivity {
+                artist,
+                releases: releases_per_year.values().sum(),
+                first_year: releases_per_year.keys().next().copied().unwrap_or_default(),
+                last_year: releases_per_year.keys().last().copied().unwrap_or_default(),
+                releases_per_year,
+                trend,
+            }
+        })
+        .collect();
+    activity.sort_by(|a, b| {
+        b.releases
+            .cmp(&a.releases)
+            .then_with(|| a.artist.cmp(&b.artist))
+    });
+    activity
+}
+
+/// Get the primary genre of a release, if it has one.
+fn genre(release: &Release) -> Option<&str> {
+    release
+        .genre_primary
+        .as_deref()
+        .map(str::trim)
+        .filter(|genre| !genre.is_empty())
+}
+
+/// Get the names of the artists of a release, each once.
+fn artists(release: &Release) -> BTreeSet<String> {
+    match release.artists.as_deref() {
+        Some(artists) if !artists.is_empty() => artists
+            .iter()
+            .map(|artist| artist.get_name().to_owned())
+            .collect(),
+        _ => BTreeSet::from([release.artists_title.trim().to_owned()]),
+    }
+}
+
+/// Compare the releases of the last years up to the latest to the years
+/// before.
+fn trend(releases_per_year: &BTreeMap<i32, usize>, latest: i32) -> Trend {
+    let between = |from: i32, to: i32| -> usize {
+        releases_per_year
+            .range(from..=to)
+            .map(|(_, count)| count)
+            .sum()
+    };
+    let recent = between(latest - TREND_YEARS + 1, latest);
+    let before = between(latest - 2 * TREND_YEARS + 1, latest - TREND_YEARS);
+    match recent.cmp(&before) {
+        Ordering::Equal if recent == 0 => Trend::Inactive,
+        Ordering::Greater => Trend::Rising,
+        Ordering::Equal => Trend::Steady,
+        Ordering::Less => Trend::Falling,
+    }
+}
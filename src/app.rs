@@ -0,0 +1,144 @@
+use crate::client::{
+    Client, Error, PlaylistItemsMod, PlaylistItemsOperations, SignedIn, SignedOut, TrackStream,
+};
+use crate::download::{DownloadReport, Downloader};
+use crate::mc::playlist::{PlaylistID, PlaylistItem};
+use crate::mc::release::{CatalogID, Track};
+use crate::mc::util::Codec;
+use crate::progress::{Operation, ProgressEvent, Reporter};
+use std::path::PathBuf;
+use std::sync::mpsc::Sender;
+
+/// Facade offering task-level operations on top of the client endpoints.
+///
+/// The endpoints remain available through [`Monstercat::client`] for
+/// anything not covered here.
+///
+/// There is no operation to follow an artist, as the MC API has no endpoint
+/// for it.
+///
+/// Example
+/// ```rust
+/// use bombay::app::Monstercat;
+/// use bombay::client::Client;
+///
+/// let mc = Monstercat::new(Client::default()); // Without authentication.
+/// let latest_res = mc.client.release().get_latest(None);
+///
+/// if let Ok(latest) = latest_res {
+///   println!("There are {} latest releases.", latest.total);
+/// }
+/// ```
+#[derive(Debug)]
+pub struct Monstercat<ClientAuthState = SignedOut> {
+    pub client: Client<ClientAuthState>,
+    pub download_directory: PathBuf,
+    pub codec: Codec,
+    progress: Option<Sender<ProgressEvent>>,
+}
+
+impl<ClientAuthState> Monstercat<ClientAuthState> {
+    /// Create a facade around a client, downloading MP3s into the client's
+    /// downloads directory, like `~/Downloads`, see [`Client::dirs`]. To
+    /// download somewhere else, use [`Monstercat::set_download_directory`].
+    pub fn new(client: Client<ClientAuthState>) -> Self {
+        Monstercat {
+            download_directory: client.dirs().downloads.clone(),
+            client,
+            codec: Codec::default(),
+            progress: None,
+        }
+    }
+
+    /// Set the directory downloads are written to.
+    pub fn set_download_directory(mut self, directory: impl Into<PathBuf>) -> Self {
+        self.download_directory = directory.into();
+        self
+    }
+
+    /// Set the codec downloads are requested in.
+    pub fn set_codec(mut self, codec: Codec) -> Self {
+        self.codec = codec;
+        self
+    }
+
+    /// Set a channel to send the progress of downloads and playlist syncs to,
+    /// see [`ProgressEvent`]. Unset by default.
+    pub fn set_progress(mut self, sender: Option<Sender<ProgressEvent>>) -> Self {
+        self.progress = sender;
+        self
+    }
+
+    /// Get a reader streaming a track's audio.
+    pub fn play(&self, track: &Track) -> Result<TrackStream, Error> {
+        self.client
+            .release()
+            .stream_by_ids(&track.release.id, &track.id)
+    }
+}
+
+impl Monstercat<SignedIn> {
+    /// Get a downloader using the facade's directory and codec.
+    pub fn downloader(&self) -> Downloader<'_> {
+        Downloader::new(&self.client, self.codec.clone())
+            .set_directory(&self.download_directory)
+            .set_progress(self.progress.clone())
+    }
+
+    /// Download every downloadable track of a release, skipping tracks already downloaded.
+    pub fn download_release(&self, catalog_id: &CatalogID) -> Result<DownloadReport, Error> {
+        self.downloader().download_release(catalog_id)
+    }
+
+    /// Make a playlist contain exactly the given tracks, adding and removing as needed.
+    ///
+    /// Every page of the playlist is read, so tracks already in it are never
+    /// added again.
+    pub fn sync_playlist(&self, playlist_id: PlaylistID, tracks: &[Track]) -> Result<(), Error> {
+        Reporter::new(self.progress.clone()).run(Operation::SyncPlaylist, None, || {
+            let current = self.client.playlist().get_all_tracks(playlist_id)?;
+
+            let removals: Vec<PlaylistItem> = current
+                .iter()
+                .enumerate()
+                .filter(|(_, track)| !tracks.iter().any(|wanted| wanted.id == track.id))
+                .map(|(sort, track)| playlist_item(playlist_id, sort, track))
+                .collect();
+
+            let additions: Vec<PlaylistItem> = tracks
+                .iter()
+                .enumerate()
+                .filter(|(_, track)| !current.iter().any(|existing| existing.id == track.id))
+                .map(|(sort, track)| playlist_item(playlist_id, sort, track))
+                .collect();
+
+            if !removals.is_empty() {
+                self.client.playlist().modify_items(
+                    playlist_id,
+                    PlaylistItemsOperations::Remove,
+                    PlaylistItemsMod { records: removals },
+                )?;
+            }
+
+            if !additions.is_empty() {
+                self.client.playlist().modify_items(
+                    playlist_id,
+                    PlaylistItemsOperations::Add,
+                    PlaylistItemsMod { records: additions },
+                )?;
+            }
+
+            Ok(())
+        })
+    }
+}
+
+/// Build the playlist item for a track at some index.
+fn playlist_item(playlist_id: PlaylistID, sort: usize, track: &Track) -> PlaylistItem {
+    PlaylistItem {
+        playlist_id,
+        release_id: track.release.id,
+        sort,
+        track_id: track.id,
+    }
+}
@@ -0,0 +1,141 @@
+use crate::mc::datetime;
+use crate::mc::release::AnyRelease;
+use chrono::{DateTime, Datelike, Days, NaiveDate};
+use chrono_tz::Tz;
+use std::collections::BTreeMap;
+
+/// Kinds of dates a release has on the calendar.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum CalendarEventKind {
+    /// Presaving opens, so listeners can add it to their library ahead of time.
+    Presave,
+    /// Early access opens, for gold members.
+    EarlyAccess,
+    /// Released to everyone.
+    Release,
+}
+
+/// Date of a release on the calendar.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CalendarEntry {
+    pub at: DateTime<Tz>,
+    pub kind: CalendarEventKind,
+    pub release: AnyRelease,
+}
+
+/// Schedule of releases, grouped by day and week in a time zone, like for a
+/// release radar or a schedule posted to a chat.
+///
+/// Releases are listed on their release date, and also on the dates they
+/// open for presaves and early access, if any.
+///
+/// Example
+/// ```rust
+/// use bombay::calendar::ReleaseCalendar;
+/// use bombay::mc::datetime;
+/// use bombay::mc::release::AnyRelease;
+///
+/// # fn example(releases: Vec<AnyRelease>) {
+/// let timezone = datetime::parse_timezone("America/New_York").unwrap();
+/// let calendar = ReleaseCalendar::new(timezone, releases);
+///
+/// for (monday, entries) in calendar.weeks() {
+///   println!("Week of {}:", monday);
+///   for entry in entries {
+///     println!("  {} {:?}: {}", entry.at.format("%a %H:%M"), entry.kind, entry.release);
+///   }
+/// }
+/// # }
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct ReleaseCalendar {
+    timezone: Tz,
+    days: BTreeMap<NaiveDate, Vec<CalendarEntry>>,
+}
+
+impl ReleaseCalendar {
+    /// Create a calendar of releases in a time zone.
+    pub fn new(timezone: Tz, releases: impl IntoIterator<Item = AnyRelease>) -> Self {
+        let mut calendar = ReleaseCalendar {
+            timezone,
+            days: BTreeMap::new(),
+        };
+        for release in releases {
+            calendar.insert(release);
+        }
+        calendar
+    }
+
+    /// Get the time zone the calendar is grouped by.
+    pub fn timezone(&self) -> Tz {
+        self.timezone
+    }
+
+    /// Add the dates of a release to the calendar.
+    pub fn insert(&mut self, release: AnyRelease) {
+        let mut dates = vec![(CalendarEventKind::Release, *release.get_date())];
+        if let AnyRelease::Release(details) = &release {
+            dates.extend(
+                details
+                    .presave_date
+                    .map(|at| (CalendarEventKind::Presave, at)),
+            );
+            dates.extend(
+                details
+                    .prerelease_date
+                    .map(|at| (CalendarEventKind::EarlyAccess, at)),
+            );
+        }
+
+        for (kind, timestamp) in dates {
+            let at = datetime::with_timezone(&timestamp, self.timezone);
+            let day = self.days.entry(at.date_naive()).or_default();
+            let index = day.partition_point(|entry| (entry.at, entry.kind) <= (at, kind));
+            day.insert(
+                index,
+                CalendarEntry {
+                    at,
+                    kind,
+                    release: release.clone(),
+                },
+            );
+        }
+    }
+
+    /// Get the entries of a day, in order.
+    pub fn on(&self, date: NaiveDate) -> &[CalendarEntry] {
+        self.days.get(&date).map_or(&[], Vec::as_slice)
+    }
+
+    /// Iterate over the days with entries, in order.
+    pub fn days(&self) -> impl Iterator<Item = (NaiveDate, &[CalendarEntry])> {
+        self.days
+            .iter()
+            .map(|(date, entries)| (*date, entries.as_slice()))
+    }
+
+    /// Iterate over the weeks with entries, in order, starting on Mondays.
+    pub fn weeks(&self) -> impl Iterator<Item = (NaiveDate, Vec<&CalendarEntry>)> {
+        let mut weeks: BTreeMap<NaiveDate, Vec<&CalendarEntry>> = BTreeMap::new();
+        for (date, entries) in &self.days {
+            let monday = *date - Days::new(date.weekday().num_days_from_monday().into());
+            weeks.entry(monday).or_default().extend(entries);
+        }
+        weeks.into_iter()
+    }
+
+    /// Iterate over all entries, in order.
+    pub fn iter(&self) -> impl Iterator<Item = &CalendarEntry> {
+        self.days.values().flatten()
+    }
+
+    /// Get the number of entries.
+    pub fn len(&self) -> usize {
+        self.days.values().map(Vec::len).sum()
+    }
+
+    /// Check whether the calendar has no entries.
+    pub fn is_empty(&self) -> bool {
+        self.days.is_empty()
+    }
+}
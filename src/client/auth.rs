@@ -1,6 +1,88 @@
 use crate::client::{Client, Error, SignedIn, SignedOut};
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Serialize, Serializer};
 use serde_json::Value;
+use std::fmt::{Debug, Display};
+use std::sync::Arc;
+use zeroize::Zeroize;
+
+/// Secret value, like a password, that is wiped from memory when dropped and
+/// never shown by `Debug`.
+///
+/// Example
+/// ```rust
+/// use bombay::client::auth::Secret;
+///
+/// let password = Secret::new("hunter2".to_owned());
+/// assert_eq!(format!("{:?}", password), "Secret([REDACTED])");
+/// assert_eq!(password.expose(), "hunter2");
+/// ```
+#[derive(Clone, Default)]
+pub struct Secret<T: Zeroize>(T);
+
+impl<T: Zeroize> Secret<T> {
+    /// Wrap a secret value.
+    pub fn new(value: T) -> Self {
+        Secret(value)
+    }
+
+    /// Get the secret value, to use it where it's needed.
+    pub fn expose(&self) -> &T {
+        &self.0
+    }
+}
+
+impl<T: Zeroize> From<T> for Secret<T> {
+    fn from(value: T) -> Self {
+        Secret(value)
+    }
+}
+
+impl<T: Zeroize> Debug for Secret<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("Secret").field(&Redacted).finish()
+    }
+}
+
+/// Stand-in shown by `Debug` for credentials, like cookies and codes.
+pub(crate) struct Redacted;
+
+impl Redacted {
+    /// Get a stand-in for an optional credential, showing only whether it's set.
+    pub(crate) fn option<T>(value: &Option<T>) -> Option<Redacted> {
+        value.as_ref().map(|_| Redacted)
+    }
+}
+
+impl Debug for Redacted {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "[REDACTED]")
+    }
+}
+
+impl<T: Zeroize> Drop for Secret<T> {
+    fn drop(&mut self) {
+        self.0.zeroize();
+    }
+}
+
+/// Serialize a secret's value, for request bodies that must carry it.
+fn serialize_secret<S: Serializer>(
+    secret: &Secret<String>,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    serializer.serialize_str(secret.expose())
+}
+
+/// Serialize an optional secret's value, for request bodies that must carry it.
+fn serialize_secret_option<S: Serializer>(
+    secret: &Option<Secret<String>>,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    match secret {
+        Some(secret) => serializer.serialize_some(secret.expose()),
+        None => serializer.serialize_none(),
+    }
+}
 
 /// Sign-in outcome variants.
 ///
@@ -13,10 +95,212 @@ use serde_json::Value;
 /// 1. `Ok(Client<SignedIn>)` - You get a signed in client!
 /// 3. `Err(Error)` - Something has gone wrong.
 ///
+/// If the sign-in first-step is challenged with a captcha, you get the
+/// challenge and a function to call back with the solved captcha token. That
+/// callback restarts the sign-in and returns another outcome, since 2FA may
+/// still be required.
+///
+/// More outcomes may be added as Monstercat adds sign-in factors.
+// Outcomes are short-lived, so the size of the client isn't worth boxing it.
+#[allow(clippy::large_enum_variant)]
+#[non_exhaustive]
 pub enum SignInOutcome {
     Authenticated(Client<SignedIn>),
     Email(EmailCallback),
     TOTP(TOTPCallback),
+    Captcha(CaptchaChallenge, CaptchaCallback),
+}
+
+/// Captcha challenge issued during sign-in.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CaptchaChallenge {
+    /// Message explaining the challenge.
+    pub message: String,
+    /// Site key to present the captcha with, if provided.
+    pub site_key: Option<String>,
+}
+
+/// Sign-in failure variants, derived from the status and error payload of a
+/// rejected sign-in request.
+#[derive(Clone, Debug, PartialEq)]
+#[non_exhaustive]
+pub enum SignInFailure {
+    /// Email or password (or 2FA code) was not accepted.
+    WrongCredentials(String),
+    /// Account is locked or disabled.
+    AccountLocked(String),
+    /// A captcha must be solved before signing in.
+    CaptchaRequired(CaptchaChallenge),
+    /// Too many sign-in attempts, optionally with seconds to wait before retrying.
+    RateLimited(Option<u64>),
+}
+
+impl Display for SignInFailure {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SignInFailure::WrongCredentials(msg) => write!(f, "Wrong credentials: {}", msg),
+            SignInFailure::AccountLocked(msg) => write!(f, "Account locked: {}", msg),
+            SignInFailure::CaptchaRequired(challenge) => {
+                write!(f, "Captcha required: {}", challenge.message)
+            }
+            SignInFailure::RateLimited(Some(secs)) => {
+                write!(f, "Rate limited, retry after {} seconds", secs)
+            }
+            SignInFailure::RateLimited(None) => write!(f, "Rate limited"),
+        }
+    }
+}
+
+impl SignInFailure {
+    /// Classify a rejected sign-in response.
+    ///
+    /// Returns `None` if the response does not look like a failure, since a
+    /// valid login with no 2FA will produce a status 400 response.
+    pub(crate) fn from_response(status: u16, response: ureq::Response) -> Option<Self> {
+        let retry_after = response
+            .header("Retry-After")
+            .and_then(|secs| secs.parse::<u64>().ok());
+
+        let reply = response.into_json::<AuthErrorReply>().ok();
+        let site_key = reply.as_ref().and_then(|reply| reply.site_key.clone());
+        let message = reply.and_then(|reply| reply.message).unwrap_or_default();
+        let norm = message.to_lowercase();
+
+        if status == 429 || norm.contains("too many") {
+            Some(SignInFailure::RateLimited(retry_after))
+        } else if norm.contains("captcha") || site_key.is_some() {
+            Some(SignInFailure::CaptchaRequired(CaptchaChallenge {
+                message,
+                site_key,
+            }))
+        } else if norm.contains("locked") || norm.contains("disabled") {
+            Some(SignInFailure::AccountLocked(message))
+        } else if status == 401
+            || status == 403
+            || norm.contains("password")
+            || norm.contains("credentials")
+        {
+            Some(SignInFailure::WrongCredentials(message))
+        } else {
+            None
+        }
+    }
+}
+
+/// Mechanism authenticating the requests of a client.
+///
+/// Endpoints never deal with authentication directly, the client asks its
+/// provider to authorize each request instead. Monstercat only offers cookie
+/// sessions today ([`CookieAuth`]), but API keys or bearer tokens can be
+/// supported by another provider without touching endpoint code.
+pub trait AuthProvider: Debug + Send + Sync {
+    /// Add credentials to an outgoing request.
+    fn authorize(&self, request: ureq::Request) -> ureq::Request;
+
+    /// Check whether there are credentials for requests made with the agent.
+    fn is_authenticated(&self, agent: &ureq::Agent) -> bool;
+
+    /// Get the session cookie the provider authenticates with, if it uses one.
+    fn cookie_auth(&self) -> Option<&CookieAuth> {
+        None
+    }
+}
+
+/// Domain of the session cookie of the MC API.
+const COOKIE_DOMAIN: &str = "player.monstercat.app";
+
+/// Name of the session cookie of the MC API.
+const COOKIE_NAME: &str = "cid";
+
+/// Cookie session authentication, the only mechanism Monstercat offers for now.
+///
+/// The session cookie is set by signing in and kept in the agent's cookie
+/// store, which adds it to requests on its own. By default, it is the "cid"
+/// cookie of the player API's host, like for a mock or staging API.
+///
+/// Example
+/// ```rust
+/// use bombay::client::auth::CookieAuth;
+/// use bombay::client::Client;
+///
+/// let url = "https://staging.example.com/api";
+/// let mc = Client::new(url.to_owned(), "https://staging.example.com/".to_owned())
+///     .set_auth_provider(CookieAuth::for_url(url).set_name("session"));
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CookieAuth {
+    /// Domain the cookie is set for.
+    pub domain: String,
+    /// Name of the cookie.
+    pub name: String,
+}
+
+impl Default for CookieAuth {
+    fn default() -> Self {
+        CookieAuth::new(COOKIE_DOMAIN.to_owned(), COOKIE_NAME.to_owned())
+    }
+}
+
+impl CookieAuth {
+    /// Create cookie authentication with a cookie of a domain.
+    pub fn new(domain: String, name: String) -> Self {
+        CookieAuth { domain, name }
+    }
+
+    /// Create cookie authentication with the "cid" cookie of a player API
+    /// URL's host, or of the MC API if the URL has none.
+    pub fn for_url(player_api: &str) -> Self {
+        let domain = url::Url::parse(player_api)
+            .ok()
+            .and_then(|url| url.host_str().map(str::to_owned))
+            .unwrap_or_else(|| COOKIE_DOMAIN.to_owned());
+        CookieAuth::new(domain, COOKIE_NAME.to_owned())
+    }
+
+    /// Set the name of the cookie.
+    pub fn set_name(mut self, name: impl Into<String>) -> Self {
+        self.name = name.into();
+        self
+    }
+
+    /// Get the value of the cookie, if the agent has it.
+    pub fn get(&self, agent: &ureq::Agent) -> Option<String> {
+        agent
+            .cookie_store()
+            .get(&self.domain, "/", &self.name)
+            .map(|cookie| cookie.value().to_owned())
+    }
+}
+
+impl AuthProvider for CookieAuth {
+    fn authorize(&self, request: ureq::Request) -> ureq::Request {
+        request
+    }
+
+    fn is_authenticated(&self, agent: &ureq::Agent) -> bool {
+        self.get(agent).is_some()
+    }
+
+    fn cookie_auth(&self) -> Option<&CookieAuth> {
+        Some(self)
+    }
+}
+
+/// Handler called when the session of a signed-in client expired, to sign in again.
+///
+/// The handler gets a signed-out client sharing the expired client's agent,
+/// so signing in with it renews the expired client's session too.
+#[derive(Clone)]
+pub(crate) struct AuthExpiredHandler(pub Arc<AuthExpiredFn>);
+
+/// Type for callback function provided to sign in again when the session expired.
+pub(crate) type AuthExpiredFn =
+    dyn Fn(&mut Client<SignedOut>) -> Result<Client<SignedIn>, Error> + Send + Sync;
+
+impl Debug for AuthExpiredHandler {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "AuthExpiredHandler")
+    }
 }
 
 /// Type for callback function provided to check on email 2FA.
@@ -25,23 +309,53 @@ pub type EmailCallback = fn(&mut Client<SignedOut>) -> Result<Client<SignedIn>,
 /// Type for callback function provided to try code for TOTP 2FA.
 pub type TOTPCallback = fn(&mut Client<SignedOut>, String) -> Result<Client<SignedIn>, Error>;
 
+/// Type for callback function provided to retry sign-in with a solved captcha token.
+pub type CaptchaCallback = fn(&mut Client<SignedOut>, String) -> Result<SignInOutcome, Error>;
+
 /// User sign-in parameters.
-#[derive(Clone, Debug, Deserialize, Serialize)]
+#[derive(Clone, Serialize)]
 #[serde(rename_all = "PascalCase")]
 pub(crate) struct SigninParameters {
     pub email: String,
-    pub password: String,
+    #[serde(serialize_with = "serialize_secret")]
+    pub password: Secret<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub auth: Option<AuthParameters>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub captcha: Option<String>,
 }
 
 /// 2-factor authentication parameters.
-#[derive(Clone, Debug, Deserialize, Serialize)]
+#[derive(Clone, Serialize)]
 pub(crate) struct AuthParameters {
     #[serde(rename = "Email", skip_serializing_if = "Option::is_none")]
     pub email: Option<String>,
-    #[serde(rename = "TOTP", skip_serializing_if = "Option::is_none")]
-    pub totp: Option<String>,
+    #[serde(
+        rename = "TOTP",
+        serialize_with = "serialize_secret_option",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub totp: Option<Secret<String>>,
+}
+
+impl Debug for SigninParameters {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SigninParameters")
+            .field("email", &self.email)
+            .field("password", &self.password)
+            .field("auth", &self.auth)
+            .field("captcha", &Redacted::option(&self.captcha))
+            .finish()
+    }
+}
+
+impl Debug for AuthParameters {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AuthParameters")
+            .field("email", &Redacted::option(&self.email))
+            .field("totp", &self.totp)
+            .finish()
+    }
 }
 
 /// Sign-in response.
@@ -54,6 +368,15 @@ pub(crate) struct AuthReply {
     pub needs_2fa: bool,
 }
 
+/// Error payload of a rejected sign-in request.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "PascalCase")]
+pub(crate) struct AuthErrorReply {
+    pub message: Option<String>,
+    pub name: Option<String>,
+    pub site_key: Option<String>,
+}
+
 /// Authentication data contained in sign-in response.
 #[derive(Clone, Debug, Deserialize, Serialize)]
 pub(crate) struct AuthData {
@@ -81,9 +404,22 @@ pub(crate) enum Auth2FAMethod {
 }
 
 /// Saved authentication credentials for callback use.
-#[derive(Debug)]
+///
+/// Dropped, and so wiped, as soon as signing in completes.
 pub(crate) struct SavedAuthDetails {
+    pub captcha: Option<String>,
     pub email: String,
     pub email_id: Option<String>,
-    pub password: String,
+    pub password: Secret<String>,
+}
+
+impl Debug for SavedAuthDetails {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SavedAuthDetails")
+            .field("captcha", &Redacted::option(&self.captcha))
+            .field("email", &self.email)
+            .field("email_id", &Redacted::option(&self.email_id))
+            .field("password", &self.password)
+            .finish()
+    }
 }
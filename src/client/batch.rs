@@ -0,0 +1,134 @@
+use crate::client::Error;
+use std::sync::{Condvar, Mutex};
+use std::thread;
+
+/// Batch of requests to run concurrently, see [`Client::join_all`](crate::client::Client::join_all).
+///
+/// Implemented for vectors of requests with the same result type, and for
+/// tuples of up to six requests with any result types. A request is a closure
+/// making one or more calls with a client, and getting a result, see
+/// [`FromPanic`].
+pub trait Batch {
+    /// Results of the requests, in the same order as the requests.
+    type Output;
+
+    /// Run the requests, at most `max_concurrent` at a time.
+    fn run(self, max_concurrent: usize) -> Self::Output;
+}
+
+/// Result of a request in a batch, which stands for the request panicking
+/// too, so one request panicking doesn't take the whole batch down.
+pub trait FromPanic {
+    /// Get the result of a request that panicked.
+    fn from_panic() -> Self;
+}
+
+/// Requests that panicked fail with [`Error::Message`].
+impl<T, E: From<Error>> FromPanic for Result<T, E> {
+    fn from_panic() -> Self {
+        Err(Error::Message("Batched request panicked.").into())
+    }
+}
+
+/// Caps the number of requests of a batch running at once.
+struct Limiter {
+    running: Mutex<usize>,
+    freed: Condvar,
+    max: usize,
+}
+
+/// Slot taken by a running request, freed when dropped, even on panic.
+struct Permit<'l>(&'l Limiter);
+
+impl Limiter {
+    fn new(max: usize) -> Self {
+        Limiter {
+            running: Mutex::new(0),
+            freed: Condvar::new(),
+            max: max.max(1),
+        }
+    }
+
+    /// Wait for a free slot.
+    fn acquire(&self) -> Permit<'_> {
+        let mut running = self.running.lock().unwrap_or_else(|err| err.into_inner());
+        while *running >= self.max {
+            running = self
+                .freed
+                .wait(running)
+                .unwrap_or_else(|err| err.into_inner());
+        }
+        *running += 1;
+        Permit(self)
+    }
+
+    /// Run a request once there is a free slot.
+    fn run<R>(&self, request: impl FnOnce() -> R) -> R {
+        let _permit = self.acquire();
+        request()
+    }
+}
+
+impl Drop for Permit<'_> {
+    fn drop(&mut self) {
+        let mut running = self.0.running.lock().unwrap_or_else(|err| err.into_inner());
+        *running -= 1;
+        self.0.freed.notify_one();
+    }
+}
+
+impl<F, R> Batch for Vec<F>
+where
+    F: FnOnce() -> R + Send,
+    R: FromPanic + Send,
+{
+    type Output = Vec<R>;
+
+    fn run(self, max_concurrent: usize) -> Self::Output {
+        let limiter = Limiter::new(max_concurrent);
+        let limiter = &limiter;
+
+        thread::scope(|scope| {
+            let handles: Vec<_> = self
+                .into_iter()
+                .map(|request| scope.spawn(move || limiter.run(request)))
+                .collect();
+
+            handles
+                .into_iter()
+                .map(|handle| handle.join().unwrap_or_else(|_| R::from_panic()))
+                .collect()
+        })
+    }
+}
+
+/// Implement [`Batch`] for a tuple of requests with their own result types.
+macro_rules! impl_batch_for_tuple {
+    ($(($request:ident, $result:ident)),+) => {
+        impl<$($request, $result),+> Batch for ($($request,)+)
+        where
+            $($request: FnOnce() -> $result + Send, $result: FromPanic + Send,)+
+        {
+            type Output = ($($result,)+);
+
+            #[allow(non_snake_case)]
+            fn run(self, max_concurrent: usize) -> Self::Output {
+                let ($($request,)+) = self;
+                let limiter = Limiter::new(max_concurrent);
+                let limiter = &limiter;
+
+                thread::scope(|scope| {
+                    $(let $result = scope.spawn(move || limiter.run($request));)+
+                    ($($result.join().unwrap_or_else(|_| $result::from_panic()),)+)
+                })
+            }
+        }
+    };
+}
+
+impl_batch_for_tuple!((A, RA));
+impl_batch_for_tuple!((A, RA), (B, RB));
+impl_batch_for_tuple!((A, RA), (B, RB), (C, RC));
+impl_batch_for_tuple!((A, RA), (B, RB), (C, RC), (D, RD));
+impl_batch_for_tuple!((A, RA), (B, RB), (C, RC), (D, RD), (E, RE));
+impl_batch_for_tuple!((A, RA), (B, RB), (C, RC), (D, RD), (E, RE), (F, RF));
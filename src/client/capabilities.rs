@@ -0,0 +1,32 @@
+use crate::client::Error;
+
+/// Parts of the MC API found to work with a client, see
+/// [`Client::probe_capabilities`](crate::client::Client::probe_capabilities).
+///
+/// The MC API changes without notice and can differ by region, so
+/// applications can check these to hide features that would fail.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Capabilities {
+    pub supports_artists: bool,
+    /// Unverified: probed with `/events`, which the MC API isn't known to
+    /// have, so `false` may only mean the guessed route is wrong.
+    pub supports_events: bool,
+    pub supports_latest_releases: bool,
+    pub supports_mood_catalog: bool,
+    pub supports_related_releases: bool,
+    pub supports_www_api: bool,
+}
+
+/// Judge whether a probe found its endpoint working.
+///
+/// Endpoints that answer with an error status, or with something that no
+/// longer deserializes, are unsupported. Errors reaching the API at all are
+/// passed on, since they say nothing about the endpoint.
+pub(crate) fn probe<T>(result: Result<T, Error>) -> Result<bool, Error> {
+    match result {
+        Ok(_) => Ok(true),
+        Err(err) if err.status().is_some() => Ok(false),
+        Err(Error::Deserialization(_)) | Err(Error::NotFound(_)) => Ok(false),
+        Err(err) => Err(err),
+    }
+}
@@ -0,0 +1,263 @@
+use crate::client::Error;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use ureq::{Middleware, MiddlewareNext, Request, Response};
+
+/// Headers that describe how the body was sent, which no longer hold once it
+/// is recorded as plain text.
+const TRANSPORT_HEADERS: [&str; 3] = ["content-encoding", "content-length", "transfer-encoding"];
+
+/// Headers holding credentials, like the session cookie, whose values are
+/// redacted before they are recorded.
+const SECRET_HEADERS: [&str; 4] = [
+    "authorization",
+    "cookie",
+    "proxy-authorization",
+    "set-cookie",
+];
+
+/// Value recorded in place of a secret.
+const REDACTED: &str = "REDACTED";
+
+/// Whether a client records requests to a cassette or replays them from one.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum CassetteMode {
+    /// Make requests to the MC API and write each request/response pair to
+    /// the cassette file, replacing it.
+    Record(PathBuf),
+    /// Answer requests from the cassette file, without any network access.
+    Replay(PathBuf),
+}
+
+/// Request/response pair in a cassette.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct Interaction {
+    pub method: String,
+    pub url: String,
+    pub status: u16,
+    pub status_text: String,
+    pub headers: Vec<(String, String)>,
+    /// Body as text, so cassettes suit API responses rather than downloads.
+    pub body: String,
+}
+
+impl Interaction {
+    /// Record a response to a request, getting the interaction and an
+    /// identical response to hand on.
+    fn record(request: &Request, response: Response) -> io::Result<(Self, Response)> {
+        let status = response.status();
+        let status_text = response.status_text().to_owned();
+        let headers = response
+            .headers_names()
+            .into_iter()
+            .filter(|name| !TRANSPORT_HEADERS.contains(&name.to_lowercase().as_str()))
+            .flat_map(|name| {
+                response
+                    .all(&name)
+                    .into_iter()
+                    .map(|value| (name.clone(), value.to_owned()))
+                    .collect::<Vec<_>>()
+            })
+            .collect();
+
+        let mut body = Vec::new();
+        response.into_reader().read_to_end(&mut body)?;
+
+        let interaction = Interaction {
+            method: request.method().to_owned(),
+            url: request.url().to_owned(),
+            status,
+            status_text,
+            headers,
+            body: String::from_utf8_lossy(&body).into_owned(),
+        };
+        // Hand on the response as received, but never write its secrets.
+        let response = interaction.response()?;
+        Ok((interaction.redacted(), response))
+    }
+
+    /// Get the interaction with the values of headers holding credentials
+    /// redacted, like session cookies.
+    ///
+    /// Cookies keep their name and attributes, so replayed responses still
+    /// set them, just not to the recorded secret.
+    pub fn redacted(mut self) -> Self {
+        for (name, value) in &mut self.headers {
+            let name = name.to_lowercase();
+            if !SECRET_HEADERS.contains(&name.as_str()) {
+                continue;
+            }
+            *value = match name.as_str() {
+                "set-cookie" => redact_cookie(value),
+                "cookie" => value
+                    .split(';')
+                    .map(redact_cookie)
+                    .collect::<Vec<_>>()
+                    .join(";"),
+                _ => REDACTED.to_owned(),
+            };
+        }
+        self
+    }
+
+    /// Check whether the interaction answers a request.
+    fn matches(&self, request: &Request) -> bool {
+        self.method.eq_ignore_ascii_case(request.method()) && self.url == request.url()
+    }
+
+    /// Build the recorded response.
+    fn response(&self) -> io::Result<Response> {
+        let mut raw = format!("HTTP/1.1 {} {}\r\n", self.status, self.status_text);
+        for (name, value) in &self.headers {
+            raw.push_str(&format!("{}: {}\r\n", name, value));
+        }
+        raw.push_str(&format!("Content-Length: {}\r\n\r\n", self.body.len()));
+        raw.push_str(&self.body);
+
+        raw.parse::<Response>()
+            .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err.to_string()))
+    }
+}
+
+/// Replace the value of a cookie, like "cid=secret; Path=/", keeping its
+/// name and attributes.
+fn redact_cookie(cookie: &str) -> String {
+    let (pair, attributes) = cookie.split_once(';').unwrap_or((cookie, ""));
+    let mut redacted = match pair.split_once('=') {
+        Some((name, _)) => format!("{name}={REDACTED}"),
+        None => REDACTED.to_owned(),
+    };
+    if !attributes.is_empty() {
+        redacted.push(';');
+        redacted.push_str(attributes);
+    }
+    redacted
+}
+
+/// Recorded request/response pairs, in the order they were made.
+///
+/// Credentials in recorded headers, like session cookies, are redacted, so
+/// cassettes can be committed along with tests.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub struct Cassette {
+    pub interactions: Vec<Interaction>,
+}
+
+impl Cassette {
+    /// Get the cassette with credentials redacted from every interaction,
+    /// like one recorded before redaction, see [`Interaction::redacted`].
+    pub fn redacted(self) -> Self {
+        Cassette {
+            interactions: self
+                .interactions
+                .into_iter()
+                .map(Interaction::redacted)
+                .collect(),
+        }
+    }
+
+    /// Read a cassette from a file.
+    pub fn load(path: &Path) -> Result<Self, Error> {
+        let file = fs::File::open(path).map_err(Error::IO)?;
+        serde_json::from_reader(io::BufReader::new(file)).map_err(Error::Deserialization)
+    }
+
+    /// Write the cassette to a file, replacing it, and create its directory.
+    pub fn save(&self, path: &Path) -> Result<(), Error> {
+        let json = serde_json::to_vec_pretty(self).map_err(Error::Deserialization)?;
+        if let Some(directory) = path.parent() {
+            fs::create_dir_all(directory).map_err(Error::IO)?;
+        }
+        fs::write(path, json).map_err(Error::IO)
+    }
+}
+
+/// Middleware recording every request/response pair to a cassette file.
+pub(crate) struct Recorder {
+    path: PathBuf,
+    cassette: Mutex<Cassette>,
+}
+
+impl Recorder {
+    pub fn new(path: PathBuf) -> Self {
+        Recorder {
+            path,
+            cassette: Mutex::new(Cassette::default()),
+        }
+    }
+
+    /// Add an interaction and write the cassette so far, so it survives crashes.
+    fn push(&self, interaction: Interaction) -> io::Result<()> {
+        let mut cassette = self.cassette.lock().unwrap_or_else(|err| err.into_inner());
+        cassette.interactions.push(interaction);
+        cassette.save(&self.path).map_err(|err| match err {
+            Error::IO(err) => err,
+            err => io::Error::new(io::ErrorKind::Other, err.to_string()),
+        })
+    }
+}
+
+impl Middleware for Recorder {
+    fn handle(&self, request: Request, next: MiddlewareNext) -> Result<Response, ureq::Error> {
+        let (interaction, outcome) = match next.handle(request.clone()) {
+            Ok(response) => {
+                let (interaction, response) = Interaction::record(&request, response)?;
+                (interaction, Ok(response))
+            }
+            Err(ureq::Error::Status(status, response)) => {
+                let (interaction, response) = Interaction::record(&request, response)?;
+                (interaction, Err(ureq::Error::Status(status, response)))
+            }
+            Err(err) => return Err(err),
+        };
+
+        self.push(interaction)?;
+        outcome
+    }
+}
+
+/// Middleware answering requests from a cassette.
+pub(crate) struct Player {
+    /// Interactions not replayed yet.
+    remaining: Mutex<Vec<Interaction>>,
+}
+
+impl Player {
+    pub fn new(cassette: Cassette) -> Self {
+        Player {
+            remaining: Mutex::new(cassette.interactions),
+        }
+    }
+}
+
+impl Middleware for Player {
+    /// Replay the first remaining interaction for the request, so repeated
+    /// requests get their responses in recorded order.
+    fn handle(&self, request: Request, _next: MiddlewareNext) -> Result<Response, ureq::Error> {
+        let mut remaining = self.remaining.lock().unwrap_or_else(|err| err.into_inner());
+        let interaction = match remaining.iter().position(|inter| inter.matches(&request)) {
+            Some(index) => remaining.remove(index),
+            None => {
+                return Err(io::Error::new(
+                    io::ErrorKind::NotFound,
+                    format!(
+                        "No recorded response to {} {}.",
+                        request.method(),
+                        request.url()
+                    ),
+                )
+                .into())
+            }
+        };
+
+        // Give the recorded outcome as ureq would have returned it.
+        let response = interaction.response()?;
+        match interaction.status >= 400 {
+            true => Err(ureq::Error::Status(interaction.status, response)),
+            false => Ok(response),
+        }
+    }
+}
@@ -0,0 +1,20 @@
+use serde_json::Value;
+
+/// Remove explicit tracks from any lists in a response, at any depth.
+///
+/// Tracks are recognized as objects with an `Explicit` field set to true, so
+/// the same filter applies to releases, playlists, moods, and anything else.
+pub(crate) fn remove_explicit(value: &mut Value) {
+    match value {
+        Value::Array(values) => {
+            values.retain(|value| !is_explicit(value));
+            values.iter_mut().for_each(remove_explicit);
+        }
+        Value::Object(object) => object.values_mut().for_each(remove_explicit),
+        _ => {}
+    }
+}
+
+fn is_explicit(value: &Value) -> bool {
+    value.get("Explicit").and_then(Value::as_bool) == Some(true)
+}
@@ -1,10 +1,14 @@
 use crate::client::endpoints::TargetAPI;
 use crate::client::response::{Paginated, Wrapped};
-use crate::client::{EndpointArtist, Error, RequestParameters};
-use crate::mc::artist::Artist;
+use crate::client::{EndpointArtist, Error, PaginationParameters, RequestParameters};
+use crate::matching::match_artist;
+use crate::mc::artist::{Artist, ArtistID, ArtistListItem};
 use std::collections::HashMap;
 use std::fmt::Display;
 
+/// Artists requested per page while looking for one by ID among all of them.
+const FIND_PAGE_SIZE: usize = 100;
+
 impl<ClientAuthState> EndpointArtist<'_, ClientAuthState> {
     /// Get all artists.
     ///
@@ -33,6 +37,44 @@ impl<ClientAuthState> EndpointArtist<'_, ClientAuthState> {
             .ok_or(Error::NotFound("all artists"))
     }
 
+    /// Get all artists as list items, for list views that don't need their
+    /// details or links, see [`ArtistListItem`].
+    ///
+    /// Every field is asked for unless the parameters select some, as field
+    /// selection is unverified, see [`RequestParameters::set_fields`]. Items
+    /// are slimmed once received.
+    ///
+    /// Example
+    /// ```rust
+    /// use bombay::client::Client;
+    ///
+    /// let mc = Client::default(); // Without authentication.
+    ///
+    /// if let Ok(artists) = mc.artist().get_all_slim(None) {
+    ///   for artist in artists.data.unwrap_or_default() {
+    ///     println!("{} ({})", artist.name, artist.uri);
+    ///   }
+    /// }
+    /// ```
+    pub fn get_all_slim(
+        &self,
+        parameters: Option<RequestParameters>,
+    ) -> Result<Paginated<ArtistListItem>, Error> {
+        let parameters = parameters.unwrap_or(RequestParameters {
+            pagination: None,
+            ..RequestParameters::default()
+        });
+
+        self.client
+            .get::<Wrapped<Paginated<ArtistListItem>>>(
+                TargetAPI::Player,
+                "/artists",
+                Some(parameters),
+            )?
+            .remove("Artists")
+            .ok_or(Error::NotFound("all artists"))
+    }
+
     /// Get artist by name uri, which is a slight variation on the name depending on the characters involved.
     ///
     /// Example
@@ -59,6 +101,116 @@ impl<ClientAuthState> EndpointArtist<'_, ClientAuthState> {
         )
     }
 
+    /// Get artist by ID, which unlike the name URI doesn't change when the
+    /// artist is renamed.
+    ///
+    /// Unverified: the MC API isn't known to serve `/artist/{id}`. When the
+    /// guessed route doesn't answer with the artist, all artists are paged
+    /// through to find it instead, which takes a request per hundred artists.
+    ///
+    /// Example
+    /// ```rust
+    /// use bombay::client::Client;
+    /// use bombay::mc::artist::ArtistID;
+    /// use uuid::uuid;
+    ///
+    /// let mc = Client::default(); // Without authentication.
+    /// let grant_id = ArtistID(uuid!("27063fd3-4fba-4119-9af0-5001e925b0d2"));
+    ///
+    /// if let Ok(grant) = mc.artist().get_by_id(&grant_id) {
+    ///   println!("Found {}.", grant.name);
+    /// }
+    /// ```
+    ///
+    /// Example URL: <https://player.monstercat.app/api/artist/27063fd3-4fba-4119-9af0-5001e925b0d2>
+    pub fn get_by_id(&self, artist_id: &ArtistID) -> Result<Artist, Error> {
+        let found = self.client.get::<Artist>(
+            TargetAPI::Player,
+            &format!("/artist/{artist_id}"),
+            None::<HashMap<String, String>>,
+        );
+
+        match found {
+            Ok(artist) if artist.id == *artist_id => Ok(artist),
+            Ok(_) | Err(Error::Deserialization(_)) => self.find_by_id(artist_id),
+            // The API answered, just not with this artist.
+            Err(err) if err.status().is_some() => self.find_by_id(artist_id),
+            Err(err) => Err(err),
+        }
+    }
+
+    /// Find an artist by ID among all artists, page by page.
+    fn find_by_id(&self, artist_id: &ArtistID) -> Result<Artist, Error> {
+        let mut offset = 0;
+        loop {
+            let page = self.get_all(Some(RequestParameters::from_pagination(
+                PaginationParameters {
+                    limit: FIND_PAGE_SIZE,
+                    offset,
+                },
+            )))?;
+            let next = page.next_offset();
+            if let Some(artist) = page
+                .data
+                .into_iter()
+                .flatten()
+                .find(|artist| artist.id == *artist_id)
+            {
+                return Ok(artist);
+            }
+
+            offset = match next {
+                Some(next) if next > offset => next,
+                _ => return Err(Error::NotFound("artist")),
+            };
+        }
+    }
+
+    /// Find an artist by name or name URI, when the exact URI isn't known.
+    ///
+    /// Tries the URI derived from the name with [`Artist::uri_from_name`], then
+    /// the same without hyphens, and finally falls back to searching artists
+    /// and picking the best match.
+    ///
+    /// Example
+    /// ```rust
+    /// use bombay::client::Client;
+    ///
+    /// let mc = Client::default(); // Without authentication.
+    ///
+    /// if let Ok(artist) = mc.artist().resolve("Ace Aura") {
+    ///   println!("Found {} at {}.", artist.name, artist.uri);
+    /// }
+    /// ```
+    pub fn resolve(&self, name_or_uri: &str) -> Result<Artist, Error> {
+        let uri = Artist::uri_from_name(name_or_uri);
+        let mut candidates = vec![uri.replace('-', "")];
+        if candidates[0] != uri {
+            candidates.insert(0, uri);
+        }
+
+        for candidate in candidates.into_iter().filter(|uri| !uri.is_empty()) {
+            match self.get_by_name_uri(&candidate) {
+                Ok(artist) => return Ok(artist),
+                // The API answered, just not with this artist.
+                Err(err) if err.status().is_some() => continue,
+                Err(Error::Deserialization(_)) => continue,
+                Err(err) => return Err(err),
+            }
+        }
+
+        let artists = self
+            .get_all(Some(RequestParameters::from_search(name_or_uri.to_owned())))?
+            .data
+            .unwrap_or_default();
+
+        match_artist(name_or_uri, artists)
+            .into_iter()
+            .next()
+            .map(|(artist, _)| artist)
+            .ok_or(Error::NotFound("artist"))
+    }
+
     /// Get latest artists.
     ///
     /// Use the optional parameters to alter the pagination or search term.
@@ -0,0 +1,52 @@
+use crate::client::endpoints::TargetAPI;
+use crate::client::response::Wrapped;
+use crate::client::{EndpointChart, Error};
+use crate::mc::chart::{Chart, ChartID};
+use std::collections::HashMap;
+
+impl<ClientAuthState> EndpointChart<'_, ClientAuthState> {
+    /// Get the identifiers of all charts.
+    ///
+    /// Unverified: `/charts` is a guessed route.
+    ///
+    /// Example URL: <https://player.monstercat.app/api/charts>
+    pub fn get_all(&self) -> Result<Vec<ChartID>, Error> {
+        self.client
+            .get::<Wrapped<Vec<ChartID>>>(
+                TargetAPI::Player,
+                "/charts",
+                None::<HashMap<String, String>>,
+            )?
+            .remove("Charts")
+            .ok_or(Error::NotFound("charts"))
+    }
+
+    /// Get the current edition of a chart.
+    ///
+    /// Unverified: `/chart/{id}` is a guessed route.
+    ///
+    /// Example
+    /// ```rust,no_run
+    /// use bombay::client::Client;
+    /// use bombay::mc::chart::ChartID;
+    ///
+    /// let mc = Client::default(); // Without authentication.
+    /// let weekly = mc.chart().get(&ChartID::Weekly).expect("Could not get chart.");
+    ///
+    /// for entry in weekly.entries {
+    ///   println!("{}. {} ({:?})", entry.rank, entry.track.title, entry.movement());
+    /// }
+    /// ```
+    ///
+    /// Example URL: <https://player.monstercat.app/api/chart/weekly>
+    pub fn get(&self, chart_id: &ChartID) -> Result<Chart, Error> {
+        self.client
+            .get::<Wrapped<Chart>>(
+                TargetAPI::Player,
+                format!("/chart/{chart_id}"),
+                None::<HashMap<String, String>>,
+            )?
+            .remove("Chart")
+            .ok_or(Error::NotFound("chart"))
+    }
+}
@@ -0,0 +1,67 @@
+use crate::client::endpoints::TargetAPI;
+use crate::client::response::Wrapped;
+use crate::client::{EndpointConnect, Error, SignedIn};
+use crate::mc::connect::{AuthCode, AuthUrl, ConnectService, Connection};
+use std::collections::HashMap;
+use url::Url;
+
+impl EndpointConnect<'_, SignedIn> {
+    /// Get the URL to send the user to, to authorize connecting a service.
+    ///
+    /// After authorizing, the service redirects back with a code (and state)
+    /// to pass to [`EndpointConnect::exchange_code`].
+    ///
+    /// Unverified: `/me/connect/{service}` is a guessed route.
+    pub fn get_auth_url(&self, service: &ConnectService) -> Result<Url, Error> {
+        let resp = self.client.get::<AuthUrl>(
+            TargetAPI::Player,
+            format!("/me/connect/{service}"),
+            None::<HashMap<String, String>>,
+        )?;
+
+        Ok(resp.url)
+    }
+
+    /// Finish connecting a service using the code it redirected back with.
+    ///
+    /// Unverified: `/me/connect/{service}/callback` is a guessed route.
+    pub fn exchange_code(
+        &self,
+        service: &ConnectService,
+        code: String,
+        state: Option<String>,
+    ) -> Result<(), Error> {
+        self.client.post_empty_response(
+            TargetAPI::Player,
+            format!("/me/connect/{service}/callback"),
+            None::<HashMap<String, String>>,
+            Some(AuthCode { code, state }),
+        )
+    }
+
+    /// Get the services connected to the user's account.
+    ///
+    /// Unverified: `/me/connections` is a guessed route.
+    pub fn get_connections(&self) -> Result<Vec<Connection>, Error> {
+        self.client
+            .get::<Wrapped<Vec<Connection>>>(
+                TargetAPI::Player,
+                "/me/connections",
+                None::<HashMap<String, String>>,
+            )?
+            .remove("Connections")
+            .ok_or(Error::NotFound("connections"))
+    }
+
+    /// Disconnect a service from the user's account.
+    ///
+    /// Unverified: `/me/connect/{service}/delete` is a guessed route.
+    pub fn disconnect(&self, service: &ConnectService) -> Result<(), Error> {
+        self.client.post_empty_response(
+            TargetAPI::Player,
+            format!("/me/connect/{service}/delete"),
+            None::<HashMap<String, String>>,
+            None::<()>,
+        )
+    }
+}
@@ -0,0 +1,76 @@
+use crate::client::endpoints::TargetAPI;
+use crate::client::response::{Paginated, Wrapped};
+use crate::client::{EndpointEvent, Error, RequestParameters, SignedIn};
+use crate::mc::event::{Event, EventID};
+use std::collections::HashMap;
+
+impl<ClientAuthState> EndpointEvent<'_, ClientAuthState> {
+    /// Get upcoming events.
+    ///
+    /// Use the optional parameters to alter the pagination or search term.
+    ///
+    /// Unverified: the MC API isn't known to serve `/events`, so the route and
+    /// the shape of its response are guesses.
+    ///
+    /// Example
+    /// ```rust
+    /// use bombay::client::Client;
+    ///
+    /// let mc = Client::default(); // Without authentication.
+    /// let events_res = mc.event().get_upcoming(None);
+    ///
+    /// if let Ok(events) = events_res {
+    ///   println!("There are {} upcoming events.", events.total);
+    /// }
+    /// ```
+    ///
+    /// Example URL: <https://player.monstercat.app/api/events>
+    pub fn get_upcoming(
+        &self,
+        parameters: Option<RequestParameters>,
+    ) -> Result<Paginated<Event>, Error> {
+        self.client
+            .get::<Wrapped<Paginated<Event>>>(TargetAPI::Player, "/events", parameters)?
+            .remove("Events")
+            .ok_or(Error::NotFound("upcoming events"))
+    }
+}
+
+impl EndpointEvent<'_, SignedIn> {
+    /// Get upcoming events the user asked to be reminded of.
+    ///
+    /// Unverified: `/me/events` is a guessed route, like [`Self::get_upcoming`].
+    pub fn get_reminded(
+        &self,
+        parameters: Option<RequestParameters>,
+    ) -> Result<Paginated<Event>, Error> {
+        self.client
+            .get::<Wrapped<Paginated<Event>>>(TargetAPI::Player, "/me/events", parameters)?
+            .remove("Events")
+            .ok_or(Error::NotFound("reminded events"))
+    }
+
+    /// Mark interest in an event and get reminded of it.
+    ///
+    /// Unverified: `/event/{id}/remind` is a guessed route.
+    pub fn remind(&self, event_id: EventID) -> Result<(), Error> {
+        self.client.post_empty_response(
+            TargetAPI::Player,
+            format!("/event/{event_id}/remind"),
+            None::<HashMap<String, String>>,
+            None::<()>,
+        )
+    }
+
+    /// Remove interest in an event and stop being reminded of it.
+    ///
+    /// Unverified: `/event/{id}/remind/delete` is a guessed route.
+    pub fn remove_reminder(&self, event_id: EventID) -> Result<(), Error> {
+        self.client.post_empty_response(
+            TargetAPI::Player,
+            format!("/event/{event_id}/remind/delete"),
+            None::<HashMap<String, String>>,
+            None::<()>,
+        )
+    }
+}
@@ -1,6 +1,9 @@
 #![doc = include_str!("README.md")]
 
 mod artist;
+mod chart;
+mod connect;
+mod event;
 mod mood;
 mod playlist;
 mod release;
@@ -8,12 +11,6 @@ mod user;
 
 use crate::client::{Client, SignedIn};
 
-pub use artist::*;
-pub use mood::*;
-pub use playlist::*;
-pub use release::*;
-pub use user::*;
-
 /// Type enumerating the two base endpoints for the Monstercat API.
 #[derive(Default)]
 pub enum TargetAPI {
@@ -38,6 +35,27 @@ pub struct EndpointArtist<'a, ClientAuthState> {
     pub client: &'a Client<ClientAuthState>,
 }
 
+/// Endpoint to retrieve charts, like the weekly top tracks.
+///
+/// Unverified: the MC API isn't known to have the `/charts` and `/chart/{id}`
+/// routes this uses, they are guesses.
+pub struct EndpointChart<'a, ClientAuthState> {
+    pub client: &'a Client<ClientAuthState>,
+}
+
+/// Endpoint to connect external services, like YouTube or Twitch, for licensing.
+///
+/// Unverified: the MC API isn't known to have the `/me/connect` routes this
+/// uses, they are guesses.
+pub struct EndpointConnect<'a, ClientAuthState = SignedIn> {
+    pub client: &'a Client<ClientAuthState>,
+}
+
+/// Endpoint to retrieve events and manage event reminders.
+pub struct EndpointEvent<'a, ClientAuthState> {
+    pub client: &'a Client<ClientAuthState>,
+}
+
 /// Endpoint to retrieve one or more moods.
 pub struct EndpointMood<'a, ClientAuthState> {
     pub client: &'a Client<ClientAuthState>,
@@ -1,7 +1,8 @@
 use crate::client::endpoints::TargetAPI;
 use crate::client::response::{Paginated, Wrapped};
 use crate::client::{EndpointMood, Error, RequestParameters};
-use crate::mc::mood::Mood;
+use crate::mc::mood::{Mood, MoodID};
+use crate::mc::release::AnyRelease;
 use std::collections::HashMap;
 use std::fmt::Display;
 
@@ -60,4 +61,65 @@ impl<ClientAuthState> EndpointMood<'_, ClientAuthState> {
             .remove("Mood")
             .ok_or(Error::NotFound("mood"))
     }
+
+    /// Get mood by ID, which unlike the name URI doesn't change when the
+    /// mood is renamed.
+    ///
+    /// Unverified: the MC API isn't known to serve `/mood/{id}`, the route and
+    /// its `Mood` wrapper are guesses after the name URI route.
+    ///
+    /// Example
+    /// ```rust,no_run
+    /// use bombay::client::Client;
+    ///
+    /// let mc = Client::default(); // Without authentication.
+    /// let chill = mc.mood().get_by_name_uri("chill").expect("Could not find mood.");
+    ///
+    /// // Later, even if the mood was renamed since.
+    /// if let Ok(mood) = mc.mood().get_by_id(&chill.id) {
+    ///   println!("Found mood {}.", mood.name);
+    /// }
+    /// ```
+    pub fn get_by_id(&self, mood_id: &MoodID) -> Result<Mood, Error> {
+        self.client
+            .get::<Wrapped<Mood>>(
+                TargetAPI::Player,
+                &format!("/mood/{mood_id}"),
+                None::<HashMap<String, String>>,
+            )?
+            .remove("Mood")
+            .ok_or(Error::NotFound("mood"))
+    }
+
+    /// Get tracks matching a mood by its name uri.
+    ///
+    /// Use the optional parameters to alter the pagination or search term.
+    ///
+    /// Unverified: the MC API isn't known to serve `/mood/{uri}/catalog`, so
+    /// the route is a guess after `/playlist/{id}/catalog`.
+    ///
+    /// Example
+    /// ```rust
+    /// use bombay::client::Client;
+    ///
+    /// let mc = Client::default(); // Without authentication.
+    /// let chill_tracks_res = mc.mood().get_tracks_by_name_uri("chill", None);
+    ///
+    /// if let Ok(chill_tracks) = chill_tracks_res {
+    ///   println!("There are {} chill tracks.", chill_tracks.total);
+    /// }
+    /// ```
+    ///
+    /// Example URL: <https://player.monstercat.app/api/mood/chill/catalog>
+    pub fn get_tracks_by_name_uri(
+        &self,
+        mood_name_uri: impl AsRef<str> + Display,
+        parameters: Option<RequestParameters>,
+    ) -> Result<Paginated<AnyRelease>, Error> {
+        self.client.get::<Paginated<AnyRelease>>(
+            TargetAPI::Player,
+            &format!("/mood/{mood_name_uri}/catalog"),
+            Some(parameters.unwrap_or_default()),
+        )
+    }
 }
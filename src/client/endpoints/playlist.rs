@@ -2,18 +2,28 @@ use std::collections::HashMap;
 
 use crate::client::endpoints::TargetAPI;
 use crate::client::{
-    EndpointPlaylist, Error, PlaylistItemMod, PlaylistItemOperations, PlaylistItemsMod,
-    PlaylistItemsOperations, Wrapped,
+    EndpointPlaylist, Error, PaginationParameters, PlaylistItemMod, PlaylistItemOperations,
+    PlaylistItemsMod, PlaylistItemsOperations, RequestParameters, UndoStep, UndoToken, Wrapped,
 };
 use crate::client::{Paginated, SignedIn};
-use crate::mc::playlist::{Playlist, PlaylistID};
-use crate::mc::release::AnyRelease;
+use crate::dedupe::normalize_isrc;
+use crate::mc::playlist::{
+    BackedUpTrack, Playlist, PlaylistBackup, PlaylistID, PlaylistInvite, PlaylistItem,
+    PlaylistMember, PlaylistMemberPermission, PlaylistPermission, PlaylistRestore,
+};
+use crate::mc::release::{AnyRelease, CatalogID, Track};
+use crate::mc::user::UserID;
 use uuid::uuid;
 
 const TOP_30: PlaylistID = PlaylistID(uuid!("991334fb-ca5e-48c6-bc73-cb83c364357d"));
 
+/// Tracks fetched per page when reading every track of a playlist.
+const TRACKS_PAGE_SIZE: usize = 100;
+
 impl<ClientAuthState> EndpointPlaylist<'_, ClientAuthState> {
     /// Get the public playlist of top 30 tracks.
+    ///
+    /// For ranks and rank movement, see [`crate::client::endpoints::EndpointChart`].
     pub fn get_top_30_playlist_id(&self) -> PlaylistID {
         TOP_30
     }
@@ -27,7 +37,7 @@ impl<ClientAuthState> EndpointPlaylist<'_, ClientAuthState> {
                 None::<HashMap<String, String>>,
             )?
             .remove("Playlist")
-            .ok_or(Error::NotFound("latest artists"))
+            .ok_or(Error::NotFound("playlist"))
     }
 
     /// Get the tracks of a playlist.
@@ -42,6 +52,83 @@ impl<ClientAuthState> EndpointPlaylist<'_, ClientAuthState> {
         )
     }
 
+    /// Get every track of a playlist, in order, page by page.
+    pub(crate) fn get_all_tracks(&self, id: PlaylistID) -> Result<Vec<Track>, Error> {
+        let mut tracks = Vec::new();
+        let mut offset = 0;
+        loop {
+            let page = self.client.get::<Paginated<AnyRelease>>(
+                TargetAPI::Player,
+                &format!("/playlist/{id}/catalog"),
+                Some(RequestParameters::from_pagination(PaginationParameters {
+                    limit: TRACKS_PAGE_SIZE,
+                    offset,
+                })),
+            )?;
+            let next = page.next_offset();
+            tracks.extend(
+                page.data
+                    .into_iter()
+                    .flatten()
+                    .filter_map(|release| match release {
+                        AnyRelease::Track(track) => Some(track),
+                        AnyRelease::Release(_) => None,
+                    }),
+            );
+
+            offset = match next {
+                Some(next) if next > offset => next,
+                _ => break,
+            };
+        }
+        Ok(tracks)
+    }
+
+    /// Get the tracks of a playlist as its items, in order.
+    pub(crate) fn get_items(&self, id: PlaylistID) -> Result<Vec<PlaylistItem>, Error> {
+        Ok(self
+            .get_all_tracks(id)?
+            .into_iter()
+            .enumerate()
+            .map(|(sort, track)| PlaylistItem {
+                playlist_id: id,
+                release_id: track.release.id,
+                sort,
+                track_id: track.id,
+            })
+            .collect())
+    }
+
+    /// Back up a playlist and all its tracks, to restore it later with
+    /// [`EndpointPlaylist::restore`].
+    ///
+    /// Tracks are fetched page by page, so long playlists are backed up whole.
+    ///
+    /// Example
+    /// ```rust,no_run
+    /// use bombay::client::Client;
+    /// use bombay::mc::playlist::PlaylistID;
+    /// use uuid::uuid;
+    ///
+    /// let mc = Client::default(); // Without authentication.
+    /// let backup = mc.playlist().backup(
+    ///     PlaylistID(uuid!("991334fb-ca5e-48c6-bc73-cb83c364357d"))
+    /// ).expect("Could not back up playlist.");
+    ///
+    /// let json = serde_json::to_string(&backup).unwrap();
+    /// std::fs::write("top_30.json", json).expect("Could not save backup.");
+    /// ```
+    pub fn backup(&self, id: PlaylistID) -> Result<PlaylistBackup, Error> {
+        let playlist = self.by_id(id)?;
+        let tracks = self
+            .get_all_tracks(id)?
+            .iter()
+            .map(BackedUpTrack::from)
+            .collect();
+
+        Ok(PlaylistBackup { playlist, tracks })
+    }
+
     /// Get playlist tile image.
     ///
     /// Example
@@ -109,7 +196,7 @@ impl EndpointPlaylist<'_, SignedIn> {
                 None::<HashMap<String, String>>,
             )?
             .remove("Playlists")
-            .ok_or(Error::NotFound("Playlists not found."))
+            .ok_or(Error::NotFound("playlists"))
     }
 
     /// Create a playlist.
@@ -122,7 +209,100 @@ impl EndpointPlaylist<'_, SignedIn> {
                 Some(playlist),
             )?
             .remove("Id")
-            .ok_or(Error::NotFound("Playlist not found."))
+            .ok_or(Error::NotFound("playlist ID"))
+    }
+
+    /// Restore a playlist backup as a new playlist.
+    ///
+    /// Tracks removed from their release since the backup are replaced by the
+    /// same recording on another release, found by ISRC, if there is one.
+    /// Those that can't be found are left out, and reported as missing.
+    pub fn restore(&self, backup: PlaylistBackup) -> Result<PlaylistRestore, Error> {
+        let mut releases: HashMap<CatalogID, Vec<Track>> = HashMap::new();
+        let mut found = Vec::new();
+        let mut replaced = Vec::new();
+        let mut missing = Vec::new();
+
+        // Find every track before creating the playlist, so that a failed
+        // lookup doesn't leave an empty playlist behind.
+        for track in backup.tracks {
+            if !releases.contains_key(&track.catalog_id) {
+                let tracks = self.release_tracks(&track.catalog_id)?;
+                releases.insert(track.catalog_id.clone(), tracks);
+            }
+            if releases[&track.catalog_id]
+                .iter()
+                .any(|present| present.id == track.track_id)
+            {
+                found.push((track.release_id, track.track_id));
+                continue;
+            }
+
+            match self.find_by_isrc(&track.isrc)? {
+                Some(other) => {
+                    found.push((other.release.id, other.id));
+                    replaced.push((track, other.id));
+                }
+                None => missing.push(track),
+            }
+        }
+
+        let playlist_id = self.create(backup.playlist)?;
+        let items: Vec<PlaylistItem> = found
+            .into_iter()
+            .enumerate()
+            .map(|(sort, (release_id, track_id))| PlaylistItem {
+                playlist_id,
+                release_id,
+                sort,
+                track_id,
+            })
+            .collect();
+        if !items.is_empty() {
+            self.modify_items(
+                playlist_id,
+                PlaylistItemsOperations::Add,
+                PlaylistItemsMod {
+                    records: items.clone(),
+                },
+            )?;
+        }
+
+        Ok(PlaylistRestore {
+            playlist_id,
+            items,
+            replaced,
+            missing,
+        })
+    }
+
+    /// Get the tracks of a release, or none if the release is gone.
+    fn release_tracks(&self, catalog_id: &CatalogID) -> Result<Vec<Track>, Error> {
+        match self.client.release().get_by_catalog_id(catalog_id) {
+            Ok((_, tracks)) => Ok(tracks),
+            Err(err) if err.status() == Some(404) => Ok(Vec::new()),
+            Err(err) => Err(err),
+        }
+    }
+
+    /// Search for a track of the same recording.
+    fn find_by_isrc(&self, isrc: &str) -> Result<Option<Track>, Error> {
+        let isrc = normalize_isrc(isrc);
+        if isrc.is_empty() {
+            return Ok(None);
+        }
+
+        Ok(self
+            .client
+            .release()
+            .get_all(Some(RequestParameters::from_search(isrc.clone())))?
+            .data
+            .unwrap_or_default()
+            .into_iter()
+            .find_map(|release| match release {
+                AnyRelease::Track(track) if normalize_isrc(&track.isrc) == isrc => Some(track),
+                _ => None,
+            }))
     }
 
     /// Edit a playlist.
@@ -180,4 +360,173 @@ impl EndpointPlaylist<'_, SignedIn> {
             None::<()>,
         )
     }
+
+    /// Edit a playlist, and get how to undo it.
+    pub fn edit_with_undo(&self, playlist: Playlist) -> Result<(Playlist, UndoToken), Error> {
+        let before = Playlist {
+            items: None,
+            ..self.by_id(playlist.id)?
+        };
+        let edited = self.edit(playlist)?;
+
+        Ok((
+            edited,
+            UndoToken {
+                playlist_id: before.id,
+                steps: vec![UndoStep::Edit(before)],
+            },
+        ))
+    }
+
+    /// Modify a single playlist item, and get how to undo it.
+    ///
+    /// Example
+    /// ```rust,no_run
+    /// use bombay::client::{Client, PlaylistItemMod, PlaylistItemOperations};
+    /// use bombay::mc::playlist::{PlaylistID, PlaylistItem};
+    /// use bombay::mc::release::{ReleaseID, TrackID};
+    /// use uuid::uuid;
+    ///
+    /// let mc = Client::default()
+    ///     .restore_session("cookie".to_owned())
+    ///     .expect("Could not restore session.");
+    /// let playlist_id = PlaylistID(uuid!("f4a8b5a4-4c2c-4b5e-9fd1-6a6a3a37d1a2"));
+    /// let record = PlaylistItem {
+    ///     playlist_id,
+    ///     release_id: ReleaseID(uuid!("6a58b6d2-bbec-4847-8dcf-45023a930968")),
+    ///     sort: 0,
+    ///     track_id: TrackID(uuid!("3c1c5bd2-9f5e-4d0b-a7a7-2bb3d1c4a0e1")),
+    /// };
+    ///
+    /// let undo = mc.playlist().modify_item_with_undo(
+    ///     playlist_id,
+    ///     PlaylistItemOperations::Remove,
+    ///     PlaylistItemMod { move_to: None, record },
+    /// ).expect("Could not remove item.");
+    ///
+    /// // Oops, put it back where it was.
+    /// mc.playlist().undo(undo).expect("Could not undo.");
+    /// ```
+    pub fn modify_item_with_undo(
+        &self,
+        playlist_id: PlaylistID,
+        operation: PlaylistItemOperations,
+        item_mod: PlaylistItemMod,
+    ) -> Result<UndoToken, Error> {
+        let before = self.get_items(playlist_id)?;
+        let undo = UndoToken::of_item(playlist_id, &operation, &item_mod, &before);
+        self.modify_item(playlist_id, operation, item_mod)?;
+        Ok(undo)
+    }
+
+    /// Modify multiple playlist items, and get how to undo it.
+    pub fn modify_items_with_undo(
+        &self,
+        playlist_id: PlaylistID,
+        operation: PlaylistItemsOperations,
+        items_mod: PlaylistItemsMod,
+    ) -> Result<UndoToken, Error> {
+        let before = self.get_items(playlist_id)?;
+        let undo = UndoToken::of_items(playlist_id, &operation, &items_mod, &before);
+        self.modify_items(playlist_id, operation, items_mod)?;
+        Ok(undo)
+    }
+
+    /// Delete playlist, and get how to undo it.
+    ///
+    /// Undoing creates the playlist anew from a backup, with another ID.
+    pub fn delete_with_undo(&self, playlist_id: PlaylistID) -> Result<UndoToken, Error> {
+        let backup = self.backup(playlist_id)?;
+        self.delete(playlist_id)?;
+
+        Ok(UndoToken {
+            playlist_id,
+            steps: vec![UndoStep::Restore(backup)],
+        })
+    }
+
+    /// Undo a playlist mutation.
+    ///
+    /// Returns the ID of the playlist undone, which is new if it was restored
+    /// after being deleted.
+    pub fn undo(&self, token: UndoToken) -> Result<PlaylistID, Error> {
+        let mut playlist_id = token.playlist_id;
+        for step in token.steps {
+            match step {
+                UndoStep::Edit(playlist) => {
+                    self.edit(playlist)?;
+                }
+                UndoStep::Item(operation, item_mod) => {
+                    self.modify_item(playlist_id, operation, item_mod)?
+                }
+                UndoStep::Items(operation, items_mod) => {
+                    self.modify_items(playlist_id, operation, items_mod)?
+                }
+                UndoStep::Restore(backup) => playlist_id = self.restore(backup)?.playlist_id,
+            }
+        }
+
+        Ok(playlist_id)
+    }
+
+    /// Get the members of a shared playlist, including its owner.
+    ///
+    /// Unverified: the MC API isn't known to have `/playlist/{id}/members`,
+    /// nor the other member routes below, they are guesses.
+    pub fn get_members(&self, playlist_id: PlaylistID) -> Result<Vec<PlaylistMember>, Error> {
+        self.client
+            .get::<Wrapped<Vec<PlaylistMember>>>(
+                TargetAPI::Player,
+                &format!("/playlist/{playlist_id}/members"),
+                None::<HashMap<String, String>>,
+            )?
+            .remove("Members")
+            .ok_or(Error::NotFound("playlist members"))
+    }
+
+    /// Invite someone by email to collaborate on a playlist.
+    ///
+    /// Unverified: `/playlist/{id}/invite` is a guessed route.
+    pub fn invite_member(
+        &self,
+        playlist_id: PlaylistID,
+        email: String,
+        permission: PlaylistPermission,
+    ) -> Result<(), Error> {
+        self.client.post_empty_response(
+            TargetAPI::Player,
+            format!("/playlist/{playlist_id}/invite"),
+            None::<HashMap<String, String>>,
+            Some(PlaylistInvite { email, permission }),
+        )
+    }
+
+    /// Change what a member is allowed to do with a playlist.
+    ///
+    /// Unverified: `/playlist/{id}/member/{user_id}` is a guessed route.
+    pub fn set_member_permission(
+        &self,
+        playlist_id: PlaylistID,
+        user_id: UserID,
+        permission: PlaylistPermission,
+    ) -> Result<(), Error> {
+        self.client.post_empty_response(
+            TargetAPI::Player,
+            format!("/playlist/{playlist_id}/member/{user_id}"),
+            None::<HashMap<String, String>>,
+            Some(PlaylistMemberPermission { permission }),
+        )
+    }
+
+    /// Remove a member from a playlist, or leave it by removing yourself.
+    ///
+    /// Unverified: `/playlist/{id}/member/{user_id}/delete` is a guessed route.
+    pub fn remove_member(&self, playlist_id: PlaylistID, user_id: UserID) -> Result<(), Error> {
+        self.client.post_empty_response(
+            TargetAPI::Player,
+            format!("/playlist/{playlist_id}/member/{user_id}/delete"),
+            None::<HashMap<String, String>>,
+            None::<()>,
+        )
+    }
 }
@@ -1,12 +1,25 @@
 use crate::client::endpoints::TargetAPI;
-use crate::client::request::RequestParameters;
-use crate::client::{EndpointRelease, Error, Paginated, SignedIn, Wrapped};
-use crate::mc::release::{AnyRelease, CatalogID, ReleaseID, Track, TrackID};
+use crate::client::request::{IdType, PaginationParameters, RequestParameters};
+use crate::client::{
+    DownloadSize, EndpointRelease, Error, Paginated, SignedIn, TrackStream, Wrapped,
+};
+use crate::mc::artist::Role;
+use crate::mc::release::{
+    AnyRelease, AnyReleaseListItem, CatalogID, Genre, ReleaseID, ReleaseListItem, ReleasePage,
+    ReleaseWithTracks, Track, TrackFeatures, TrackID, VersionSet,
+};
 use crate::mc::util::Codec;
-use serde_json::Value;
+#[cfg(feature = "datetime")]
+use chrono::NaiveDate;
+#[cfg(feature = "datetime")]
+use chrono_tz::Tz;
+use iso8601_timestamp::Timestamp;
 use std::collections::HashMap;
 use std::fmt::Display;
 
+/// Releases requested per page while looking for those of some days.
+const SCHEDULE_PAGE_SIZE: usize = 50;
+
 impl<ClientAuthState> EndpointRelease<'_, ClientAuthState> {
     /// Get all releases.
     ///
@@ -35,6 +48,57 @@ impl<ClientAuthState> EndpointRelease<'_, ClientAuthState> {
             .ok_or(Error::NotFound("all releases"))
     }
 
+    /// Get all releases as list items, for list views that don't need their
+    /// tracks or artists, see [`ReleaseListItem`].
+    ///
+    /// Every field is asked for unless the parameters select some, as the
+    /// releases listed include tracks, whose release and genre are not
+    /// release fields, see [`RequestParameters::set_fields`]. Items are
+    /// slimmed once received.
+    ///
+    /// Example
+    /// ```rust
+    /// use bombay::client::{Client, PaginationParameters, RequestParameters};
+    ///
+    /// let mc = Client::default(); // Without authentication.
+    /// let page = RequestParameters::from_pagination(PaginationParameters { limit: 100, offset: 0 });
+    ///
+    /// if let Ok(releases) = mc.release().get_all_slim(Some(page)) {
+    ///   for release in releases.data.unwrap_or_default() {
+    ///     println!("{} by {}", release.title, release.artists_title);
+    ///   }
+    /// }
+    /// ```
+    pub fn get_all_slim(
+        &self,
+        parameters: Option<RequestParameters>,
+    ) -> Result<Paginated<ReleaseListItem>, Error> {
+        let parameters = parameters.unwrap_or(RequestParameters {
+            pagination: None,
+            ..RequestParameters::default()
+        });
+
+        let page = self
+            .client
+            .get::<Wrapped<Paginated<AnyReleaseListItem>>>(
+                TargetAPI::Player,
+                "/releases",
+                Some(parameters),
+            )?
+            .remove("Releases")
+            .ok_or(Error::NotFound("all releases"))?;
+
+        Ok(Paginated {
+            data: page
+                .data
+                .map(|items| items.into_iter().map(ReleaseListItem::from).collect()),
+            not_found: page.not_found,
+            total: page.total,
+            limit: page.limit,
+            offset: page.offset,
+        })
+    }
+
     /// Get latest releases.
     ///
     /// Use the optional parameters to alter the pagination or search term.
@@ -112,10 +176,82 @@ impl<ClientAuthState> EndpointRelease<'_, ClientAuthState> {
             .ok_or(Error::NotFound("artist releases"))
     }
 
-    /// Get a release by its catalog ID.
+    /// Get releases of a genre, like the MC player's genre browsing.
     ///
     /// Use the optional parameters to alter the pagination or search term.
     ///
+    /// Unverified: the `genres` filter of `/releases` is a guess, and so are
+    /// the values it is sent, which are the genres' display names, like
+    /// "Drum & Bass".
+    ///
+    /// Example
+    /// ```rust
+    /// use bombay::client::Client;
+    /// use bombay::mc::release::Genre;
+    ///
+    /// let mc = Client::default(); // Without authentication.
+    /// let dnb_res = mc.release().get_by_genre(Genre::DrumAndBass, None);
+    ///
+    /// if let Ok(dnb) = dnb_res {
+    ///   println!("There are {} Drum & Bass releases.", dnb.total);
+    /// }
+    /// ```
+    ///
+    /// Example URL: <https://player.monstercat.app/api/releases?genres=Drum%20%26%20Bass>
+    pub fn get_by_genre(
+        &self,
+        genre: Genre,
+        parameters: Option<RequestParameters>,
+    ) -> Result<Paginated<AnyRelease>, Error> {
+        let mut queries: HashMap<String, String> = parameters.unwrap_or_default().into();
+        queries.insert("genres".to_owned(), genre.to_string());
+
+        self.client
+            .get::<Wrapped<Paginated<AnyRelease>>>(TargetAPI::Player, "/releases", Some(queries))?
+            .remove("Releases")
+            .ok_or(Error::NotFound("genre releases"))
+    }
+
+    /// Get artist's latest releases by their name uri, keeping only those
+    /// where the artist has a role, like remixer rather than primary artist.
+    ///
+    /// The role is filtered locally, so the pagination and total still refer
+    /// to all of the artist's releases. Releases that don't include their
+    /// artists are left out.
+    ///
+    /// Example
+    /// ```rust
+    /// use bombay::client::Client;
+    /// use bombay::mc::artist::Role;
+    ///
+    /// let mc = Client::default(); // Without authentication.
+    /// let remixes_res = mc.release().get_by_artist_name_uri_with_role("rogue", Role::Remixer, None);
+    ///
+    /// if let Ok(remixes) = remixes_res {
+    ///   for release in remixes.data.unwrap_or_default() {
+    ///     println!("Rogue remixed {}!", release.get_title());
+    ///   }
+    /// }
+    /// ```
+    pub fn get_by_artist_name_uri_with_role(
+        &self,
+        artist_name_uri: impl AsRef<str> + Display,
+        role: Role,
+        parameters: Option<RequestParameters>,
+    ) -> Result<Paginated<AnyRelease>, Error> {
+        let mut releases = self.get_by_artist_name_uri(&artist_name_uri, parameters)?;
+
+        if let Some(data) = releases.data.as_mut() {
+            data.retain(|release| release.has_artist_role(artist_name_uri.as_ref(), &role));
+        }
+
+        Ok(releases)
+    }
+
+    /// Get a release by its catalog ID, or by its UPC.
+    ///
+    /// The ID is normalized first, so "mcs1186" finds the same release as "MCS1186".
+    ///
     /// Example
     /// ```rust
     /// use bombay::client::Client;
@@ -136,31 +272,55 @@ impl<ClientAuthState> EndpointRelease<'_, ClientAuthState> {
     /// }
     /// ```
     ///
-    /// Example URL: <https://player.monstercat.app/api/catalog/release/MCS1186>
+    /// Example URL: <https://player.monstercat.app/api/catalog/release/MCS1186?idType=catalogId>
     pub fn get_by_catalog_id(
         &self,
         catalog_id: &CatalogID,
     ) -> Result<(AnyRelease, Vec<Track>), Error> {
-        let mut related_wrapper = self.client.get::<Wrapped<Value>>(
+        // Deserialized in one pass, rather than through an intermediate `Value`.
+        self.get_by_id_type(catalog_id.normalized(), IdType::CatalogId)
+    }
+
+    /// Get a release by any kind of identifier the API accepts.
+    ///
+    /// Unverified for [`IdType::Id`] and [`IdType::Upc`], only `catalogId` is
+    /// known to be accepted.
+    ///
+    /// Example
+    /// ```rust
+    /// use bombay::client::{Client, IdType};
+    ///
+    /// let mc = Client::default(); // Without authentication.
+    /// let release_and_tracks_res = mc.release().get_by_id_type(
+    ///     "6a58b6d2-bbec-4847-8dcf-45023a930968",
+    ///     IdType::Id,
+    /// );
+    ///
+    /// if let Ok((any_release, _)) = release_and_tracks_res {
+    ///   println!("Found {}!", any_release.get_title());
+    /// }
+    /// ```
+    ///
+    /// Example URL: <https://player.monstercat.app/api/catalog/release/MCS1186?idType=catalogId>
+    pub fn get_by_id_type(
+        &self,
+        id: impl Display,
+        id_type: IdType,
+    ) -> Result<(AnyRelease, Vec<Track>), Error> {
+        let release_with_tracks = self.client.get::<ReleaseWithTracks>(
             TargetAPI::Player,
-            &format!("/catalog/release/{catalog_id}?idType=catalogId"),
-            None::<HashMap<String, String>>,
+            &format!("/catalog/release/{id}"),
+            Some(id_type),
         )?;
 
-        let release_val = related_wrapper
-            .remove("Release")
+        let release_obj = release_with_tracks
+            .release
             .ok_or(Error::NotFound("release"))?;
 
-        let release_obj =
-            serde_json::from_value::<AnyRelease>(release_val).map_err(Error::Deserialization)?;
-
-        let tracks_val = related_wrapper
-            .remove("Tracks")
+        let tracks_obj = release_with_tracks
+            .tracks
             .ok_or(Error::NotFound("release tracks"))?;
 
-        let tracks_obj =
-            serde_json::from_value::<Vec<Track>>(tracks_val).map_err(Error::Deserialization)?;
-
         Ok((release_obj, tracks_obj))
     }
 
@@ -191,6 +351,58 @@ impl<ClientAuthState> EndpointRelease<'_, ClientAuthState> {
         )
     }
 
+    /// Get release cover art by release ID, optionally scaled down to a
+    /// width in pixels, like for thumbnails.
+    ///
+    /// For many releases at once, see [`crate::cover_art::CoverArtCache`].
+    ///
+    /// Example URL: <https://player.monstercat.app/api/release/6a58b6d2-bbec-4847-8dcf-45023a930968/cover?width=256>
+    pub fn get_cover_art_by_id(
+        &self,
+        release_id: &ReleaseID,
+        width: Option<u32>,
+    ) -> Result<Box<dyn std::io::Read + Send + Sync>, Error> {
+        let queries: HashMap<String, String> = width
+            .map(|width| ("width".to_owned(), width.to_string()))
+            .into_iter()
+            .collect();
+
+        self.client.get_reader(
+            TargetAPI::Player,
+            format!("/release/{release_id}/cover"),
+            Some(queries),
+        )
+    }
+
+    /// Get a release's page data from the WWW API by its catalog ID.
+    ///
+    /// The page includes details the player API leaves out, like credits,
+    /// writers, copyright lines, and related merch.
+    ///
+    /// Example
+    /// ```rust
+    /// use bombay::client::Client;
+    /// use bombay::mc::release::CatalogID;
+    ///
+    /// let mc = Client::default(); // Without authentication.
+    /// let page_res = mc.release().get_www_page(&CatalogID("MCS1186".to_owned()));
+    ///
+    /// if let Ok(page) = page_res {
+    ///   for credit in page.credits.unwrap_or_default() {
+    ///     println!("{}: {}", credit.role, credit.name);
+    ///   }
+    /// }
+    /// ```
+    ///
+    /// Example URL: <https://www.monstercat.com/api/release/MCS1186>
+    pub fn get_www_page(&self, catalog_id: &CatalogID) -> Result<ReleasePage, Error> {
+        self.client.get::<ReleasePage>(
+            TargetAPI::WWW,
+            &format!("api/release/{catalog_id}"),
+            None::<HashMap<String, String>>,
+        )
+    }
+
     /// Get releases related to another by the release id.
     ///
     /// Use the optional parameters to alter the pagination or search term.
@@ -234,8 +446,166 @@ impl<ClientAuthState> EndpointRelease<'_, ClientAuthState> {
         )
     }
 
+    /// Get tracks similar to a track, finer grained than related releases.
+    ///
+    /// Use the optional parameters to alter the pagination.
+    ///
+    /// Unverified: the MC API isn't known to have `/similar-tracks/{id}`, the
+    /// route is a guess after `/related-releases/{id}`.
+    ///
+    /// Example
+    /// ```rust,no_run
+    /// use bombay::client::Client;
+    /// use bombay::mc::release::TrackID;
+    /// use uuid::uuid;
+    ///
+    /// let mc = Client::default(); // Without authentication.
+    /// let similar = mc
+    ///     .release()
+    ///     .get_similar_tracks(&TrackID(uuid!("a1c2b04d-4c9e-4eb8-a5e2-5f8d1b8b5b1a")), None)
+    ///     .expect("Could not get similar tracks.");
+    ///
+    /// for track in similar.data.unwrap_or_default() {
+    ///   println!("Similar: {} by {}", track.title, track.artists_title);
+    /// }
+    /// ```
+    ///
+    /// Example URL: <https://player.monstercat.app/api/similar-tracks/a1c2b04d-4c9e-4eb8-a5e2-5f8d1b8b5b1a>
+    pub fn get_similar_tracks(
+        &self,
+        track_id: &TrackID,
+        parameters: Option<RequestParameters>,
+    ) -> Result<Paginated<Track>, Error> {
+        self.client.get::<Paginated<Track>>(
+            TargetAPI::Player,
+            &format!("/similar-tracks/{track_id}"),
+            parameters,
+        )
+    }
+
+    /// Get a track's audio features, like energy and danceability.
+    ///
+    /// Unverified: the MC API isn't known to have `/track/{id}/features`, the
+    /// route is a guess. Features that come with tracks are on [`Track`] too.
+    ///
+    /// Example
+    /// ```rust
+    /// use bombay::client::Client;
+    /// use bombay::mc::release::TrackID;
+    /// use uuid::uuid;
+    ///
+    /// let mc = Client::default(); // Without authentication.
+    /// let features_res = mc.release().get_track_features(
+    ///   &TrackID(uuid!("00164f5c-3a1e-44ad-8b73-bfdde22b8b6e"))
+    /// );
+    ///
+    /// if let Ok(features) = features_res {
+    ///   println!("Energy: {:?}", features.energy);
+    /// }
+    /// ```
+    ///
+    /// Example URL: <https://player.monstercat.app/api/track/00164f5c-3a1e-44ad-8b73-bfdde22b8b6e/features>
+    pub fn get_track_features(&self, track_id: &TrackID) -> Result<TrackFeatures, Error> {
+        self.client.get::<TrackFeatures>(
+            TargetAPI::Player,
+            &format!("/track/{track_id}/features"),
+            None::<HashMap<String, String>>,
+        )
+    }
+
+    /// Get remixes of a track, found by searching releases with the track's title.
+    ///
+    /// There is no dedicated remix listing, so this searches and keeps remixes
+    /// sharing the track's title. Use the optional parameters to search further
+    /// than the first page.
+    pub fn get_remixes(
+        &self,
+        track: &Track,
+        parameters: Option<RequestParameters>,
+    ) -> Result<Vec<Track>, Error> {
+        let parameters = parameters
+            .unwrap_or_default()
+            .set_search(track.title.clone());
+
+        let mut remixes = Vec::new();
+        for release in self.get_all(Some(parameters))?.data.unwrap_or_default() {
+            let tracks = match release {
+                AnyRelease::Track(found) => vec![found],
+                AnyRelease::Release(found) => match found.tracks {
+                    Some(tracks) => tracks,
+                    None => self.get_by_catalog_id(&found.catalog_id)?.1,
+                },
+            };
+
+            for found in tracks {
+                if found.is_remix()
+                    && found.is_version_of(track)
+                    && !remixes.iter().any(|remix: &Track| remix.id == found.id)
+                {
+                    remixes.push(found);
+                }
+            }
+        }
+
+        Ok(remixes)
+    }
+
+    /// Get other versions of a track, like its instrumental or clean edit,
+    /// found on its release and by searching releases with its title.
+    ///
+    /// Use the optional parameters to search further than the first page.
+    ///
+    /// Example
+    /// ```rust,no_run
+    /// use bombay::client::Client;
+    /// use bombay::mc::release::CatalogID;
+    ///
+    /// let mc = Client::default(); // Without authentication.
+    /// let (_, tracks) = mc
+    ///     .release()
+    ///     .get_by_catalog_id(&CatalogID("MCS1186".to_owned()))
+    ///     .expect("Could not get release.");
+    ///
+    /// for track in tracks {
+    ///   let versions = mc.release().get_versions(&track, None).expect("Could not get versions.");
+    ///   match versions.stream_safe() {
+    ///     Some(safe) => println!("Play {} ({})", safe.title, safe.version),
+    ///     None => println!("Skip {}", track.title),
+    ///   }
+    /// }
+    /// ```
+    pub fn get_versions(
+        &self,
+        track: &Track,
+        parameters: Option<RequestParameters>,
+    ) -> Result<VersionSet, Error> {
+        let catalog_id = CatalogID(track.release.catalog_id.clone());
+        let mut candidates = self.get_by_catalog_id(&catalog_id)?.1;
+
+        let parameters = parameters
+            .unwrap_or_default()
+            .set_search(track.title.clone());
+        for release in self.get_all(Some(parameters))?.data.unwrap_or_default() {
+            match release {
+                AnyRelease::Track(found) => candidates.push(found),
+                AnyRelease::Release(found) if found.catalog_id == catalog_id => {}
+                AnyRelease::Release(found) => match found.tracks {
+                    Some(tracks) => candidates.extend(tracks),
+                    None => candidates.extend(self.get_by_catalog_id(&found.catalog_id)?.1),
+                },
+            }
+        }
+
+        Ok(VersionSet::from_candidates(track.clone(), candidates))
+    }
+
     /// Stream track using release id and track id.
     ///
+    /// Tracks streamed as HLS playlists are read as one stream, segment after
+    /// segment. Encrypted HLS and DASH streams are not supported. Other
+    /// streams resume where they left off if the connection drops, see
+    /// [`TrackStream`].
+    ///
     /// Example
     /// ```rust
     /// use bombay::client::Client;
@@ -255,12 +625,108 @@ impl<ClientAuthState> EndpointRelease<'_, ClientAuthState> {
         &self,
         release_id: &ReleaseID,
         track_id: &TrackID,
-    ) -> Result<Box<dyn std::io::Read + Send + Sync>, Error> {
-        self.client.get_reader(
+    ) -> Result<TrackStream, Error> {
+        let stream_res = self.client.get_stream(
             TargetAPI::Player,
             format!("/release/{release_id}/track-stream/{track_id}"),
             None::<HashMap<String, String>>,
-        )
+        );
+
+        check_early_access(stream_res)
+    }
+
+    /// Get the releases that are not out yet, soonest last.
+    ///
+    /// Releases are paged through newest first, until they are out. To group
+    /// them by day, see `calendar::ReleaseCalendar` (`datetime` feature).
+    pub fn get_upcoming(&self) -> Result<Vec<AnyRelease>, Error> {
+        let now = Timestamp::now_utc();
+        let mut upcoming = Vec::new();
+        let mut offset = 0;
+
+        loop {
+            let page = self.get_all(Some(RequestParameters::from_pagination(
+                PaginationParameters {
+                    limit: SCHEDULE_PAGE_SIZE,
+                    offset,
+                },
+            )))?;
+            let next = page.next_offset();
+            let releases = page.data.unwrap_or_default();
+            let done = releases.iter().any(|release| *release.get_date() <= now);
+
+            upcoming.extend(
+                releases
+                    .into_iter()
+                    .filter(|release| *release.get_date() > now),
+            );
+
+            offset = match next {
+                Some(next) if next > offset && !done => next,
+                _ => break,
+            };
+        }
+
+        Ok(upcoming)
+    }
+
+    /// Get the releases coming out on a day in a time zone, like today in the
+    /// user's time zone, whatever time zone each release is scheduled in.
+    ///
+    /// Releases are paged through newest first, until they are from before
+    /// that day.
+    ///
+    /// Example
+    /// ```rust
+    /// use bombay::client::Client;
+    /// use bombay::mc::datetime;
+    /// use iso8601_timestamp::Timestamp;
+    ///
+    /// let mc = Client::default(); // Without authentication.
+    /// let timezone = datetime::parse_timezone("Europe/Paris").unwrap();
+    /// let today = datetime::in_timezone(&Timestamp::now_utc(), "Europe/Paris").date_naive();
+    ///
+    /// if let Ok(releases) = mc.release().get_releasing_on(today, timezone) {
+    ///   for release in releases {
+    ///     println!("Out today: {}", release);
+    ///   }
+    /// }
+    /// ```
+    #[cfg(feature = "datetime")]
+    pub fn get_releasing_on(
+        &self,
+        date: NaiveDate,
+        timezone: Tz,
+    ) -> Result<Vec<AnyRelease>, Error> {
+        let day_of =
+            |release: &AnyRelease| release.released_at().with_timezone(&timezone).date_naive();
+        let mut releasing = Vec::new();
+        let mut offset = 0;
+
+        loop {
+            let page = self.get_all(Some(RequestParameters::from_pagination(
+                PaginationParameters {
+                    limit: SCHEDULE_PAGE_SIZE,
+                    offset,
+                },
+            )))?;
+            let next = page.next_offset();
+            let releases = page.data.unwrap_or_default();
+            let done = releases.iter().all(|release| day_of(release) < date);
+
+            releasing.extend(
+                releases
+                    .into_iter()
+                    .filter(|release| day_of(release) == date),
+            );
+
+            offset = match next {
+                Some(next) if next > offset && !done => next,
+                _ => break,
+            };
+        }
+
+        Ok(releasing)
     }
 }
 
@@ -272,10 +738,114 @@ impl EndpointRelease<'_, SignedIn> {
         track_id: &TrackID,
         codec: Option<Codec>,
     ) -> Result<Box<dyn std::io::Read + Send + Sync>, Error> {
-        self.client.get_reader(
+        self.download_sized_by_ids(release_id, track_id, codec)
+            .map(|(reader, _)| reader)
+    }
+
+    /// Download track using release id and track id, with its size, if the
+    /// API tells it.
+    pub(crate) fn download_sized_by_ids(
+        &self,
+        release_id: &ReleaseID,
+        track_id: &TrackID,
+        codec: Option<Codec>,
+    ) -> Result<(Box<dyn std::io::Read + Send + Sync>, Option<u64>), Error> {
+        let download_res = self.client.get_sized_reader(
             TargetAPI::Player,
             format!("/release/{release_id}/track-download/{track_id}"),
             Some(RequestParameters::from_codec(codec.unwrap_or_default())),
+        );
+
+        check_early_access(download_res)
+    }
+
+    /// Estimate the size of downloading a release's tracks with some codec,
+    /// before starting to download them.
+    ///
+    /// Each downloadable track's size is asked for with a HEAD request, one
+    /// after another. Tracks the API doesn't tell the size of are kept, but
+    /// left out of the total.
+    ///
+    /// Unverified: the release is looked up by its ID with [`IdType::Id`],
+    /// which is a guessed `idType`.
+    ///
+    /// Example
+    /// ```rust,no_run
+    /// use bombay::client::{Client, SignedIn};
+    /// use bombay::mc::release::ReleaseID;
+    /// use bombay::mc::util::Codec;
+    /// use uuid::uuid;
+    ///
+    /// # fn example(mc: Client<SignedIn>) -> Result<(), bombay::client::Error> {
+    /// let size = mc.release().estimate_download_size(
+    ///     &ReleaseID(uuid!("e7c6a280-6af3-4101-af9f-5c809afb6541")),
+    ///     Codec::WAV,
+    /// )?;
+    /// println!("This album is {:.1} GB.", size.bytes() as f64 / 1e9);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn estimate_download_size(
+        &self,
+        release_id: &ReleaseID,
+        codec: Codec,
+    ) -> Result<DownloadSize, Error> {
+        let (_, tracks) = self.get_by_id_type(release_id, IdType::Id)?;
+
+        let mut sizes = Vec::new();
+        for track in tracks.iter().filter(|track| track.downloadable) {
+            let size = self.client.content_length(
+                TargetAPI::Player,
+                format!("/release/{release_id}/track-download/{}", track.id),
+                Some(RequestParameters::from_codec(codec.clone())),
+            )?;
+            sizes.push((track.id, size));
+        }
+
+        Ok(DownloadSize { tracks: sizes })
+    }
+
+    /// Get releases in early access, which gold members can listen to before everyone else.
+    ///
+    /// Use the optional parameters to alter the pagination or search term.
+    ///
+    /// Unverified: the MC API isn't known to serve `/catalog/early-access`,
+    /// the route is a guess after `/catalog/latest-releases`.
+    pub fn get_early_access(
+        &self,
+        parameters: Option<RequestParameters>,
+    ) -> Result<Paginated<AnyRelease>, Error> {
+        self.client.get::<Paginated<AnyRelease>>(
+            TargetAPI::Player,
+            "/catalog/early-access",
+            Some(parameters.unwrap_or_default()),
         )
     }
 }
+
+/// Turn a rejected stream or download of locked early access content into
+/// [`Error::EarlyAccessRequired`]. Other results are returned as they are.
+fn check_early_access<RT>(result: Result<RT, Error>) -> Result<RT, Error> {
+    match result {
+        Err(Error::Request(err)) => match *err {
+            ureq::Error::Status(status @ (401 | 403), response) => {
+                let status_text = response.status_text().to_owned();
+                let body = response.into_string().unwrap_or_default();
+
+                if body.to_lowercase().contains("early access") {
+                    return Err(Error::EarlyAccessRequired);
+                }
+
+                // The body was consumed, so rebuild the response for the caller.
+                match ureq::Response::new(status, &status_text, &body) {
+                    Ok(response) => Err(Error::Request(Box::new(ureq::Error::Status(
+                        status, response,
+                    )))),
+                    Err(err) => Err(Error::from(err)),
+                }
+            }
+            err => Err(Error::from(err)),
+        },
+        res => res,
+    }
+}
@@ -1,39 +1,37 @@
 use std::collections::HashMap;
 
 use crate::client::endpoints::TargetAPI;
-use crate::client::{EndpointUser, Error, RequestParameters, Wrapped};
+use crate::client::{
+    EndpointUser, Error, PaginationParameters, PlaylistItemsMod, PlaylistItemsOperations,
+    RequestParameters, Wrapped,
+};
 use crate::client::{Paginated, SignedIn};
+use crate::mc::playlist::{PlaylistID, PlaylistItem};
 use crate::mc::user::{
-    EditableSettings, EditableUserInfo, NewEmail, NewPassword, NotificationInterests, PlayerCode,
-    Settings, ShopCode, User,
+    AccountDeletion, BackupCodes, DataExport, EditableSettings, EditableUserInfo, NewEmail,
+    NewPassword, NotificationInterests, PlayerCode, PlayerWidget, PlaylistExport, Settings,
+    ShopCode, TwoFactorToken, User, UserExport, UserInfo,
 };
-use crate::mc::util::{ClaimVideoId, License, LicenseID};
-use serde_json::Value;
+use crate::mc::util::{ClaimVideoId, DateRange, License, LicenseID, LicenseStats};
+use iso8601_timestamp::Timestamp;
+
+/// Licenses requested per page while exporting user data.
+const EXPORT_PAGE_SIZE: usize = 50;
 
 impl EndpointUser<'_, SignedIn> {
     /// Get user information and settings.
     pub fn get_info(&self) -> Result<(Settings, User), Error> {
-        let mut user_info_wrapper = self.client.get::<Wrapped<Value>>(
+        let user_info = self.client.get::<UserInfo>(
             TargetAPI::Player,
             "/me",
             None::<HashMap<String, String>>,
         )?;
 
-        let settings_val = user_info_wrapper
-            .remove("Settings")
-            .ok_or(Error::NotFound("user settings"))?;
-
-        let release_obj =
-            serde_json::from_value::<Settings>(settings_val).map_err(Error::Deserialization)?;
-
-        let user_val = user_info_wrapper
-            .remove("User")
-            .ok_or(Error::NotFound("user information"))?;
+        let settings = user_info.settings.ok_or(Error::NotFound("user settings"))?;
 
-        let tracks_obj =
-            serde_json::from_value::<User>(user_val).map_err(Error::Deserialization)?;
+        let user = user_info.user.ok_or(Error::NotFound("user information"))?;
 
-        Ok((release_obj, tracks_obj))
+        Ok((settings, user))
     }
 
     /// Set some editable user information.
@@ -47,7 +45,10 @@ impl EndpointUser<'_, SignedIn> {
     }
 
     /// Set some editable user settings.
+    ///
+    /// Settings are validated first, see [`EditableSettings::validate`].
     pub fn set_settings(&self, user_info: EditableSettings) -> Result<(), Error> {
+        self.validate_settings(&user_info)?;
         self.client.post_empty_response(
             TargetAPI::Player,
             "/me/settings",
@@ -56,6 +57,49 @@ impl EndpointUser<'_, SignedIn> {
         )
     }
 
+    /// Change user settings from the current ones, which are fetched first,
+    /// so settings left alone are posted as they are instead of reset.
+    ///
+    /// Returns the settings posted.
+    ///
+    /// Example
+    /// ```rust,no_run
+    /// use bombay::client::Client;
+    /// use bombay::mc::util::Codec;
+    ///
+    /// let mc = Client::default()
+    ///     .restore_session("cookie".to_owned())
+    ///     .expect("Could not restore session.");
+    ///
+    /// mc.user()
+    ///     .update_settings(|settings| settings.preferred_format = Some(Codec::FLAC))
+    ///     .expect("Could not update settings.");
+    /// ```
+    pub fn update_settings(
+        &self,
+        update: impl FnOnce(&mut EditableSettings),
+    ) -> Result<EditableSettings, Error> {
+        let (current, _) = self.get_info()?;
+        let mut settings = EditableSettings::from(&current);
+        update(&mut settings);
+
+        self.set_settings(settings.clone())?;
+        Ok(settings)
+    }
+
+    /// Validate settings, only getting the connected services if needed.
+    fn validate_settings(&self, settings: &EditableSettings) -> Result<(), Error> {
+        let connections =
+            match settings.say_song == Some(true) || settings.auto_say_song == Some(true) {
+                true => self.client.connect().get_connections()?,
+                false => Vec::new(),
+            };
+
+        settings
+            .validate(&connections)
+            .map_err(Error::InvalidSettings)
+    }
+
     /// Get streaming widget player code.
     pub fn get_player_code(&self) -> Result<String, Error> {
         let resp = self.client.get::<PlayerCode>(
@@ -77,6 +121,43 @@ impl EndpointUser<'_, SignedIn> {
         )
     }
 
+    /// Revoke the streaming widget player code, so the current widget stops working.
+    ///
+    /// Unverified: the MC API isn't known to have `/me/player-code/delete`,
+    /// the route is a guess after the playlist delete route.
+    pub fn revoke_player_code(&self) -> Result<(), Error> {
+        self.client.post_empty_response(
+            TargetAPI::Player,
+            "/me/player-code/delete",
+            None::<HashMap<String, String>>,
+            None::<()>,
+        )
+    }
+
+    /// Get streaming widget configuration.
+    ///
+    /// Unverified: the MC API isn't known to have `/me/player-widget`, the
+    /// route and its fields are guesses.
+    pub fn get_player_widget(&self) -> Result<PlayerWidget, Error> {
+        self.client.get::<PlayerWidget>(
+            TargetAPI::Player,
+            "/me/player-widget",
+            None::<HashMap<String, String>>,
+        )
+    }
+
+    /// Set some streaming widget configuration.
+    ///
+    /// Unverified: `/me/player-widget` is a guessed route.
+    pub fn set_player_widget(&self, widget: PlayerWidget) -> Result<(), Error> {
+        self.client.post_empty_response(
+            TargetAPI::Player,
+            "/me/player-widget",
+            None::<HashMap<String, String>>,
+            Some(widget),
+        )
+    }
+
     /// Set a account and login new email.
     pub fn set_email(&self, new_email: String) -> Result<(), Error> {
         self.client.post_empty_response(
@@ -140,6 +221,53 @@ impl EndpointUser<'_, SignedIn> {
         )
     }
 
+    /// Resend the 2FA confirmation email, after enabling 2FA with email.
+    pub fn resend_2fa_email(&self) -> Result<(), Error> {
+        self.client.post_empty_response(
+            TargetAPI::Player,
+            "/me/two-factor/resend-email",
+            None::<HashMap<String, String>>,
+            None::<()>,
+        )
+    }
+
+    /// Confirm 2FA with email using the token from the confirmation email.
+    ///
+    /// Unverified: the MC API isn't known to have
+    /// `/me/two-factor/confirm-email`, the route is a guess.
+    pub fn confirm_2fa_email(&self, token: String) -> Result<(), Error> {
+        self.client.post_empty_response(
+            TargetAPI::Player,
+            "/me/two-factor/confirm-email",
+            None::<HashMap<String, String>>,
+            Some(TwoFactorToken { token }),
+        )
+    }
+
+    /// Get 2FA backup codes.
+    ///
+    /// Unverified: the MC API isn't known to have
+    /// `/me/two-factor/backup-codes`, the route is a guess.
+    pub fn get_backup_codes(&self) -> Result<BackupCodes, Error> {
+        self.client.get::<BackupCodes>(
+            TargetAPI::Player,
+            "/me/two-factor/backup-codes",
+            None::<HashMap<String, String>>,
+        )
+    }
+
+    /// Generate new 2FA backup codes, replacing any existing ones.
+    ///
+    /// Unverified: `/me/two-factor/backup-codes` is a guessed route.
+    pub fn generate_backup_codes(&self) -> Result<BackupCodes, Error> {
+        self.client.post::<BackupCodes>(
+            TargetAPI::Player,
+            "/me/two-factor/backup-codes",
+            None::<HashMap<String, String>>,
+            None::<()>,
+        )
+    }
+
     /// Get TOTP QR code PNG image.
     pub fn get_totp_qr_code_image(&self) -> Result<Box<dyn std::io::Read + Send + Sync>, Error> {
         self.client.get_reader(
@@ -173,6 +301,135 @@ impl EndpointUser<'_, SignedIn> {
             .ok_or(Error::NotFound("licenses"))
     }
 
+    /// Gather the user's profile, settings, playlists with their tracks
+    /// (including the library), and licenses, to back up as JSON.
+    ///
+    /// Tracks and licenses are read page by page, so nothing is left out.
+    ///
+    /// Example
+    /// ```rust,no_run
+    /// use bombay::client::{Client, SignedIn};
+    ///
+    /// # fn example(mc: &Client<SignedIn>) -> Result<(), Box<dyn std::error::Error>> {
+    /// let export = mc.user().export_all()?;
+    /// std::fs::write("backup.json", serde_json::to_vec_pretty(&export)?)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn export_all(&self) -> Result<UserExport, Error> {
+        let (settings, user) = self.get_info()?;
+
+        let playlists = self
+            .client
+            .playlist()
+            .get_all()?
+            .data
+            .unwrap_or_default()
+            .into_iter()
+            .map(|playlist| {
+                let items = self.client.playlist().get_items(playlist.id)?;
+                Ok(PlaylistExport { playlist, items })
+            })
+            .collect::<Result<Vec<_>, Error>>()?;
+
+        let mut licenses = Vec::new();
+        let mut offset = 0;
+        loop {
+            let page = self.get_licenses(Some(RequestParameters::from_pagination(
+                PaginationParameters {
+                    limit: EXPORT_PAGE_SIZE,
+                    offset,
+                },
+            )))?;
+            licenses.extend(page.data.iter().flatten().cloned());
+
+            offset = match page.next_offset() {
+                Some(next) if next > offset => next,
+                _ => break,
+            };
+        }
+
+        Ok(UserExport {
+            exported_at: Timestamp::now_utc(),
+            user,
+            settings,
+            playlists,
+            licenses,
+        })
+    }
+
+    /// Restore the playlists of a backup made with [`Self::export_all`].
+    ///
+    /// Each playlist is created anew with its tracks, except the library,
+    /// whose tracks are added to the current library if missing. Profile,
+    /// settings, and licenses are not restored.
+    ///
+    /// Returns the ID of each exported playlist along with its restored ID.
+    pub fn import(&self, export: UserExport) -> Result<Vec<(PlaylistID, PlaylistID)>, Error> {
+        let playlists = self.client.playlist();
+        let library = playlists
+            .get_all()?
+            .data
+            .unwrap_or_default()
+            .into_iter()
+            .find(|playlist| playlist.my_library);
+
+        let mut restored = Vec::new();
+        for PlaylistExport { playlist, items } in export.playlists {
+            let exported_id = playlist.id;
+            let (playlist_id, present) = match (&library, playlist.my_library) {
+                (Some(library), true) => {
+                    (library.id, self.client.playlist().get_items(library.id)?)
+                }
+                _ => (playlists.create(playlist)?, Vec::new()),
+            };
+
+            let records: Vec<PlaylistItem> = items
+                .into_iter()
+                .filter(|item| {
+                    !present
+                        .iter()
+                        .any(|present| present.track_id == item.track_id)
+                })
+                .map(|item| PlaylistItem {
+                    playlist_id,
+                    ..item
+                })
+                .collect();
+            if !records.is_empty() {
+                playlists.modify_items(
+                    playlist_id,
+                    PlaylistItemsOperations::Add,
+                    PlaylistItemsMod { records },
+                )?;
+            }
+
+            restored.push((exported_id, playlist_id));
+        }
+
+        Ok(restored)
+    }
+
+    /// Get usage statistics of a creator license over a range of time, like
+    /// views and claims of the licensed channel.
+    ///
+    /// Unverified: the MC API isn't known to have `/self/license/{id}/stats`,
+    /// the route and its fields are guesses.
+    pub fn get_license_stats(
+        &self,
+        license_id: LicenseID,
+        range: DateRange,
+    ) -> Result<LicenseStats, Error> {
+        self.client.get::<LicenseStats>(
+            TargetAPI::Player,
+            &format!("/self/license/{license_id}/stats"),
+            Some(HashMap::from([
+                ("start".to_owned(), range.start.to_string()),
+                ("end".to_owned(), range.end.to_string()),
+            ])),
+        )
+    }
+
     /// Delete creator license registered with your account.
     pub fn remove_license(&self, license_id: LicenseID) -> Result<(), Error> {
         self.client.post_empty_response(
@@ -193,6 +450,47 @@ impl EndpointUser<'_, SignedIn> {
         )
     }
 
+    /// Request an export of all of the user's account data.
+    ///
+    /// Exports take a while, check on them with [`EndpointUser::get_export_status`].
+    ///
+    /// Unverified: the MC API isn't known to have `/me/data-export`, the route
+    /// is a guess.
+    pub fn request_data_export(&self) -> Result<(), Error> {
+        self.client.post_empty_response(
+            TargetAPI::Player,
+            "/me/data-export",
+            None::<HashMap<String, String>>,
+            None::<()>,
+        )
+    }
+
+    /// Get the status of the latest account data export request.
+    ///
+    /// Unverified: `/me/data-export` is a guessed route.
+    pub fn get_export_status(&self) -> Result<DataExport, Error> {
+        self.client.get::<DataExport>(
+            TargetAPI::Player,
+            "/me/data-export",
+            None::<HashMap<String, String>>,
+        )
+    }
+
+    /// Permanently delete the user's account, confirming with the password.
+    ///
+    /// This can't be undone. The client is no longer useful afterwards.
+    ///
+    /// Unverified: the MC API isn't known to have `/me/delete`. As a guessed
+    /// route, it may fail, but it may also do something other than expected.
+    pub fn delete_account(&self, password: String) -> Result<(), Error> {
+        self.client.post_empty_response(
+            TargetAPI::Player,
+            "/me/delete",
+            None::<HashMap<String, String>>,
+            Some(AccountDeletion { password }),
+        )
+    }
+
     /// Generate gold member shop discount code.
     ///
     /// These are supposed to be used for 30 days. Try to reuse instead of generating on demand.
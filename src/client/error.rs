@@ -1,7 +1,12 @@
+use crate::client::auth::SignInFailure;
+use crate::client::offline;
+use crate::mc::user::SettingsConflict;
 use std::fmt::Display;
+use std::time::Duration;
 
 /// Bombay error type.
 #[derive(Debug)]
+#[non_exhaustive]
 pub enum Error {
     IO(std::io::Error),
     Request(Box<ureq::Error>),
@@ -9,6 +14,13 @@ pub enum Error {
     Message(&'static str),
     NotFound(&'static str),
     SignIn(&'static str),
+    SignInDetailed(SignInFailure),
+    EarlyAccessRequired,
+    ResponseTooLarge(u64),
+    /// Settings were not sent, because the API would not accept them together.
+    InvalidSettings(SettingsConflict),
+    /// Request would have reached the network while the client is offline.
+    Offline,
 }
 
 impl Display for Error {
@@ -20,8 +32,99 @@ impl Display for Error {
             Error::Message(str_err) => write!(f, "{}", str_err),
             Error::NotFound(item) => write!(f, "Could not find {}.", item),
             Error::SignIn(str_err) => write!(f, "Could not sign in. {}.", str_err),
+            Error::SignInDetailed(failure) => write!(f, "Could not sign in. {}.", failure),
+            Error::ResponseTooLarge(max) => {
+                write!(
+                    f,
+                    "Response body is larger than the limit of {} bytes.",
+                    max
+                )
+            }
+            Error::InvalidSettings(conflict) => write!(f, "Invalid settings. {}.", conflict),
+            Error::Offline => {
+                write!(
+                    f,
+                    "Client is offline, and the request has no recorded response."
+                )
+            }
+            Error::EarlyAccessRequired => {
+                write!(
+                    f,
+                    "Content is in early access, which requires gold membership."
+                )
+            }
         }
     }
 }
 
 impl std::error::Error for Error {}
+
+impl From<ureq::Error> for Error {
+    fn from(err: ureq::Error) -> Self {
+        match offline::is_stopped(&err) {
+            true => Error::Offline,
+            false => Error::Request(Box::new(err)),
+        }
+    }
+}
+
+impl Error {
+    /// Get the HTTP status of the response, if the API answered with an error status.
+    pub fn status(&self) -> Option<u16> {
+        match self {
+            Error::Request(err) => match err.as_ref() {
+                ureq::Error::Status(status, _) => Some(*status),
+                ureq::Error::Transport(_) => None,
+            },
+            _ => None,
+        }
+    }
+
+    /// Check whether the same request could succeed if tried again later.
+    ///
+    /// This is the case for network failures, rate limiting, and server errors,
+    /// but not for bodies that failed to decode, which would decode no better
+    /// the next time.
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            Error::IO(err) => !matches!(
+                err.kind(),
+                std::io::ErrorKind::InvalidData | std::io::ErrorKind::UnexpectedEof
+            ),
+            Error::SignInDetailed(SignInFailure::RateLimited(_)) => true,
+            Error::Request(err) => match err.as_ref() {
+                ureq::Error::Status(status, _) => matches!(status, 408 | 429 | 500..=599),
+                ureq::Error::Transport(_) => true,
+            },
+            _ => false,
+        }
+    }
+
+    /// Check whether the error is due to missing or expired authentication,
+    /// which signing in (again) could fix.
+    pub fn is_auth_error(&self) -> bool {
+        match self {
+            Error::SignIn(_) | Error::SignInDetailed(_) => true,
+            _ => self.status() == Some(401),
+        }
+    }
+
+    /// Get how long the API asked to wait before trying again, if it did.
+    ///
+    /// Only the seconds form of the Retry-After header is understood.
+    pub fn retry_after(&self) -> Option<Duration> {
+        match self {
+            Error::Request(err) => match err.as_ref() {
+                ureq::Error::Status(_, response) => response
+                    .header("Retry-After")
+                    .and_then(|seconds| seconds.trim().parse().ok())
+                    .map(Duration::from_secs),
+                ureq::Error::Transport(_) => None,
+            },
+            Error::SignInDetailed(SignInFailure::RateLimited(secs)) => {
+                secs.map(Duration::from_secs)
+            }
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,192 @@
+use crate::client::Error;
+use std::collections::VecDeque;
+use std::io::{self, Read};
+use url::Url;
+
+/// Content types HLS playlists are served with.
+const CONTENT_TYPES: [&str; 3] = [
+    "application/vnd.apple.mpegurl",
+    "application/x-mpegurl",
+    "audio/mpegurl",
+];
+
+/// Check whether a response is an HLS playlist, by its content type.
+pub(crate) fn is_playlist(content_type: &str) -> bool {
+    CONTENT_TYPES
+        .iter()
+        .any(|hls| content_type.eq_ignore_ascii_case(hls))
+}
+
+/// Variant stream listed in a master playlist.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Variant {
+    /// Peak bits per second of the stream.
+    pub bandwidth: u64,
+    pub uri: Url,
+}
+
+/// Segment of audio listed in a media playlist.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Segment {
+    /// Duration, in seconds.
+    pub duration: f64,
+    pub uri: Url,
+}
+
+/// HLS (m3u8) playlist, as served for segmented streams.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Playlist {
+    /// Playlist of variant streams, like several bitrates of the same track.
+    Master(Vec<Variant>),
+    /// Playlist of the segments of one stream, in order.
+    Media(Vec<Segment>),
+}
+
+impl Playlist {
+    /// Parse a playlist, resolving relative URIs against the playlist's URL.
+    ///
+    /// Encrypted segments are not supported.
+    ///
+    /// Example
+    /// ```rust
+    /// use bombay::client::hls::Playlist;
+    /// use url::Url;
+    ///
+    /// let base = Url::parse("https://cdn.example.com/track/index.m3u8").unwrap();
+    /// let playlist = Playlist::parse(
+    ///     "#EXTM3U\n#EXT-X-TARGETDURATION:10\n#EXTINF:10.0,\n0.aac\n#EXTINF:4.5,\n1.aac\n#EXT-X-ENDLIST\n",
+    ///     &base,
+    /// )
+    /// .unwrap();
+    ///
+    /// match playlist {
+    ///     Playlist::Media(segments) => {
+    ///         assert_eq!(segments.len(), 2);
+    ///         assert_eq!(segments[1].uri.as_str(), "https://cdn.example.com/track/1.aac");
+    ///     }
+    ///     Playlist::Master(_) => unreachable!(),
+    /// }
+    /// ```
+    pub fn parse(text: &str, base: &Url) -> Result<Self, Error> {
+        let mut lines = text.lines().map(str::trim).filter(|line| !line.is_empty());
+        if lines.next() != Some("#EXTM3U") {
+            return Err(Error::Message("Stream is not an HLS playlist."));
+        }
+
+        let mut variants = Vec::new();
+        let mut segments = Vec::new();
+        let mut bandwidth = None;
+        let mut duration = None;
+
+        for line in lines {
+            if let Some(attributes) = line.strip_prefix("#EXT-X-STREAM-INF:") {
+                bandwidth = Some(attribute(attributes, "BANDWIDTH").unwrap_or(0));
+            } else if let Some(info) = line.strip_prefix("#EXTINF:") {
+                let seconds = info.split(',').next().unwrap_or_default();
+                duration = Some(seconds.trim().parse().unwrap_or(0.0));
+            } else if let Some(attributes) = line.strip_prefix("#EXT-X-KEY:") {
+                if !attributes.contains("METHOD=NONE") {
+                    return Err(Error::Message("Encrypted HLS streams are not supported."));
+                }
+            } else if !line.starts_with('#') {
+                let uri = base
+                    .join(line)
+                    .map_err(|_| Error::Message("HLS playlist has an invalid URI."))?;
+                if let Some(bandwidth) = bandwidth.take() {
+                    variants.push(Variant { bandwidth, uri });
+                } else if let Some(duration) = duration.take() {
+                    segments.push(Segment { duration, uri });
+                }
+            }
+        }
+
+        match variants.is_empty() {
+            true => Ok(Playlist::Media(segments)),
+            false => Ok(Playlist::Master(variants)),
+        }
+    }
+}
+
+/// Get a numeric attribute of a tag, like `BANDWIDTH=128000`.
+fn attribute(attributes: &str, name: &str) -> Option<u64> {
+    attributes
+        .split(',')
+        .filter_map(|pair| pair.split_once('='))
+        .find(|(key, _)| key.trim() == name)
+        .and_then(|(_, value)| value.trim().parse().ok())
+}
+
+/// Reader stitching the segments of an HLS stream into one stream, fetching
+/// each segment as the previous one is read to the end.
+pub struct SegmentReader {
+    agent: ureq::Agent,
+    remaining: VecDeque<Url>,
+    current: Option<Box<dyn Read + Send + Sync>>,
+}
+
+impl SegmentReader {
+    /// Create a reader of a playlist's stream, choosing the variant with the
+    /// highest bandwidth of a master playlist.
+    pub fn new(agent: ureq::Agent, playlist: Playlist) -> Result<Self, Error> {
+        let segments = match playlist {
+            Playlist::Media(segments) => segments,
+            Playlist::Master(variants) => {
+                let variant = variants
+                    .into_iter()
+                    .max_by_key(|variant| variant.bandwidth)
+                    .ok_or(Error::NotFound("HLS variant"))?;
+                let response = agent
+                    .get(variant.uri.as_str())
+                    .call()
+                    .map_err(Error::from)?;
+                let text = response.into_string().map_err(Error::IO)?;
+                match Playlist::parse(&text, &variant.uri)? {
+                    Playlist::Media(segments) => segments,
+                    Playlist::Master(_) => {
+                        return Err(Error::Message("HLS variant is not a media playlist."))
+                    }
+                }
+            }
+        };
+
+        Ok(SegmentReader {
+            agent,
+            remaining: segments.into_iter().map(|segment| segment.uri).collect(),
+            current: None,
+        })
+    }
+
+    /// Start reading the next segment, if any are left.
+    fn next_segment(&mut self) -> io::Result<bool> {
+        let uri = match self.remaining.pop_front() {
+            Some(uri) => uri,
+            None => return Ok(false),
+        };
+
+        let response = self
+            .agent
+            .get(uri.as_str())
+            .call()
+            .map_err(|err| io::Error::new(io::ErrorKind::Other, err))?;
+        self.current = Some(response.into_reader());
+        Ok(true)
+    }
+}
+
+impl Read for SegmentReader {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        loop {
+            if let Some(current) = self.current.as_mut() {
+                let read = current.read(buf)?;
+                if read > 0 || buf.is_empty() {
+                    return Ok(read);
+                }
+                self.current = None;
+            }
+
+            if !self.next_segment()? {
+                return Ok(0);
+            }
+        }
+    }
+}
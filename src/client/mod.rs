@@ -1,25 +1,62 @@
 #![doc = include_str!("README.md")]
 
 pub mod auth;
+mod batch;
+mod capabilities;
+#[cfg(feature = "cassette")]
+pub mod cassette;
+mod content_filter;
 pub mod endpoints;
 mod error;
+pub mod hls;
+mod offline;
+mod ping;
 mod request;
 mod response;
+mod session_file;
+mod single_flight;
+mod snapshot;
+mod stream;
+mod undo;
 
+use crate::compat::{self, ApiVersion, UnknownEnumPolicy};
+use crate::dirs::Dirs;
+use crate::mc::release::{CatalogID, ReleaseID};
+use crate::mc::util::Region;
 use auth::*;
+pub use batch::{Batch, FromPanic};
+use capabilities::probe;
+pub use capabilities::Capabilities;
 use const_format::formatcp;
 use endpoints::*;
 pub use error::*;
+pub use ping::{Ping, Reachability};
 pub use request::*;
 pub use response::*;
 use serde::de::DeserializeOwned;
+use serde_json::Value;
+#[cfg(feature = "encryption")]
+pub use session_file::PassphraseCipher;
+pub use session_file::SessionFile;
+use single_flight::{Role, SingleFlight};
+use snapshot::SNAPSHOT_VERSION;
+pub use snapshot::{ClientSnapshot, SnapshotCipher};
 use std::marker::PhantomData;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
 use std::{collections::HashMap, fmt::Display};
+pub use stream::TrackStream;
+pub use undo::{UndoStep, UndoToken};
 use ureq::{self, Request, Response};
+use uuid::uuid;
 
 const USER_AGENT: &str = formatcp!("{} v{}", env!("CARGO_PKG_NAME"), env!("CARGO_PKG_VERSION"));
 const URL_PLAYER_API: &str = "https://player.monstercat.app/api";
 const URL_WWW_API: &str = "https://www.monstercat.com/";
+/// Paths of the routes sent the region, as the only ones with content to
+/// localize for it, like place names and time zones of events.
+const REGIONAL_PATHS: &[&str] = &["/events", "/me/events"];
 
 /// Zero-size type to indicate the user signed-out state of a client.
 #[derive(Debug)]
@@ -33,50 +70,517 @@ pub struct SignedIn;
 /// Note that this struct uses zero-sized phantom data expose different
 /// functionality based on whether or not the user is authenticated.
 ///
-#[derive(Debug)]
+/// A signed-in client shares its agent, and so its cookies, with the
+/// signed-out client it came from. Use a new signed-out client per account,
+/// or see [`crate::accounts::AccountManager`].
+///
+/// Its `Debug` output leaves out the agent, holding the session cookie, and
+/// redacts any credentials kept while signing in.
 pub struct Client<ClientAuthState = SignedOut> {
     pub agent: ureq::Agent,
+    aliases: Vec<(String, String)>,
+    api_version: ApiVersion,
     auth: Option<SavedAuthDetails>,
+    auth_provider: Arc<dyn AuthProvider>,
+    dirs: Dirs,
+    filter_explicit: bool,
+    locale: Option<String>,
+    max_body_size: Option<u64>,
+    offline: Arc<AtomicBool>,
+    on_auth_expired: Option<AuthExpiredHandler>,
+    on_shutdown: Option<ShutdownHandler>,
+    region: Option<Region>,
+    single_flight: Option<Arc<SingleFlight>>,
+    unknown_enum_policy: Option<UnknownEnumPolicy>,
     url_player_api: String,
     url_www_api: String,
     user_agent: String,
     user_state: PhantomData<ClientAuthState>,
 }
 
+impl<ClientAuthState> std::fmt::Debug for Client<ClientAuthState> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Client")
+            .field("agent", &Redacted)
+            .field("aliases", &self.aliases)
+            .field("api_version", &self.api_version)
+            .field("auth", &self.auth)
+            .field("auth_provider", &self.auth_provider)
+            .field("dirs", &self.dirs)
+            .field("filter_explicit", &self.filter_explicit)
+            .field("locale", &self.locale)
+            .field("max_body_size", &self.max_body_size)
+            .field("offline", &self.offline)
+            .field("on_auth_expired", &self.on_auth_expired)
+            .field("on_shutdown", &self.on_shutdown)
+            .field("region", &self.region)
+            .field("single_flight", &self.single_flight)
+            .field("unknown_enum_policy", &self.unknown_enum_policy)
+            .field("url_player_api", &self.url_player_api)
+            .field("url_www_api", &self.url_www_api)
+            .field("user_agent", &self.user_agent)
+            .finish()
+    }
+}
+
 impl Default for Client<SignedOut> {
     fn default() -> Self {
+        let offline = Arc::new(AtomicBool::new(false));
         Client {
+            agent: offline::build_agent(ureq::AgentBuilder::new(), &offline),
+            offline,
             user_state: PhantomData,
             url_player_api: URL_PLAYER_API.to_owned(),
             url_www_api: URL_WWW_API.to_owned(),
             user_agent: USER_AGENT.to_owned(),
+            aliases: Vec::new(),
+            api_version: ApiVersion::default(),
             auth: None,
-            agent: ureq::Agent::new(),
+            auth_provider: Arc::new(CookieAuth::default()),
+            dirs: Dirs::default(),
+            filter_explicit: false,
+            locale: None,
+            max_body_size: None,
+            on_auth_expired: None,
+            on_shutdown: None,
+            region: None,
+            single_flight: None,
+            unknown_enum_policy: None,
         }
     }
 }
 
 impl<ClientAuthState> Client<ClientAuthState> {
     /// Get endpoint for artist-related functions.
-    pub fn artist(&self) -> EndpointArtist<ClientAuthState> {
+    pub fn artist(&self) -> EndpointArtist<'_, ClientAuthState> {
         EndpointArtist { client: self }
     }
 
+    /// Get endpoint for chart-related functions.
+    pub fn chart(&self) -> EndpointChart<'_, ClientAuthState> {
+        EndpointChart { client: self }
+    }
+
+    /// Get endpoint for event-related functions.
+    pub fn event(&self) -> EndpointEvent<'_, ClientAuthState> {
+        EndpointEvent { client: self }
+    }
+
     /// Get endpoint for mood-related functions.
-    pub fn mood(&self) -> EndpointMood<ClientAuthState> {
+    pub fn mood(&self) -> EndpointMood<'_, ClientAuthState> {
         EndpointMood { client: self }
     }
 
     /// Get endpoint for playlist-related functions.
-    pub fn playlist(&self) -> EndpointPlaylist<ClientAuthState> {
+    pub fn playlist(&self) -> EndpointPlaylist<'_, ClientAuthState> {
         EndpointPlaylist { client: self }
     }
 
     /// Get endpoint for release-related functions.
-    pub fn release(&self) -> EndpointRelease<ClientAuthState> {
+    pub fn release(&self) -> EndpointRelease<'_, ClientAuthState> {
         EndpointRelease { client: self }
     }
 
+    /// Set the API version responses are parsed as, the current API by default.
+    ///
+    /// Pin an older version to parse responses of, for example, a recording
+    /// server replaying older data. See [`crate::compat`].
+    pub fn set_api_version(mut self, api_version: ApiVersion) -> Self {
+        self.api_version = api_version;
+        self
+    }
+
+    /// Set keys the MC API renamed, as (alias, current), so responses using
+    /// an alias parse as if they used the current key, without waiting on a
+    /// Bombay release. None by default.
+    ///
+    /// Aliases apply to keys of objects at any depth, in responses to this
+    /// client and clients signed in from it. See also [`compat::with_aliases`].
+    ///
+    /// Example
+    /// ```rust
+    /// use bombay::client::Client;
+    ///
+    /// // Say MC renamed "Name" to "DisplayName".
+    /// let mc = Client::default().set_aliases(vec![("DisplayName".to_owned(), "Name".to_owned())]);
+    /// ```
+    pub fn set_aliases(mut self, aliases: Vec<(String, String)>) -> Self {
+        self.aliases = aliases;
+        self
+    }
+
+    /// Set the mechanism authenticating requests, cookie sessions by default.
+    pub fn set_auth_provider(mut self, auth_provider: impl AuthProvider + 'static) -> Self {
+        self.auth_provider = Arc::new(auth_provider);
+        self
+    }
+
+    /// Set whether the client is offline, so requests never reach the network
+    /// and fail with [`Error::Offline`] instead.
+    ///
+    /// Requests replayed from a cassette are still answered, so a cassette
+    /// recorded while online serves as a snapshot to browse offline. Clients
+    /// sharing an agent, like a signed-in client and the client it came from,
+    /// go offline together.
+    ///
+    /// Example
+    /// ```rust
+    /// use bombay::client::{Client, Error};
+    ///
+    /// let mc = Client::default().set_offline(true);
+    /// assert!(matches!(mc.release().get_latest(None), Err(Error::Offline)));
+    /// ```
+    pub fn set_offline(self, offline: bool) -> Self {
+        self.offline.store(offline, Ordering::Relaxed);
+        self
+    }
+
+    /// Check whether the client is offline, see [`Client::set_offline`].
+    pub fn is_offline(&self) -> bool {
+        self.offline.load(Ordering::Relaxed)
+    }
+
+    /// Set the maximum size, in bytes, of JSON response bodies, unlimited by default.
+    ///
+    /// Larger responses fail with [`Error::ResponseTooLarge`] instead of being
+    /// read into memory. Readers, like track streams, are not limited.
+    pub fn set_max_body_size(mut self, max_body_size: Option<u64>) -> Self {
+        self.max_body_size = max_body_size;
+        self
+    }
+
+    /// Set whether explicit tracks are left out of responses, for family-safe
+    /// apps. Disabled by default.
+    ///
+    /// Tracks are filtered out of every response, including releases,
+    /// playlists, and moods, after they are received. So pages may hold fewer
+    /// items than their limit, and totals still count explicit tracks.
+    pub fn set_filter_explicit(mut self, enabled: bool) -> Self {
+        self.filter_explicit = enabled;
+        self
+    }
+
+    /// Set what to do with enum values Bombay doesn't know in responses, like
+    /// a new platform. Unset by default, keeping them as `Other`, or following
+    /// the policy of the thread, see [`compat::with_unknown_enum_policy`].
+    ///
+    /// Clients signed in from this one keep the policy, while other clients
+    /// in the process have their own.
+    pub fn set_unknown_enum_policy(mut self, policy: Option<UnknownEnumPolicy>) -> Self {
+        self.unknown_enum_policy = policy;
+        self
+    }
+
+    /// Set the directories features persisting state default to, like the
+    /// download directory of [`crate::app::Monstercat`]. By default, they
+    /// follow the platform's conventions, see [`Dirs::platform`].
+    pub fn set_dirs(mut self, dirs: Dirs) -> Self {
+        self.dirs = dirs;
+        self
+    }
+
+    /// Get the directories features persisting state default to.
+    pub fn dirs(&self) -> &Dirs {
+        &self.dirs
+    }
+
+    /// Get the URL of the web player the client's player API belongs to, for
+    /// share and embed links, like `https://player.monstercat.app`.
+    ///
+    /// Example
+    /// ```rust
+    /// use bombay::client::Client;
+    ///
+    /// let mc = Client::default(); // Without authentication.
+    /// assert_eq!(mc.player_url().unwrap().as_str(), "https://player.monstercat.app/");
+    /// ```
+    pub fn player_url(&self) -> Result<url::Url, Error> {
+        let mut url = url::Url::parse(&self.url_player_api)
+            .map_err(|_| Error::Message("Invalid player API URL."))?;
+        let path = url.path().trim_end_matches('/');
+        let path = path.strip_suffix("/api").unwrap_or(path).to_owned();
+        url.set_path(&path);
+        url.set_query(None);
+        Ok(url)
+    }
+
+    /// Set the locale of responses, as a language tag like "en-US", sent as
+    /// the Accept-Language header. Unset by default, leaving it to the API.
+    pub fn set_locale(mut self, locale: Option<String>) -> Self {
+        self.locale = locale;
+        self
+    }
+
+    /// Set the region to localize responses for, like place names and
+    /// timezones. Unset by default, leaving it to the API.
+    ///
+    /// Unverified: the API isn't known to take a `region` query, it is a
+    /// guess. It is only sent to the event routes, the only ones with place
+    /// names and time zones to localize.
+    pub fn set_region(mut self, region: Option<Region>) -> Self {
+        self.region = region;
+        self
+    }
+
+    /// Set whether identical GET requests made concurrently, for example by
+    /// several threads hydrating the same list, are coalesced into one.
+    ///
+    /// Callers waiting on another's request share its parsed result. If that
+    /// request fails, they make their own. Disabled by default.
+    pub fn set_single_flight(mut self, enabled: bool) -> Self {
+        self.single_flight = enabled.then(|| Arc::new(SingleFlight::default()));
+        self
+    }
+
+    /// Set a callback called with a snapshot of the client when it shuts
+    /// down, like to save its session with [`SessionFile`].
+    ///
+    /// Clients signed in from this one keep the callback, and it is called
+    /// once, by whichever shuts down first.
+    pub fn set_on_shutdown(
+        mut self,
+        callback: impl Fn(ClientSnapshot) -> Result<(), Error> + Send + Sync + 'static,
+    ) -> Self {
+        self.on_shutdown = Some(ShutdownHandler {
+            callback: Arc::new(callback),
+            done: Arc::new(AtomicBool::new(false)),
+        });
+        self
+    }
+
+    /// Shut the client down, for deterministic cleanup in long-running
+    /// daemons.
+    ///
+    /// The client, and clients sharing its agent, go offline for good, see
+    /// [`Client::set_offline`]. So retries in flight, like renewing an expired
+    /// session or resuming a [`TrackStream`], fail instead of reaching the
+    /// network, and so do new requests. Then the callback set with
+    /// [`Client::set_on_shutdown`] is called, like to persist the session.
+    ///
+    /// Dropping a client doesn't shut it down, as clients share their agent,
+    /// like a signed-in client and the signed-out client it came from. See
+    /// [`crate::shutdown::ShutdownGuard`] to shut down on drop.
+    pub fn shutdown(&self) -> Result<(), Error> {
+        let handler = self
+            .on_shutdown
+            .as_ref()
+            .filter(|handler| !handler.done.swap(true, Ordering::SeqCst));
+        // The snapshot is taken while online, to restore the session online.
+        let snapshot = handler.map(|_| self.snapshot());
+        self.offline.store(true, Ordering::Relaxed);
+
+        match (handler, snapshot) {
+            (Some(handler), Some(snapshot)) => (handler.callback)(snapshot),
+            _ => Ok(()),
+        }
+    }
+
+    /// Take a snapshot of the client's cookies and settings, to share its
+    /// session with another process, see [`ClientSnapshot`].
+    pub fn snapshot(&self) -> ClientSnapshot {
+        let cookies = self
+            .agent
+            .cookie_store()
+            .iter_unexpired()
+            .cloned()
+            .collect();
+
+        ClientSnapshot {
+            version: SNAPSHOT_VERSION,
+            api_version: self.api_version,
+            cookies,
+            filter_explicit: self.filter_explicit,
+            locale: self.locale.clone(),
+            max_body_size: self.max_body_size,
+            offline: self.is_offline(),
+            region: self.region.clone(),
+            single_flight: self.single_flight.is_some(),
+            url_player_api: self.url_player_api.clone(),
+            url_www_api: self.url_www_api.clone(),
+            user_agent: self.user_agent.clone(),
+        }
+    }
+
+    /// Take the cookies and settings of a snapshot, with a new agent.
+    fn apply_snapshot(&mut self, snapshot: ClientSnapshot) -> Result<(), Error> {
+        let cookie_store = cookie_store::CookieStore::from_cookies(
+            snapshot.cookies.into_iter().map(Ok::<_, Error>),
+            false,
+        )?;
+        self.offline.store(snapshot.offline, Ordering::Relaxed);
+        self.agent = offline::build_agent(
+            ureq::AgentBuilder::new().cookie_store(cookie_store),
+            &self.offline,
+        );
+
+        self.api_version = snapshot.api_version;
+        self.filter_explicit = snapshot.filter_explicit;
+        self.locale = snapshot.locale;
+        self.max_body_size = snapshot.max_body_size;
+        self.region = snapshot.region;
+        self.single_flight = match snapshot.single_flight {
+            true => Some(self.single_flight.take().unwrap_or_default()),
+            false => None,
+        };
+        self.url_player_api = snapshot.url_player_api;
+        self.url_www_api = snapshot.url_www_api;
+        self.user_agent = snapshot.user_agent;
+        Ok(())
+    }
+
+    /// Run a batch of requests concurrently and get all of their results.
+    ///
+    /// Requests are closures making calls with the client, for example to
+    /// hydrate a page from several endpoints in one round trip rather than
+    /// one after another. Each request gets a thread, and at most
+    /// `max_concurrent` run at once. Results keep the order of the requests.
+    ///
+    /// Example
+    /// ```rust
+    /// use bombay::client::Client;
+    ///
+    /// let mc = Client::default(); // Without authentication.
+    ///
+    /// let (artist, releases, photo) = mc.join_all(
+    ///     4,
+    ///     (
+    ///         || mc.artist().get_by_name_uri("rogue"),
+    ///         || mc.release().get_by_artist_name_uri("rogue", None),
+    ///         || mc.artist().get_photo("rogue"),
+    ///     ),
+    /// );
+    ///
+    /// if let (Ok(artist), Ok(releases)) = (artist, releases) {
+    ///     println!("{} has {} releases.", artist.name, releases.total);
+    /// }
+    /// ```
+    pub fn join_all<B: Batch>(&self, max_concurrent: usize, requests: B) -> B::Output {
+        requests.run(max_concurrent)
+    }
+
+    /// Check which parts of the MC API work with this client.
+    ///
+    /// Each part is probed concurrently with a small request to one of its
+    /// endpoints. Parts answering with an error status, or with a response that
+    /// no longer deserializes, are unsupported. Fails if the API can't be
+    /// reached at all.
+    ///
+    /// Events are probed through an unverified route, see
+    /// [`Capabilities::supports_events`].
+    ///
+    /// Example
+    /// ```rust
+    /// use bombay::client::Client;
+    ///
+    /// let mc = Client::default(); // Without authentication.
+    ///
+    /// if let Ok(capabilities) = mc.probe_capabilities() {
+    ///     if !capabilities.supports_mood_catalog {
+    ///         println!("Moods are unavailable, hiding mood radio.");
+    ///     }
+    /// }
+    /// ```
+    pub fn probe_capabilities(&self) -> Result<Capabilities, Error>
+    where
+        Self: Sync,
+    {
+        let single = || {
+            Some(RequestParameters::from_pagination(PaginationParameters {
+                limit: 1,
+                offset: 0,
+            }))
+        };
+        let release_id = ReleaseID(uuid!("6a58b6d2-bbec-4847-8dcf-45023a930968"));
+
+        let (artists, events, latest_releases, moods, related_releases, www_api) = self.join_all(
+            6,
+            (
+                || probe(self.artist().get_all(single())),
+                || probe(self.event().get_upcoming(single())),
+                || probe(self.release().get_latest(single())),
+                || probe(self.mood().get_all(single())),
+                || probe(self.release().get_related_by_id(&release_id, single())),
+                || {
+                    probe(
+                        self.release()
+                            .get_cover_art(&CatalogID("MCS1186".to_owned())),
+                    )
+                },
+            ),
+        );
+
+        Ok(Capabilities {
+            supports_artists: artists?,
+            supports_events: events?,
+            supports_latest_releases: latest_releases?,
+            supports_mood_catalog: moods?,
+            supports_related_releases: related_releases?,
+            supports_www_api: www_api?,
+        })
+    }
+
+    /// Check whether each configured API can be reached, and how quickly.
+    ///
+    /// Both APIs get a small request to their base URL, concurrently. Any
+    /// answer, even an error status, counts as reachable. Nothing is retried,
+    /// and APIs not answering within a few seconds are unreachable.
+    ///
+    /// Example
+    /// ```rust
+    /// use bombay::client::Client;
+    ///
+    /// let mc = Client::default(); // Without authentication.
+    /// let ping = mc.ping();
+    ///
+    /// if ping.is_offline() {
+    ///     println!("Offline, showing cached releases.");
+    /// } else {
+    ///     println!("Player API answered in {:?}.", ping.player_api.latency);
+    /// }
+    /// ```
+    pub fn ping(&self) -> Ping
+    where
+        Self: Sync,
+    {
+        let reach = |api: TargetAPI, url: &str| {
+            let started = Instant::now();
+            let status = match self
+                .build_head_request(api, "", None)
+                .timeout(ping::PING_TIMEOUT)
+                .call()
+            {
+                Ok(response) => Some(response.status()),
+                Err(ureq::Error::Status(status, _)) => Some(status),
+                Err(ureq::Error::Transport(_)) => None,
+            };
+
+            Ok::<_, Error>(Reachability {
+                url: url.to_owned(),
+                status,
+                latency: started.elapsed(),
+            })
+        };
+
+        let (player_api, www_api) = self.join_all(
+            2,
+            (
+                || reach(TargetAPI::Player, &self.url_player_api),
+                || reach(TargetAPI::WWW, &self.url_www_api),
+            ),
+        );
+
+        // Checks that panicked didn't reach their API.
+        let unreached = |url: &str| Reachability {
+            url: url.to_owned(),
+            status: None,
+            latency: Duration::ZERO,
+        };
+        Ping {
+            player_api: player_api.unwrap_or_else(|_| unreached(&self.url_player_api)),
+            www_api: www_api.unwrap_or_else(|_| unreached(&self.url_www_api)),
+        }
+    }
+
     /// Use the client to make a custom GET request to the API.
     pub fn get<RT: DeserializeOwned>(
         &self,
@@ -84,10 +588,81 @@ impl<ClientAuthState> Client<ClientAuthState> {
         path: impl AsRef<str> + Display,
         queries: Option<impl Into<HashMap<String, String>>>,
     ) -> Result<RT, Error> {
-        self.process_response::<RT>(
-            self.build_get_request(api_type, path, queries.map(|q| q.into()))
-                .call(),
-        )
+        let request = self.build_get_request(api_type, path, queries.map(|q| q.into()));
+        self.with_auth_retry(|| self.get_once::<RT>(request.clone()))
+    }
+
+    /// Use the client to make a custom GET request to the API, keeping the
+    /// response status, headers, and timing along with the value.
+    ///
+    /// Any endpoint call can be made this way by passing the same path and
+    /// queries the endpoint uses. These requests are never coalesced.
+    ///
+    /// Example
+    /// ```rust,no_run
+    /// use bombay::client::endpoints::TargetAPI;
+    /// use bombay::client::{Client, Paginated};
+    /// use bombay::mc::release::AnyRelease;
+    /// use std::collections::HashMap;
+    ///
+    /// let mc = Client::default(); // Without authentication.
+    /// let latest = mc
+    ///     .get_with_meta::<Paginated<AnyRelease>>(
+    ///         TargetAPI::Player,
+    ///         "/catalog/latest-releases",
+    ///         None::<HashMap<String, String>>,
+    ///     )
+    ///     .expect("Could not get latest releases.");
+    ///
+    /// println!("Took {:?}, cached for {:?}.", latest.duration, latest.header("Cache-Control"));
+    /// ```
+    pub fn get_with_meta<RT: DeserializeOwned>(
+        &self,
+        api_type: TargetAPI,
+        path: impl AsRef<str> + Display,
+        queries: Option<impl Into<HashMap<String, String>>>,
+    ) -> Result<WithMeta<RT>, Error> {
+        let request = self.build_get_request(api_type, path, queries.map(|q| q.into()));
+
+        self.with_auth_retry(|| {
+            let started = Instant::now();
+            let response = request.clone().call().map_err(Error::from)?;
+
+            let status = response.status();
+            let headers = response_headers(&response);
+            let value = self.process_response::<RT>(Ok(response))?;
+
+            Ok(WithMeta {
+                value,
+                status,
+                headers,
+                duration: started.elapsed(),
+            })
+        })
+    }
+
+    /// Make a GET request, coalesced with identical requests in flight if enabled.
+    fn get_once<RT: DeserializeOwned>(&self, request: Request) -> Result<RT, Error> {
+        let single_flight = match &self.single_flight {
+            Some(single_flight) => single_flight,
+            None => return self.process_response::<RT>(request.call()),
+        };
+
+        let url = request.url().to_owned();
+        match single_flight.join(&url) {
+            Role::Leader(leader) => {
+                let value = self.process_response::<Value>(request.call()).map(Arc::new);
+                leader.land(value.as_ref().ok().cloned());
+                let value = value?;
+                self.deserializing(|| RT::deserialize(&*value).map_err(Error::Deserialization))
+            }
+            Role::Follower(flight) => match flight.wait() {
+                Some(value) => {
+                    self.deserializing(|| RT::deserialize(&*value).map_err(Error::Deserialization))
+                }
+                None => self.process_response::<RT>(request.call()),
+            },
+        }
     }
 
     /// Use the client to make a custom GET request to the API and get a reader to the content.
@@ -97,14 +672,56 @@ impl<ClientAuthState> Client<ClientAuthState> {
         path: impl AsRef<str> + Display,
         queries: Option<impl Into<HashMap<String, String>>>,
     ) -> Result<Box<dyn std::io::Read + Send + Sync>, Error> {
-        let response = self
-            .build_get_request(api_type, path, queries.map(|q| q.into()))
-            .call();
+        self.get_sized_reader(api_type, path, queries)
+            .map(|(reader, _)| reader)
+    }
+
+    /// Get a reader to the content, like [`Client::get_reader`], with its
+    /// size from the Content-Length header, if the API tells it.
+    pub(crate) fn get_sized_reader(
+        &self,
+        api_type: TargetAPI,
+        path: impl AsRef<str> + Display,
+        queries: Option<impl Into<HashMap<String, String>>>,
+    ) -> Result<(Box<dyn std::io::Read + Send + Sync>, Option<u64>), Error> {
+        let request = self.build_get_request(api_type, path, queries.map(|q| q.into()));
+
+        self.with_auth_retry(|| match request.clone().call() {
+            Ok(res) => {
+                let size = res
+                    .header("Content-Length")
+                    .and_then(|length| length.parse().ok());
+                Ok((res.into_reader(), size))
+            }
+            Err(err) => Err(Error::from(err)),
+        })
+    }
 
-        match response {
-            Ok(res) => Ok(res.into_reader()),
-            Err(err) => Err(Error::Request(Box::new(err))),
+    /// Get a reader to a stream, like [`Client::get_reader`], stitching the
+    /// segments together if the stream is served as an HLS playlist, and
+    /// resuming it otherwise if the connection drops.
+    pub(crate) fn get_stream(
+        &self,
+        api_type: TargetAPI,
+        path: impl AsRef<str> + Display,
+        queries: Option<impl Into<HashMap<String, String>>>,
+    ) -> Result<TrackStream, Error> {
+        let request = self.build_get_request(api_type, path, queries.map(|q| q.into()));
+
+        let response = self.with_auth_retry(|| request.clone().call().map_err(Error::from))?;
+
+        if !hls::is_playlist(response.content_type()) {
+            return Ok(TrackStream::new(request, response));
         }
+
+        let base = url::Url::parse(response.get_url())
+            .map_err(|_| Error::Message("HLS playlist has an invalid URL."))?;
+        let text = response.into_string().map_err(Error::IO)?;
+        let playlist = hls::Playlist::parse(&text, &base)?;
+        Ok(TrackStream::unresumable(Box::new(hls::SegmentReader::new(
+            self.agent.clone(),
+            playlist,
+        )?)))
     }
 
     /// Use the client to make a custom POST request to the API.
@@ -116,10 +733,56 @@ impl<ClientAuthState> Client<ClientAuthState> {
         data: Option<impl serde::Serialize>,
     ) -> Result<RT, Error> {
         let request = self.build_post_request(api_type, path, queries.map(|q| q.into()));
-        match data {
-            Some(data) => self.process_response::<RT>(request.send_json(data)),
-            None => self.process_response::<RT>(request.call()),
-        }
+        self.with_auth_retry(|| match &data {
+            Some(data) => self.process_response::<RT>(request.clone().send_json(data)),
+            None => self.process_response::<RT>(request.clone().call()),
+        })
+    }
+
+    /// Use the client to make a custom POST request to the API with a form-encoded body.
+    pub fn post_form<RT: DeserializeOwned>(
+        &self,
+        api_type: TargetAPI,
+        path: impl AsRef<str> + Display,
+        queries: Option<impl Into<HashMap<String, String>>>,
+        form: &[(&str, &str)],
+    ) -> Result<RT, Error> {
+        let request = self.build_post_request(api_type, path, queries.map(|q| q.into()));
+        self.with_auth_retry(|| self.process_response::<RT>(request.clone().send_form(form)))
+    }
+
+    /// Use the client to make a custom POST request to the API with a raw body of some content type.
+    ///
+    /// Example
+    /// ```rust,no_run
+    /// use bombay::client::endpoints::TargetAPI;
+    /// use bombay::client::Client;
+    /// use serde_json::Value;
+    /// use std::collections::HashMap;
+    ///
+    /// let mc = Client::default(); // Without authentication.
+    /// let response: Value = mc
+    ///     .post_bytes(
+    ///         TargetAPI::Player,
+    ///         "/some/endpoint",
+    ///         None::<HashMap<String, String>>,
+    ///         "text/plain; charset=utf-8",
+    ///         b"Hello, Monstercat!",
+    ///     )
+    ///     .expect("Could not post.");
+    /// ```
+    pub fn post_bytes<RT: DeserializeOwned>(
+        &self,
+        api_type: TargetAPI,
+        path: impl AsRef<str> + Display,
+        queries: Option<impl Into<HashMap<String, String>>>,
+        content_type: &str,
+        body: &[u8],
+    ) -> Result<RT, Error> {
+        let request = self
+            .build_post_request(api_type, path, queries.map(|q| q.into()))
+            .set("Content-Type", content_type);
+        self.with_auth_retry(|| self.process_response::<RT>(request.clone().send_bytes(body)))
     }
 
     /// Use the client to make a custom POST request to the API, expecting empty response.
@@ -131,12 +794,114 @@ impl<ClientAuthState> Client<ClientAuthState> {
         data: Option<impl serde::Serialize>,
     ) -> Result<(), Error> {
         let request = self.build_post_request(api_type, path, queries.map(|q| q.into()));
-        match data {
-            Some(data) => self.process_empty_response(request.send_json(data)),
-            None => self.process_empty_response(request.call()),
+        self.with_auth_retry(|| match &data {
+            Some(data) => self.process_empty_response(request.clone().send_json(data)),
+            None => self.process_empty_response(request.clone().call()),
+        })
+    }
+
+    /// Use the client to make a custom HEAD request to the API, getting the
+    /// response status and headers without the content.
+    ///
+    /// Useful to check that something exists, or how large it is, cheaply.
+    ///
+    /// Example
+    /// ```rust,no_run
+    /// use bombay::client::endpoints::TargetAPI;
+    /// use bombay::client::Client;
+    /// use std::collections::HashMap;
+    ///
+    /// let mc = Client::default(); // Without authentication.
+    /// let head = mc
+    ///     .head(TargetAPI::Player, "/artist/rogue", None::<HashMap<String, String>>)
+    ///     .expect("Could not find Rogue.");
+    ///
+    /// println!("Status {}, type {:?}.", head.status, head.header("Content-Type"));
+    /// ```
+    pub fn head(
+        &self,
+        api_type: TargetAPI,
+        path: impl AsRef<str> + Display,
+        queries: Option<impl Into<HashMap<String, String>>>,
+    ) -> Result<WithMeta<()>, Error> {
+        let request = self.build_head_request(api_type, path, queries.map(|q| q.into()));
+
+        self.with_auth_retry(|| {
+            let started = Instant::now();
+            let response = request.clone().call().map_err(Error::from)?;
+
+            Ok(WithMeta {
+                value: (),
+                status: response.status(),
+                headers: response_headers(&response),
+                duration: started.elapsed(),
+            })
+        })
+    }
+
+    /// Make a HEAD request to the API and get the size of the content, if told.
+    pub(crate) fn content_length(
+        &self,
+        api_type: TargetAPI,
+        path: impl AsRef<str> + Display,
+        queries: Option<impl Into<HashMap<String, String>>>,
+    ) -> Result<Option<u64>, Error> {
+        Ok(self
+            .head(api_type, path, queries)?
+            .header("Content-Length")
+            .and_then(|length| length.parse::<u64>().ok()))
+    }
+
+    /// Make a request and, if the session expired, let the auth expiry handler
+    /// sign in again and retry the request once.
+    fn with_auth_retry<RT>(&self, attempt: impl Fn() -> Result<RT, Error>) -> Result<RT, Error> {
+        let handler = match &self.on_auth_expired {
+            Some(handler) => handler,
+            None => return attempt(),
+        };
+
+        // The session cookie is dropped from the store once it expires.
+        if !self.auth_provider.is_authenticated(&self.agent) {
+            self.renew_auth(handler)?;
+            return attempt();
+        }
+
+        match attempt() {
+            Err(err) if err.status() == Some(401) => {
+                self.renew_auth(handler)?;
+                attempt()
+            }
+            res => res,
         }
     }
 
+    /// Call the auth expiry handler with a signed-out client sharing this client's agent.
+    fn renew_auth(&self, handler: &AuthExpiredHandler) -> Result<(), Error> {
+        let mut signed_out = Client {
+            agent: self.agent.clone(),
+            aliases: self.aliases.clone(),
+            api_version: self.api_version,
+            auth: None,
+            auth_provider: self.auth_provider.clone(),
+            dirs: self.dirs.clone(),
+            filter_explicit: self.filter_explicit,
+            locale: self.locale.clone(),
+            max_body_size: self.max_body_size,
+            offline: self.offline.clone(),
+            on_auth_expired: None,
+            on_shutdown: None,
+            region: self.region.clone(),
+            single_flight: None,
+            unknown_enum_policy: self.unknown_enum_policy.clone(),
+            url_player_api: self.url_player_api.clone(),
+            url_www_api: self.url_www_api.clone(),
+            user_agent: self.user_agent.clone(),
+            user_state: PhantomData,
+        };
+
+        (handler.0)(&mut signed_out).map(|_| ())
+    }
+
     /// Construct get request for targeted API, including any query parameters.
     fn build_get_request(
         &self,
@@ -151,6 +916,24 @@ impl<ClientAuthState> Client<ClientAuthState> {
         .set("User-Agent", &self.user_agent)
         .set("Accept", "application/json");
 
+        let request = self.localize_request(self.auth_provider.authorize(request), path.as_ref());
+        self.add_request_queries(request, queries)
+    }
+
+    /// Construct head request for targeted API, including any query parameters.
+    fn build_head_request(
+        &self,
+        api: TargetAPI,
+        path: impl AsRef<str> + Display,
+        queries: Option<HashMap<String, String>>,
+    ) -> Request {
+        let request = match api {
+            TargetAPI::Player => self.agent.head(&format!("{}{}", self.url_player_api, path)),
+            TargetAPI::WWW => self.agent.head(&format!("{}{}", self.url_www_api, path)),
+        }
+        .set("User-Agent", &self.user_agent);
+
+        let request = self.localize_request(self.auth_provider.authorize(request), path.as_ref());
         self.add_request_queries(request, queries)
     }
 
@@ -168,15 +951,36 @@ impl<ClientAuthState> Client<ClientAuthState> {
         .set("User-Agent", &self.user_agent)
         .set("Accept", "application/json");
 
+        let request = self.localize_request(self.auth_provider.authorize(request), path.as_ref());
         self.add_request_queries(request, queries)
     }
 
+    /// Add the configured locale to a request, and the region if the path
+    /// is one of [`REGIONAL_PATHS`].
+    fn localize_request(&self, mut req: Request, path: &str) -> Request {
+        if let Some(locale) = &self.locale {
+            req = req.set("Accept-Language", locale);
+        }
+
+        match &self.region {
+            Some(region) if REGIONAL_PATHS.contains(&path) => {
+                req = req.query("region", &region.to_string());
+            }
+            _ => {}
+        }
+
+        req
+    }
+
     fn add_request_queries(
         &self,
         mut req: Request,
         queries: Option<HashMap<String, String>>,
     ) -> Request {
         if let Some(parameters) = queries {
+            // Sorted, so identical parameters always make identical URLs.
+            let parameters: std::collections::BTreeMap<String, String> =
+                parameters.into_iter().collect();
             for (parameter, value) in parameters {
                 req = req.query(&parameter, &value);
             }
@@ -190,9 +994,58 @@ impl<ClientAuthState> Client<ClientAuthState> {
         &self,
         result: Result<Response, ureq::Error>,
     ) -> Result<RT, Error> {
-        match result {
-            Ok(response) => response.into_json::<RT>().map_err(Error::IO),
-            Err(err) => Err(Error::Request(Box::new(err))),
+        let response = result.map_err(Error::from)?;
+
+        // Only take the slower path through a JSON value when there is data to adjust.
+        if self.api_version != ApiVersion::default()
+            || self.filter_explicit
+            || !self.aliases.is_empty()
+        {
+            let mut value =
+                compat::upgrade(self.read_response::<Value>(response)?, self.api_version);
+            compat::rename_aliases(&mut value, &self.aliases);
+            if self.filter_explicit {
+                content_filter::remove_explicit(&mut value);
+            }
+            return self.deserializing(|| RT::deserialize(value).map_err(Error::Deserialization));
+        }
+
+        self.deserializing(|| self.read_response::<RT>(response))
+    }
+
+    /// Run deserialization under the client's unknown enum policy, if set.
+    fn deserializing<R>(&self, f: impl FnOnce() -> R) -> R {
+        match &self.unknown_enum_policy {
+            Some(policy) => compat::with_unknown_enum_policy(policy.clone(), f),
+            None => f(),
+        }
+    }
+
+    /// Deserialize a response body, within the maximum body size if set.
+    fn read_response<RT: DeserializeOwned>(&self, response: Response) -> Result<RT, Error> {
+        let max_body_size = match self.max_body_size {
+            Some(max_body_size) => max_body_size,
+            None => return response.into_json::<RT>().map_err(Error::IO),
+        };
+
+        let content_length = response
+            .header("Content-Length")
+            .and_then(|length| length.parse::<u64>().ok());
+        if content_length.map_or(false, |length| length > max_body_size) {
+            return Err(Error::ResponseTooLarge(max_body_size));
+        }
+
+        // Deserialize while reading, so the body is never buffered whole.
+        let mut reader = LimitedReader {
+            inner: std::io::BufReader::new(response.into_reader()),
+            remaining: max_body_size,
+            exceeded: false,
+        };
+        let parsed = serde_json::from_reader::<_, RT>(&mut reader);
+
+        match parsed {
+            _ if reader.exceeded => Err(Error::ResponseTooLarge(max_body_size)),
+            parsed => parsed.map_err(Error::Deserialization),
         }
     }
 
@@ -200,32 +1053,96 @@ impl<ClientAuthState> Client<ClientAuthState> {
     fn process_empty_response(&self, result: Result<Response, ureq::Error>) -> Result<(), Error> {
         match result {
             Ok(_) => Ok(()),
-            Err(err) => Err(Error::Request(Box::new(err))),
+            Err(err) => Err(Error::from(err)),
         }
     }
 }
 
 impl Client<SignedOut> {
     /// Create a new signed-out client.
+    ///
+    /// Sessions are kept with the "cid" cookie of the player API's host,
+    /// see [`CookieAuth`] to use another cookie.
     pub fn new(player_api: String, www_api: String) -> Client<SignedOut> {
+        let offline = Arc::new(AtomicBool::new(false));
         Client {
+            agent: offline::build_agent(ureq::AgentBuilder::new(), &offline),
+            offline,
+            auth_provider: Arc::new(CookieAuth::for_url(&player_api)),
             user_state: PhantomData,
             url_player_api: player_api,
             url_www_api: www_api,
             user_agent: USER_AGENT.to_owned(),
+            aliases: Vec::new(),
+            api_version: ApiVersion::default(),
             auth: None,
-            agent: ureq::Agent::new(),
+            dirs: Dirs::default(),
+            filter_explicit: false,
+            locale: None,
+            max_body_size: None,
+            on_auth_expired: None,
+            on_shutdown: None,
+            region: None,
+            single_flight: None,
+            unknown_enum_policy: None,
         }
     }
 
+    /// Create a signed-out client from a snapshot, with its cookies and
+    /// settings, see [`ClientSnapshot`].
+    ///
+    /// The client authenticates with the "cid" cookie of the snapshot's player
+    /// API, like [`Client::new`]. Use [`Client::restore_snapshot`] to resume
+    /// the session the snapshot holds.
+    pub fn from_snapshot(snapshot: ClientSnapshot) -> Result<Client<SignedOut>, Error> {
+        let mut client = Client::new(
+            snapshot.url_player_api.clone(),
+            snapshot.url_www_api.clone(),
+        );
+        client.apply_snapshot(snapshot)?;
+        Ok(client)
+    }
+
+    /// Record requests to a cassette file, or replay them from one offline,
+    /// for deterministic tests and reproducible bug reports.
+    ///
+    /// Requests are matched by method and URL, and repeated requests get
+    /// their responses in recorded order. This replaces the client's agent,
+    /// so set it before signing in.
+    ///
+    /// Keep cassettes in the client's directories with
+    /// [`Dirs::cassette_file`], like
+    /// `CassetteMode::Record(client.dirs().cassette_file("bug-42"))`.
+    #[cfg(feature = "cassette")]
+    pub fn set_cassette(mut self, mode: cassette::CassetteMode) -> Result<Self, Error> {
+        let builder = ureq::AgentBuilder::new();
+        let builder = match mode {
+            cassette::CassetteMode::Record(path) => {
+                builder.middleware(cassette::Recorder::new(path))
+            }
+            cassette::CassetteMode::Replay(path) => {
+                builder.middleware(cassette::Player::new(cassette::Cassette::load(&path)?))
+            }
+        };
+        self.agent = offline::build_agent(builder, &self.offline);
+        Ok(self)
+    }
+
     /// Sign in and get a sign-in outcomes, depending on 2FA settings.
     pub fn sign_in(&mut self, email: String, password: String) -> Result<SignInOutcome, Error> {
-        let signin_parameters = SigninParameters {
+        self.sign_in_with_parameters(SigninParameters {
             auth: None,
+            captcha: None,
             email,
-            password,
-        };
+            password: Secret::new(password),
+        })
+    }
 
+    /// Sign in with the given parameters and get a sign-in outcome.
+    fn sign_in_with_parameters(
+        &mut self,
+        signin_parameters: SigninParameters,
+    ) -> Result<SignInOutcome, Error> {
         let signin_res = self.post::<AuthReply>(
             TargetAPI::Player,
             "/sign-in",
@@ -234,9 +1151,22 @@ impl Client<SignedOut> {
         );
 
         // If we get a valid response and need 2FA, handle that.
-        // Do nothing with an error. For some reason, a valid login
-        // with no 2FA will produce a status 400 response.
-        if let Ok(resp) = signin_res {
+        // Only bail on an error that describes a sign-in failure. For some
+        // reason, a valid login with no 2FA will produce a status 400 response.
+        let signin_res = match Self::check_signin_response(signin_res) {
+            Err(Error::SignInDetailed(SignInFailure::CaptchaRequired(challenge))) => {
+                self.auth = Some(SavedAuthDetails {
+                    captcha: None,
+                    email: signin_parameters.email,
+                    email_id: None,
+                    password: signin_parameters.password,
+                });
+
+                return Ok(SignInOutcome::Captcha(challenge, Self::captcha_callback));
+            }
+            res => res?,
+        };
+        if let Some(resp) = signin_res {
             if resp.needs_2fa {
                 let second_factor = resp
                     .default_auth_type
@@ -247,6 +1177,7 @@ impl Client<SignedOut> {
                     .ok_or(Error::SignIn("Bad sign-in response, missing auth data."))?;
 
                 let mut auth = SavedAuthDetails {
+                    captcha: signin_parameters.captcha,
                     email: signin_parameters.email.clone(),
                     email_id: None,
                     password: signin_parameters.password,
@@ -288,16 +1219,33 @@ impl Client<SignedOut> {
         &mut self,
         signin_param: SigninParameters,
     ) -> Result<Client<SignedIn>, Error> {
-        match self.post::<AuthReply>(
+        let signin_res = self.post::<AuthReply>(
             TargetAPI::Player,
             "/sign-in",
             None::<HashMap<String, String>>,
             Some(signin_param),
-        ) {
-            Ok(_) => self.verify_signin_cookie(),
+        );
+
+        Self::check_signin_response(signin_res)?;
+        self.verify_signin_cookie()
+    }
+
+    /// Keep a successful sign-in response, or turn a rejected one into a detailed
+    /// sign-in failure. Rejections that don't look like failures are ignored,
+    /// as a valid sign-in without 2FA may be answered with status 400.
+    ///
+    /// Other errors, like unreadable responses, are returned as they are.
+    fn check_signin_response<RT>(result: Result<RT, Error>) -> Result<Option<RT>, Error> {
+        match result {
+            Ok(resp) => Ok(Some(resp)),
             Err(Error::Request(boxed_err)) => match *boxed_err {
-                ureq::Error::Status(200, _) => self.verify_signin_cookie(),
-                _ => Err(Error::Request(boxed_err)),
+                ureq::Error::Status(status, response) => {
+                    match SignInFailure::from_response(status, response) {
+                        Some(failure) => Err(Error::SignInDetailed(failure)),
+                        None => Ok(None),
+                    }
+                }
+                transport_err => Err(Error::from(transport_err)),
             },
             Err(err) => Err(err),
         }
@@ -311,8 +1259,9 @@ impl Client<SignedOut> {
     ) -> Result<EmailCallback, Error> {
         let signin_parameters = SigninParameters {
             auth: None,
+            captcha: None,
             email,
-            password,
+            password: Secret::new(password),
         };
 
         let signin_res = self.post::<AuthDataEmail>(
@@ -322,12 +1271,13 @@ impl Client<SignedOut> {
             Some(signin_parameters.clone()),
         );
 
-        if let Ok(email_auth_data) = signin_res {
+        if let Some(email_auth_data) = Self::check_signin_response(signin_res)? {
             let id = email_auth_data
                 .id
                 .ok_or(Error::SignIn("Bad sign-in response, missing email id."))?;
 
             self.auth = Some(SavedAuthDetails {
+                captcha: None,
                 email: signin_parameters.email.clone(),
                 email_id: Some(id),
                 password: signin_parameters.password,
@@ -348,8 +1298,9 @@ impl Client<SignedOut> {
     ) -> Result<Client<SignedIn>, Error> {
         let signin_parameters = SigninParameters {
             auth: None,
+            captcha: None,
             email,
-            password,
+            password: Secret::new(password),
         };
 
         let signin_res = self.post::<AuthReply>(
@@ -360,15 +1311,17 @@ impl Client<SignedOut> {
         );
 
         // If we get a valid response and need 2FA, handle that.
-        // Do nothing with an error. For some reason, a valid login
-        // with no 2FA will produce a status 400 response.
-        if let Ok(resp) = signin_res {
+        // Only bail on an error that describes a sign-in failure. For some
+        // reason, a valid login with no 2FA will produce a status 400 response.
+        let signin_res = Self::check_signin_response(signin_res)?;
+        if let Some(resp) = signin_res {
             if resp.needs_2fa {
                 let second_factor = resp
                     .default_auth_type
                     .ok_or(Error::SignIn("Bad sign-in response, missing 2FA method."))?;
 
                 self.auth = Some(SavedAuthDetails {
+                    captcha: None,
                     email: signin_parameters.email.clone(),
                     email_id: None,
                     password: signin_parameters.password,
@@ -397,6 +1350,7 @@ impl Client<SignedOut> {
                 email: auth.email_id.clone(),
                 totp: None,
             }),
+            captcha: auth.captcha.clone(),
         })
     }
 
@@ -412,30 +1366,99 @@ impl Client<SignedOut> {
             password: auth.password.clone(),
             auth: Some(AuthParameters {
                 email: None,
-                totp: Some(code),
+                totp: Some(Secret::new(code)),
             }),
+            captcha: auth.captcha.clone(),
+        })
+    }
+
+    /// Function to restart login with a solved captcha token after username and password was already provided.
+    fn captcha_callback(&mut self, token: String) -> Result<SignInOutcome, Error> {
+        let auth = self
+            .auth
+            .take()
+            .ok_or(Error::SignIn("Missing sign-in data, needed for captcha."))?;
+
+        self.sign_in_with_parameters(SigninParameters {
+            email: auth.email,
+            password: auth.password,
+            auth: None,
+            captcha: Some(token),
         })
     }
 
+    /// Resume a session saved with [`Client::session_cookie`], without signing in again.
+    ///
+    /// The client gets a new agent holding only this cookie. This only checks
+    /// the cookie is set, not that the session is still valid.
+    pub fn restore_session(&mut self, cookie: String) -> Result<Client<SignedIn>, Error> {
+        let url = url::Url::parse(&self.url_player_api)
+            .map_err(|_| Error::Message("Invalid player API URL."))?;
+        let name = self
+            .auth_provider
+            .cookie_auth()
+            .ok_or(Error::SignIn(
+                "Client doesn't authenticate with a session cookie.",
+            ))?
+            .name
+            .clone();
+
+        let mut cookie_store = cookie_store::CookieStore::default();
+        cookie_store
+            .insert_raw(&ureq::Cookie::new(name, cookie), &url)
+            .map_err(|_| Error::SignIn("Session cookie was rejected."))?;
+        self.agent = offline::build_agent(
+            ureq::AgentBuilder::new().cookie_store(cookie_store),
+            &self.offline,
+        );
+
+        self.verify_signin_cookie()
+    }
+
+    /// Resume the session of a snapshot, without signing in again, see
+    /// [`ClientSnapshot`].
+    ///
+    /// The client takes the snapshot's cookies and settings, with a new agent,
+    /// and keeps its own auth provider and callbacks, so make it for the same
+    /// player API, or with [`Client::from_snapshot`]. Like
+    /// [`Client::restore_session`], this only checks the session cookie is
+    /// there, not that the session is still valid.
+    pub fn restore_snapshot(
+        &mut self,
+        snapshot: ClientSnapshot,
+    ) -> Result<Client<SignedIn>, Error> {
+        self.apply_snapshot(snapshot)?;
+        self.verify_signin_cookie()
+    }
+
     /// After a login strategy (may have) worked, confirm there is a login cookie.
     fn verify_signin_cookie(&mut self) -> Result<Client<SignedIn>, Error> {
         // Ensure saved auth details are removed.
         self.auth = None;
 
-        match self
-            .agent
-            .cookie_store()
-            .get("player.monstercat.app", "/", "cid")
-        {
-            Some(_) => Ok(Client {
+        match self.auth_provider.is_authenticated(&self.agent) {
+            true => Ok(Client {
                 agent: self.agent.clone(),
+                aliases: self.aliases.clone(),
+                api_version: self.api_version,
                 auth: None,
+                auth_provider: self.auth_provider.clone(),
+                dirs: self.dirs.clone(),
+                filter_explicit: self.filter_explicit,
+                locale: self.locale.clone(),
+                max_body_size: self.max_body_size,
+                offline: self.offline.clone(),
+                on_auth_expired: None,
+                on_shutdown: self.on_shutdown.clone(),
+                region: self.region.clone(),
+                single_flight: self.single_flight.clone(),
+                unknown_enum_policy: self.unknown_enum_policy.clone(),
                 url_player_api: self.url_player_api.clone(),
                 url_www_api: self.url_www_api.clone(),
                 user_agent: self.user_agent.clone(),
                 user_state: PhantomData,
             }),
-            None => Err(Error::SignIn(
+            false => Err(Error::SignIn(
                 "Sign-in verification failed, missing cookie.",
             )),
         }
@@ -443,8 +1466,103 @@ impl Client<SignedOut> {
 }
 
 impl Client<SignedIn> {
+    /// Get endpoint for connecting external services.
+    pub fn connect(&self) -> EndpointConnect<'_, SignedIn> {
+        EndpointConnect { client: self }
+    }
+
     /// Get endpoint for user-related functions.
-    pub fn user(&self) -> EndpointUser<SignedIn> {
+    pub fn user(&self) -> EndpointUser<'_, SignedIn> {
         EndpointUser { client: self }
     }
+
+    /// Set a callback to sign in again when the session expires.
+    ///
+    /// The callback gets a signed-out client sharing this client's agent, so
+    /// signing in with it renews this client's session. The request that found
+    /// the session expired is then retried once.
+    ///
+    /// Example
+    /// ```rust,no_run
+    /// use bombay::client::{Client, SignedIn};
+    ///
+    /// # fn example(mc: Client<SignedIn>) {
+    /// let mc = mc.set_on_auth_expired(|signed_out| {
+    ///     signed_out.sign_in_2fa_totp(
+    ///         "email".to_owned(),
+    ///         "password".to_owned(),
+    ///         "123456".to_owned(),
+    ///     )
+    /// });
+    /// # }
+    /// ```
+    pub fn set_on_auth_expired(
+        mut self,
+        callback: impl Fn(&mut Client<SignedOut>) -> Result<Client<SignedIn>, Error>
+            + Send
+            + Sync
+            + 'static,
+    ) -> Self {
+        self.on_auth_expired = Some(AuthExpiredHandler(Arc::new(callback)));
+        self
+    }
+
+    /// Get the session cookie, to persist and later resume with [`Client::restore_session`].
+    pub fn session_cookie(&self) -> Option<String> {
+        self.auth_provider.cookie_auth()?.get(&self.agent)
+    }
+}
+
+/// Callback called once with a snapshot of a client when it shuts down.
+#[derive(Clone)]
+pub(crate) struct ShutdownHandler {
+    callback: Arc<ShutdownFn>,
+    done: Arc<AtomicBool>,
+}
+
+/// Type for callback function provided to be told a client shuts down.
+type ShutdownFn = dyn Fn(ClientSnapshot) -> Result<(), Error> + Send + Sync;
+
+impl std::fmt::Debug for ShutdownHandler {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "ShutdownHandler")
+    }
+}
+
+/// Get the headers of a response, by lowercase header name.
+fn response_headers(response: &Response) -> HashMap<String, String> {
+    response
+        .headers_names()
+        .into_iter()
+        .filter_map(|name| {
+            let value = response.header(&name)?.to_owned();
+            Some((name.to_ascii_lowercase(), value))
+        })
+        .collect()
+}
+
+/// Reader failing once more than a number of bytes are read, noting that it did.
+struct LimitedReader<R> {
+    inner: R,
+    remaining: u64,
+    exceeded: bool,
+}
+
+impl<R: std::io::Read> std::io::Read for LimitedReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        // Allow reading one byte past the limit to tell a body of exactly the limit from a larger one.
+        let max = buf.len().min(self.remaining.saturating_add(1) as usize);
+        let read = self.inner.read(&mut buf[..max])?;
+
+        if read as u64 > self.remaining {
+            self.exceeded = true;
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::Other,
+                "Response body too large.",
+            ));
+        }
+
+        self.remaining -= read as u64;
+        Ok(read)
+    }
 }
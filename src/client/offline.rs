@@ -0,0 +1,50 @@
+use std::fmt::Display;
+use std::io;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use ureq::{AgentBuilder, Middleware, MiddlewareNext, Request, Response};
+
+/// Marker of a request stopped because its client is offline.
+#[derive(Debug)]
+struct Stopped;
+
+impl Display for Stopped {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Client is offline.")
+    }
+}
+
+impl std::error::Error for Stopped {}
+
+/// Middleware stopping requests from reaching the network while offline.
+///
+/// It is the agent's last middleware, so requests answered by earlier ones,
+/// like a replayed cassette, are still served.
+struct Guard(Arc<AtomicBool>);
+
+impl Middleware for Guard {
+    fn handle(&self, request: Request, next: MiddlewareNext) -> Result<Response, ureq::Error> {
+        match self.0.load(Ordering::Relaxed) {
+            true => Err(io::Error::new(io::ErrorKind::NotConnected, Stopped).into()),
+            false => next.handle(request),
+        }
+    }
+}
+
+/// Build an agent which stops requests while the offline flag is set.
+pub(crate) fn build_agent(builder: AgentBuilder, offline: &Arc<AtomicBool>) -> ureq::Agent {
+    builder.middleware(Guard(offline.clone())).build()
+}
+
+/// Check whether a request failed because its client is offline.
+pub(crate) fn is_stopped(err: &ureq::Error) -> bool {
+    let transport = match err {
+        ureq::Error::Transport(transport) => transport,
+        ureq::Error::Status(..) => return false,
+    };
+
+    std::error::Error::source(transport)
+        .and_then(|source| source.downcast_ref::<io::Error>())
+        .and_then(|io_err| io_err.get_ref())
+        .map_or(false, |inner| inner.is::<Stopped>())
+}
@@ -0,0 +1,42 @@
+use std::time::Duration;
+
+/// Longest to wait for an API to answer a ping.
+pub(crate) const PING_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Whether an API answered a ping, see
+/// [`Client::ping`](crate::client::Client::ping).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Reachability {
+    /// Base URL of the API.
+    pub url: String,
+    /// Status the API answered with, or none if it couldn't be reached.
+    pub status: Option<u16>,
+    /// Time until the API answered, or gave up on.
+    pub latency: Duration,
+}
+
+impl Reachability {
+    /// Check whether the API answered at all, even with an error status.
+    pub fn is_reachable(&self) -> bool {
+        self.status.is_some()
+    }
+}
+
+/// Reachability of each API a client is configured with.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Ping {
+    pub player_api: Reachability,
+    pub www_api: Reachability,
+}
+
+impl Ping {
+    /// Check whether every API answered.
+    pub fn is_online(&self) -> bool {
+        self.player_api.is_reachable() && self.www_api.is_reachable()
+    }
+
+    /// Check whether no API answered, so applications can work from cache.
+    pub fn is_offline(&self) -> bool {
+        !self.player_api.is_reachable() && !self.www_api.is_reachable()
+    }
+}
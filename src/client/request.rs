@@ -1,10 +1,15 @@
-use crate::mc::{playlist::PlaylistItem, util::Codec};
+use crate::mc::{playlist::PlaylistItem, tag::Tags, util::Codec};
+use iso8601_timestamp::Timestamp;
 use serde::{Deserialize, Serialize};
 use std::{collections::HashMap, fmt::Display};
 
 /// Object to set HTTP request query parameters.
+///
+/// Build it from [`RequestParameters::default`] or one of the `from_`
+/// constructors, and its setters, as parameters may be added.
 #[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
 #[serde(rename_all = "lowercase")]
+#[non_exhaustive]
 pub struct RequestParameters {
     pub filters: Option<HashMap<String, String>>,
     pub codec: Option<Codec>,
@@ -13,6 +18,9 @@ pub struct RequestParameters {
     pub creator_friendly: Option<bool>,
     pub no_gold: Option<bool>,
     pub pagination: Option<PaginationParameters>,
+    pub tags: Option<Tags>,
+    pub updated_since: Option<Timestamp>,
+    pub fields: Option<Vec<String>>,
 }
 
 impl Default for RequestParameters {
@@ -25,6 +33,9 @@ impl Default for RequestParameters {
             creator_friendly: None,
             no_gold: None,
             pagination: Some(PaginationParameters::default()),
+            tags: None,
+            updated_since: None,
+            fields: None,
         }
     }
 }
@@ -45,6 +56,18 @@ impl From<RequestParameters> for HashMap<String, String> {
             queries.insert("sort".to_owned(), sort);
         }
 
+        if let Some(tags) = val.tags {
+            queries.insert("tags".to_owned(), tags.normalized().to_string());
+        }
+
+        if let Some(updated_since) = val.updated_since {
+            queries.insert("updatedSince".to_owned(), updated_since.to_string());
+        }
+
+        if let Some(fields) = val.fields {
+            queries.insert("fields".to_owned(), fields.join(","));
+        }
+
         if let Some(pagination) = val.pagination {
             let pagination_map: HashMap<String, String> = pagination.into();
             queries.extend(pagination_map)
@@ -65,6 +88,9 @@ impl RequestParameters {
             creator_friendly: None,
             no_gold: None,
             pagination: Some(pagination),
+            tags: None,
+            updated_since: None,
+            fields: None,
         }
     }
 
@@ -78,6 +104,9 @@ impl RequestParameters {
             creator_friendly: None,
             no_gold: None,
             pagination: Some(PaginationParameters::default()),
+            tags: None,
+            updated_since: None,
+            fields: None,
         }
     }
 
@@ -91,6 +120,9 @@ impl RequestParameters {
             creator_friendly: None,
             no_gold: None,
             pagination: None,
+            tags: None,
+            updated_since: None,
+            fields: None,
         }
     }
 
@@ -105,8 +137,242 @@ impl RequestParameters {
         self.search = Some(search_term);
         self
     }
+
+    /// Set request parameters to only ask for items with all of these tags.
+    ///
+    /// Unverified: no endpoint is known to filter by a `tags` query, it is a
+    /// guess. Check the tags of the items that come back too.
+    pub fn set_tags(mut self, tags: Tags) -> Self {
+        self.tags = Some(tags);
+        self
+    }
+
+    /// Set request parameters to only ask for items updated since some time.
+    ///
+    /// Unverified: no endpoint is known to take `updatedSince`, the query name
+    /// is a guess. Mirrors should still check each item with
+    /// `Release::updated_since` or `Artist::updated_since`.
+    pub fn set_updated_since(mut self, since: Timestamp) -> Self {
+        self.updated_since = Some(since);
+        self
+    }
+
+    /// Set request parameters to only ask for some fields of the items, like
+    /// [`ReleaseField::Title`], to trim the payloads of list views.
+    ///
+    /// Fields the model can't be deserialized without, see [`Field::REQUIRED`],
+    /// are always asked for.
+    ///
+    /// This is speculative: the MC API doesn't document field selection, and
+    /// may ignore it and answer with every field anyway. So this only ever
+    /// trims payloads, and nothing relies on fields being left out.
+    ///
+    /// Example
+    /// ```rust
+    /// use bombay::client::{ReleaseField, RequestParameters};
+    ///
+    /// let parameters = RequestParameters::default()
+    ///     .set_fields(&[ReleaseField::Title, ReleaseField::CoverFileId]);
+    /// assert!(parameters.fields.unwrap().contains(&"CoverFileId".to_owned()));
+    /// ```
+    pub fn set_fields<F: Field>(mut self, fields: &[F]) -> Self {
+        let mut names: Vec<String> = Vec::new();
+        for field in F::REQUIRED.iter().chain(fields) {
+            let name = field.to_string();
+            if !names.contains(&name) {
+                names.push(name);
+            }
+        }
+        self.fields = Some(names);
+        self
+    }
+}
+
+/// Field of a model, as named by the MC API, to ask for only some fields with
+/// [`RequestParameters::set_fields`].
+pub trait Field: Copy + Display + 'static {
+    /// Fields the model can't be deserialized without.
+    const REQUIRED: &'static [Self];
 }
 
+/// Declare the fields of a model, with their names in the MC API, and which
+/// the model requires.
+macro_rules! model_fields {
+    (
+        $(#[$meta:meta])*
+        $name:ident,
+        required: [$($required:ident),+],
+        $($field:ident => $api_name:literal),+ $(,)?
+    ) => {
+        $(#[$meta])*
+        #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Deserialize, Serialize)]
+        #[non_exhaustive]
+        pub enum $name {
+            $($field),+
+        }
+
+        impl Display for $name {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                match self {
+                    $($name::$field => write!(f, $api_name)),+
+                }
+            }
+        }
+
+        impl Field for $name {
+            const REQUIRED: &'static [Self] = &[$($name::$required),+];
+        }
+    };
+}
+
+model_fields!(
+    /// Fields of a [`Release`](crate::mc::release::Release).
+    ReleaseField,
+    required: [
+        ArtistsTitle,
+        CatalogId,
+        Description,
+        FeaturedArtistsTitle,
+        Id,
+        ReleaseDate,
+        ReleaseDateTimezone,
+        Title,
+        Kind,
+        Version
+    ],
+    AlbumNotes => "AlbumNotes",
+    Artists => "Artists",
+    ArtistsTitle => "ArtistsTitle",
+    BrandId => "BrandId",
+    BrandTitle => "BrandTitle",
+    CatalogId => "CatalogId",
+    CopyrightPLine => "CopyrightPLine",
+    CoverFileId => "CoverFileId",
+    Description => "Description",
+    Downloadable => "Downloadable",
+    FeaturedArtistsTitle => "FeaturedArtistsTitle",
+    GenrePrimary => "GenrePrimary",
+    GenreSecondary => "GenreSecondary",
+    Grid => "GRid",
+    Id => "Id",
+    InEarlyAccess => "InEarlyAccess",
+    Links => "Links",
+    PrereleaseDate => "PrereleaseDate",
+    PresaveDate => "PresaveDate",
+    ReleaseDate => "ReleaseDate",
+    ReleaseDateTimezone => "ReleaseDateTimezone",
+    SpotifyId => "SpotifyId",
+    Streamable => "Streamable",
+    Tags => "Tags",
+    Title => "Title",
+    Tracks => "Tracks",
+    Kind => "Type",
+    Upc => "UPC",
+    Version => "Version",
+    YouTubeUrl => "YouTubeUrl",
+);
+
+model_fields!(
+    /// Fields of a [`Track`](crate::mc::release::Track).
+    ///
+    /// Tracks require most of their fields, so selecting fields mostly trims
+    /// their artists and audio features.
+    TrackField,
+    required: [
+        ArtistsTitle,
+        Bpm,
+        Brand,
+        BrandId,
+        CreatorFriendly,
+        Downloadable,
+        Duration,
+        Explicit,
+        GenrePrimary,
+        GenreSecondary,
+        Isrc,
+        Id,
+        InEarlyAccess,
+        LockStatus,
+        Public,
+        Release,
+        Streamable,
+        Title,
+        TrackNumber,
+        Version
+    ],
+    Artists => "Artists",
+    ArtistsTitle => "ArtistsTitle",
+    Bpm => "BPM",
+    Brand => "Brand",
+    BrandId => "BrandId",
+    CreatorFriendly => "CreatorFriendly",
+    DebutDate => "DebutDate",
+    Downloadable => "Downloadable",
+    Duration => "Duration",
+    Explicit => "Explicit",
+    GenrePrimary => "GenrePrimary",
+    GenreSecondary => "GenreSecondary",
+    Isrc => "ISRC",
+    Id => "Id",
+    InEarlyAccess => "InEarlyAccess",
+    LockStatus => "LockStatus",
+    Public => "Public",
+    PlaylistSort => "PlaylistSort",
+    Release => "Release",
+    Streamable => "Streamable",
+    Tags => "Tags",
+    Title => "Title",
+    TrackNumber => "TrackNumber",
+    Version => "Version",
+);
+
+model_fields!(
+    /// Fields of an [`Artist`](crate::mc::artist::Artist).
+    ArtistField,
+    required: [Details, Id, Name, Public, ShowEvent, Uri],
+    About => "About",
+    ActiveYears => "ActiveYears",
+    Details => "Details",
+    FeaturedReleaseId => "FeaturedReleaseId",
+    Id => "Id",
+    Links => "Links",
+    Name => "Name",
+    ProfileFileId => "ProfileFileId",
+    Public => "Public",
+    ShowEvent => "ShowEvent",
+    Tags => "Tags",
+    Uri => "URI",
+);
+
+model_fields!(
+    /// Fields of a [`Playlist`](crate::mc::playlist::Playlist).
+    PlaylistField,
+    required: [
+        Archived,
+        CreatedAt,
+        Description,
+        Id,
+        IsPublic,
+        MyLibrary,
+        NumRecords,
+        Title,
+        UpdatedAt
+    ],
+    Archived => "Archived",
+    BackgroundFileId => "BackgroundFileId",
+    CreatedAt => "CreatedAt",
+    Description => "Description",
+    Id => "Id",
+    IsPublic => "IsPublic",
+    Items => "Items",
+    MyLibrary => "MyLibrary",
+    NumRecords => "NumRecords",
+    TileFileId => "TileFileId",
+    Title => "Title",
+    UpdatedAt => "UpdatedAt",
+    UserId => "UserId",
+);
+
 /// Type to set pagination for response.
 #[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
 #[serde(rename_all = "PascalCase")]
@@ -126,6 +392,16 @@ impl Default for PaginationParameters {
     }
 }
 
+impl PaginationParameters {
+    /// Get these parameters with the offset clamped to some total number of items.
+    pub fn clamped(&self, total: usize) -> Self {
+        PaginationParameters {
+            limit: self.limit,
+            offset: self.offset.min(total),
+        }
+    }
+}
+
 impl From<PaginationParameters> for HashMap<String, String> {
     fn from(val: PaginationParameters) -> Self {
         let mut queries = HashMap::new();
@@ -135,9 +411,48 @@ impl From<PaginationParameters> for HashMap<String, String> {
     }
 }
 
+/// Kinds of identifiers a release can be looked up by.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub enum IdType {
+    /// Catalog code, like "MCS1186".
+    CatalogId,
+    /// Release UUID.
+    ///
+    /// Unverified: the API isn't known to take `idType=id`, the value is a
+    /// guess.
+    Id,
+    /// Barcode, 12 or 13 digits.
+    ///
+    /// Unverified: the API isn't known to take `idType=upc`, the value is a
+    /// guess. UPCs are found with [`IdType::CatalogId`] too, which is what
+    /// `EndpointRelease::get_by_catalog_id` sends.
+    Upc,
+}
+
+impl Display for IdType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            IdType::CatalogId => write!(f, "catalogId"),
+            IdType::Id => write!(f, "id"),
+            IdType::Upc => write!(f, "upc"),
+        }
+    }
+}
+
+impl From<IdType> for HashMap<String, String> {
+    fn from(val: IdType) -> Self {
+        let mut queries = HashMap::new();
+        queries.insert("idType".to_owned(), val.to_string());
+        queries
+    }
+}
+
 /// Valid operations for single playlist item.
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
+#[non_exhaustive]
 pub enum PlaylistItemOperations {
     Add,
     Remove,
@@ -169,6 +484,7 @@ impl From<PlaylistItemOperations> for HashMap<String, String> {
 /// Valid operations for multiple playlist items.
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
+#[non_exhaustive]
 pub enum PlaylistItemsOperations {
     Add,
     Remove,
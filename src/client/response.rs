@@ -1,5 +1,9 @@
+use crate::client::PaginationParameters;
+use crate::mc::release::TrackID;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::fmt::Display;
+use std::time::Duration;
 
 /// Pagination information and a vector of some MC type.
 #[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
@@ -12,5 +16,157 @@ pub struct Paginated<T> {
     pub offset: usize,
 }
 
+impl<T> Paginated<T> {
+    /// Get the number of items actually returned in this page.
+    pub fn received(&self) -> usize {
+        self.data.as_ref().map_or(0, Vec::len)
+    }
+
+    /// Get the offset of the page after this one, or `None` if this is the last page.
+    ///
+    /// The offset advances by the number of items received rather than the
+    /// requested limit, so a short page does not skip items. Offsets never
+    /// overflow and never go past the total.
+    pub fn next_offset(&self) -> Option<usize> {
+        let received = self.received();
+        if received == 0 {
+            return None;
+        }
+
+        let next = self.offset.saturating_add(received).min(self.total);
+        (next < self.total).then_some(next)
+    }
+
+    /// Check this page against the pagination that was requested for it.
+    ///
+    /// Anomalies don't make the page unusable, but automatic pagination should
+    /// not trust the page math blindly when any are present.
+    pub fn anomalies(&self, requested: &PaginationParameters) -> Vec<PaginationAnomaly> {
+        let mut anomalies = Vec::new();
+        let received = self.received();
+
+        if requested.offset > self.total {
+            anomalies.push(PaginationAnomaly::OffsetPastTotal {
+                offset: requested.offset,
+                total: self.total,
+            });
+        }
+
+        if self.offset != requested.offset {
+            anomalies.push(PaginationAnomaly::OffsetMismatch {
+                requested: requested.offset,
+                returned: self.offset,
+            });
+        }
+
+        // Fewer items than asked for is only expected on the last page.
+        let expected = requested
+            .limit
+            .min(self.total.saturating_sub(requested.offset));
+        if received < expected {
+            anomalies.push(PaginationAnomaly::ShortPage { expected, received });
+        }
+
+        if received > requested.limit {
+            anomalies.push(PaginationAnomaly::LimitExceeded {
+                limit: requested.limit,
+                received,
+            });
+        }
+
+        anomalies
+    }
+}
+
+/// Something off about a page compared to what was requested.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum PaginationAnomaly {
+    /// The requested offset is past the total number of items.
+    OffsetPastTotal { offset: usize, total: usize },
+    /// The page reports a different offset than the one requested.
+    OffsetMismatch { requested: usize, returned: usize },
+    /// Fewer items were returned than the limit and total call for.
+    ShortPage { expected: usize, received: usize },
+    /// More items were returned than the requested limit.
+    LimitExceeded { limit: usize, received: usize },
+}
+
+impl Display for PaginationAnomaly {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PaginationAnomaly::OffsetPastTotal { offset, total } => {
+                write!(f, "Offset {} is past the total of {} items.", offset, total)
+            }
+            PaginationAnomaly::OffsetMismatch {
+                requested,
+                returned,
+            } => write!(
+                f,
+                "Requested offset {} but the page starts at {}.",
+                requested, returned
+            ),
+            PaginationAnomaly::ShortPage { expected, received } => {
+                write!(f, "Expected {} items but received {}.", expected, received)
+            }
+            PaginationAnomaly::LimitExceeded { limit, received } => write!(
+                f,
+                "Received {} items, more than the limit of {}.",
+                received, limit
+            ),
+        }
+    }
+}
+
+/// Some MC type along with details of the HTTP response it came from.
+#[derive(Clone, Debug, PartialEq)]
+pub struct WithMeta<T> {
+    pub value: T,
+    /// HTTP status code of the response.
+    pub status: u16,
+    /// Response headers, by lowercase header name.
+    pub headers: HashMap<String, String>,
+    /// Time from sending the request until the response body was read.
+    pub duration: Duration,
+}
+
+impl<T> WithMeta<T> {
+    /// Get a response header by name, ignoring letter case.
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .get(&name.to_ascii_lowercase())
+            .map(String::as_str)
+    }
+
+    /// Transform the value, keeping the response details.
+    pub fn map<U>(self, f: impl FnOnce(T) -> U) -> WithMeta<U> {
+        WithMeta {
+            value: f(self.value),
+            status: self.status,
+            headers: self.headers,
+            duration: self.duration,
+        }
+    }
+}
+
+/// Estimated size of downloading some tracks.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DownloadSize {
+    /// Size of each track in bytes, if the API told it.
+    pub tracks: Vec<(TrackID, Option<u64>)>,
+}
+
+impl DownloadSize {
+    /// Get the total size in bytes of the tracks with a known size.
+    pub fn bytes(&self) -> u64 {
+        self.tracks.iter().filter_map(|(_, size)| *size).sum()
+    }
+
+    /// Check whether the size of every track is known, so the total is exact.
+    pub fn is_exact(&self) -> bool {
+        self.tracks.iter().all(|(_, size)| size.is_some())
+    }
+}
+
 /// Some MC type wrapped in a HashMap to facilitate dynamic parent key.
 pub type Wrapped<T> = HashMap<String, T>;
@@ -0,0 +1,199 @@
+use crate::client::{ClientSnapshot, Error, SnapshotCipher};
+#[cfg(feature = "encryption")]
+use chacha20poly1305::aead::{rand_core::RngCore, Aead, AeadCore, KeyInit, OsRng};
+#[cfg(feature = "encryption")]
+use chacha20poly1305::{XChaCha20Poly1305, XNonce};
+use std::fs;
+use std::io::Write;
+use std::path::Path;
+#[cfg(feature = "encryption")]
+use zeroize::Zeroizing;
+
+/// Header of files sealed with a [`PassphraseCipher`], with the format version.
+#[cfg(feature = "encryption")]
+const SEALED_HEADER: &[u8] = b"bombay-sealed-1\n";
+#[cfg(feature = "encryption")]
+const SALT_LEN: usize = 16;
+#[cfg(feature = "encryption")]
+const NONCE_LEN: usize = 24;
+
+/// Session saved to a file, to sign in once and reuse the session across
+/// runs of a CLI.
+///
+/// It holds a [`ClientSnapshot`], so the same security notes apply. Files
+/// are only readable by their owner on Unix, but [`SessionFile::save`] still
+/// writes the session cookie in plaintext. With the `encryption` feature,
+/// `SessionFile::save_encrypted` seals it with a passphrase instead.
+#[derive(Clone, Debug)]
+pub struct SessionFile {
+    pub snapshot: ClientSnapshot,
+}
+
+impl SessionFile {
+    /// Create a session file of a client snapshot.
+    pub fn new(snapshot: ClientSnapshot) -> Self {
+        SessionFile { snapshot }
+    }
+
+    /// Read a session saved with [`SessionFile::save`].
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, Error> {
+        let bytes = fs::read(path).map_err(Error::IO)?;
+        Ok(SessionFile::new(ClientSnapshot::from_bytes(&bytes)?))
+    }
+
+    /// Write the session to a file, replacing it, in plaintext.
+    ///
+    /// The file's directory is created if needed, so sessions can be saved
+    /// to [`Dirs::session_file`](crate::dirs::Dirs::session_file) right away.
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), Error> {
+        write_private(path.as_ref(), &self.snapshot.to_bytes()?)
+    }
+
+    /// Read a session sealed with a cipher, see [`SessionFile::save_sealed`].
+    pub fn load_sealed(
+        path: impl AsRef<Path>,
+        cipher: &impl SnapshotCipher,
+    ) -> Result<Self, Error> {
+        let sealed = fs::read(path).map_err(Error::IO)?;
+        Ok(SessionFile::new(ClientSnapshot::from_sealed_bytes(
+            &sealed, cipher,
+        )?))
+    }
+
+    /// Write the session to a file, replacing it, sealed with a cipher.
+    pub fn save_sealed(
+        &self,
+        path: impl AsRef<Path>,
+        cipher: &impl SnapshotCipher,
+    ) -> Result<(), Error> {
+        write_private(path.as_ref(), &self.snapshot.to_sealed_bytes(cipher)?)
+    }
+
+    /// Read a session saved with [`SessionFile::save_encrypted`].
+    ///
+    /// A wrong passphrase, or a file changed since it was saved, fails to
+    /// decrypt.
+    #[cfg(feature = "encryption")]
+    pub fn load_encrypted(path: impl AsRef<Path>, passphrase: &str) -> Result<Self, Error> {
+        SessionFile::load_sealed(path, &PassphraseCipher::new(passphrase))
+    }
+
+    /// Write the session to a file, replacing it, encrypted with a
+    /// passphrase, see [`PassphraseCipher`].
+    ///
+    /// Example
+    /// ```rust,no_run
+    /// use bombay::client::{Client, Error, SessionFile, SignedIn};
+    ///
+    /// # fn example(signed_in: Client<SignedIn>) -> Result<(), Error> {
+    /// SessionFile::new(signed_in.snapshot()).save_encrypted("session.bin", "passphrase")?;
+    ///
+    /// // On the next run, resume the session without signing in again.
+    /// let session = SessionFile::load_encrypted("session.bin", "passphrase")?;
+    /// let signed_in = Client::default().restore_snapshot(session.snapshot)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "encryption")]
+    pub fn save_encrypted(&self, path: impl AsRef<Path>, passphrase: &str) -> Result<(), Error> {
+        self.save_sealed(path, &PassphraseCipher::new(passphrase))
+    }
+}
+
+/// Write a file only its owner can read, on Unix, creating its directory.
+fn write_private(path: &Path, bytes: &[u8]) -> Result<(), Error> {
+    if let Some(directory) = path
+        .parent()
+        .filter(|parent| !parent.as_os_str().is_empty())
+    {
+        fs::create_dir_all(directory).map_err(Error::IO)?;
+    }
+
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+
+    let mut file = options.open(path).map_err(Error::IO)?;
+    // The mode only applies to new files, so an existing one is restricted
+    // before anything is written to it.
+    #[cfg(unix)]
+    file.set_permissions(std::os::unix::fs::PermissionsExt::from_mode(0o600))
+        .map_err(Error::IO)?;
+    file.write_all(bytes).map_err(Error::IO)
+}
+
+/// Cipher sealing snapshots with a passphrase, using XChaCha20-Poly1305 with
+/// a key derived by Argon2id.
+///
+/// Each seal gets a random salt and nonce, kept in front of the ciphertext.
+/// Deriving the key is deliberately slow, to make guessing the passphrase of
+/// a stolen file expensive, so seal once rather than per request.
+#[cfg(feature = "encryption")]
+pub struct PassphraseCipher {
+    passphrase: Zeroizing<String>,
+}
+
+#[cfg(feature = "encryption")]
+impl std::fmt::Debug for PassphraseCipher {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PassphraseCipher")
+            .field("passphrase", &crate::client::auth::Redacted)
+            .finish()
+    }
+}
+
+#[cfg(feature = "encryption")]
+impl PassphraseCipher {
+    /// Create a cipher with a passphrase.
+    pub fn new(passphrase: &str) -> Self {
+        PassphraseCipher {
+            passphrase: Zeroizing::new(passphrase.to_owned()),
+        }
+    }
+
+    /// Derive the key of a salt.
+    fn cipher(&self, salt: &[u8]) -> Result<XChaCha20Poly1305, Error> {
+        let mut key = Zeroizing::new([0u8; 32]);
+        argon2::Argon2::default()
+            .hash_password_into(self.passphrase.as_bytes(), salt, key.as_mut())
+            .map_err(|_| Error::Message("Could not derive a key from the passphrase."))?;
+        Ok(XChaCha20Poly1305::new(key.as_ref().into()))
+    }
+}
+
+#[cfg(feature = "encryption")]
+impl SnapshotCipher for PassphraseCipher {
+    fn seal(&self, plaintext: &[u8]) -> Result<Vec<u8>, Error> {
+        let mut salt = [0u8; SALT_LEN];
+        OsRng.fill_bytes(&mut salt);
+        let nonce = XChaCha20Poly1305::generate_nonce(&mut OsRng);
+        let ciphertext = self
+            .cipher(&salt)?
+            .encrypt(&nonce, plaintext)
+            .map_err(|_| Error::Message("Could not encrypt the session."))?;
+
+        let mut sealed = SEALED_HEADER.to_vec();
+        sealed.extend_from_slice(&salt);
+        sealed.extend_from_slice(&nonce);
+        sealed.extend(ciphertext);
+        Ok(sealed)
+    }
+
+    fn open(&self, sealed: &[u8]) -> Result<Vec<u8>, Error> {
+        let sealed = sealed
+            .strip_prefix(SEALED_HEADER)
+            .filter(|rest| rest.len() >= SALT_LEN + NONCE_LEN)
+            .ok_or(Error::Message(
+                "Session was not encrypted with a passphrase.",
+            ))?;
+        let (salt, rest) = sealed.split_at(SALT_LEN);
+        let (nonce, ciphertext) = rest.split_at(NONCE_LEN);
+
+        self.cipher(salt)?
+            .decrypt(XNonce::from_slice(nonce), ciphertext)
+            .map_err(|_| {
+                Error::Message("Could not decrypt the session, wrong passphrase or damaged file.")
+            })
+    }
+}
@@ -0,0 +1,101 @@
+use serde_json::Value;
+use std::collections::HashMap;
+use std::sync::{Arc, Condvar, Mutex};
+
+/// Outcome of a request shared with the callers waiting on it. `None` means
+/// the request failed, and waiting callers should make their own request.
+type Outcome = Option<Arc<Value>>;
+
+/// Request in flight, which callers for the same URL wait on.
+#[derive(Debug, Default)]
+pub(crate) struct Flight {
+    outcome: Mutex<Option<Outcome>>,
+    landed: Condvar,
+}
+
+impl Flight {
+    /// Wait for the request to finish and get its outcome.
+    pub fn wait(&self) -> Outcome {
+        let mut outcome = self.outcome.lock().unwrap_or_else(|err| err.into_inner());
+        loop {
+            if let Some(outcome) = outcome.as_ref() {
+                return outcome.clone();
+            }
+            outcome = self
+                .landed
+                .wait(outcome)
+                .unwrap_or_else(|err| err.into_inner());
+        }
+    }
+}
+
+/// Role of a caller in a flight.
+pub(crate) enum Role<'a> {
+    /// The caller makes the request, then lands the flight.
+    Leader(Leader<'a>),
+    /// The caller waits on another caller's request.
+    Follower(Arc<Flight>),
+}
+
+/// Lead of a flight, which lands it as failed if dropped before landing, like
+/// when the request panics, so followers make their own request instead of
+/// waiting forever.
+pub(crate) struct Leader<'a> {
+    single_flight: &'a SingleFlight,
+    url: String,
+    flight: Arc<Flight>,
+    landed: bool,
+}
+
+impl Leader<'_> {
+    /// Finish the flight, sharing the outcome with its followers.
+    pub fn land(mut self, outcome: Outcome) {
+        self.landed = true;
+        self.single_flight.land(&self.url, &self.flight, outcome);
+    }
+}
+
+impl Drop for Leader<'_> {
+    fn drop(&mut self) {
+        if !self.landed {
+            self.single_flight.land(&self.url, &self.flight, None);
+        }
+    }
+}
+
+/// Coalesces identical GET requests made concurrently into a single request.
+#[derive(Debug, Default)]
+pub(crate) struct SingleFlight {
+    in_flight: Mutex<HashMap<String, Arc<Flight>>>,
+}
+
+impl SingleFlight {
+    /// Join the flight for a URL, leading it if there is none.
+    pub fn join(&self, url: &str) -> Role<'_> {
+        let mut in_flight = self.in_flight.lock().unwrap_or_else(|err| err.into_inner());
+        match in_flight.get(url) {
+            Some(flight) => Role::Follower(flight.clone()),
+            None => {
+                let flight = Arc::new(Flight::default());
+                in_flight.insert(url.to_owned(), flight.clone());
+                Role::Leader(Leader {
+                    single_flight: self,
+                    url: url.to_owned(),
+                    flight,
+                    landed: false,
+                })
+            }
+        }
+    }
+
+    /// Finish a led flight, sharing the outcome with its followers.
+    fn land(&self, url: &str, flight: &Flight, outcome: Outcome) {
+        self.in_flight
+            .lock()
+            .unwrap_or_else(|err| err.into_inner())
+            .remove(url);
+
+        *flight.outcome.lock().unwrap_or_else(|err| err.into_inner()) = Some(outcome);
+        flight.landed.notify_all();
+    }
+}
@@ -0,0 +1,138 @@
+use crate::client::auth::Redacted;
+use crate::client::Error;
+use crate::compat::ApiVersion;
+use crate::mc::util::Region;
+use serde::{Deserialize, Serialize};
+
+/// Version of the snapshot format, bumped when it changes incompatibly.
+pub(crate) const SNAPSHOT_VERSION: u32 = 1;
+
+/// State of a client, to share a session with worker processes or serverless
+/// invocations without each of them signing in again.
+///
+/// Take one with [`Client::snapshot`](crate::client::Client::snapshot), turn
+/// it into bytes with [`ClientSnapshot::to_bytes`], and rehydrate a client
+/// elsewhere with [`Client::from_snapshot`](crate::client::Client::from_snapshot),
+/// or resume its session with
+/// [`Client::restore_snapshot`](crate::client::Client::restore_snapshot).
+/// The cookie store is copied while locked, so taking a snapshot while other
+/// threads make requests with the client gives a consistent copy.
+///
+/// It holds the cookies, including the session cookie, and the client's
+/// settings. Callbacks, the auth provider, field aliases, the unknown enum
+/// policy, and the directories are left out, the client restoring the
+/// snapshot keeps its own.
+///
+/// # Security
+///
+/// Anyone holding the bytes can act as the signed-in user until the session
+/// expires or is signed out, the same as holding the password for that long.
+/// Plain bytes are only as safe as where they are kept, so rather seal them
+/// with a [`SnapshotCipher`] before handing them to a queue, a cache, or an
+/// environment variable, and share the key over another channel. Sealing
+/// hides the session, but does not stop a sealed snapshot from being replayed
+/// by whoever has the key. Sign out to end every copy of a session at once.
+///
+/// Example
+/// ```rust
+/// use bombay::client::{Client, ClientSnapshot};
+///
+/// let mc = Client::default().set_locale(Some("fr-CA".to_owned()));
+/// let bytes = mc.snapshot().to_bytes().unwrap();
+///
+/// // In a worker process.
+/// let worker = Client::from_snapshot(ClientSnapshot::from_bytes(&bytes).unwrap()).unwrap();
+/// assert_eq!(worker.snapshot().locale(), Some("fr-CA"));
+/// ```
+#[derive(Clone, Deserialize, Serialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct ClientSnapshot {
+    pub(crate) version: u32,
+    pub(crate) api_version: ApiVersion,
+    pub(crate) cookies: Vec<cookie_store::Cookie<'static>>,
+    pub(crate) filter_explicit: bool,
+    pub(crate) locale: Option<String>,
+    pub(crate) max_body_size: Option<u64>,
+    pub(crate) offline: bool,
+    pub(crate) region: Option<Region>,
+    pub(crate) single_flight: bool,
+    pub(crate) url_player_api: String,
+    pub(crate) url_www_api: String,
+    pub(crate) user_agent: String,
+}
+
+impl std::fmt::Debug for ClientSnapshot {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ClientSnapshot")
+            .field("version", &self.version)
+            .field("api_version", &self.api_version)
+            .field("cookies", &Redacted)
+            .field("filter_explicit", &self.filter_explicit)
+            .field("locale", &self.locale)
+            .field("max_body_size", &self.max_body_size)
+            .field("offline", &self.offline)
+            .field("region", &self.region)
+            .field("single_flight", &self.single_flight)
+            .field("url_player_api", &self.url_player_api)
+            .field("url_www_api", &self.url_www_api)
+            .field("user_agent", &self.user_agent)
+            .finish()
+    }
+}
+
+/// Cipher sealing snapshots, so their bytes don't hold the session cookie
+/// in plaintext.
+///
+/// Use an authenticated cipher (AEAD), like AES-GCM or ChaCha20-Poly1305,
+/// with a random nonce per seal kept next to the ciphertext, so tampered
+/// bytes fail to open instead of restoring a forged session.
+pub trait SnapshotCipher {
+    /// Encrypt and authenticate the bytes of a snapshot.
+    fn seal(&self, plaintext: &[u8]) -> Result<Vec<u8>, Error>;
+    /// Check and decrypt bytes sealed with [`SnapshotCipher::seal`].
+    fn open(&self, sealed: &[u8]) -> Result<Vec<u8>, Error>;
+}
+
+impl ClientSnapshot {
+    /// Get the locale of responses the client was set to.
+    pub fn locale(&self) -> Option<&str> {
+        self.locale.as_deref()
+    }
+
+    /// Get the player API URL the client was made for.
+    pub fn url_player_api(&self) -> &str {
+        &self.url_player_api
+    }
+
+    /// Check whether the snapshot holds a cookie by name, like the session
+    /// cookie, without exposing its value.
+    pub fn has_cookie(&self, name: &str) -> bool {
+        self.cookies.iter().any(|cookie| cookie.name() == name)
+    }
+
+    /// Get the snapshot as plain bytes, see the security notes of
+    /// [`ClientSnapshot`].
+    pub fn to_bytes(&self) -> Result<Vec<u8>, Error> {
+        serde_json::to_vec(self).map_err(Error::Deserialization)
+    }
+
+    /// Read a snapshot from bytes made with [`ClientSnapshot::to_bytes`].
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        let snapshot: ClientSnapshot =
+            serde_json::from_slice(bytes).map_err(Error::Deserialization)?;
+        match snapshot.version {
+            SNAPSHOT_VERSION => Ok(snapshot),
+            _ => Err(Error::Message("Unsupported client snapshot version.")),
+        }
+    }
+
+    /// Get the snapshot as bytes sealed with a cipher.
+    pub fn to_sealed_bytes(&self, cipher: &impl SnapshotCipher) -> Result<Vec<u8>, Error> {
+        cipher.seal(&self.to_bytes()?)
+    }
+
+    /// Read a snapshot from bytes made with [`ClientSnapshot::to_sealed_bytes`].
+    pub fn from_sealed_bytes(sealed: &[u8], cipher: &impl SnapshotCipher) -> Result<Self, Error> {
+        ClientSnapshot::from_bytes(&cipher.open(sealed)?)
+    }
+}
@@ -0,0 +1,119 @@
+use crate::client::Error;
+use std::io::{self, Read};
+use ureq::Request;
+
+/// Times a stream is resumed in a row without reading anything, before
+/// giving up on it.
+const MAX_RESUMES: u32 = 3;
+
+/// Reader of a track's audio, which resumes where it left off when the
+/// connection drops, like when the signed URL it was redirected to expires
+/// mid-playback.
+///
+/// Resuming requests the track from the API again, which signs a new URL,
+/// and asks for the rest from the byte offset reached. If the server sends
+/// the whole track instead, the bytes already read are skipped, and a part
+/// starting anywhere else is an error. Tracks served
+/// as HLS playlists are read segment by segment, and are not resumed.
+pub struct TrackStream {
+    request: Option<Request>,
+    reader: Box<dyn Read + Send + Sync>,
+    position: u64,
+    resumes: u32,
+}
+
+impl TrackStream {
+    /// Create a stream resumed by making the request again.
+    pub(crate) fn new(request: Request, response: ureq::Response) -> Self {
+        TrackStream {
+            request: Some(request),
+            reader: response.into_reader(),
+            position: 0,
+            resumes: 0,
+        }
+    }
+
+    /// Create a stream that is not resumed.
+    pub(crate) fn unresumable(reader: Box<dyn Read + Send + Sync>) -> Self {
+        TrackStream {
+            request: None,
+            reader,
+            position: 0,
+            resumes: 0,
+        }
+    }
+
+    /// Get the number of bytes read so far.
+    pub fn position(&self) -> u64 {
+        self.position
+    }
+
+    /// Request the rest of the track, from the position reached.
+    fn resume(&mut self, request: Request) -> io::Result<()> {
+        let response = request
+            .set("Range", &format!("bytes={}-", self.position))
+            .call()
+            .map_err(|err| io::Error::new(io::ErrorKind::Other, Error::from(err)))?;
+
+        let ranged = match response.status() {
+            206 => {
+                // Only the rest of the track picks up where the stream left off.
+                let start = response
+                    .header("Content-Range")
+                    .and_then(|range| range.strip_prefix("bytes "))
+                    .and_then(|range| range.split_once('-'))
+                    .and_then(|(start, _)| start.trim().parse::<u64>().ok());
+                if start != Some(self.position) {
+                    return Err(io::Error::new(
+                        io::ErrorKind::InvalidData,
+                        "Resumed stream does not start where it left off.",
+                    ));
+                }
+                true
+            }
+            _ => false,
+        };
+        let mut reader = response.into_reader();
+        if !ranged {
+            let skipped = io::copy(&mut (&mut reader).take(self.position), &mut io::sink())?;
+            if skipped < self.position {
+                return Err(io::ErrorKind::UnexpectedEof.into());
+            }
+        }
+
+        self.reader = reader;
+        Ok(())
+    }
+}
+
+impl Read for TrackStream {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        loop {
+            let mut err = match self.reader.read(buf) {
+                Ok(read) => {
+                    self.position += read as u64;
+                    if read > 0 {
+                        self.resumes = 0;
+                    }
+                    return Ok(read);
+                }
+                Err(err) if err.kind() == io::ErrorKind::Interrupted => continue,
+                Err(err) => err,
+            };
+
+            // Failed resumes are tried again, until out of resumes.
+            loop {
+                let request = match &self.request {
+                    Some(request) if self.resumes < MAX_RESUMES => request.clone(),
+                    _ => return Err(err),
+                };
+                self.resumes += 1;
+
+                match self.resume(request) {
+                    Ok(()) => break,
+                    Err(resume_err) => err = resume_err,
+                }
+            }
+        }
+    }
+}
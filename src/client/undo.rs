@@ -0,0 +1,158 @@
+use crate::client::{
+    PlaylistItemMod, PlaylistItemOperations, PlaylistItemsMod, PlaylistItemsOperations,
+};
+use crate::mc::playlist::{Playlist, PlaylistBackup, PlaylistID, PlaylistItem};
+use serde::{Deserialize, Serialize};
+
+/// Inverse of a playlist mutation, to undo it with
+/// [`EndpointPlaylist::undo`](crate::client::endpoints::EndpointPlaylist::undo).
+///
+/// It is computed from the state of the playlist before the mutation, so
+/// undoing it after other changes to the playlist may not give back exactly
+/// what was there. Tokens can be saved, to undo after restarting an editor.
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct UndoToken {
+    /// Playlist the mutation was made to.
+    pub playlist_id: PlaylistID,
+    /// Operations undoing the mutation, in the order they are applied.
+    pub steps: Vec<UndoStep>,
+}
+
+/// Operation applied to undo a playlist mutation.
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+#[non_exhaustive]
+pub enum UndoStep {
+    /// Edit the playlist back to how it was.
+    Edit(Playlist),
+    /// Modify a single item, like moving it back to where it was.
+    Item(PlaylistItemOperations, PlaylistItemMod),
+    /// Add back removed items, or remove added ones.
+    Items(PlaylistItemsOperations, PlaylistItemsMod),
+    /// Create a deleted playlist anew, with its tracks.
+    Restore(PlaylistBackup),
+}
+
+impl UndoToken {
+    /// Get the inverse of modifying a single item, given the items of the
+    /// playlist before.
+    pub(crate) fn of_item(
+        playlist_id: PlaylistID,
+        operation: &PlaylistItemOperations,
+        item_mod: &PlaylistItemMod,
+        before: &[PlaylistItem],
+    ) -> Self {
+        let record = &item_mod.record;
+        let sort = find_item(before, record, &[]).map_or(record.sort, |item| item.sort);
+        let at = |sort: usize| PlaylistItem {
+            sort,
+            ..record.clone()
+        };
+        let move_back = |from: usize| {
+            UndoStep::Item(
+                PlaylistItemOperations::To,
+                PlaylistItemMod {
+                    move_to: Some(sort as u32),
+                    record: at(from),
+                },
+            )
+        };
+
+        let steps = match operation {
+            PlaylistItemOperations::Add => vec![UndoStep::Item(
+                PlaylistItemOperations::Remove,
+                PlaylistItemMod {
+                    move_to: None,
+                    record: at(before.len()),
+                },
+            )],
+            PlaylistItemOperations::Remove => vec![
+                UndoStep::Item(
+                    PlaylistItemOperations::Add,
+                    PlaylistItemMod {
+                        move_to: None,
+                        record: at(before.len().saturating_sub(1)),
+                    },
+                ),
+                move_back(before.len().saturating_sub(1)),
+            ],
+            PlaylistItemOperations::Up => vec![move_back(sort.saturating_sub(1))],
+            PlaylistItemOperations::Down => {
+                vec![move_back((sort + 1).min(before.len().saturating_sub(1)))]
+            }
+            PlaylistItemOperations::To => {
+                vec![move_back(item_mod.move_to.map_or(sort, |to| to as usize))]
+            }
+        };
+
+        UndoToken { playlist_id, steps }
+    }
+
+    /// Get the inverse of modifying multiple items, given the items of the
+    /// playlist before.
+    pub(crate) fn of_items(
+        playlist_id: PlaylistID,
+        operation: &PlaylistItemsOperations,
+        items_mod: &PlaylistItemsMod,
+        before: &[PlaylistItem],
+    ) -> Self {
+        let steps = match operation {
+            PlaylistItemsOperations::Add => vec![UndoStep::Items(
+                PlaylistItemsOperations::Remove,
+                items_mod.clone(),
+            )],
+            PlaylistItemsOperations::Remove => {
+                // Removed items are added back at the end, then moved back
+                // where they were, first to last so positions hold.
+                let mut removed: Vec<PlaylistItem> = Vec::new();
+                for record in &items_mod.records {
+                    if let Some(item) = find_item(before, record, &removed) {
+                        removed.push(item.clone());
+                    }
+                }
+                removed.sort_by_key(|item| item.sort);
+
+                let kept = before.len() - removed.len();
+                let mut steps = vec![UndoStep::Items(
+                    PlaylistItemsOperations::Add,
+                    PlaylistItemsMod {
+                        records: removed.clone(),
+                    },
+                )];
+                steps.extend(removed.into_iter().enumerate().map(|(added, item)| {
+                    UndoStep::Item(
+                        PlaylistItemOperations::To,
+                        PlaylistItemMod {
+                            move_to: Some(item.sort as u32),
+                            record: PlaylistItem {
+                                sort: kept + added,
+                                ..item
+                            },
+                        },
+                    )
+                }));
+                steps
+            }
+        };
+
+        UndoToken { playlist_id, steps }
+    }
+}
+
+/// Find the item a record is of, by track and position, so only the right
+/// copy of a track in a playlist more than once is found.
+///
+/// Records with a position the track isn't at are of its first copy not
+/// found yet.
+fn find_item<'a>(
+    before: &'a [PlaylistItem],
+    record: &PlaylistItem,
+    found: &[PlaylistItem],
+) -> Option<&'a PlaylistItem> {
+    let unfound = |item: &&PlaylistItem| item.track_id == record.track_id && !found.contains(item);
+    before
+        .iter()
+        .filter(unfound)
+        .find(|item| item.sort == record.sort)
+        .or_else(|| before.iter().find(unfound))
+}
@@ -0,0 +1,271 @@
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::cell::RefCell;
+use std::sync::Arc;
+
+/// Versions of the MC API, by the shape of the data they respond with.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Deserialize, Serialize)]
+#[non_exhaustive]
+pub enum ApiVersion {
+    /// Older data with camelCase keys and `_id` identifiers.
+    Legacy,
+    /// The live API, with PascalCase keys.
+    #[default]
+    Current,
+}
+
+/// Enum value the MC API sent that Bombay doesn't know yet.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct UnknownVariant {
+    /// Name of the enum, like "Platform".
+    pub enum_name: &'static str,
+    pub value: String,
+}
+
+/// What to do with enum values Bombay doesn't know, like a new platform.
+///
+/// Strict mirrors may rather fail than store a value they can't interpret,
+/// while players just keep going.
+#[derive(Clone, Default)]
+pub enum UnknownEnumPolicy {
+    /// Fail to deserialize.
+    Error,
+    /// Keep the value, as the enum's `Other` variant where it has one.
+    #[default]
+    Other,
+    /// Call back with the value, then keep it like [`UnknownEnumPolicy::Other`].
+    Callback(Arc<dyn Fn(&UnknownVariant) + Send + Sync>),
+}
+
+impl std::fmt::Debug for UnknownEnumPolicy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            UnknownEnumPolicy::Error => write!(f, "Error"),
+            UnknownEnumPolicy::Other => write!(f, "Other"),
+            UnknownEnumPolicy::Callback(_) => write!(f, "Callback"),
+        }
+    }
+}
+
+thread_local! {
+    /// Policy for unknown enum values on this thread.
+    static SCOPED_UNKNOWN_ENUM_POLICY: RefCell<Option<UnknownEnumPolicy>> = const { RefCell::new(None) };
+}
+
+/// Run something, like deserializing, with a policy for unknown enum values
+/// that applies on this thread only. Elsewhere, unknown values are kept as
+/// `Other`, unless a client has its own policy, see
+/// `Client::set_unknown_enum_policy`.
+///
+/// Example
+/// ```rust
+/// use bombay::compat::{self, UnknownEnumPolicy};
+/// use bombay::mc::util::Platform;
+///
+/// let strict = compat::with_unknown_enum_policy(UnknownEnumPolicy::Error, || {
+///     serde_json::from_str::<Platform>("\"myspace\"")
+/// });
+/// assert!(strict.is_err());
+///
+/// let lenient = serde_json::from_str::<Platform>("\"myspace\"").unwrap();
+/// assert_eq!(lenient, Platform::Other("myspace".to_owned()));
+/// ```
+pub fn with_unknown_enum_policy<R>(policy: UnknownEnumPolicy, f: impl FnOnce() -> R) -> R {
+    /// Restores the previous policy, even if `f` panics.
+    struct Restore(Option<UnknownEnumPolicy>);
+
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            let previous = self.0.take();
+            SCOPED_UNKNOWN_ENUM_POLICY.with(|scoped| *scoped.borrow_mut() = previous);
+        }
+    }
+
+    let previous = SCOPED_UNKNOWN_ENUM_POLICY.with(|scoped| scoped.replace(Some(policy)));
+    let _restore = Restore(previous);
+    f()
+}
+
+/// Handle an unknown enum value by the policy in effect, getting the value to
+/// keep it as or an error.
+pub(crate) fn unknown_variant<T>(
+    enum_name: &'static str,
+    value: &str,
+    other: T,
+) -> Result<T, serde_json::Error> {
+    let policy = SCOPED_UNKNOWN_ENUM_POLICY
+        .with(|scoped| scoped.borrow().clone())
+        .unwrap_or_default();
+
+    match policy {
+        UnknownEnumPolicy::Error => Err(unknown_variant_error(enum_name, value)),
+        UnknownEnumPolicy::Other => Ok(other),
+        UnknownEnumPolicy::Callback(callback) => {
+            callback(&UnknownVariant {
+                enum_name,
+                value: value.to_owned(),
+            });
+            Ok(other)
+        }
+    }
+}
+
+/// Get the error for an unknown enum value, for enums that have nowhere to
+/// keep it.
+pub(crate) fn unknown_variant_error(enum_name: &'static str, value: &str) -> serde_json::Error {
+    serde::de::Error::custom(format!("unknown {} {:?}", enum_name, value))
+}
+
+/// Keys that were renamed beyond a change of letter case, as (legacy, current).
+const LEGACY_RENAMES: &[(&str, &str)] = &[("_id", "Id")];
+
+thread_local! {
+    /// Keys aliased to current keys on this thread, as (alias, current).
+    static SCOPED_ALIASES: RefCell<Vec<(String, String)>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Run something, like deserializing, with keys the MC API renamed aliased
+/// to their current keys, as (alias, current), on this thread only.
+///
+/// Data using an alias then parses as if it used the current key, without
+/// waiting on a Bombay release. Aliases apply to keys of objects at any
+/// depth, in every API version. Clients take their own, see
+/// `Client::add_alias`.
+///
+/// Example
+/// ```rust
+/// use bombay::compat::{self, ApiVersion};
+/// use bombay::mc::util::Link;
+/// use serde_json::json;
+///
+/// // Say MC renamed "Url" to "Href".
+/// let renamed = json!({ "Platform": "spotify", "Href": "https://open.spotify.com/" });
+/// let aliases = vec![("Href".to_owned(), "Url".to_owned())];
+/// let link: Link = compat::with_aliases(aliases, || {
+///     compat::from_value(renamed, ApiVersion::Current)
+/// })
+/// .unwrap();
+/// assert_eq!(link.url.as_str(), "https://open.spotify.com/");
+/// ```
+pub fn with_aliases<R>(aliases: Vec<(String, String)>, f: impl FnOnce() -> R) -> R {
+    /// Restores the previous aliases, even if `f` panics.
+    struct Restore(Vec<(String, String)>);
+
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            let previous = std::mem::take(&mut self.0);
+            SCOPED_ALIASES.with(|scoped| *scoped.borrow_mut() = previous);
+        }
+    }
+
+    let previous = SCOPED_ALIASES.with(|scoped| scoped.replace(aliases));
+    let _restore = Restore(previous);
+    f()
+}
+
+/// Check whether any aliases apply on this thread, see [`with_aliases`].
+fn has_aliases() -> bool {
+    SCOPED_ALIASES.with(|scoped| !scoped.borrow().is_empty())
+}
+
+/// Rename keys aliased on this thread to their current keys, recursively.
+///
+/// Keys already present under their current name are kept as they are.
+pub fn apply_aliases(value: &mut Value) {
+    SCOPED_ALIASES.with(|scoped| rename_aliases(value, &scoped.borrow()));
+}
+
+/// Rename aliased keys, as (alias, current), to their current keys,
+/// recursively.
+pub(crate) fn rename_aliases(value: &mut Value, aliases: &[(String, String)]) {
+    if aliases.is_empty() {
+        return;
+    }
+
+    match value {
+        Value::Object(object) => {
+            for (alias, current) in aliases {
+                if object.contains_key(current) {
+                    continue;
+                }
+                if let Some(aliased) = object.remove(alias) {
+                    object.insert(current.clone(), aliased);
+                }
+            }
+            object
+                .values_mut()
+                .for_each(|value| rename_aliases(value, aliases));
+        }
+        Value::Array(values) => values
+            .iter_mut()
+            .for_each(|value| rename_aliases(value, aliases)),
+        _ => {}
+    }
+}
+
+/// Upgrade data from some API version to the shape of the current API,
+/// including any aliases on this thread, see [`with_aliases`].
+pub fn upgrade(value: Value, from: ApiVersion) -> Value {
+    let mut value = match from {
+        ApiVersion::Legacy => upgrade_legacy(value),
+        ApiVersion::Current => value,
+    };
+
+    apply_aliases(&mut value);
+    value
+}
+
+/// Deserialize data from some API version into a current model.
+///
+/// Example
+/// ```rust
+/// use bombay::compat::{self, ApiVersion};
+/// use bombay::mc::util::Link;
+/// use serde_json::json;
+///
+/// let legacy = json!({ "platform": "spotify", "url": "https://open.spotify.com/" });
+/// let link: Link = compat::from_value(legacy, ApiVersion::Legacy).unwrap();
+/// assert_eq!(link.url.as_str(), "https://open.spotify.com/");
+/// ```
+pub fn from_value<T: DeserializeOwned>(
+    value: Value,
+    version: ApiVersion,
+) -> Result<T, serde_json::Error> {
+    serde_json::from_value(upgrade(value, version))
+}
+
+/// Deserialize JSON text from some API version into a current model.
+pub fn from_str<T: DeserializeOwned>(s: &str, version: ApiVersion) -> Result<T, serde_json::Error> {
+    match version {
+        ApiVersion::Current if !has_aliases() => serde_json::from_str(s),
+        _ => from_value(serde_json::from_str(s)?, version),
+    }
+}
+
+/// Rename the keys of legacy objects, recursively.
+fn upgrade_legacy(value: Value) -> Value {
+    match value {
+        Value::Object(object) => Value::Object(
+            object
+                .into_iter()
+                .map(|(key, value)| (upgrade_legacy_key(&key), upgrade_legacy(value)))
+                .collect::<Map<String, Value>>(),
+        ),
+        Value::Array(values) => Value::Array(values.into_iter().map(upgrade_legacy).collect()),
+        value => value,
+    }
+}
+
+/// Get the current name of a legacy key.
+fn upgrade_legacy_key(key: &str) -> String {
+    if let Some((_, current)) = LEGACY_RENAMES.iter().find(|(legacy, _)| *legacy == key) {
+        return (*current).to_owned();
+    }
+
+    let mut chars = key.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
@@ -0,0 +1,151 @@
+use crate::client::{Batch, Client, Error};
+use crate::mc::release::ReleaseID;
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::io::Read;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+
+/// Cover art fetched at once by default when prefetching.
+pub const DEFAULT_MAX_CONCURRENT: usize = 8;
+
+/// Image bytes of cover art, shared between the cache and its users.
+pub type CoverArt = Arc<Vec<u8>>;
+
+/// Cache of release cover art, by release and width, which can fetch the
+/// covers of a whole list at once.
+///
+/// Cover art is kept in memory, and on disk in `covers` of the client's cache
+/// directory, see [`Client::dirs`], so it isn't fetched again next time.
+///
+/// Example
+/// ```rust,no_run
+/// use bombay::client::Client;
+/// use bombay::cover_art::CoverArtCache;
+///
+/// let mc = Client::default(); // Without authentication.
+/// let covers = CoverArtCache::new(&mc);
+///
+/// if let Ok(latest) = mc.release().get_latest(None) {
+///   let ids: Vec<_> = latest.data.unwrap_or_default().iter().map(|release| *release.get_release_id()).collect();
+///
+///   // Fetch every thumbnail up front, so showing the list doesn't wait on each.
+///   for (release_id, err) in covers.prefetch(&ids, 256) {
+///     println!("No cover for {}: {}", release_id, err);
+///   }
+/// }
+/// ```
+pub struct CoverArtCache<'a, ClientAuthState> {
+    client: &'a Client<ClientAuthState>,
+    directory: Option<PathBuf>,
+    images: Mutex<HashMap<(ReleaseID, u32), CoverArt>>,
+    max_concurrent: usize,
+}
+
+impl<'a, ClientAuthState> CoverArtCache<'a, ClientAuthState>
+where
+    Client<ClientAuthState>: Sync,
+{
+    /// Create an empty cache fetching with a client, keeping cover art in
+    /// the client's cache directory.
+    pub fn new(client: &'a Client<ClientAuthState>) -> Self {
+        CoverArtCache {
+            client,
+            directory: Some(client.dirs().cache.join("covers")),
+            images: Mutex::new(HashMap::new()),
+            max_concurrent: DEFAULT_MAX_CONCURRENT,
+        }
+    }
+
+    /// Set the directory cover art is kept in on disk, or `None` to only
+    /// keep it in memory.
+    pub fn set_directory(mut self, directory: Option<PathBuf>) -> Self {
+        self.directory = directory;
+        self
+    }
+
+    /// Set the most cover art fetched at once when prefetching.
+    pub fn set_max_concurrent(mut self, max_concurrent: usize) -> Self {
+        self.max_concurrent = max_concurrent;
+        self
+    }
+
+    /// Get cover art of a release at a width cached in memory, without
+    /// reading or fetching it.
+    pub fn cached(&self, release_id: &ReleaseID, width: u32) -> Option<CoverArt> {
+        self.lock().get(&(*release_id, width)).cloned()
+    }
+
+    /// Get cover art of a release at a width, reading it from disk or
+    /// fetching it if not cached in memory yet.
+    pub fn get(&self, release_id: &ReleaseID, width: u32) -> Result<CoverArt, Error> {
+        match self.cached(release_id, width) {
+            Some(image) => Ok(image),
+            None => self.fetch(release_id, width),
+        }
+    }
+
+    /// Fetch the cover art of releases at a width concurrently, skipping
+    /// those cached and listed more than once.
+    ///
+    /// Returns the releases whose cover art couldn't be fetched.
+    pub fn prefetch(&self, release_ids: &[ReleaseID], width: u32) -> Vec<(ReleaseID, Error)> {
+        let mut seen = HashSet::new();
+        let release_ids: Vec<_> = release_ids
+            .iter()
+            .filter(|release_id| seen.insert(**release_id))
+            .filter(|release_id| self.cached(release_id, width).is_none())
+            .collect();
+        let fetches: Vec<_> = release_ids
+            .iter()
+            .map(|release_id| move || self.fetch(release_id, width))
+            .collect();
+
+        fetches
+            .run(self.max_concurrent)
+            .into_iter()
+            .zip(release_ids)
+            .filter_map(|(fetched, release_id)| fetched.err().map(|err| (*release_id, err)))
+            .collect()
+    }
+
+    /// Forget all cover art cached in memory, keeping what is on disk.
+    pub fn clear(&self) {
+        self.lock().clear();
+    }
+
+    /// Read cover art from disk, or fetch it and write it there, and cache it.
+    fn fetch(&self, release_id: &ReleaseID, width: u32) -> Result<CoverArt, Error> {
+        let path = self
+            .directory
+            .as_ref()
+            .map(|directory| directory.join(format!("{release_id}-{width}")));
+        let image = match path.as_ref().and_then(|path| fs::read(path).ok()) {
+            Some(image) => image,
+            None => {
+                let mut image = Vec::new();
+                self.client
+                    .release()
+                    .get_cover_art_by_id(release_id, Some(width))?
+                    .read_to_end(&mut image)
+                    .map_err(Error::IO)?;
+                // The disk is only a cache, so failing to write it isn't an error.
+                if let Some(path) = &path {
+                    let _ = path
+                        .parent()
+                        .map_or(Ok(()), fs::create_dir_all)
+                        .and_then(|_| fs::write(path, &image));
+                }
+                image
+            }
+        };
+
+        let image = Arc::new(image);
+        self.lock().insert((*release_id, width), image.clone());
+        Ok(image)
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, HashMap<(ReleaseID, u32), CoverArt>> {
+        self.images.lock().unwrap_or_else(|err| err.into_inner())
+    }
+}
@@ -0,0 +1,102 @@
+use crate::mc::release::Track;
+use std::cmp::Ordering;
+use std::collections::HashMap;
+
+/// Which release to keep a track from when it appears on several, like a
+/// single, its album, and a compilation.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub enum CanonicalRelease {
+    /// The release that came out first, usually the single.
+    #[default]
+    Earliest,
+    /// The release that came out last.
+    Latest,
+    /// The first release of these kinds, like `["Album", "EP", "Single"]`,
+    /// compared case-insensitively. Other kinds come after, and ties go to
+    /// the earliest release.
+    KindOrder(Vec<String>),
+}
+
+impl CanonicalRelease {
+    /// Check whether a track's release is preferred over another's.
+    fn prefers(&self, track: &Track, other: &Track) -> bool {
+        let by_date = track.release.release_date.cmp(&other.release.release_date);
+        let ordering = match self {
+            CanonicalRelease::Earliest => by_date,
+            CanonicalRelease::Latest => by_date.reverse(),
+            CanonicalRelease::KindOrder(kinds) => {
+                let rank = |track: &Track| {
+                    kinds
+                        .iter()
+                        .position(|kind| kind.eq_ignore_ascii_case(&track.release.kind))
+                        .unwrap_or(kinds.len())
+                };
+                rank(track).cmp(&rank(other)).then(by_date)
+            }
+        };
+        ordering == Ordering::Less
+    }
+}
+
+/// Collapse tracks with the same ISRC, like a track appearing on a single,
+/// its album, and compilations, keeping the one from the earliest release.
+///
+/// Each track stays where its ISRC first appeared. Tracks without an ISRC
+/// are all kept.
+///
+/// Example
+/// ```rust
+/// use bombay::dedupe::dedupe_tracks;
+/// use bombay::mc::release::Track;
+///
+/// # fn example(single: Track) {
+/// let mut compilation = single.clone();
+/// compilation.release.kind = "Compilation".to_owned();
+///
+/// assert_eq!(dedupe_tracks(vec![single, compilation]).len(), 1);
+/// # }
+/// ```
+pub fn dedupe_tracks(tracks: impl IntoIterator<Item = Track>) -> Vec<Track> {
+    dedupe_tracks_with(tracks, &CanonicalRelease::default())
+}
+
+/// Collapse tracks with the same ISRC, keeping the one from the release the
+/// policy prefers, see [`dedupe_tracks`].
+pub fn dedupe_tracks_with(
+    tracks: impl IntoIterator<Item = Track>,
+    policy: &CanonicalRelease,
+) -> Vec<Track> {
+    let mut deduped: Vec<Track> = Vec::new();
+    let mut by_isrc: HashMap<String, usize> = HashMap::new();
+
+    for track in tracks {
+        let isrc = normalize_isrc(&track.isrc);
+        if isrc.is_empty() {
+            deduped.push(track);
+            continue;
+        }
+
+        match by_isrc.get(&isrc) {
+            Some(&index) => {
+                if policy.prefers(&track, &deduped[index]) {
+                    deduped[index] = track;
+                }
+            }
+            None => {
+                by_isrc.insert(isrc, deduped.len());
+                deduped.push(track);
+            }
+        }
+    }
+
+    deduped
+}
+
+/// Normalize an ISRC for comparison, as they are sometimes written with
+/// dashes, like "CA-6D2-21-00326".
+pub(crate) fn normalize_isrc(isrc: &str) -> String {
+    isrc.chars()
+        .filter(char::is_ascii_alphanumeric)
+        .map(|c| c.to_ascii_uppercase())
+        .collect()
+}
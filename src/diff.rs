@@ -0,0 +1,134 @@
+use serde::Serialize;
+use serde_json::Value;
+use std::fmt::Display;
+
+/// Change to one field between two instances of a model.
+#[derive(Clone, Debug, PartialEq)]
+pub struct FieldChange {
+    /// JSON pointer to the field, by serialized field names, like `/Links/0/Url`.
+    pub path: String,
+    /// Value before, or `None` if the field was added.
+    pub before: Option<Value>,
+    /// Value after, or `None` if the field was removed.
+    pub after: Option<Value>,
+}
+
+impl Display for FieldChange {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let show = |value: &Option<Value>| match value {
+            Some(value) => value.to_string(),
+            None => "(none)".to_owned(),
+        };
+        write!(
+            f,
+            "{}: {} -> {}",
+            self.path,
+            show(&self.before),
+            show(&self.after)
+        )
+    }
+}
+
+/// Field-level changes between two instances of a model.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Diff {
+    pub changes: Vec<FieldChange>,
+}
+
+impl Diff {
+    /// Check whether nothing changed.
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+
+    /// Check whether a field, or anything within it, changed.
+    ///
+    /// The path is a JSON pointer like in [`FieldChange::path`], so "/Links"
+    /// is changed when "/Links/0/Url" is.
+    pub fn changed(&self, path: &str) -> bool {
+        self.changes.iter().any(|change| {
+            change
+                .path
+                .strip_prefix(path)
+                .map_or(false, |rest| rest.is_empty() || rest.starts_with('/'))
+        })
+    }
+}
+
+impl Display for Diff {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for change in &self.changes {
+            writeln!(f, "{}", change)?;
+        }
+        Ok(())
+    }
+}
+
+/// Get the field-level changes between two instances of a model, like two
+/// versions of a release or playlist.
+///
+/// Models are compared by their serialized form, so paths use the field names
+/// of the MC API. Lists are compared item by item, by position.
+///
+/// Fails if either model can't be serialized.
+///
+/// Example
+/// ```rust
+/// use bombay::diff;
+/// use bombay::mc::release::Release;
+///
+/// # fn example(before: Release) {
+/// let mut after = before.clone();
+/// after.title = "Souvenir (Remixes)".to_owned();
+///
+/// let diff = diff::diff(&before, &after).expect("Could not serialize releases.");
+/// assert!(diff.changed("/Title"));
+/// println!("{}", diff);
+/// # }
+/// ```
+pub fn diff<T: Serialize>(before: &T, after: &T) -> Result<Diff, serde_json::Error> {
+    let before = serde_json::to_value(before)?;
+    let after = serde_json::to_value(after)?;
+
+    let mut changes = Vec::new();
+    diff_values(String::new(), Some(&before), Some(&after), &mut changes);
+    Ok(Diff { changes })
+}
+
+/// Compare two values at some path, recursing into objects and arrays.
+fn diff_values(
+    path: String,
+    before: Option<&Value>,
+    after: Option<&Value>,
+    changes: &mut Vec<FieldChange>,
+) {
+    match (before, after) {
+        (Some(Value::Object(before)), Some(Value::Object(after))) => {
+            let mut keys: Vec<&String> = before.keys().chain(after.keys()).collect();
+            keys.sort();
+            keys.dedup();
+
+            for key in keys {
+                let path = format!("{}/{}", path, escape(key));
+                diff_values(path, before.get(key), after.get(key), changes);
+            }
+        }
+        (Some(Value::Array(before)), Some(Value::Array(after))) => {
+            for index in 0..before.len().max(after.len()) {
+                let path = format!("{}/{}", path, index);
+                diff_values(path, before.get(index), after.get(index), changes);
+            }
+        }
+        (before, after) if before != after => changes.push(FieldChange {
+            path,
+            before: before.cloned(),
+            after: after.cloned(),
+        }),
+        _ => {}
+    }
+}
+
+/// Escape a key for use in a JSON pointer.
+fn escape(key: &str) -> String {
+    key.replace('~', "~0").replace('/', "~1")
+}
@@ -0,0 +1,213 @@
+use std::env;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// Name of the directories Bombay keeps its state in.
+const APP_NAME: &str = "bombay";
+
+/// Name of the directory downloads go to, in the user's downloads directory.
+const DOWNLOADS_NAME: &str = "Monstercat";
+
+/// Directories Bombay keeps its state on disk in, so caches, downloads, and
+/// sessions stay organized instead of landing in the working directory.
+///
+/// [`Dirs::platform`] follows the conventions of each platform, with
+/// sessions and snapshots in subdirectories of the state directory:
+///
+/// * Linux and BSDs: `$XDG_CACHE_HOME/bombay` for the cache,
+///   `$XDG_DOWNLOAD_DIR/Monstercat` for downloads, and
+///   `$XDG_STATE_HOME/bombay` for state. The download directory is read
+///   from the environment, or else from `$XDG_CONFIG_HOME/user-dirs.dirs`,
+///   where desktops keep it.
+/// * macOS: `~/Library/Caches/bombay` for the cache, `~/Downloads/Monstercat`
+///   for downloads, and `~/Library/Application Support/bombay` for state.
+/// * Windows: `%LOCALAPPDATA%\bombay\cache` for the cache,
+///   `~\Downloads\Monstercat` for downloads, and `%LOCALAPPDATA%\bombay` for
+///   state.
+///
+/// XDG variables that are unset or not absolute fall back to their defaults,
+/// like `~/.cache`, `~/Downloads`, and `~/.local/state`. Without a home
+/// directory, everything goes under `.bombay` in the working directory.
+///
+/// Clients carry their directories, set with `Client::set_dirs`, for
+/// features persisting state to default to, like the download directory of
+/// `Monstercat` and `Downloader`, the on-disk cover art of `CoverArtCache`,
+/// and cassettes, see [`Dirs::cassette_file`].
+///
+/// Example
+/// ```rust
+/// use bombay::dirs::Dirs;
+///
+/// // Keep everything next to a portable app, but downloads in the usual place.
+/// let dirs = Dirs::under("state").set_downloads(Dirs::platform().downloads);
+/// assert_eq!(
+///     dirs.session_file("default"),
+///     std::path::Path::new("state").join("sessions").join("default.json")
+/// );
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Dirs {
+    /// Directory of data that can be fetched again, like cover art.
+    pub cache: PathBuf,
+    /// Directory tracks and releases are downloaded to.
+    pub downloads: PathBuf,
+    /// Directory of session files, see `client::SessionFile`.
+    pub sessions: PathBuf,
+    /// Directory of client snapshots, see `client::ClientSnapshot`.
+    pub snapshots: PathBuf,
+}
+
+impl Default for Dirs {
+    /// By default, directories follow the platform's conventions, see
+    /// [`Dirs::platform`].
+    fn default() -> Self {
+        Dirs::platform()
+    }
+}
+
+impl Dirs {
+    /// Get the directories following the conventions of the platform.
+    pub fn platform() -> Self {
+        // Only absolute paths are valid, as per the XDG base directory spec.
+        let absolute = |name: &str| {
+            env::var_os(name)
+                .map(PathBuf::from)
+                .filter(|path| path.is_absolute())
+        };
+
+        let home = match cfg!(windows) {
+            true => absolute("USERPROFILE"),
+            false => absolute("HOME"),
+        };
+        let Some(home) = home else {
+            return Dirs::under(format!(".{APP_NAME}"));
+        };
+        let downloads = home.join("Downloads").join(DOWNLOADS_NAME);
+
+        if cfg!(windows) {
+            let local = absolute("LOCALAPPDATA")
+                .unwrap_or_else(|| home.join("AppData").join("Local"))
+                .join(APP_NAME);
+            Dirs {
+                downloads,
+                ..Dirs::under(local)
+            }
+        } else if cfg!(target_os = "macos") {
+            let support = home
+                .join("Library")
+                .join("Application Support")
+                .join(APP_NAME);
+            Dirs {
+                cache: home.join("Library").join("Caches").join(APP_NAME),
+                downloads,
+                sessions: support.join("sessions"),
+                snapshots: support.join("snapshots"),
+            }
+        } else {
+            let state = absolute("XDG_STATE_HOME")
+                .unwrap_or_else(|| home.join(".local").join("state"))
+                .join(APP_NAME);
+            Dirs {
+                cache: absolute("XDG_CACHE_HOME")
+                    .unwrap_or_else(|| home.join(".cache"))
+                    .join(APP_NAME),
+                downloads: absolute("XDG_DOWNLOAD_DIR")
+                    .or_else(|| {
+                        let config =
+                            absolute("XDG_CONFIG_HOME").unwrap_or_else(|| home.join(".config"));
+                        let user_dirs = fs::read_to_string(config.join("user-dirs.dirs")).ok()?;
+                        user_dir(&user_dirs, "XDG_DOWNLOAD_DIR", &home)
+                    })
+                    .map_or(downloads, |directory| directory.join(DOWNLOADS_NAME)),
+                sessions: state.join("sessions"),
+                snapshots: state.join("snapshots"),
+            }
+        }
+    }
+
+    /// Get the directories all under one root directory, like for a portable
+    /// install or tests.
+    pub fn under(root: impl AsRef<Path>) -> Self {
+        let root = root.as_ref();
+        Dirs {
+            cache: root.join("cache"),
+            downloads: root.join("downloads"),
+            sessions: root.join("sessions"),
+            snapshots: root.join("snapshots"),
+        }
+    }
+
+    /// Set the directory of data that can be fetched again.
+    pub fn set_cache(mut self, directory: impl Into<PathBuf>) -> Self {
+        self.cache = directory.into();
+        self
+    }
+
+    /// Set the directory tracks and releases are downloaded to.
+    pub fn set_downloads(mut self, directory: impl Into<PathBuf>) -> Self {
+        self.downloads = directory.into();
+        self
+    }
+
+    /// Set the directory of session files.
+    pub fn set_sessions(mut self, directory: impl Into<PathBuf>) -> Self {
+        self.sessions = directory.into();
+        self
+    }
+
+    /// Set the directory of client snapshots.
+    pub fn set_snapshots(mut self, directory: impl Into<PathBuf>) -> Self {
+        self.snapshots = directory.into();
+        self
+    }
+
+    /// Get the path of a session file by name, like an account's, in the
+    /// sessions directory.
+    pub fn session_file(&self, name: &str) -> PathBuf {
+        self.sessions.join(format!("{name}.json"))
+    }
+
+    /// Get the path of a client snapshot by name in the snapshots directory.
+    pub fn snapshot_file(&self, name: &str) -> PathBuf {
+        self.snapshots.join(format!("{name}.json"))
+    }
+
+    /// Get the path of a cassette by name, like a bug report's, in the
+    /// snapshots directory, see `Client::set_cassette`.
+    pub fn cassette_file(&self, name: &str) -> PathBuf {
+        self.snapshots
+            .join("cassettes")
+            .join(format!("{name}.json"))
+    }
+
+    /// Create every directory that doesn't exist yet, with their parents.
+    pub fn create_all(&self) -> io::Result<()> {
+        for directory in [
+            &self.cache,
+            &self.downloads,
+            &self.sessions,
+            &self.snapshots,
+        ] {
+            fs::create_dir_all(directory)?;
+        }
+        Ok(())
+    }
+}
+
+/// Get a directory from the contents of a `user-dirs.dirs` file, with lines
+/// like `XDG_DOWNLOAD_DIR="$HOME/Downloads"`.
+///
+/// Only paths that are absolute or relative to `$HOME` are valid, as per
+/// xdg-user-dirs, and `$HOME` itself means the directory is disabled.
+fn user_dir(user_dirs: &str, name: &str, home: &Path) -> Option<PathBuf> {
+    let value = user_dirs.lines().find_map(|line| {
+        let (key, value) = line.trim().split_once('=')?;
+        (key.trim() == name).then(|| value.trim().trim_matches('"'))
+    })?;
+    let path = match value.strip_prefix("$HOME") {
+        Some(rest) => home.join(rest.trim_start_matches('/')),
+        None => PathBuf::from(value),
+    };
+    (path.is_absolute() && path != home).then_some(path)
+}
@@ -0,0 +1,540 @@
+use crate::client::{Client, Error, SignedIn};
+use crate::mc::release::{AnyRelease, CatalogID, Track, TrackID};
+use crate::mc::util::Codec;
+#[cfg(feature = "media")]
+use crate::media::{self, Transcoder};
+use crate::progress::{Operation, ProgressEvent, Reporter};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::fs;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::Sender;
+#[cfg(feature = "media")]
+use std::sync::Arc;
+
+/// Name of the manifest file kept in the download directory.
+pub const MANIFEST_FILE_NAME: &str = ".bombay-manifest.json";
+
+/// Columns of audit manifests, in order.
+const AUDIT_COLUMNS: [&str; 7] = [
+    "catalog_id",
+    "track_id",
+    "isrc",
+    "codec",
+    "path",
+    "hash",
+    "creator_friendly",
+];
+
+/// Downloads tracks and releases into a directory, the client's downloads
+/// directory by default, see [`Client::dirs`].
+///
+/// Example
+/// ```rust,no_run
+/// use bombay::client::{Client, SignedIn};
+/// use bombay::download::Downloader;
+/// use bombay::mc::release::CatalogID;
+/// use bombay::mc::util::Codec;
+///
+/// # fn example(mc: &Client<SignedIn>) -> Result<(), bombay::client::Error> {
+/// let downloader = Downloader::new(mc, Codec::FLAC).set_directory("downloads");
+/// let report = downloader.download_release(&CatalogID("MCS1186".to_owned()))?;
+/// println!(
+///     "Downloaded {} tracks, skipped {}.",
+///     report.downloaded.len(),
+///     report.skipped.len()
+/// );
+/// # Ok(())
+/// # }
+/// ```
+pub struct Downloader<'a> {
+    client: &'a Client<SignedIn>,
+    pub directory: PathBuf,
+    pub codec: Codec,
+    audit_format: Option<AuditFormat>,
+    progress: Reporter,
+    #[cfg(feature = "media")]
+    transcoder: Option<Arc<dyn Transcoder>>,
+}
+
+impl<'a> Downloader<'a> {
+    /// Create a downloader writing files with the given codec into the
+    /// client's downloads directory.
+    pub fn new(client: &'a Client<SignedIn>, codec: Codec) -> Self {
+        Downloader {
+            client,
+            directory: client.dirs().downloads.clone(),
+            codec,
+            audit_format: None,
+            progress: Reporter::default(),
+            #[cfg(feature = "media")]
+            transcoder: None,
+        }
+    }
+
+    /// Set the directory files are written to.
+    pub fn set_directory(mut self, directory: impl Into<PathBuf>) -> Self {
+        self.directory = directory.into();
+        self
+    }
+
+    /// Set a transcoder converting tracks the MC API doesn't serve in the
+    /// downloader's codec. Unset by default.
+    ///
+    /// Tracks the API refuses to serve in the codec are downloaded in
+    /// another lossless codec, and any file whose detected codec isn't the
+    /// downloader's is transcoded.
+    #[cfg(feature = "media")]
+    pub fn set_transcoder(mut self, transcoder: Option<Arc<dyn Transcoder>>) -> Self {
+        self.transcoder = transcoder;
+        self
+    }
+
+    /// Set the format of an audit manifest written to the directory after
+    /// each download, listing every downloaded file with its license status.
+    /// Unset by default.
+    pub fn set_audit_format(mut self, audit_format: Option<AuditFormat>) -> Self {
+        self.audit_format = audit_format;
+        self
+    }
+
+    /// Set a channel to send download progress to, see [`ProgressEvent`].
+    /// Unset by default.
+    ///
+    /// Each track is an item, named after its file.
+    pub fn set_progress(mut self, sender: Option<Sender<ProgressEvent>>) -> Self {
+        self.progress = Reporter::new(sender);
+        self
+    }
+
+    /// Get the path of the audit manifest written in a format.
+    pub fn audit_path(&self, format: &AuditFormat) -> PathBuf {
+        self.directory
+            .join(format!("bombay-audit.{}", format.extension()))
+    }
+
+    /// Get the path of the manifest of downloaded files.
+    pub fn manifest_path(&self) -> PathBuf {
+        self.directory.join(MANIFEST_FILE_NAME)
+    }
+
+    /// Load the manifest of downloaded files, or an empty one if there is none yet.
+    pub fn load_manifest(&self) -> Result<Manifest, Error> {
+        Manifest::load(self.manifest_path())
+    }
+
+    /// Download a single track, returning the path of the written file.
+    ///
+    /// The track is always downloaded, even if already present, and recorded
+    /// in the manifest.
+    pub fn download_track(&self, track: &Track) -> Result<PathBuf, Error> {
+        self.progress.run(Operation::DownloadTrack, Some(1), || {
+            let mut manifest = self.load_manifest()?;
+            let path = self.download_and_record(track, &mut manifest)?;
+            manifest.save(self.manifest_path())?;
+            self.write_audit(&manifest)?;
+
+            Ok(path)
+        })
+    }
+
+    /// Download every downloadable track of a release, skipping tracks the
+    /// manifest shows are already present in the same codec.
+    pub fn download_release(&self, catalog_id: &CatalogID) -> Result<DownloadReport, Error> {
+        let fetched = self.client.release().get_by_catalog_id(catalog_id);
+        let items = fetched
+            .as_ref()
+            .ok()
+            .map(|(_, tracks)| tracks.iter().filter(|track| track.downloadable).count());
+
+        self.progress.run(Operation::DownloadRelease, items, || {
+            let (release, tracks) = fetched?;
+
+            if let AnyRelease::Release(release) = &release {
+                if release.downloadable == Some(false) {
+                    return Err(Error::Message("Release is not downloadable."));
+                }
+            }
+
+            let mut manifest = self.load_manifest()?;
+            let mut report = DownloadReport::default();
+
+            for track in tracks.iter().filter(|track| track.downloadable) {
+                let present = manifest
+                    .find(&track.id, &self.codec)
+                    .filter(|entry| entry.is_present(&self.directory))
+                    .map(|entry| self.directory.join(&entry.path));
+
+                match present {
+                    Some(path) => {
+                        self.progress.send(ProgressEvent::ItemDone {
+                            item: track_file_name(track, &self.codec),
+                        });
+                        report.skipped.push(path);
+                    }
+                    None => {
+                        report
+                            .downloaded
+                            .push(self.download_and_record(track, &mut manifest)?);
+                        // Save as we go, so an interrupted download still skips finished tracks.
+                        manifest.save(self.manifest_path())?;
+                    }
+                }
+            }
+
+            self.write_audit(&manifest)?;
+            Ok(report)
+        })
+    }
+
+    /// Transcode a downloaded file if its detected codec isn't the
+    /// downloader's, getting the hash and size of the final file.
+    ///
+    /// If transcoding fails, the downloaded file is left in place.
+    #[cfg(feature = "media")]
+    fn transcode_if_needed(
+        &self,
+        path: &Path,
+        hash: String,
+        size: u64,
+    ) -> Result<(String, u64), Error> {
+        let transcoder = match &self.transcoder {
+            Some(transcoder) => transcoder,
+            None => return Ok((hash, size)),
+        };
+        match media::sniff_file(path).map_err(Error::IO)? {
+            Some(found) if found != self.codec => {}
+            _ => return Ok((hash, size)),
+        }
+
+        let source = path.with_extension("source");
+        fs::rename(path, &source).map_err(Error::IO)?;
+        if let Err(err) = transcoder.transcode(&source, path, &self.codec) {
+            // Keep the downloaded file over whatever the transcoder left.
+            fs::rename(&source, path).map_err(Error::IO)?;
+            return Err(Error::IO(err));
+        }
+        fs::remove_file(&source).map_err(Error::IO)?;
+
+        let mut hasher = HashingWriter::new(io::sink());
+        io::copy(&mut fs::File::open(path).map_err(Error::IO)?, &mut hasher).map_err(Error::IO)?;
+        Ok((hasher.hash(), hasher.size))
+    }
+
+    /// Write the audit manifest, if its format is set.
+    fn write_audit(&self, manifest: &Manifest) -> Result<(), Error> {
+        match &self.audit_format {
+            Some(format) => manifest.save_audit(self.audit_path(format), format),
+            None => Ok(()),
+        }
+    }
+
+    /// Download a track into the directory and record it in the manifest.
+    fn download_and_record(
+        &self,
+        track: &Track,
+        manifest: &mut Manifest,
+    ) -> Result<PathBuf, Error> {
+        let file_name = track_file_name(track, &self.codec);
+        let reader = self.client.release().download_sized_by_ids(
+            &track.release.id,
+            &track.id,
+            Some(self.codec.clone()),
+        );
+        // Falling back to another codec isn't a retry of the same download,
+        // so it isn't reported as one.
+        #[cfg(feature = "media")]
+        let reader = match (reader, &self.transcoder) {
+            (Err(err), Some(_)) if matches!(err.status(), Some(400 | 404 | 415)) => {
+                let source = match self.codec {
+                    Codec::FLAC => Codec::WAV,
+                    _ => Codec::FLAC,
+                };
+                self.client.release().download_sized_by_ids(
+                    &track.release.id,
+                    &track.id,
+                    Some(source),
+                )
+            }
+            (reader, _) => reader,
+        };
+        let (reader, size) = reader?;
+        let mut reader = self.progress.reader(file_name.clone(), size, reader);
+
+        fs::create_dir_all(&self.directory).map_err(Error::IO)?;
+        let path = self.directory.join(&file_name);
+        let mut file_out = HashingWriter::new(fs::File::create(&path).map_err(Error::IO)?);
+        io::copy(&mut reader, &mut file_out).map_err(Error::IO)?;
+        let (hash, size) = (file_out.hash(), file_out.size);
+        drop(file_out);
+        #[cfg(feature = "media")]
+        let (hash, size) = self.transcode_if_needed(&path, hash, size)?;
+
+        manifest.insert(ManifestEntry {
+            catalog_id: CatalogID(track.release.catalog_id.clone()),
+            track_id: track.id,
+            isrc: track.isrc.clone(),
+            codec: self.codec.clone(),
+            path: PathBuf::from(&file_name),
+            hash,
+            size,
+            creator_friendly: Some(track.creator_friendly),
+        });
+        self.progress
+            .send(ProgressEvent::ItemDone { item: file_name });
+
+        Ok(path)
+    }
+}
+
+/// Outcome of downloading a release.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct DownloadReport {
+    /// Paths of the files that were downloaded.
+    pub downloaded: Vec<PathBuf>,
+    /// Paths of the files that were already present, so were not downloaded again.
+    pub skipped: Vec<PathBuf>,
+}
+
+impl DownloadReport {
+    /// Get the paths of all of the release's files, downloaded or skipped.
+    pub fn files(&self) -> impl Iterator<Item = &PathBuf> {
+        self.downloaded.iter().chain(self.skipped.iter())
+    }
+}
+
+/// Record of a downloaded file.
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct ManifestEntry {
+    pub catalog_id: CatalogID,
+    pub track_id: TrackID,
+    /// Empty for files downloaded before ISRCs were recorded.
+    #[serde(default)]
+    pub isrc: String,
+    pub codec: Codec,
+    /// Path of the file, relative to the download directory.
+    pub path: PathBuf,
+    /// Hash of the file content (64-bit FNV-1a, in hex).
+    pub hash: String,
+    pub size: u64,
+    /// Whether the track was licensed for creators when downloaded. Unknown
+    /// for files downloaded before it was recorded.
+    #[serde(default)]
+    pub creator_friendly: Option<bool>,
+}
+
+impl ManifestEntry {
+    /// Get the fields of the entry in audit manifests, in column order.
+    fn audit_fields(&self) -> [Option<String>; 7] {
+        [
+            Some(self.catalog_id.to_string()),
+            Some(self.track_id.to_string()),
+            Some(self.isrc.clone()),
+            Some(self.codec.to_string()),
+            Some(self.path.to_string_lossy().into_owned()),
+            Some(self.hash.clone()),
+            self.creator_friendly.map(|friendly| friendly.to_string()),
+        ]
+    }
+
+    /// Check whether the file is still in the directory, with the recorded size.
+    pub fn is_present(&self, directory: &Path) -> bool {
+        fs::metadata(directory.join(&self.path)).map_or(false, |meta| meta.len() == self.size)
+    }
+
+    /// Check whether the file is still in the directory, with the recorded content.
+    ///
+    /// Unlike [`ManifestEntry::is_present`], this reads the whole file.
+    pub fn verify(&self, directory: &Path) -> Result<bool, Error> {
+        let mut file = match fs::File::open(directory.join(&self.path)) {
+            Ok(file) => file,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(false),
+            Err(err) => return Err(Error::IO(err)),
+        };
+
+        let mut hasher = HashingWriter::new(io::sink());
+        io::copy(&mut file, &mut hasher).map_err(Error::IO)?;
+
+        Ok(hasher.size == self.size && hasher.hash() == self.hash)
+    }
+}
+
+/// Bookkeeping of downloaded files, keyed by track and codec.
+#[derive(Clone, Debug, Default, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct Manifest {
+    pub entries: Vec<ManifestEntry>,
+}
+
+impl Manifest {
+    /// Load a manifest from a JSON file, or get an empty one if the file doesn't exist.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, Error> {
+        match fs::File::open(path) {
+            Ok(file) => {
+                serde_json::from_reader(io::BufReader::new(file)).map_err(Error::Deserialization)
+            }
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(Manifest::default()),
+            Err(err) => Err(Error::IO(err)),
+        }
+    }
+
+    /// Save the manifest to a JSON file.
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), Error> {
+        let file = fs::File::create(path).map_err(Error::IO)?;
+        serde_json::to_writer_pretty(io::BufWriter::new(file), self).map_err(Error::Deserialization)
+    }
+
+    /// Find the entry of a track downloaded with a codec.
+    pub fn find(&self, track_id: &TrackID, codec: &Codec) -> Option<&ManifestEntry> {
+        self.entries
+            .iter()
+            .find(|entry| entry.track_id == *track_id && entry.codec == *codec)
+    }
+
+    /// Add an entry, replacing any entry of the same track and codec.
+    pub fn insert(&mut self, entry: ManifestEntry) {
+        self.entries
+            .retain(|other| !(other.track_id == entry.track_id && other.codec == entry.codec));
+        self.entries.push(entry);
+    }
+
+    /// Write an audit manifest listing every file with its catalog ID, track
+    /// ID, ISRC, codec, path, hash, and license status, to prove licensing
+    /// compliance.
+    ///
+    /// Both formats have the same fields, named like the CSV header:
+    /// `catalog_id,track_id,isrc,codec,path,hash,creator_friendly`. Unknown
+    /// license status is empty in CSV and null in JSON.
+    pub fn write_audit(&self, mut writer: impl Write, format: &AuditFormat) -> Result<(), Error> {
+        match format {
+            AuditFormat::Csv => {
+                writeln!(writer, "{}", AUDIT_COLUMNS.join(",")).map_err(Error::IO)?;
+                for row in self.entries.iter().map(ManifestEntry::audit_fields) {
+                    let fields: Vec<String> = row
+                        .iter()
+                        .map(|field| csv_field(field.as_deref().unwrap_or_default()))
+                        .collect();
+                    writeln!(writer, "{}", fields.join(",")).map_err(Error::IO)?;
+                }
+                Ok(())
+            }
+            AuditFormat::Json => {
+                let records: Vec<Map<String, Value>> = self
+                    .entries
+                    .iter()
+                    .map(|entry| {
+                        let mut record: Map<_, _> = AUDIT_COLUMNS
+                            .iter()
+                            .zip(entry.audit_fields())
+                            .map(|(column, field)| (column.to_string(), field.into()))
+                            .collect();
+                        // Keep the license status a boolean rather than text.
+                        record.insert("creator_friendly".to_owned(), entry.creator_friendly.into());
+                        record
+                    })
+                    .collect();
+                serde_json::to_writer_pretty(writer, &records).map_err(Error::Deserialization)
+            }
+        }
+    }
+
+    /// Save an audit manifest to a file, see [`Manifest::write_audit`].
+    pub fn save_audit(&self, path: impl AsRef<Path>, format: &AuditFormat) -> Result<(), Error> {
+        let mut writer = io::BufWriter::new(fs::File::create(path).map_err(Error::IO)?);
+        self.write_audit(&mut writer, format)?;
+        writer.flush().map_err(Error::IO)
+    }
+
+    /// Get the entries whose files are missing or changed, reading every file.
+    pub fn verify(&self, directory: &Path) -> Result<Vec<&ManifestEntry>, Error> {
+        let mut invalid = Vec::new();
+        for entry in &self.entries {
+            if !entry.verify(directory)? {
+                invalid.push(entry);
+            }
+        }
+        Ok(invalid)
+    }
+}
+
+/// Format of audit manifests.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum AuditFormat {
+    Csv,
+    Json,
+}
+
+impl AuditFormat {
+    /// Get the file extension of the format.
+    pub fn extension(&self) -> &'static str {
+        match self {
+            AuditFormat::Csv => "csv",
+            AuditFormat::Json => "json",
+        }
+    }
+}
+
+/// Quote a CSV field if needed, as in RFC 4180.
+fn csv_field(field: &str) -> String {
+    match field.contains([',', '"', '\n', '\r']) {
+        true => format!("\"{}\"", field.replace('"', "\"\"")),
+        false => field.to_owned(),
+    }
+}
+
+/// Writer passing bytes through while hashing them (64-bit FNV-1a) and counting them.
+struct HashingWriter<W> {
+    inner: W,
+    hash: u64,
+    size: u64,
+}
+
+impl<W> HashingWriter<W> {
+    fn new(inner: W) -> Self {
+        HashingWriter {
+            inner,
+            hash: 0xcbf29ce484222325,
+            size: 0,
+        }
+    }
+
+    fn hash(&self) -> String {
+        format!("{:016x}", self.hash)
+    }
+}
+
+impl<W: Write> Write for HashingWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let written = self.inner.write(buf)?;
+        for byte in &buf[..written] {
+            self.hash ^= u64::from(*byte);
+            self.hash = self.hash.wrapping_mul(0x100000001b3);
+        }
+        self.size += written as u64;
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+/// Get the file name used for a downloaded track, like `01 - Artists - Title.flac`.
+pub fn track_file_name(track: &Track, codec: &Codec) -> String {
+    let name = format!(
+        "{:02} - {} - {}",
+        track.track_number, track.artists_title, track.title
+    );
+
+    let safe_name: String = name
+        .chars()
+        .map(|c| match c {
+            '/' | '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|' => '_',
+            c => c,
+        })
+        .collect();
+
+    format!("{}.{}", safe_name.trim(), codec.extension())
+}
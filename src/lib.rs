@@ -63,6 +63,8 @@
 //!         .unwrap();
 //!         totp_callback(&mut client_unauth, totp_gen.generate_current().unwrap())
 //!     }
+//!     // User was challenged with a captcha, or something new came up.
+//!     _ => Err(Error::SignIn("Unsupported sign-in outcome.")),
 //! }
 //! .expect("Failed to sign in");
 //! ```
@@ -71,6 +73,7 @@
 //!
 //! ```rust
 //! use bombay::client::{Error, Client, PaginationParameters, RequestParameters};
+//! use bombay::mc::artist::ArtistID;
 //! use std::error;
 //! use uuid::uuid;
 //!
@@ -89,7 +92,7 @@
 //!         .ok_or(Error::Message("Oh no! Where did Grant go?!"))?;
 //!
 //!     // And Grant should be in there.
-//!     let grant_id = uuid!("27063fd3-4fba-4119-9af0-5001e925b0d2");
+//!     let grant_id = ArtistID(uuid!("27063fd3-4fba-4119-9af0-5001e925b0d2"));
 //!     let grant = artists
 //!         .iter()
 //!         .find(|artist| artist.id == grant_id)
@@ -161,9 +164,104 @@
 //! }
 //! ```
 
+/// Module containing a manager of signed-in clients for several accounts.
+#[cfg(feature = "client")]
+pub mod accounts;
+
+/// Module containing statistics of releases over time, like genre share and
+/// label output.
+#[cfg(feature = "datetime")]
+pub mod analytics;
+
+/// Module containing a facade of task-level operations, for applications
+/// that don't want to compose endpoints manually.
+#[cfg(feature = "client")]
+pub mod app;
+
+/// Module containing a calendar of releases, grouped by day and week.
+#[cfg(feature = "datetime")]
+pub mod calendar;
+
 /// Module containing all components for the function of the API Client itself.
+#[cfg(feature = "client")]
 pub mod client;
 
+/// Module containing the API versions Bombay can parse data from, and the
+/// shims to upgrade older data to the current models.
+pub mod compat;
+
+/// Module containing a cache of release cover art, fetched concurrently.
+#[cfg(feature = "client")]
+pub mod cover_art;
+
+/// Module containing the directories Bombay keeps its state on disk in,
+/// following platform conventions.
+pub mod dirs;
+
+/// Module containing deduplication of tracks released several times, like
+/// on a single and its album.
+pub mod dedupe;
+
+/// Module containing field-level diffs between two instances of a model.
+pub mod diff;
+
+/// Module containing helpers to download tracks and releases to disk.
+#[cfg(feature = "client")]
+pub mod download;
+
+/// Module containing a health check of platform links, like an artist's.
+#[cfg(feature = "client")]
+pub mod links;
+
+/// Module containing fuzzy matching of names and titles against artists and tracks.
+pub mod matching;
+
+/// Module containing audio format detection and transcoding hooks.
+#[cfg(feature = "media")]
+pub mod media;
+
 /// Module containing types necessary to interact with the Monstercat
 /// API, that are representative of some _thing_, like an artist or playlist.
 pub mod mc;
+
+/// Module containing player building blocks, like a play queue, decoupled
+/// from audio output.
+pub mod player;
+
+/// Module containing progress events, reported the same way by every long
+/// operation.
+#[cfg(feature = "client")]
+pub mod progress;
+
+/// Module containing endless radios of tracks, seeded by a mood or a track.
+#[cfg(feature = "client")]
+pub mod radio;
+
+/// Module containing a scheduler of periodic jobs, like watching releases or
+/// keeping a session alive.
+#[cfg(feature = "client")]
+pub mod scheduler;
+
+/// Module containing ranked search across artists, releases, and playlists.
+#[cfg(feature = "client")]
+pub mod search;
+
+/// Module containing a guard shutting down what a long-running daemon
+/// started, like schedulers and clients.
+#[cfg(feature = "client")]
+pub mod shutdown;
+
+/// Module containing smart playlists, kept in sync with the catalog tracks
+/// matching a rule.
+#[cfg(feature = "client")]
+pub mod smart_playlist;
+
+/// Module containing helpers to test code built on Bombay without hitting
+/// the MC API.
+#[cfg(feature = "testing")]
+pub mod testing;
+
+/// Module containing watchers polling the MC API for changes, like to
+/// artist pages.
+#[cfg(feature = "client")]
+pub mod watch;
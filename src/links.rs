@@ -0,0 +1,106 @@
+use crate::client::{Batch, Error};
+use crate::mc::util::Link;
+use std::time::Duration;
+use ureq::{Agent, AgentBuilder};
+use url::Url;
+
+/// How many links are checked at once by [`validate`].
+const MAX_CONCURRENT: usize = 8;
+
+/// How long to wait on each platform before calling the link unreachable.
+const TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Health of a link, as found by requesting its URL.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum LinkStatus {
+    /// The URL answered successfully.
+    Ok,
+    /// The URL redirects elsewhere, which the link could be updated to.
+    Redirected(Url),
+    /// The URL answered with an error status, like 404.
+    Dead(u16),
+    /// The URL could not be reached at all, like when the domain is gone.
+    Unreachable(String),
+}
+
+/// Health of one link.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LinkReport {
+    pub link: Link,
+    pub status: LinkStatus,
+}
+
+impl LinkReport {
+    /// Check whether the link needs attention, that is, it is not plainly ok.
+    pub fn needs_attention(&self) -> bool {
+        self.status != LinkStatus::Ok
+    }
+}
+
+/// Check every link concurrently, reporting dead and redirected ones.
+///
+/// Each URL gets a HEAD request, or a GET request if the platform doesn't
+/// allow HEAD. Redirects are reported rather than followed.
+///
+/// Example
+/// ```rust,no_run
+/// use bombay::client::Client;
+/// use bombay::links;
+///
+/// let mc = Client::default(); // Without authentication.
+/// let rogue = mc.artist().get_by_name_uri("rogue").expect("Could not find Rogue.");
+///
+/// for report in links::validate(&rogue.links.unwrap_or_default()) {
+///   if report.needs_attention() {
+///     println!("{} link {}: {:?}", report.link.platform, report.link.url, report.status);
+///   }
+/// }
+/// ```
+pub fn validate(links: &[Link]) -> Vec<LinkReport> {
+    let agent = AgentBuilder::new().redirects(0).timeout(TIMEOUT).build();
+    validate_with(&agent, links, MAX_CONCURRENT)
+}
+
+/// Check every link with some agent, at most `max_concurrent` at once.
+///
+/// The agent should not follow redirects, or redirected links are reported
+/// as ok.
+pub fn validate_with(agent: &Agent, links: &[Link], max_concurrent: usize) -> Vec<LinkReport> {
+    let checks: Vec<_> = links
+        .iter()
+        .map(|link| move || Ok::<_, Error>(check(agent, &link.url)))
+        .collect();
+
+    checks
+        .run(max_concurrent)
+        .into_iter()
+        .zip(links)
+        .map(|(status, link)| LinkReport {
+            link: link.clone(),
+            status: status.unwrap_or_else(|err| LinkStatus::Unreachable(err.to_string())),
+        })
+        .collect()
+}
+
+/// Check a single URL.
+fn check(agent: &Agent, url: &Url) -> LinkStatus {
+    let response = match agent.head(url.as_str()).call() {
+        Err(ureq::Error::Status(405, _)) => agent.get(url.as_str()).call(),
+        response => response,
+    };
+
+    match response {
+        Ok(response) if (300..400).contains(&response.status()) => {
+            match response
+                .header("Location")
+                .and_then(|location| url.join(location).ok())
+            {
+                Some(location) => LinkStatus::Redirected(location),
+                None => LinkStatus::Dead(response.status()),
+            }
+        }
+        Ok(_) => LinkStatus::Ok,
+        Err(ureq::Error::Status(status, _)) => LinkStatus::Dead(status),
+        Err(ureq::Error::Transport(transport)) => LinkStatus::Unreachable(transport.to_string()),
+    }
+}
@@ -0,0 +1,139 @@
+use crate::mc::artist::Artist;
+use crate::mc::release::Track;
+use std::cmp::Ordering;
+
+/// Lowest similarity considered a match by default.
+pub const DEFAULT_THRESHOLD: f32 = 0.6;
+
+/// Match a name against artists, best match first.
+///
+/// Artists can come from any source, like a fetched page of artists or a
+/// local cache. Only matches at or above [`DEFAULT_THRESHOLD`] are kept.
+///
+/// Example
+/// ```rust
+/// # #[cfg(feature = "client")] {
+/// use bombay::client::Client;
+/// use bombay::matching::match_artist;
+///
+/// let mc = Client::default(); // Without authentication.
+///
+/// if let Ok(artists) = mc.artist().get_all(None) {
+///   for (artist, score) in match_artist("Ace Aura", artists.data.unwrap_or_default()) {
+///     println!("{:.2} {}", score, artist.name);
+///   }
+/// }
+/// # }
+/// ```
+pub fn match_artist(name: &str, artists: impl IntoIterator<Item = Artist>) -> Vec<(Artist, f32)> {
+    let mut matches: Vec<(Artist, f32)> = artists
+        .into_iter()
+        .map(|artist| {
+            let score = similarity(name, &artist.name);
+            (artist, score)
+        })
+        .filter(|(_, score)| *score >= DEFAULT_THRESHOLD)
+        .collect();
+
+    sort_by_score(&mut matches);
+    matches
+}
+
+/// Match an artist and title against tracks, best match first.
+///
+/// The title is compared both with and without the track's version, so
+/// "Title (Extended Mix)" still matches a track titled "Title" with version
+/// "Extended Mix". The title weighs more than the artist.
+pub fn match_track(
+    artist: &str,
+    title: &str,
+    tracks: impl IntoIterator<Item = Track>,
+) -> Vec<(Track, f32)> {
+    let mut matches: Vec<(Track, f32)> = tracks
+        .into_iter()
+        .map(|track| {
+            let versioned = format!("{} {}", track.title, track.version);
+            let title_score = similarity(title, &track.title).max(similarity(title, &versioned));
+            let artist_score = similarity(artist, &track.artists_title);
+            let score = 0.6 * title_score + 0.4 * artist_score;
+            (track, score)
+        })
+        .filter(|(_, score)| *score >= DEFAULT_THRESHOLD)
+        .collect();
+
+    sort_by_score(&mut matches);
+    matches
+}
+
+/// Similarity of two strings between 0 and 1, ignoring case and punctuation.
+///
+/// This is the better of the normalized Levenshtein similarity, which forgives
+/// typos, and the word overlap, which forgives reordered words.
+pub fn similarity(a: &str, b: &str) -> f32 {
+    let a = normalize(a);
+    let b = normalize(b);
+
+    if a.is_empty() && b.is_empty() {
+        return 1.0;
+    }
+
+    levenshtein_similarity(&a, &b).max(token_similarity(&a, &b))
+}
+
+/// Lowercase and replace anything but letters and digits with single spaces.
+pub fn normalize(text: &str) -> String {
+    text.to_lowercase()
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// One minus the edit distance, relative to the longer string.
+fn levenshtein_similarity(a: &str, b: &str) -> f32 {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    let longest = a.len().max(b.len());
+
+    if longest == 0 {
+        return 1.0;
+    }
+
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    let mut current = vec![0; b.len() + 1];
+
+    for (i, a_char) in a.iter().enumerate() {
+        current[0] = i + 1;
+        for (j, b_char) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(a_char != b_char);
+            current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+        std::mem::swap(&mut previous, &mut current);
+    }
+
+    1.0 - previous[b.len()] as f32 / longest as f32
+}
+
+/// Shared words relative to all distinct words of both strings.
+fn token_similarity(a: &str, b: &str) -> f32 {
+    let mut a_words: Vec<&str> = a.split(' ').filter(|word| !word.is_empty()).collect();
+    let mut b_words: Vec<&str> = b.split(' ').filter(|word| !word.is_empty()).collect();
+    a_words.sort_unstable();
+    a_words.dedup();
+    b_words.sort_unstable();
+    b_words.dedup();
+
+    let shared = a_words.iter().filter(|word| b_words.contains(word)).count();
+    let total = a_words.len() + b_words.len() - shared;
+
+    if total == 0 {
+        return 0.0;
+    }
+
+    shared as f32 / total as f32
+}
+
+/// Sort matches best first.
+fn sort_by_score<T>(matches: &mut [(T, f32)]) {
+    matches.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(Ordering::Equal));
+}
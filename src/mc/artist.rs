@@ -1,11 +1,35 @@
+use crate::compat;
 use crate::mc::release::ReleaseID;
+use crate::mc::tag::Tags;
 use crate::mc::util::{CacheDetails, Link};
-use serde::Deserializer;
+use iso8601_timestamp::Timestamp;
 use serde::{Deserialize, Serialize};
+use serde::{Deserializer, Serializer};
+use serde_with::{DeserializeFromStr, SerializeDisplay};
+use std::ops::Deref;
+use std::{fmt::Display, str::FromStr};
 use uuid::Uuid;
 
+/// NewType for artist identifier, wraps a UUID and adds type safety.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Deserialize, Serialize)]
+pub struct ArtistID(pub Uuid);
+
+impl Deref for ArtistID {
+    type Target = Uuid;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl Display for ArtistID {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
 /// Enumerated type to capture the possible artist types.
-#[derive(Clone, Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
 #[serde(untagged)]
 pub enum AnyArtist {
     Artist(Box<Artist>),
@@ -13,8 +37,111 @@ pub enum AnyArtist {
     ReleaseArtist(Box<ReleaseArtist>),
 }
 
+impl AnyArtist {
+    /// Get any artist's name.
+    pub fn get_name(&self) -> &str {
+        match self {
+            AnyArtist::Artist(artist) => &artist.name,
+            AnyArtist::AlbumArtist(artist) => &artist.name,
+            AnyArtist::ReleaseArtist(artist) => &artist.name,
+        }
+    }
+
+    /// Get any artist's identifier.
+    pub fn get_id(&self) -> &ArtistID {
+        match self {
+            AnyArtist::Artist(artist) => &artist.id,
+            AnyArtist::AlbumArtist(artist) => &artist.artist_id,
+            AnyArtist::ReleaseArtist(artist) => &artist.id,
+        }
+    }
+
+    /// Get any artist's name URI.
+    pub fn get_uri(&self) -> &str {
+        match self {
+            AnyArtist::Artist(artist) => &artist.uri,
+            AnyArtist::AlbumArtist(artist) => &artist.uri,
+            AnyArtist::ReleaseArtist(artist) => &artist.uri,
+        }
+    }
+
+    /// Get any artist's role on a release, if it is related to one.
+    pub fn get_role(&self) -> Option<&Role> {
+        match self {
+            AnyArtist::Artist(_) => None,
+            AnyArtist::AlbumArtist(artist) => Some(&artist.role),
+            AnyArtist::ReleaseArtist(artist) => Some(&artist.role),
+        }
+    }
+}
+
+/// Group artists by their role, in order of each role's first appearance.
+///
+/// Artists without a role are left out.
+pub(crate) fn group_by_role(artists: &[AnyArtist]) -> Vec<(Role, Vec<&AnyArtist>)> {
+    let mut credits: Vec<(Role, Vec<&AnyArtist>)> = Vec::new();
+
+    for artist in artists {
+        let Some(role) = artist.get_role() else {
+            continue;
+        };
+
+        match credits.iter_mut().find(|(other, _)| other == role) {
+            Some((_, artists)) => artists.push(artist),
+            None => credits.push((role.clone(), vec![artist])),
+        }
+    }
+
+    credits
+}
+
+/// Variants of roles an artist or contributor can have on a release.
+///
+/// Unknown roles are kept as [`Role::Other`].
+#[derive(Clone, Debug, PartialEq, Eq, Hash, DeserializeFromStr, SerializeDisplay)]
+#[non_exhaustive]
+pub enum Role {
+    Primary,
+    Featured,
+    Remixer,
+    Producer,
+    Writer,
+    Other(String),
+}
+
+impl Display for Role {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Role::Primary => "Primary",
+                Role::Featured => "Featured",
+                Role::Remixer => "Remixer",
+                Role::Producer => "Producer",
+                Role::Writer => "Writer",
+                Role::Other(unk) => unk,
+            }
+        )
+    }
+}
+
+impl FromStr for Role {
+    type Err = serde_json::Error;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s.trim().to_lowercase().as_str() {
+            "primary" | "main" | "artist" => Role::Primary,
+            "featured" | "featuring" | "feat" => Role::Featured,
+            "remixer" | "remix" => Role::Remixer,
+            "producer" => Role::Producer,
+            "writer" | "songwriter" | "composer" | "lyricist" => Role::Writer,
+            _ => compat::unknown_variant("Role", s, Role::Other(s.trim().to_owned()))?,
+        })
+    }
+}
+
 /// Most detailed artist object returned by the MC API.
-#[derive(Clone, Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
 #[serde(rename_all = "PascalCase")]
 pub struct Artist {
     pub about: Option<String>,
@@ -25,7 +152,7 @@ pub struct Artist {
     pub featured_release_cover_file_id: Option<String>,
     pub featured_release_id: Option<String>,
     pub featured_video_url: Option<String>,
-    pub id: Uuid,
+    pub id: ArtistID,
     pub landscape_file_id: Option<String>,
     pub links: Option<Vec<Link>>,
     pub logo_file_id: Option<String>,
@@ -35,17 +162,80 @@ pub struct Artist {
     pub public: bool,
     pub show_event: bool,
     pub square_file_id: Option<String>,
-    pub tags: Option<Vec<String>>,
+    pub tags: Option<Tags>,
     #[serde(alias = "URI")]
     pub uri: String,
 }
 
+impl Artist {
+    /// Derive the name URI of an artist from their name, like "ace-aura" for
+    /// "Ace Aura".
+    ///
+    /// Names are lowercased, "&" becomes "and", apostrophes and periods are
+    /// dropped, and words are joined with hyphens. Some artists' URIs don't
+    /// follow these rules, see `EndpointArtist::resolve` to find them anyway.
+    pub fn uri_from_name(name: &str) -> String {
+        name.to_lowercase()
+            .replace('&', " and ")
+            .chars()
+            .filter(|c| !matches!(c, '\'' | '’' | '.'))
+            .collect::<String>()
+            .split(|c: char| !c.is_alphanumeric())
+            .filter(|word| !word.is_empty())
+            .collect::<Vec<_>>()
+            .join("-")
+    }
+
+    /// Check whether the artist has a tag, see [`Tags::has_tag`].
+    pub fn has_tag(&self, tag: &str) -> bool {
+        self.tags.as_ref().map_or(false, |tags| tags.has_tag(tag))
+    }
+
+    /// Check whether the artist changed since some time, judging by its cache time.
+    ///
+    /// Artists without cache details are assumed to have changed.
+    pub fn updated_since(&self, since: &Timestamp) -> bool {
+        self.cache_details
+            .as_ref()
+            .map_or(true, |cache| cache.updated_since(since))
+    }
+}
+
+/// Artist as shown in a list, with only what list views need.
+///
+/// It reads the same payloads as [`Artist`], skipping its heavy fields, like
+/// details, links, and cache details, without building them. Get pages of
+/// them with `EndpointArtist::get_all_slim`.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct ArtistListItem {
+    pub id: ArtistID,
+    pub name: String,
+    pub profile_file_id: Option<Uuid>,
+    #[serde(alias = "URI")]
+    pub uri: String,
+}
+
+impl From<&Artist> for ArtistListItem {
+    fn from(artist: &Artist) -> Self {
+        ArtistListItem {
+            id: artist.id,
+            name: artist.name.clone(),
+            profile_file_id: artist.profile_file_id,
+            uri: artist.uri.clone(),
+        }
+    }
+}
+
 /// Additional details regarding this artist.
 ///
 /// Thanks to this
 /// [thread](https://users.rust-lang.org/t/how-can-i-handle-duplicate-fields-when-specifying-multiple-aliases-using-serde/46426/7)
 /// for a clever solution to conflicting key names after capitalization normalization.
-#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+///
+/// Flattened fields can't be serialized as strings, so details are serialized
+/// with their capitalized key names, which they are read back from as well.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize)]
 #[serde(rename_all = "PascalCase")]
 pub struct ArtistDetails {
     #[serde(deserialize_with = "helper_artist_details_about", flatten)]
@@ -60,6 +250,30 @@ pub struct ArtistDetails {
     pub show_events: Option<String>,
 }
 
+/// Facilitate serialization of the details, with capitalized key names.
+#[derive(Serialize)]
+#[serde(rename_all = "PascalCase")]
+struct HelperArtistDetails<'a> {
+    about: &'a Option<String>,
+    bookings: &'a Option<String>,
+    management: &'a Option<String>,
+    management_details: &'a Option<String>,
+    show_events: &'a Option<String>,
+}
+
+impl Serialize for ArtistDetails {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        HelperArtistDetails {
+            about: &self.about,
+            bookings: &self.bookings,
+            management: &self.management,
+            management_details: &self.management_details,
+            show_events: &self.show_events,
+        }
+        .serialize(serializer)
+    }
+}
+
 /// Facilitate extraction of inconsistently capitalized field.
 #[derive(Deserialize)]
 struct HelperArtistDetailsAbout {
@@ -131,9 +345,9 @@ fn helper_artist_details_management_details<'d, D: Deserializer<'d>>(
 /// Facilitate extraction of inconsistently capitalized field.
 #[derive(Deserialize)]
 struct HelperArtistDetailsShowEvents {
-    #[serde(rename = "Management")]
+    #[serde(rename = "ShowEvents")]
     opt_0: Option<String>,
-    #[serde(rename = "management")]
+    #[serde(rename = "showEvents")]
     opt_1: Option<String>,
 }
 
@@ -147,32 +361,32 @@ fn helper_artist_details_show_events<'d, D: Deserializer<'d>>(
 }
 
 /// Artist object related to an album.
-#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
 #[serde(rename_all = "PascalCase")]
 pub struct AlbumArtist {
-    pub artist_id: Uuid,
+    pub artist_id: ArtistID,
     pub artist_number: usize,
     pub name: String,
     pub profile_file_id: Option<Uuid>,
     pub platform: String,
     pub public: bool,
     pub release_id: ReleaseID,
-    pub role: String,
+    pub role: Role,
     pub square_file_id: Option<String>,
     #[serde(alias = "URI")]
     pub uri: String,
 }
 
 /// Artist object related to a release.
-#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
 #[serde(rename_all = "PascalCase")]
 pub struct ReleaseArtist {
     pub catalog_record_id: String,
-    pub id: Uuid,
+    pub id: ArtistID,
     pub name: String,
     pub profile_file_id: Option<Uuid>,
     pub public: bool,
-    pub role: String,
+    pub role: Role,
     #[serde(alias = "URI")]
     pub uri: String,
 }
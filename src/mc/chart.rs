@@ -0,0 +1,98 @@
+use crate::compat;
+use crate::mc::artist::Artist;
+use crate::mc::release::{Genre, Track};
+use iso8601_timestamp::Timestamp;
+use serde::{Deserialize, Serialize};
+use serde_with::{DeserializeFromStr, SerializeDisplay};
+use std::fmt::Display;
+use std::str::FromStr;
+
+/// Identifier of a chart, as used in chart URLs like `weekly` or `genre/dubstep`.
+///
+/// Unknown charts are kept as [`ChartID::Other`].
+#[derive(Clone, Debug, PartialEq, Eq, Hash, DeserializeFromStr, SerializeDisplay)]
+#[non_exhaustive]
+pub enum ChartID {
+    /// The top 30 tracks, also published as a playlist.
+    Top30,
+    /// The top tracks of the week.
+    Weekly,
+    /// The top tracks of a genre.
+    Genre(Genre),
+    Other(String),
+}
+
+impl Display for ChartID {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ChartID::Top30 => write!(f, "top-30"),
+            ChartID::Weekly => write!(f, "weekly"),
+            // Genre slugs follow the same rules as artist name URIs.
+            ChartID::Genre(genre) => {
+                write!(f, "genre/{}", Artist::uri_from_name(&genre.to_string()))
+            }
+            ChartID::Other(unk) => write!(f, "{}", unk),
+        }
+    }
+}
+
+impl FromStr for ChartID {
+    type Err = serde_json::Error;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim().trim_matches('/');
+        Ok(match s.to_lowercase().as_str() {
+            "top-30" | "top30" => ChartID::Top30,
+            "weekly" => ChartID::Weekly,
+            lower => match lower.strip_prefix("genre/") {
+                Some(genre) => ChartID::Genre(genre.parse()?),
+                None => compat::unknown_variant("ChartID", s, ChartID::Other(s.to_owned()))?,
+            },
+        })
+    }
+}
+
+/// Ranked list of tracks.
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct Chart {
+    pub entries: Vec<ChartEntry>,
+    pub id: ChartID,
+    pub title: String,
+    pub updated_at: Option<Timestamp>,
+}
+
+/// Track at some rank of a chart.
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct ChartEntry {
+    pub peak_rank: Option<u32>,
+    /// Rank in the previous edition of the chart, if it was on it.
+    pub previous_rank: Option<u32>,
+    pub rank: u32,
+    pub track: Track,
+    pub weeks_on_chart: Option<u32>,
+}
+
+impl ChartEntry {
+    /// Get how the entry moved since the previous edition of the chart.
+    pub fn movement(&self) -> RankMovement {
+        match self.previous_rank {
+            None => RankMovement::New,
+            Some(previous) if previous > self.rank => RankMovement::Up(previous - self.rank),
+            Some(previous) if previous < self.rank => RankMovement::Down(self.rank - previous),
+            Some(_) => RankMovement::Same,
+        }
+    }
+}
+
+/// Movement of a chart entry since the previous edition of the chart.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum RankMovement {
+    /// Not on the previous edition.
+    New,
+    /// Up some number of ranks.
+    Up(u32),
+    /// Down some number of ranks.
+    Down(u32),
+    Same,
+}
@@ -0,0 +1,83 @@
+use crate::compat;
+use iso8601_timestamp::Timestamp;
+use serde::{Deserialize, Serialize};
+use serde_with::{DeserializeFromStr, SerializeDisplay};
+use std::{fmt::Display, str::FromStr};
+#[cfg(feature = "client")]
+use url::Url;
+
+/// Variants of services an account can be connected to, for licensing.
+///
+/// Unknown services are kept as [`ConnectService::Other`].
+#[derive(Clone, Debug, PartialEq, DeserializeFromStr, SerializeDisplay)]
+#[non_exhaustive]
+pub enum ConnectService {
+    YouTube,
+    Twitch,
+    Other(String),
+}
+
+impl Display for ConnectService {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                ConnectService::YouTube => "youtube",
+                ConnectService::Twitch => "twitch",
+                ConnectService::Other(unk) => unk,
+            }
+        )
+    }
+}
+
+impl FromStr for ConnectService {
+    type Err = serde_json::Error;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s.trim().to_lowercase().as_str() {
+            "youtube" | "google" => ConnectService::YouTube,
+            "twitch" => ConnectService::Twitch,
+            _ => compat::unknown_variant(
+                "ConnectService",
+                s,
+                ConnectService::Other(s.trim().to_owned()),
+            )?,
+        })
+    }
+}
+
+/// External account connected to the user's account.
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct Connection {
+    pub created_at: Option<Timestamp>,
+    pub external_id: Option<String>,
+    pub name: Option<String>,
+    pub service: ConnectService,
+}
+
+/// Simple type to capture the OAuth authorization URL response.
+///
+/// These sorts of simple wrappers are made to maintain the call patterns
+/// and to leave room for future expansion, such as additional fields or
+/// letter case changes.
+#[cfg(feature = "client")]
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "PascalCase")]
+pub(crate) struct AuthUrl {
+    #[serde(alias = "URL")]
+    pub url: Url,
+}
+
+/// Simple type to capture the OAuth code exchange request.
+///
+/// These sorts of simple wrappers are made to maintain the call patterns
+/// and to leave room for future expansion, such as additional fields or
+/// letter case changes.
+#[cfg(feature = "client")]
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "PascalCase")]
+pub(crate) struct AuthCode {
+    pub code: String,
+    pub state: Option<String>,
+}
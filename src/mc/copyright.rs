@@ -0,0 +1,192 @@
+use serde::{Deserialize, Serialize};
+use std::fmt::Display;
+
+/// Copyright line, like the "℗ 2022 Monstercat" of
+/// [`Release::copyright_p_line`](crate::mc::release::Release::copyright_p_line),
+/// parsed into its symbol, year, and owner.
+///
+/// Lines are free text, so either part may be missing, see
+/// [`Release::copyright`](crate::mc::release::Release::copyright) for one
+/// falling back to the release's own details.
+///
+/// Example
+/// ```rust
+/// use bombay::mc::copyright::CopyrightLine;
+///
+/// let line = CopyrightLine::parse("(P) 2019-2022 Monstercat, Inc.");
+/// assert_eq!(line.year, Some(2019));
+/// assert_eq!(line.owner.as_deref(), Some("Monstercat, Inc."));
+/// assert_eq!(line.to_string(), "℗ 2019 Monstercat, Inc.");
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash, Deserialize, Serialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct CopyrightLine {
+    /// Symbol the line starts with, ℗ if it has none.
+    #[serde(default)]
+    pub symbol: CopyrightSymbol,
+    /// Year, the first one if the line has a range.
+    pub year: Option<i32>,
+    pub owner: Option<String>,
+}
+
+/// Symbol a copyright line starts with.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Deserialize, Serialize)]
+#[non_exhaustive]
+pub enum CopyrightSymbol {
+    /// ℗, or (P), of the copyright in a sound recording.
+    #[default]
+    Phonogram,
+    /// ©, or (C).
+    Copyright,
+    /// The word "Copyright", without a symbol.
+    Word,
+}
+
+impl Display for CopyrightSymbol {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                CopyrightSymbol::Phonogram => "℗",
+                CopyrightSymbol::Copyright => "©",
+                CopyrightSymbol::Word => "Copyright",
+            }
+        )
+    }
+}
+
+/// Line like "℗ 2022 Monstercat", with its symbol and what the line has.
+impl Display for CopyrightLine {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.symbol)?;
+        if let Some(year) = self.year {
+            write!(f, " {year}")?;
+        }
+        if let Some(owner) = &self.owner {
+            write!(f, " {owner}")?;
+        }
+        Ok(())
+    }
+}
+
+impl CopyrightLine {
+    /// Parse a copyright line, with or without its ℗, ©, (P), or "Copyright"
+    /// prefix, and with the year before or after the owner.
+    ///
+    /// Only years leading or trailing the line are taken as its year, so
+    /// owners with a number in their name, like "Studio 1999 Records", are
+    /// kept whole.
+    pub fn parse(line: &str) -> Self {
+        let mut symbol = None;
+        let mut words: Vec<&str> = Vec::new();
+
+        for word in line.split_whitespace() {
+            let (found, rest) = match word {
+                // Prefixes spelled out only count before the owner.
+                "P" | "p" if words.is_empty() => (Some(CopyrightSymbol::Phonogram), ""),
+                "C" | "c" if words.is_empty() => (Some(CopyrightSymbol::Copyright), ""),
+                _ if words.is_empty() && word.eq_ignore_ascii_case("copyright") => {
+                    (Some(CopyrightSymbol::Word), "")
+                }
+                _ => split_symbol(word),
+            };
+            // Symbols win over the word, like in "Copyright © 2022".
+            if found.is_some() && matches!(symbol, None | Some(CopyrightSymbol::Word)) {
+                symbol = found;
+            }
+            if !rest.is_empty() {
+                words.push(rest);
+            }
+        }
+
+        let (year, start, end) = match leading_year(&words) {
+            (Some(year), start) => (Some(year), start, words.len()),
+            (None, _) => {
+                let (year, end) = trailing_year(&words);
+                (year, 0, end)
+            }
+        };
+
+        let owner = words[start..end]
+            .join(" ")
+            .trim_matches(|c: char| c.is_whitespace() || matches!(c, ',' | ';' | '-' | '–'))
+            .to_owned();
+        CopyrightLine {
+            symbol: symbol.unwrap_or_default(),
+            year,
+            owner: (!owner.is_empty()).then_some(owner),
+        }
+    }
+}
+
+/// Get the first year of the words leading a line, like "2019 - 2022", and
+/// the index of the first word after them.
+fn leading_year(words: &[&str]) -> (Option<i32>, usize) {
+    let mut year = None;
+    let mut end = 0;
+    for word in words {
+        match years(word) {
+            Some(first) => {
+                year.get_or_insert(first);
+            }
+            None if year.is_some() && is_dash(word) => {}
+            None => break,
+        }
+        end += 1;
+    }
+    (year, end)
+}
+
+/// Get the first year of the words trailing a line, like "2019 - 2022", and
+/// the index of the first of them.
+fn trailing_year(words: &[&str]) -> (Option<i32>, usize) {
+    let mut year = None;
+    let mut start = words.len();
+    for word in words.iter().rev() {
+        match years(word) {
+            Some(first) => year = Some(first),
+            None if year.is_some() && is_dash(word) => {}
+            None => break,
+        }
+        start -= 1;
+    }
+    (year, start)
+}
+
+/// Split the symbol off the start of a word, like "℗2022", getting the rest.
+fn split_symbol(word: &str) -> (Option<CopyrightSymbol>, &str) {
+    let symbols = [
+        ("℗", CopyrightSymbol::Phonogram),
+        ("(P)", CopyrightSymbol::Phonogram),
+        ("(p)", CopyrightSymbol::Phonogram),
+        ("©", CopyrightSymbol::Copyright),
+        ("(C)", CopyrightSymbol::Copyright),
+        ("(c)", CopyrightSymbol::Copyright),
+    ];
+    symbols
+        .into_iter()
+        .find_map(|(prefix, symbol)| Some((Some(symbol), word.strip_prefix(prefix)?)))
+        .unwrap_or((None, word))
+}
+
+/// Check whether a word is a dash between years.
+fn is_dash(word: &str) -> bool {
+    matches!(word, "-" | "–" | "/")
+}
+
+/// Get the first year of a word made of years, like "2022," or "2019-2022".
+fn years(word: &str) -> Option<i32> {
+    let mut first = None;
+    for part in word
+        .trim_matches(|c| matches!(c, ',' | '.' | ';'))
+        .split(['-', '–', '/'])
+    {
+        let year = part
+            .parse()
+            .ok()
+            .filter(|year| part.len() == 4 && (1900..2100).contains(year))?;
+        first.get_or_insert(year);
+    }
+    first
+}
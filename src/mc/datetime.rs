@@ -0,0 +1,67 @@
+use chrono::{DateTime, Locale, TimeZone, Utc};
+use chrono_tz::Tz;
+use iso8601_timestamp::Timestamp;
+
+/// Get a time zone by its IANA name, like "America/Vancouver".
+pub fn parse_timezone(name: &str) -> Option<Tz> {
+    name.trim().parse().ok()
+}
+
+/// Get a locale from a language tag, like "en-US" or "fr_CA".
+///
+/// A tag with only a language, like "de", gets the region of the same name,
+/// or the US for English.
+pub fn parse_locale(tag: &str) -> Option<Locale> {
+    let tag = tag.trim().replace('-', "_");
+    let tag = match tag.split_once('_') {
+        Some((language, region)) => {
+            format!("{}_{}", language.to_lowercase(), region.to_uppercase())
+        }
+        None if tag.eq_ignore_ascii_case("en") => "en_US".to_owned(),
+        None => format!("{}_{}", tag.to_lowercase(), tag.to_uppercase()),
+    };
+
+    Locale::try_from(tag.as_str()).ok()
+}
+
+/// Get a timestamp as the date and time in a time zone, by IANA name.
+///
+/// Unknown time zones are treated as UTC.
+pub fn in_timezone(timestamp: &Timestamp, timezone: &str) -> DateTime<Tz> {
+    with_timezone(timestamp, parse_timezone(timezone).unwrap_or(Tz::UTC))
+}
+
+/// Get a timestamp as the date and time in a time zone.
+pub fn with_timezone(timestamp: &Timestamp, timezone: Tz) -> DateTime<Tz> {
+    let millis = timestamp
+        .duration_since(Timestamp::UNIX_EPOCH)
+        .whole_milliseconds();
+    Utc.timestamp_millis_opt(millis as i64)
+        .single()
+        .unwrap_or_default()
+        .with_timezone(&timezone)
+}
+
+/// Format the date of a timestamp in a time zone, the way a locale writes
+/// dates, like "09/22/2022" for "en-US" or "22.09.2022" for "de-DE".
+///
+/// Unknown locales get the date as "2022-09-22".
+///
+/// Example
+/// ```rust
+/// use bombay::mc::datetime;
+/// use iso8601_timestamp::Timestamp;
+///
+/// // Just after midnight in London is still the day before in Vancouver.
+/// let timestamp = Timestamp::parse("2022-09-23T00:30:00Z").unwrap();
+///
+/// assert_eq!(datetime::format_date(&timestamp, "Europe/London", "en-GB"), "23/09/22");
+/// assert_eq!(datetime::format_date(&timestamp, "America/Vancouver", "??"), "2022-09-22");
+/// ```
+pub fn format_date(timestamp: &Timestamp, timezone: &str, locale: &str) -> String {
+    let date = in_timezone(timestamp, timezone);
+    match parse_locale(locale) {
+        Some(locale) => date.format_localized("%x", locale).to_string(),
+        None => date.format("%Y-%m-%d").to_string(),
+    }
+}
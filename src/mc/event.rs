@@ -0,0 +1,42 @@
+use crate::mc::util::Link;
+use iso8601_timestamp::Timestamp;
+use serde::{Deserialize, Serialize};
+use std::fmt::Display;
+use std::ops::Deref;
+use uuid::Uuid;
+
+/// NewType for event identifier, wraps a UUID and adds type safety.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Deserialize, Serialize)]
+pub struct EventID(pub Uuid);
+
+impl Deref for EventID {
+    type Target = Uuid;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl Display for EventID {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// Show or event featuring Monstercat artists.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct Event {
+    pub artists_title: Option<String>,
+    pub city: Option<String>,
+    pub country: Option<String>,
+    pub description: Option<String>,
+    pub end_date: Option<Timestamp>,
+    pub id: EventID,
+    pub links: Option<Vec<Link>>,
+    pub reminded: Option<bool>,
+    pub start_date: Timestamp,
+    pub timezone: Option<String>,
+    pub title: String,
+    pub venue: Option<String>,
+}
@@ -0,0 +1,319 @@
+use crate::mc::artist::{Artist, ArtistID};
+use crate::mc::event::EventID;
+use crate::mc::mood::MoodID;
+use crate::mc::playlist::{Playlist, PlaylistID};
+use crate::mc::release::{Release, ReleaseID, Track, TrackID};
+use crate::mc::user::UserID;
+use crate::mc::util::LicenseID;
+use std::fmt::{Debug, Display};
+use std::hash::{Hash, Hasher};
+use std::ops::Deref;
+use std::str::FromStr;
+use url::Url;
+use uuid::Uuid;
+
+/// Kinds of entities identified by a UUID.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum EntityKind {
+    Artist,
+    Event,
+    License,
+    Mood,
+    Playlist,
+    Release,
+    Track,
+    User,
+}
+
+impl EntityKind {
+    /// All entity kinds.
+    pub const ALL: [EntityKind; 8] = [
+        EntityKind::Artist,
+        EntityKind::Event,
+        EntityKind::License,
+        EntityKind::Mood,
+        EntityKind::Playlist,
+        EntityKind::Release,
+        EntityKind::Track,
+        EntityKind::User,
+    ];
+}
+
+impl Display for EntityKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            EntityKind::Artist => write!(f, "artist"),
+            EntityKind::Event => write!(f, "event"),
+            EntityKind::License => write!(f, "license"),
+            EntityKind::Mood => write!(f, "mood"),
+            EntityKind::Playlist => write!(f, "playlist"),
+            EntityKind::Release => write!(f, "release"),
+            EntityKind::Track => write!(f, "track"),
+            EntityKind::User => write!(f, "user"),
+        }
+    }
+}
+
+impl FromStr for EntityKind {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        EntityKind::ALL
+            .into_iter()
+            .find(|kind| kind.to_string().eq_ignore_ascii_case(s.trim()))
+            .ok_or(())
+    }
+}
+
+/// Identifier newtype of an entity, wrapping a UUID, so code like caches
+/// and URL builders can work with any of them.
+///
+/// Example
+/// ```rust
+/// use bombay::mc::id::{EntityId, EntityKind};
+/// use bombay::mc::release::TrackID;
+///
+/// let track_id = TrackID::parse("3c1c5bd2-9f5e-4d0b-a7a7-2bb3d1c4a0e1").unwrap();
+/// assert_eq!(track_id.entity_kind(), EntityKind::Track);
+/// assert_eq!(track_id.uuid().to_string(), "3c1c5bd2-9f5e-4d0b-a7a7-2bb3d1c4a0e1");
+/// ```
+pub trait EntityId: Copy + Debug + Display + Eq + Hash + Send + Sync + 'static {
+    /// Kind of entity the identifier is for.
+    const KIND: EntityKind;
+
+    /// Wrap a UUID.
+    fn from_uuid(uuid: Uuid) -> Self;
+
+    /// Get the wrapped UUID.
+    fn uuid(&self) -> Uuid;
+
+    /// Get the kind of entity the identifier is for.
+    fn entity_kind(&self) -> EntityKind {
+        Self::KIND
+    }
+
+    /// Parse an identifier from a UUID, in any of its usual formats.
+    fn parse(s: &str) -> Result<Self, uuid::Error> {
+        Uuid::parse_str(s.trim()).map(Self::from_uuid)
+    }
+}
+
+/// Implement [`EntityId`] for identifier newtypes of a UUID.
+macro_rules! impl_entity_id {
+    ($(($id:ident, $kind:ident)),+) => {
+        $(impl EntityId for $id {
+            const KIND: EntityKind = EntityKind::$kind;
+
+            fn from_uuid(uuid: Uuid) -> Self {
+                $id(uuid)
+            }
+
+            fn uuid(&self) -> Uuid {
+                self.0
+            }
+        })+
+    };
+}
+
+impl_entity_id!(
+    (ArtistID, Artist),
+    (EventID, Event),
+    (LicenseID, License),
+    (MoodID, Mood),
+    (PlaylistID, Playlist),
+    (ReleaseID, Release),
+    (TrackID, Track),
+    (UserID, User)
+);
+
+/// Identifier of any kind of entity, as parsed by [`parse_any_id`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum AnyID {
+    Artist(ArtistID),
+    Event(EventID),
+    License(LicenseID),
+    Mood(MoodID),
+    Playlist(PlaylistID),
+    Release(ReleaseID),
+    Track(TrackID),
+    User(UserID),
+}
+
+impl AnyID {
+    /// Create an identifier of a kind of entity.
+    pub fn new(kind: EntityKind, uuid: Uuid) -> Self {
+        match kind {
+            EntityKind::Artist => AnyID::Artist(ArtistID(uuid)),
+            EntityKind::Event => AnyID::Event(EventID(uuid)),
+            EntityKind::License => AnyID::License(LicenseID(uuid)),
+            EntityKind::Mood => AnyID::Mood(MoodID(uuid)),
+            EntityKind::Playlist => AnyID::Playlist(PlaylistID(uuid)),
+            EntityKind::Release => AnyID::Release(ReleaseID(uuid)),
+            EntityKind::Track => AnyID::Track(TrackID(uuid)),
+            EntityKind::User => AnyID::User(UserID(uuid)),
+        }
+    }
+
+    /// Get the kind of entity the identifier is for.
+    pub fn entity_kind(&self) -> EntityKind {
+        match self {
+            AnyID::Artist(id) => id.entity_kind(),
+            AnyID::Event(id) => id.entity_kind(),
+            AnyID::License(id) => id.entity_kind(),
+            AnyID::Mood(id) => id.entity_kind(),
+            AnyID::Playlist(id) => id.entity_kind(),
+            AnyID::Release(id) => id.entity_kind(),
+            AnyID::Track(id) => id.entity_kind(),
+            AnyID::User(id) => id.entity_kind(),
+        }
+    }
+
+    /// Get the wrapped UUID.
+    pub fn uuid(&self) -> Uuid {
+        match self {
+            AnyID::Artist(id) => id.uuid(),
+            AnyID::Event(id) => id.uuid(),
+            AnyID::License(id) => id.uuid(),
+            AnyID::Mood(id) => id.uuid(),
+            AnyID::Playlist(id) => id.uuid(),
+            AnyID::Release(id) => id.uuid(),
+            AnyID::Track(id) => id.uuid(),
+            AnyID::User(id) => id.uuid(),
+        }
+    }
+}
+
+impl Display for AnyID {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}:{}", self.entity_kind(), self.uuid())
+    }
+}
+
+/// Parse an identifier along with the kind of entity it is for, from either
+/// a kind-prefixed UUID like `track:<uuid>` or a URL with a path like
+/// `/playlist/<uuid>`.
+///
+/// A bare UUID doesn't say what it identifies, so it isn't parsed.
+///
+/// Example
+/// ```rust
+/// use bombay::mc::id::{parse_any_id, AnyID, EntityKind};
+///
+/// let id = parse_any_id("https://www.monstercat.com/playlist/f4a8b5a4-4c2c-4b5e-9fd1-6a6a3a37d1a2").unwrap();
+/// assert_eq!(id.entity_kind(), EntityKind::Playlist);
+///
+/// let id = parse_any_id("track:3c1c5bd2-9f5e-4d0b-a7a7-2bb3d1c4a0e1").unwrap();
+/// assert!(matches!(id, AnyID::Track(_)));
+///
+/// assert!(parse_any_id("3c1c5bd2-9f5e-4d0b-a7a7-2bb3d1c4a0e1").is_none());
+/// ```
+pub fn parse_any_id(s: &str) -> Option<AnyID> {
+    let s = s.trim();
+
+    if let Ok(url) = Url::parse(s) {
+        if url.has_host() {
+            let segments: Vec<&str> = url.path_segments()?.collect();
+            return segments.windows(2).rev().find_map(|pair| {
+                let kind = pair[0].parse().ok()?;
+                let uuid = Uuid::parse_str(pair[1]).ok()?;
+                Some(AnyID::new(kind, uuid))
+            });
+        }
+    }
+
+    let (kind, uuid) = s.split_once(':')?;
+    Some(AnyID::new(
+        kind.parse().ok()?,
+        Uuid::parse_str(uuid.trim()).ok()?,
+    ))
+}
+
+/// Model with an identifier that stays the same across responses.
+pub trait Identified {
+    /// Type of the identifier.
+    type ID: Eq + Hash;
+
+    /// Get the model's identifier.
+    fn get_id(&self) -> &Self::ID;
+}
+
+impl Identified for Artist {
+    type ID = ArtistID;
+
+    fn get_id(&self) -> &Self::ID {
+        &self.id
+    }
+}
+
+impl Identified for Playlist {
+    type ID = PlaylistID;
+
+    fn get_id(&self) -> &Self::ID {
+        &self.id
+    }
+}
+
+impl Identified for Release {
+    type ID = ReleaseID;
+
+    fn get_id(&self) -> &Self::ID {
+        &self.id
+    }
+}
+
+impl Identified for Track {
+    type ID = TrackID;
+
+    fn get_id(&self) -> &Self::ID {
+        &self.id
+    }
+}
+
+/// Wrapper comparing and hashing a model by its identifier only.
+///
+/// Models compare all of their fields, so the same track fetched twice may
+/// differ, for example by cache details. Wrapped, they can be used in hash
+/// sets and maps, or to dedup lists.
+///
+/// Example
+/// ```rust
+/// use bombay::mc::id::ById;
+/// use bombay::mc::release::Track;
+/// use std::collections::HashSet;
+///
+/// fn count_unique(tracks: Vec<Track>) -> usize {
+///     let unique: HashSet<ById<Track>> = tracks.into_iter().map(ById).collect();
+///     unique.len()
+/// }
+/// ```
+#[derive(Clone, Debug)]
+pub struct ById<T>(pub T);
+
+impl<T> ById<T> {
+    /// Unwrap the model.
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T> Deref for ById<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T: Identified> PartialEq for ById<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.get_id() == other.0.get_id()
+    }
+}
+
+impl<T: Identified> Eq for ById<T> {}
+
+impl<T: Identified> Hash for ById<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.0.get_id().hash(state);
+    }
+}
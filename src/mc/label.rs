@@ -1,13 +1,60 @@
-use serde_repr::{Deserialize_repr, Serialize_repr};
+use crate::compat;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
 /// Variants of Monstercat brands.
-#[derive(Clone, Debug, PartialEq, Serialize_repr, Deserialize_repr)]
-#[serde(rename_all = "PascalCase")]
-#[repr(u8)]
+#[derive(Clone, Debug, PartialEq)]
+#[non_exhaustive]
 pub enum Brand {
-    Uncaged = 1,
-    Instinct = 2,
-    CallofTheWild = 3,
-    Silk = 4,
-    MonstercatSilkShowcase = 5,
+    Uncaged,
+    Instinct,
+    CallofTheWild,
+    Silk,
+    MonstercatSilkShowcase,
+    Other(u8),
+}
+
+impl Brand {
+    /// Get the brand's ID as used by the MC API.
+    pub fn id(&self) -> u8 {
+        match self {
+            Brand::Uncaged => 1,
+            Brand::Instinct => 2,
+            Brand::CallofTheWild => 3,
+            Brand::Silk => 4,
+            Brand::MonstercatSilkShowcase => 5,
+            Brand::Other(id) => *id,
+        }
+    }
+
+    /// Get a brand from its ID, with unknown IDs handled per
+    /// [`compat::UnknownEnumPolicy`].
+    pub fn from_id(id: u8) -> Result<Self, serde_json::Error> {
+        Ok(match id {
+            1 => Brand::Uncaged,
+            2 => Brand::Instinct,
+            3 => Brand::CallofTheWild,
+            4 => Brand::Silk,
+            5 => Brand::MonstercatSilkShowcase,
+            unk => compat::unknown_variant("Brand", &unk.to_string(), Brand::Other(unk))?,
+        })
+    }
+}
+
+impl Serialize for Brand {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_u8(self.id())
+    }
+}
+
+impl<'de> Deserialize<'de> for Brand {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let id = u8::deserialize(deserializer)?;
+        Brand::from_id(id).map_err(serde::de::Error::custom)
+    }
 }
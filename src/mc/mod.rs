@@ -1,9 +1,20 @@
 #![doc = include_str!("README.md")]
 
 pub mod artist;
+pub mod chart;
+pub mod connect;
+pub mod copyright;
+#[cfg(feature = "datetime")]
+pub mod datetime;
+pub mod event;
+pub mod id;
 pub mod label;
 pub mod mood;
+pub mod notes;
 pub mod playlist;
 pub mod release;
+pub mod share;
+pub mod tag;
+pub mod urls;
 pub mod user;
 pub mod util;
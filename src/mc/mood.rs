@@ -1,14 +1,36 @@
+use crate::compat;
+use crate::mc::release::TrackFeatures;
 use iso8601_timestamp::Timestamp;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
-use std::fmt::Display;
+use serde_with::{DeserializeFromStr, SerializeDisplay};
+use std::ops::Deref;
+use std::{fmt::Display, str::FromStr};
 use uuid::Uuid;
 
+/// NewType for mood identifier, wraps a UUID and adds type safety.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Deserialize, Serialize)]
+pub struct MoodID(pub Uuid);
+
+impl Deref for MoodID {
+    type Target = Uuid;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl Display for MoodID {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
 /// Mood object used for categorizing songs.
 #[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
 #[serde(rename_all = "PascalCase")]
 pub struct Mood {
-    pub id: Uuid,
+    pub id: MoodID,
     pub name: String,
     pub uri: String,
     pub description: String,
@@ -21,19 +43,46 @@ pub struct Mood {
     pub background_file_id: Uuid,
 }
 
+impl Mood {
+    /// Check whether track features fall within every parameter range of this mood.
+    ///
+    /// Features missing from the track are not held against it.
+    pub fn matches(&self, features: &TrackFeatures) -> bool {
+        self.params
+            .iter()
+            .flatten()
+            .all(|config| config.contains(features))
+    }
+}
+
 /// Configuration of mood parameter.
 #[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
 #[serde(rename_all = "PascalCase")]
 pub struct MoodParamConfig {
-    pub mood_id: Uuid,
+    pub mood_id: MoodID,
     pub param: MoodParam,
     pub min: f32,
     pub max: f32,
 }
 
+impl MoodParamConfig {
+    /// Check whether track features fall within this parameter's range.
+    ///
+    /// Returns true if the track is missing this feature.
+    pub fn contains(&self, features: &TrackFeatures) -> bool {
+        features
+            .get(&self.param)
+            .map_or(true, |value| self.min <= value && value <= self.max)
+    }
+}
+
 /// Variants of mood parameters.
-#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
-#[serde(rename_all = "lowercase")]
+///
+/// Unknown parameters are kept as [`MoodParam::Other`]. Parameters written
+/// by older versions of Bombay, which misspelled "speechiness" and
+/// "valence", are still read.
+#[derive(Clone, Debug, PartialEq, DeserializeFromStr, SerializeDisplay)]
+#[non_exhaustive]
 pub enum MoodParam {
     Acousticness,
     Danceability,
@@ -41,6 +90,7 @@ pub enum MoodParam {
     Instrumentalness,
     Liveness,
     Loudness,
+    Other(String),
     Speechiness,
     Valence,
 }
@@ -54,8 +104,29 @@ impl Display for MoodParam {
             MoodParam::Instrumentalness => write!(f, "instrumentalness"),
             MoodParam::Liveness => write!(f, "liveness"),
             MoodParam::Loudness => write!(f, "loudness"),
-            MoodParam::Speechiness => write!(f, "speechniess"),
-            MoodParam::Valence => write!(f, "valance"),
+            MoodParam::Other(unk) => write!(f, "{}", unk),
+            MoodParam::Speechiness => write!(f, "speechiness"),
+            MoodParam::Valence => write!(f, "valence"),
         }
     }
 }
+
+impl FromStr for MoodParam {
+    type Err = serde_json::Error;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut norm = s.to_lowercase();
+        norm.retain(|c| !c.is_whitespace());
+        Ok(match norm.as_str() {
+            "acousticness" => MoodParam::Acousticness,
+            "danceability" => MoodParam::Danceability,
+            "energy" => MoodParam::Energy,
+            "instrumentalness" => MoodParam::Instrumentalness,
+            "liveness" => MoodParam::Liveness,
+            "loudness" => MoodParam::Loudness,
+            // Older versions wrote the last two misspelled.
+            "speechiness" | "speechniess" => MoodParam::Speechiness,
+            "valence" | "valance" => MoodParam::Valence,
+            _ => compat::unknown_variant("MoodParam", s, MoodParam::Other(norm))?,
+        })
+    }
+}
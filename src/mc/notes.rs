@@ -0,0 +1,402 @@
+use serde::{Deserialize, Serialize};
+use url::Url;
+
+/// Schemes of URLs kept as links, others are shown as plain text.
+const LINK_SCHEMES: [&str; 3] = ["http", "https", "mailto"];
+
+/// Block of rich text, like album notes, see [`parse`].
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[non_exhaustive]
+pub enum Block {
+    /// Heading, of level 1 (largest) to 6.
+    Heading { level: u8, content: Vec<Inline> },
+    /// Paragraph of text.
+    Paragraph(Vec<Inline>),
+    /// Item of a list, like a track of a tracklist.
+    ListItem(Vec<Inline>),
+}
+
+/// Part of the text of a block.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[non_exhaustive]
+pub enum Inline {
+    /// Plain text, with HTML entities decoded.
+    Text(String),
+    /// Link, only to web or email addresses.
+    Link { text: String, url: Url },
+}
+
+/// Parse rich text, like [`Release::album_notes`](crate::mc::release::Release::album_notes),
+/// into blocks UIs can render safely, without injecting its markup.
+///
+/// Notes mix HTML and markdown-ish text, so both are understood: paragraphs
+/// (`<p>`, `<br>`, or blank lines), headings (`<h2>` or `## `), list items
+/// (`<li>` or `- `), and links (`<a href>`, `[text](url)`, or bare URLs).
+/// Other tags and emphasis are dropped, keeping their text, and scripts and
+/// styles are dropped whole. Links to anything but web or email addresses,
+/// like `javascript:` URLs, are kept as plain text.
+///
+/// Example
+/// ```rust
+/// use bombay::mc::notes::{self, Block, Inline};
+///
+/// let blocks = notes::parse(
+///     "<h2>Credits</h2><p>Mixed by <a href=\"https://example.com\">Whales</a> &amp; friends.</p>",
+/// );
+/// assert_eq!(blocks.len(), 2);
+/// assert!(matches!(&blocks[1], Block::Paragraph(content) if matches!(&content[1], Inline::Link { text, .. } if text == "Whales")));
+/// assert_eq!(
+///     notes::to_plaintext(&blocks),
+///     "Credits\n\nMixed by Whales (https://example.com/) & friends."
+/// );
+/// ```
+pub fn parse(notes: &str) -> Vec<Block> {
+    let text = strip_html(notes);
+    let mut blocks = Vec::new();
+    let mut paragraph = Vec::new();
+
+    for line in text.lines() {
+        let line = line.split_whitespace().collect::<Vec<_>>().join(" ");
+        if let Some((level, heading)) = heading(&line) {
+            push_paragraph(&mut blocks, &mut paragraph);
+            let content = inlines(heading);
+            if !content.is_empty() {
+                blocks.push(Block::Heading { level, content });
+            }
+        } else if let Some(item) = list_item(&line) {
+            push_paragraph(&mut blocks, &mut paragraph);
+            let content = inlines(item);
+            if !content.is_empty() {
+                blocks.push(Block::ListItem(content));
+            }
+        } else if line.is_empty() {
+            push_paragraph(&mut blocks, &mut paragraph);
+        } else {
+            paragraph.push(line);
+        }
+    }
+    push_paragraph(&mut blocks, &mut paragraph);
+
+    blocks
+}
+
+/// Get blocks as plain text, with blank lines between blocks and links
+/// followed by their URL.
+pub fn to_plaintext(blocks: &[Block]) -> String {
+    join_blocks(blocks, |block| match block {
+        Block::Heading { content, .. } | Block::Paragraph(content) => plaintext(content),
+        Block::ListItem(content) => format!("- {}", plaintext(content)),
+    })
+}
+
+/// Get blocks as markdown, with the text escaped, so it only holds the
+/// markup of the blocks themselves.
+pub fn to_markdown(blocks: &[Block]) -> String {
+    join_blocks(blocks, |block| match block {
+        Block::Heading { level, content } => {
+            format!("{} {}", "#".repeat(*level as usize), markdown(content))
+        }
+        Block::Paragraph(content) => markdown(content),
+        Block::ListItem(content) => format!("- {}", markdown(content)),
+    })
+}
+
+/// Join blocks, consecutive list items on their own lines, and others
+/// separated by blank lines.
+fn join_blocks(blocks: &[Block], format: impl Fn(&Block) -> String) -> String {
+    let mut joined = String::new();
+    let mut previous: Option<&Block> = None;
+    for block in blocks {
+        match previous {
+            Some(Block::ListItem(_)) if matches!(block, Block::ListItem(_)) => joined.push('\n'),
+            Some(_) => joined.push_str("\n\n"),
+            None => {}
+        }
+        joined.push_str(&format(block));
+        previous = Some(block);
+    }
+    joined
+}
+
+fn plaintext(content: &[Inline]) -> String {
+    content
+        .iter()
+        .map(|inline| match inline {
+            Inline::Text(text) => text.clone(),
+            Inline::Link { text, url } if is_bare(text, url) => url.to_string(),
+            Inline::Link { text, url } => format!("{text} ({url})"),
+        })
+        .collect()
+}
+
+fn markdown(content: &[Inline]) -> String {
+    content
+        .iter()
+        .map(|inline| match inline {
+            Inline::Text(text) => escape_markdown(text),
+            Inline::Link { text, url } => format!(
+                "[{}]({})",
+                escape_markdown(text),
+                url.as_str().replace('(', "%28").replace(')', "%29")
+            ),
+        })
+        .collect()
+}
+
+fn escape_markdown(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        if matches!(c, '\\' | '`' | '*' | '_' | '[' | ']' | '<' | '>') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+/// Check whether a link's text is just its URL.
+fn is_bare(text: &str, url: &Url) -> bool {
+    text == url.as_str() || Some(text) == url.as_str().strip_suffix('/')
+}
+
+fn push_paragraph(blocks: &mut Vec<Block>, paragraph: &mut Vec<String>) {
+    if !paragraph.is_empty() {
+        let content = inlines(&paragraph.join(" "));
+        paragraph.clear();
+        if !content.is_empty() {
+            blocks.push(Block::Paragraph(content));
+        }
+    }
+}
+
+/// Get the level and text of a markdown heading line, like "## Credits".
+fn heading(line: &str) -> Option<(u8, &str)> {
+    let level = line.chars().take_while(|c| *c == '#').count();
+    let text = line[level..].strip_prefix(' ')?;
+    (1..=6).contains(&level).then_some((level as u8, text))
+}
+
+/// Get the text of a markdown list item line, like "- Souvenir".
+fn list_item(line: &str) -> Option<&str> {
+    ["- ", "* ", "• "]
+        .iter()
+        .find_map(|marker| line.strip_prefix(marker))
+}
+
+/// Split text into plain text and links.
+fn inlines(text: &str) -> Vec<Inline> {
+    let text = text.replace("**", "").replace("__", "");
+    let mut content = Vec::new();
+    let mut rest = text.as_str();
+
+    while let Some((start, end, label, url)) = next_link(rest) {
+        push_text(&mut content, &rest[..start]);
+        match Url::parse(url)
+            .ok()
+            .filter(|url| LINK_SCHEMES.contains(&url.scheme()))
+        {
+            Some(url) => content.push(Inline::Link {
+                text: label.unwrap_or(url.as_str()).to_owned(),
+                url,
+            }),
+            None => push_text(&mut content, label.unwrap_or(url)),
+        }
+        rest = &rest[end..];
+    }
+    push_text(&mut content, rest);
+
+    content
+}
+
+/// Add text, merged with the text before it.
+fn push_text(content: &mut Vec<Inline>, text: &str) {
+    if text.is_empty() {
+        return;
+    }
+    match content.last_mut() {
+        Some(Inline::Text(previous)) => previous.push_str(text),
+        _ => content.push(Inline::Text(text.to_owned())),
+    }
+}
+
+/// Find the first link in text, markdown or a bare URL, getting where it
+/// starts and ends, its label if any, and its URL.
+fn next_link(text: &str) -> Option<(usize, usize, Option<&str>, &str)> {
+    let markdown = text.match_indices('[').find_map(|(start, _)| {
+        let label_end = start + text[start..].find("](")?;
+        let url_end = label_end + text[label_end..].find(')')?;
+        let label = &text[start + 1..label_end];
+        (!label.contains('[')).then_some((
+            start,
+            url_end + 1,
+            Some(label),
+            &text[label_end + 2..url_end],
+        ))
+    });
+
+    let bare = ["https://", "http://"]
+        .iter()
+        .filter_map(|scheme| text.find(scheme))
+        .min()
+        .map(|start| {
+            let url = text[start..]
+                .split(char::is_whitespace)
+                .next()
+                .unwrap_or_default()
+                .trim_end_matches(['.', ',', ';', ':', '!', '?', ')', '"']);
+            (start, start + url.len(), None, url)
+        });
+
+    match (markdown, bare) {
+        (Some(markdown), Some(bare)) if bare.0 < markdown.0 => Some(bare),
+        (Some(markdown), _) => Some(markdown),
+        (None, bare) => bare,
+    }
+}
+
+/// Turn HTML into markdown-ish text, with entities decoded, block tags as
+/// line breaks, and links as markdown links.
+fn strip_html(notes: &str) -> String {
+    let mut text = String::with_capacity(notes.len());
+    let mut hrefs: Vec<Option<String>> = Vec::new();
+    let mut rest = notes;
+
+    while let Some(start) = rest.find('<') {
+        text.push_str(&decode_entities(&rest[..start]));
+        let after = &rest[start + 1..];
+
+        if let Some(comment) = after.strip_prefix("!--") {
+            rest = comment.find("-->").map_or("", |end| &comment[end + 3..]);
+            continue;
+        }
+        let is_tag = after.starts_with(|c: char| c.is_ascii_alphabetic() || c == '/' || c == '!');
+        let Some(end) = after.find('>').filter(|_| is_tag) else {
+            text.push('<');
+            rest = after;
+            continue;
+        };
+
+        let tag = &after[..end];
+        rest = &after[end + 1..];
+        let closing = tag.starts_with('/');
+        let name = tag
+            .trim_start_matches('/')
+            .split(|c: char| c.is_whitespace() || c == '/')
+            .next()
+            .unwrap_or_default()
+            .to_ascii_lowercase();
+
+        match name.as_str() {
+            "script" | "style" if !closing => {
+                let close = format!("</{name}");
+                rest = match rest.to_ascii_lowercase().find(&close) {
+                    Some(at) => rest[at..].find('>').map_or("", |end| &rest[at + end + 1..]),
+                    None => "",
+                };
+            }
+            "br" => text.push('\n'),
+            "p" | "div" | "ul" | "ol" | "blockquote" | "table" | "tr" => text.push_str("\n\n"),
+            "li" if closing => text.push('\n'),
+            "li" => text.push_str("\n- "),
+            "h1" | "h2" | "h3" | "h4" | "h5" | "h6" => {
+                text.push_str("\n\n");
+                if !closing {
+                    text.push_str(&"#".repeat((name.as_bytes()[1] - b'0') as usize));
+                    text.push(' ');
+                }
+            }
+            "a" if closing => {
+                if let Some(Some(href)) = hrefs.pop() {
+                    text.push_str("](");
+                    text.push_str(&href.replace('(', "%28").replace(')', "%29"));
+                    text.push(')');
+                }
+            }
+            "a" => {
+                let href = attribute(tag, "href");
+                if href.is_some() {
+                    text.push('[');
+                }
+                hrefs.push(href);
+            }
+            _ => {}
+        }
+    }
+    text.push_str(&decode_entities(rest));
+
+    text
+}
+
+/// Get the value of an attribute of an HTML tag, with entities decoded.
+fn attribute(tag: &str, name: &str) -> Option<String> {
+    let lower = tag.to_ascii_lowercase();
+    let pattern = format!("{name}=");
+    let (at, _) = lower
+        .match_indices(&pattern)
+        .find(|(at, _)| lower[..*at].ends_with(char::is_whitespace))?;
+    let value = &tag[at + pattern.len()..];
+
+    let value = match value.chars().next()? {
+        quote @ ('"' | '\'') => value[1..].split(quote).next()?,
+        _ => value.split(char::is_whitespace).next()?,
+    };
+    Some(decode_entities(value.trim()))
+}
+
+/// Decode HTML entities, like `&amp;` and `&#8217;`, keeping unknown ones.
+fn decode_entities(text: &str) -> String {
+    let mut decoded = String::with_capacity(text.len());
+    let mut rest = text;
+
+    while let Some(start) = rest.find('&') {
+        decoded.push_str(&rest[..start]);
+        rest = &rest[start..];
+        let entity = rest[1..]
+            .find(';')
+            .filter(|end| *end <= 10)
+            .and_then(|end| Some((end, entity(&rest[1..end + 1])?)));
+        match entity {
+            Some((end, c)) => {
+                decoded.push(c);
+                rest = &rest[end + 2..];
+            }
+            None => {
+                decoded.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    decoded.push_str(rest);
+
+    decoded
+}
+
+/// Get the character of an HTML entity by name, like "amp" or "#39".
+fn entity(name: &str) -> Option<char> {
+    Some(match name {
+        "amp" => '&',
+        "lt" => '<',
+        "gt" => '>',
+        "quot" => '"',
+        "apos" => '\'',
+        "nbsp" => ' ',
+        "ndash" => '–',
+        "mdash" => '—',
+        "hellip" => '…',
+        "lsquo" => '‘',
+        "rsquo" => '’',
+        "ldquo" => '“',
+        "rdquo" => '”',
+        "copy" => '©',
+        "reg" => '®',
+        "trade" => '™',
+        _ => {
+            let number = name.strip_prefix('#')?;
+            let code = match number.strip_prefix(['x', 'X']) {
+                Some(hex) => u32::from_str_radix(hex, 16).ok()?,
+                None => number.parse().ok()?,
+            };
+            char::from_u32(code)?
+        }
+    })
+}
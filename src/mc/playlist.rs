@@ -1,11 +1,18 @@
-use crate::mc::release::{ReleaseID, TrackID};
+use crate::compat;
+use crate::mc::release::{CatalogID, ReleaseID, Track, TrackID};
+use crate::mc::share::{self, EmbedOptions};
+use crate::mc::user::UserID;
+use iso8601_timestamp::Timestamp;
 use serde::{Deserialize, Serialize};
+use serde_with::{DeserializeFromStr, SerializeDisplay};
 use std::fmt::Display;
 use std::ops::Deref;
+use std::str::FromStr;
+use url::Url;
 use uuid::Uuid;
 
 /// NewType for playlist identifier, wraps a UUID and adds type safety.
-#[derive(Clone, Copy, Debug, PartialEq, Deserialize, Serialize)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Deserialize, Serialize)]
 pub struct PlaylistID(pub Uuid);
 
 impl Deref for PlaylistID {
@@ -41,6 +48,22 @@ pub struct Playlist {
     pub user_id: Option<Uuid>,
 }
 
+impl Playlist {
+    /// Get the URL to share the playlist on a web player, like the client's,
+    /// see `Client::player_url`.
+    ///
+    /// Only public playlists can be opened by others.
+    pub fn share_url(&self, player: &Url) -> Url {
+        share::player_url(player, &["playlist", &self.id.to_string()])
+    }
+
+    /// Get the HTML of a widget to embed the playlist from a web player in a
+    /// web page.
+    pub fn embed_html(&self, player: &Url, options: &EmbedOptions) -> String {
+        share::embed_html(player, &["playlist", &self.id.to_string()], options)
+    }
+}
+
 /// Track present in a playlist.
 #[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
 #[serde(rename_all = "PascalCase")]
@@ -54,3 +77,137 @@ pub struct PlaylistItem {
     /// ID of this track.
     pub track_id: TrackID,
 }
+
+/// Backup of a playlist and its tracks, to restore it if it gets deleted.
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct PlaylistBackup {
+    pub playlist: Playlist,
+    /// Tracks of the playlist, in order.
+    pub tracks: Vec<BackedUpTrack>,
+}
+
+/// Track of a playlist backup, with enough to find it again if it was
+/// removed from its release.
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct BackedUpTrack {
+    pub artists_title: String,
+    /// Catalog ID of the release this track is from.
+    pub catalog_id: CatalogID,
+    /// ISRC of the recording, to find it on another release.
+    pub isrc: String,
+    pub release_id: ReleaseID,
+    pub title: String,
+    pub track_id: TrackID,
+}
+
+impl From<&Track> for BackedUpTrack {
+    fn from(track: &Track) -> Self {
+        BackedUpTrack {
+            artists_title: track.artists_title.clone(),
+            catalog_id: CatalogID(track.release.catalog_id.clone()),
+            isrc: track.isrc.clone(),
+            release_id: track.release.id,
+            title: track.title.clone(),
+            track_id: track.id,
+        }
+    }
+}
+
+/// Outcome of restoring a playlist backup.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PlaylistRestore {
+    /// ID of the playlist created from the backup.
+    pub playlist_id: PlaylistID,
+    /// Items added to the playlist, in order.
+    pub items: Vec<PlaylistItem>,
+    /// Tracks no longer on their release, replaced by the same recording
+    /// (by ISRC) on another release.
+    pub replaced: Vec<(BackedUpTrack, TrackID)>,
+    /// Tracks that could not be found anymore, which were left out.
+    pub missing: Vec<BackedUpTrack>,
+}
+
+/// Member of a shared playlist, and what they're allowed to do with it.
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct PlaylistMember {
+    pub accepted: Option<bool>,
+    pub email: Option<String>,
+    pub invited_at: Option<Timestamp>,
+    pub name: Option<String>,
+    pub permission: PlaylistPermission,
+    pub user_id: UserID,
+}
+
+/// Variants of permissions a member can have on a shared playlist.
+///
+/// Unknown permissions are kept as [`PlaylistPermission::Other`].
+#[derive(Clone, Debug, PartialEq, DeserializeFromStr, SerializeDisplay)]
+#[non_exhaustive]
+pub enum PlaylistPermission {
+    /// Can listen to the playlist, even if it is private.
+    View,
+    /// Can also add, remove, and reorder items.
+    Edit,
+    /// Created the playlist, can also manage members.
+    Owner,
+    Other(String),
+}
+
+impl Display for PlaylistPermission {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                PlaylistPermission::View => "view",
+                PlaylistPermission::Edit => "edit",
+                PlaylistPermission::Owner => "owner",
+                PlaylistPermission::Other(unk) => unk,
+            }
+        )
+    }
+}
+
+impl FromStr for PlaylistPermission {
+    type Err = serde_json::Error;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s.trim().to_lowercase().as_str() {
+            "view" | "viewer" | "read" => PlaylistPermission::View,
+            "edit" | "editor" | "write" => PlaylistPermission::Edit,
+            "owner" | "admin" => PlaylistPermission::Owner,
+            _ => compat::unknown_variant(
+                "PlaylistPermission",
+                s,
+                PlaylistPermission::Other(s.trim().to_owned()),
+            )?,
+        })
+    }
+}
+
+/// Simple type to capture the playlist invitation request.
+///
+/// These sorts of simple wrappers are made to maintain the call patterns
+/// and to leave room for future expansion, such as additional fields or
+/// letter case changes.
+#[cfg(feature = "client")]
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "PascalCase")]
+pub(crate) struct PlaylistInvite {
+    pub email: String,
+    pub permission: PlaylistPermission,
+}
+
+/// Simple type to capture the playlist member permission request.
+///
+/// These sorts of simple wrappers are made to maintain the call patterns
+/// and to leave room for future expansion, such as additional fields or
+/// letter case changes.
+#[cfg(feature = "client")]
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "PascalCase")]
+pub(crate) struct PlaylistMemberPermission {
+    pub permission: PlaylistPermission,
+}
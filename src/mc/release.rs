@@ -1,14 +1,30 @@
-use crate::mc::artist::AnyArtist;
+use crate::compat;
+use crate::matching::normalize;
+use crate::mc::artist::{self, AnyArtist, Role};
+use crate::mc::copyright::CopyrightLine;
+#[cfg(feature = "datetime")]
+use crate::mc::datetime;
 use crate::mc::label::Brand;
+use crate::mc::mood::MoodParam;
+use crate::mc::notes::{self, Block};
+use crate::mc::share::{self, EmbedOptions};
+use crate::mc::tag::{self, Tags};
 use crate::mc::util::{CacheDetails, Link};
+#[cfg(feature = "datetime")]
+use chrono::{DateTime, Datelike};
+#[cfg(feature = "datetime")]
+use chrono_tz::Tz;
 use iso8601_timestamp::Timestamp;
 use serde::{Deserialize, Serialize};
+use serde_with::{DeserializeFromStr, SerializeDisplay};
 use std::fmt::Display;
 use std::ops::Deref;
+use std::str::FromStr;
+use url::Url;
 use uuid::Uuid;
 
 /// NewType for release identifier, wraps a UUID and adds type safety.
-#[derive(Clone, Copy, Debug, PartialEq, Deserialize, Serialize)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Deserialize, Serialize)]
 pub struct ReleaseID(pub Uuid);
 
 impl Deref for ReleaseID {
@@ -26,7 +42,7 @@ impl Display for ReleaseID {
 }
 
 /// NewType for track identifier, wraps a UUID and adds type safety.
-#[derive(Clone, Copy, Debug, PartialEq, Deserialize, Serialize)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Deserialize, Serialize)]
 pub struct TrackID(pub Uuid);
 
 impl Deref for TrackID {
@@ -44,7 +60,7 @@ impl Display for TrackID {
 }
 
 /// NewType for release catalog identifier, wraps a UUID and adds type safety.
-#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Deserialize, Serialize)]
 pub struct CatalogID(pub String);
 
 impl Deref for CatalogID {
@@ -55,14 +71,111 @@ impl Deref for CatalogID {
     }
 }
 
+impl CatalogID {
+    /// Create a catalog ID, trimmed and uppercased, like "MCS1186" for " mcs1186".
+    ///
+    /// Returns nothing if the ID is empty or has characters other than
+    /// letters, digits, and hyphens.
+    pub fn new(id: impl AsRef<str>) -> Option<CatalogID> {
+        let id = id.as_ref().trim().to_uppercase();
+        let valid = !id.is_empty() && id.chars().all(|c| c.is_ascii_alphanumeric() || c == '-');
+        valid.then_some(CatalogID(id))
+    }
+
+    /// Get the catalog ID trimmed and uppercased, see [`CatalogID::new`].
+    pub fn normalized(&self) -> CatalogID {
+        CatalogID(self.0.trim().to_uppercase())
+    }
+
+    /// Check whether the ID is a UPC (barcode) rather than a catalog code.
+    ///
+    /// Releases can be looked up by either, and UPCs are 12 or 13 digits.
+    pub fn is_upc(&self) -> bool {
+        let id = self.0.trim();
+        matches!(id.len(), 12 | 13) && id.chars().all(|c| c.is_ascii_digit())
+    }
+}
+
 impl Display for CatalogID {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "{}", self.0)
     }
 }
 
+/// Variants of genres releases are browsed by, as the MC player lists them.
+///
+/// Unknown genres are kept as [`Genre::Other`].
+#[derive(Clone, Debug, PartialEq, Eq, Hash, DeserializeFromStr, SerializeDisplay)]
+#[non_exhaustive]
+pub enum Genre {
+    BassHouse,
+    Drumstep,
+    DrumAndBass,
+    Dubstep,
+    Electro,
+    ElectroHouse,
+    FutureBass,
+    FutureHouse,
+    GlitchHop,
+    House,
+    MelodicDubstep,
+    NuDisco,
+    Trance,
+    Trap,
+    Other(String),
+}
+
+impl Display for Genre {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Genre::BassHouse => "Bass House",
+                Genre::Drumstep => "Drumstep",
+                Genre::DrumAndBass => "Drum & Bass",
+                Genre::Dubstep => "Dubstep",
+                Genre::Electro => "Electro",
+                Genre::ElectroHouse => "Electro House",
+                Genre::FutureBass => "Future Bass",
+                Genre::FutureHouse => "Future House",
+                Genre::GlitchHop => "Glitch Hop",
+                Genre::House => "House",
+                Genre::MelodicDubstep => "Melodic Dubstep",
+                Genre::NuDisco => "Nu Disco",
+                Genre::Trance => "Trance",
+                Genre::Trap => "Trap",
+                Genre::Other(unk) => unk,
+            }
+        )
+    }
+}
+
+impl FromStr for Genre {
+    type Err = serde_json::Error;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match normalize(s).as_str() {
+            "bass house" => Genre::BassHouse,
+            "drumstep" => Genre::Drumstep,
+            "drum and bass" | "drum bass" | "dnb" => Genre::DrumAndBass,
+            "dubstep" => Genre::Dubstep,
+            "electro" => Genre::Electro,
+            "electro house" => Genre::ElectroHouse,
+            "future bass" => Genre::FutureBass,
+            "future house" => Genre::FutureHouse,
+            "glitch hop" => Genre::GlitchHop,
+            "house" => Genre::House,
+            "melodic dubstep" => Genre::MelodicDubstep,
+            "nu disco" | "nudisco" => Genre::NuDisco,
+            "trance" => Genre::Trance,
+            "trap" => Genre::Trap,
+            _ => compat::unknown_variant("Genre", s, Genre::Other(s.trim().to_owned()))?,
+        })
+    }
+}
+
 /// Enumerated type to capture the possible release types.
-#[derive(Clone, Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
 #[serde(untagged)]
 pub enum AnyRelease {
     Release(Release),
@@ -102,6 +215,62 @@ impl AnyRelease {
         }
     }
 
+    /// Get any release's time zone of its release date, by IANA name.
+    pub fn get_timezone(&self) -> &str {
+        match self {
+            AnyRelease::Release(a) => &a.release_date_timezone,
+            AnyRelease::Track(t) => &t.release.release_date_timezone,
+        }
+    }
+
+    /// Get any release's catalog ID, or that of its release for tracks.
+    pub fn get_catalog_id(&self) -> &str {
+        match self {
+            AnyRelease::Release(a) => &a.catalog_id.0,
+            AnyRelease::Track(t) => &t.release.catalog_id,
+        }
+    }
+
+    /// Get the instant of any release, see [`Release::released_at`].
+    #[cfg(feature = "datetime")]
+    pub fn released_at(&self) -> DateTime<Tz> {
+        datetime::in_timezone(self.get_date(), self.get_timezone())
+    }
+
+    /// Get any release's release date formatted for a locale, see
+    /// [`Release::display_date`].
+    #[cfg(feature = "datetime")]
+    pub fn display_date(&self, locale: &str, timezone: Option<&str>) -> String {
+        datetime::format_date(
+            self.get_date(),
+            timezone.unwrap_or(self.get_timezone()),
+            locale,
+        )
+    }
+
+    /// Check whether any release has a tag, see [`Tags::has_tag`].
+    pub fn has_tag(&self, tag: &str) -> bool {
+        match self {
+            AnyRelease::Release(release) => release.has_tag(tag),
+            AnyRelease::Track(track) => track.has_tag(tag),
+        }
+    }
+
+    /// Check whether an artist, by name URI, has a role on any release.
+    ///
+    /// Releases that don't include their artists never match.
+    pub fn has_artist_role(&self, artist_name_uri: &str, role: &Role) -> bool {
+        let artists = match self {
+            AnyRelease::Release(release) => &release.artists,
+            AnyRelease::Track(track) => &track.artists,
+        };
+
+        artists.iter().flatten().any(|artist| {
+            artist.get_uri().eq_ignore_ascii_case(artist_name_uri)
+                && artist.get_role() == Some(role)
+        })
+    }
+
     /// Get any release's release identifier.
     pub fn get_release_id(&self) -> &ReleaseID {
         match self {
@@ -111,8 +280,29 @@ impl AnyRelease {
     }
 }
 
+/// Summary line, like "Souvenir by Whales feat. Dutch Melrose (MCS1186) - 2022-09-22",
+/// with the date in the release's time zone, or in UTC without the
+/// `datetime` feature.
+impl Display for AnyRelease {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        #[cfg(feature = "datetime")]
+        let date = self.released_at().format("%Y-%m-%d");
+        #[cfg(not(feature = "datetime"))]
+        let date = self.get_date().date();
+
+        write!(
+            f,
+            "{} by {} ({}) - {}",
+            self.get_title(),
+            self.get_artists(),
+            self.get_catalog_id(),
+            date
+        )
+    }
+}
+
 /// Most detailed release object returned by the MC API.
-#[derive(Clone, Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
 #[serde(rename_all = "PascalCase")]
 pub struct Release {
     pub album_notes: Option<String>,
@@ -141,7 +331,7 @@ pub struct Release {
     pub release_date_timezone: String,
     pub spotify_id: Option<String>,
     pub streamable: Option<bool>,
-    pub tags: Option<Vec<String>>,
+    pub tags: Option<Tags>,
     pub title: String,
     pub tracks: Option<Vec<Track>>,
     #[serde(alias = "Type")]
@@ -153,9 +343,299 @@ pub struct Release {
     pub youtube_url: Option<String>,
 }
 
-/// Summarized release details.
+impl Release {
+    /// Get the URL to share the release on a web player, like the client's,
+    /// see `Client::player_url`.
+    pub fn share_url(&self, player: &Url) -> Url {
+        share::player_url(player, &["release", &self.catalog_id])
+    }
+
+    /// Get the HTML of a widget to embed the release from a web player in a
+    /// web page.
+    pub fn embed_html(&self, player: &Url, options: &EmbedOptions) -> String {
+        share::embed_html(player, &["release", &self.catalog_id], options)
+    }
+
+    /// Check whether the release has a tag, see [`Tags::has_tag`].
+    pub fn has_tag(&self, tag: &str) -> bool {
+        self.tags.as_ref().map_or(false, |tags| tags.has_tag(tag))
+    }
+
+    /// Check whether the release's primary or secondary genre is some genre.
+    pub fn has_genre(&self, genre: &Genre) -> bool {
+        [&self.genre_primary, &self.genre_secondary]
+            .into_iter()
+            .flatten()
+            .any(|name| name.parse::<Genre>().map_or(false, |name| name == *genre))
+    }
+
+    /// Get the release's artists grouped by role, like primary and featured artists.
+    ///
+    /// Returns nothing if the release doesn't include its artists.
+    pub fn credits(&self) -> Vec<(Role, Vec<&AnyArtist>)> {
+        artist::group_by_role(self.artists.as_deref().unwrap_or_default())
+    }
+
+    /// Get the album notes as blocks to render, see [`notes::parse`].
+    ///
+    /// Returns nothing if the release has no notes.
+    pub fn notes(&self) -> Vec<Block> {
+        self.album_notes
+            .as_deref()
+            .map(notes::parse)
+            .unwrap_or_default()
+    }
+
+    /// Get the instant of release, in the release's own time zone.
+    ///
+    /// Unknown time zones are treated as UTC.
+    #[cfg(feature = "datetime")]
+    pub fn released_at(&self) -> DateTime<Tz> {
+        datetime::in_timezone(&self.release_date, &self.release_date_timezone)
+    }
+
+    /// Get the year of release, in the release's own time zone.
+    #[cfg(feature = "datetime")]
+    pub fn release_year(&self) -> i32 {
+        self.released_at().year()
+    }
+
+    /// Get the parsed copyright line of the release, see [`CopyrightLine::parse`].
+    ///
+    /// Parts missing from the line, or the whole line, fall back to the year
+    /// of release and the label the release is under. The year is in the
+    /// release's own time zone, or in UTC without the `datetime` feature.
+    pub fn copyright(&self) -> CopyrightLine {
+        #[cfg(feature = "datetime")]
+        let year = self.release_year();
+        #[cfg(not(feature = "datetime"))]
+        let year = self.release_date.year();
+
+        copyright_or(
+            self.copyright_p_line.as_deref(),
+            CopyrightLine {
+                year: Some(year),
+                owner: self.brand_title.clone(),
+                ..CopyrightLine::default()
+            },
+        )
+    }
+
+    /// Get the release date formatted for a locale, like "en-US", in a time
+    /// zone by IANA name, or else in the release's own time zone.
+    ///
+    /// See [`datetime::format_date`].
+    #[cfg(feature = "datetime")]
+    pub fn display_date(&self, locale: &str, timezone: Option<&str>) -> String {
+        datetime::format_date(
+            &self.release_date,
+            timezone.unwrap_or(&self.release_date_timezone),
+            locale,
+        )
+    }
+
+    /// Get when early access to the release ends, if it is still in early access.
+    ///
+    /// Early access ends when the release is released to everyone.
+    pub fn early_access_until(&self) -> Option<&Timestamp> {
+        let in_early_access = self.prerelease_date.is_some()
+            || self
+                .tracks
+                .iter()
+                .flatten()
+                .any(|track| track.in_early_access);
+
+        (in_early_access && self.release_date > Timestamp::now_utc()).then_some(&self.release_date)
+    }
+
+    /// Get the release's tracks that are versions of the given track, including itself.
+    ///
+    /// Versions share a title, like the original mix, the extended mix, and the VIP.
+    /// Returns nothing if the release doesn't include its tracks.
+    pub fn variants_of(&self, track: &Track) -> Vec<&Track> {
+        self.tracks
+            .iter()
+            .flatten()
+            .filter(|other| other.is_version_of(track))
+            .collect()
+    }
+
+    /// Get the original mix of the given track from the release's tracks, if present.
+    pub fn original_of(&self, track: &Track) -> Option<&Track> {
+        self.variants_of(track)
+            .into_iter()
+            .find(|other| other.is_original())
+    }
+
+    /// Check whether the release changed since some time, judging by its cache time.
+    ///
+    /// Releases without cache details are assumed to have changed.
+    pub fn updated_since(&self, since: &Timestamp) -> bool {
+        self.cache_details
+            .as_ref()
+            .map_or(true, |cache| cache.updated_since(since))
+    }
+}
+
+/// Release as shown in a list, like a grid of covers, with only what list
+/// views need.
+///
+/// It reads the same payloads as [`Release`], skipping its heavy fields, like
+/// tracks, artists, links, and cache details, without building them. Get
+/// pages of them with `EndpointRelease::get_all_slim`, which lists tracks
+/// found among releases as their release.
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct ReleaseListItem {
+    pub artists_title: String,
+    pub catalog_id: CatalogID,
+    pub cover_file_id: Option<String>,
+    pub genre_primary: Option<String>,
+    pub id: ReleaseID,
+    pub release_date: Timestamp,
+    pub release_date_timezone: String,
+    pub title: String,
+    #[serde(alias = "Type")]
+    pub kind: String,
+    pub version: String,
+}
+
+impl ReleaseListItem {
+    /// Get the instant of release, in the release's own time zone.
+    ///
+    /// Unknown time zones are treated as UTC.
+    #[cfg(feature = "datetime")]
+    pub fn released_at(&self) -> DateTime<Tz> {
+        datetime::in_timezone(&self.release_date, &self.release_date_timezone)
+    }
+}
+
+impl From<&Release> for ReleaseListItem {
+    fn from(release: &Release) -> Self {
+        ReleaseListItem {
+            artists_title: release.artists_title.clone(),
+            catalog_id: release.catalog_id.clone(),
+            cover_file_id: release.cover_file_id.clone(),
+            genre_primary: release.genre_primary.clone(),
+            id: release.id,
+            release_date: release.release_date,
+            release_date_timezone: release.release_date_timezone.clone(),
+            title: release.title.clone(),
+            kind: release.kind.clone(),
+            version: release.version.clone(),
+        }
+    }
+}
+
+/// Item of a list of releases, which may hold tracks, like [`AnyRelease`],
+/// read as a release list item.
+#[cfg(feature = "client")]
+#[derive(Clone, Debug, Deserialize)]
+#[serde(untagged)]
+pub(crate) enum AnyReleaseListItem {
+    Release(ReleaseListItem),
+    Track {
+        #[serde(rename = "Release")]
+        release: ReleaseListItem,
+        #[serde(rename = "GenrePrimary")]
+        genre_primary: Option<String>,
+    },
+}
+
+#[cfg(feature = "client")]
+impl From<AnyReleaseListItem> for ReleaseListItem {
+    fn from(item: AnyReleaseListItem) -> Self {
+        match item {
+            AnyReleaseListItem::Release(release) => release,
+            AnyReleaseListItem::Track {
+                release,
+                genre_primary,
+            } => ReleaseListItem {
+                genre_primary: release.genre_primary.or(genre_primary),
+                ..release
+            },
+        }
+    }
+}
+
+/// Simple type to capture the release and tracks response.
+///
+/// These sorts of simple wrappers are made to maintain the call patterns
+/// and to leave room for future expansion, such as additional fields or
+/// letter case changes.
+#[cfg(feature = "client")]
+#[derive(Clone, Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub(crate) struct ReleaseWithTracks {
+    pub release: Option<AnyRelease>,
+    pub tracks: Option<Vec<Track>>,
+}
+
+/// Release page data from the WWW API, with details the player API leaves out.
 #[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
 #[serde(rename_all = "PascalCase")]
+pub struct ReleasePage {
+    pub copyright_c_line: Option<String>,
+    pub copyright_p_line: Option<String>,
+    pub credits: Option<Vec<Credit>>,
+    pub merch: Option<Vec<MerchItem>>,
+    pub release: Release,
+    pub tracks: Option<Vec<Track>>,
+    pub writers: Option<Vec<String>>,
+}
+
+impl ReleasePage {
+    /// Get the parsed copyright line of the release page, or else of its
+    /// release, see [`Release::copyright`].
+    pub fn copyright(&self) -> CopyrightLine {
+        copyright_or(self.copyright_p_line.as_deref(), self.release.copyright())
+    }
+
+    /// Get the credits specific to a track of the release.
+    pub fn track_credits(&self, track_id: &TrackID) -> Vec<&Credit> {
+        self.credits
+            .iter()
+            .flatten()
+            .filter(|credit| credit.track_id.as_ref() == Some(track_id))
+            .collect()
+    }
+}
+
+/// Parse a copyright line, if there is one, with the parts it is missing
+/// taken from a fallback.
+fn copyright_or(line: Option<&str>, fallback: CopyrightLine) -> CopyrightLine {
+    match line.map(CopyrightLine::parse) {
+        Some(line) => CopyrightLine {
+            symbol: line.symbol,
+            year: line.year.or(fallback.year),
+            owner: line.owner.or(fallback.owner),
+        },
+        None => fallback,
+    }
+}
+
+/// Person credited on a release page, like a producer or mastering engineer.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct Credit {
+    pub name: String,
+    pub role: Role,
+    pub track_id: Option<TrackID>,
+}
+
+/// Merchandise related to a release, as listed on its release page.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct MerchItem {
+    pub image_url: Option<Url>,
+    pub price: Option<String>,
+    pub title: String,
+    pub url: Url,
+}
+
+/// Summarized release details.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "PascalCase")]
 pub struct ReleaseSummary {
     pub artists_title: String,
     pub catalog_id: String,
@@ -164,7 +644,7 @@ pub struct ReleaseSummary {
     pub id: ReleaseID,
     pub release_date: Timestamp,
     pub release_date_timezone: String,
-    pub tags: Option<Vec<String>>,
+    pub tags: Option<Tags>,
     pub title: String,
     #[serde(alias = "Type")]
     pub kind: String,
@@ -174,7 +654,7 @@ pub struct ReleaseSummary {
 }
 
 /// Detailed release track information.
-#[derive(Clone, Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
 #[serde(rename_all = "PascalCase")]
 pub struct Track {
     pub artists: Option<Vec<AnyArtist>>,
@@ -188,6 +668,24 @@ pub struct Track {
     pub downloadable: bool,
     pub duration: usize,
     pub explicit: bool,
+    /// Audio features, only present if the API provides them, see
+    /// [`Track::features`].
+    #[serde(alias = "acousticness", skip_serializing_if = "Option::is_none")]
+    pub acousticness: Option<f32>,
+    #[serde(alias = "danceability", skip_serializing_if = "Option::is_none")]
+    pub danceability: Option<f32>,
+    #[serde(alias = "energy", skip_serializing_if = "Option::is_none")]
+    pub energy: Option<f32>,
+    #[serde(alias = "instrumentalness", skip_serializing_if = "Option::is_none")]
+    pub instrumentalness: Option<f32>,
+    #[serde(alias = "liveness", skip_serializing_if = "Option::is_none")]
+    pub liveness: Option<f32>,
+    #[serde(alias = "loudness", skip_serializing_if = "Option::is_none")]
+    pub loudness: Option<f32>,
+    #[serde(alias = "speechiness", skip_serializing_if = "Option::is_none")]
+    pub speechiness: Option<f32>,
+    #[serde(alias = "valence", skip_serializing_if = "Option::is_none")]
+    pub valence: Option<f32>,
     pub genre_primary: String,
     pub genre_secondary: String,
     #[serde(alias = "ISRC")]
@@ -199,8 +697,215 @@ pub struct Track {
     pub playlist_sort: Option<u32>,
     pub release: ReleaseSummary,
     pub streamable: bool,
-    pub tags: Option<Vec<String>>,
+    pub tags: Option<Tags>,
     pub title: String,
     pub track_number: usize,
     pub version: String,
 }
+
+impl Track {
+    /// Get the audio features the track came with, like to match it against
+    /// moods.
+    pub fn features(&self) -> TrackFeatures {
+        TrackFeatures {
+            acousticness: self.acousticness,
+            danceability: self.danceability,
+            energy: self.energy,
+            instrumentalness: self.instrumentalness,
+            liveness: self.liveness,
+            loudness: self.loudness,
+            speechiness: self.speechiness,
+            valence: self.valence,
+        }
+    }
+
+    /// Get the URL to share the track on a web player, like the client's,
+    /// on its release's page, see `Client::player_url`.
+    pub fn share_url(&self, player: &Url) -> Url {
+        let mut url = share::player_url(player, &["release", &self.release.catalog_id]);
+        url.query_pairs_mut()
+            .append_pair("track", &self.id.to_string());
+        url
+    }
+
+    /// Get the HTML of a widget to embed the track from a web player in a web
+    /// page.
+    pub fn embed_html(&self, player: &Url, options: &EmbedOptions) -> String {
+        share::embed_html(player, &["track", &self.id.to_string()], options)
+    }
+
+    /// Check whether the track has a tag, see [`Tags::has_tag`].
+    pub fn has_tag(&self, tag: &str) -> bool {
+        self.tags.as_ref().map_or(false, |tags| tags.has_tag(tag))
+    }
+
+    /// Check whether the track's primary or secondary genre is some genre.
+    pub fn has_genre(&self, genre: &Genre) -> bool {
+        [&self.genre_primary, &self.genre_secondary]
+            .into_iter()
+            .any(|name| name.parse::<Genre>().map_or(false, |name| name == *genre))
+    }
+
+    /// Get the track's artists grouped by role, like primary artists and remixers.
+    ///
+    /// Returns nothing if the track doesn't include its artists.
+    pub fn credits(&self) -> Vec<(Role, Vec<&AnyArtist>)> {
+        artist::group_by_role(self.artists.as_deref().unwrap_or_default())
+    }
+
+    /// Get when early access to the track ends, if it is still in early access.
+    pub fn early_access_until(&self) -> Option<&Timestamp> {
+        (self.in_early_access && self.release.release_date > Timestamp::now_utc())
+            .then_some(&self.release.release_date)
+    }
+
+    /// Check whether the track is the original mix, rather than a remix, VIP, etc.
+    pub fn is_original(&self) -> bool {
+        matches!(
+            normalize(&self.version).as_str(),
+            "" | "original" | "original mix"
+        )
+    }
+
+    /// Check whether the track is a remix, judging by its version and tags.
+    pub fn is_remix(&self) -> bool {
+        normalize(&self.version)
+            .split(' ')
+            .any(|word| word == tag::REMIX)
+            || self.has_tag(tag::REMIX)
+    }
+
+    /// Check whether two tracks are versions of the same song, by title.
+    pub fn is_version_of(&self, other: &Track) -> bool {
+        normalize(&self.title) == normalize(&other.title)
+    }
+
+    /// Check whether the track is an instrumental, judging by its version and tags.
+    pub fn is_instrumental(&self) -> bool {
+        self.version_has(tag::INSTRUMENTAL) || self.has_tag(tag::INSTRUMENTAL)
+    }
+
+    /// Check whether the track is a clean edit, judging by its version and tags.
+    pub fn is_clean(&self) -> bool {
+        self.version_has(tag::CLEAN) || self.has_tag(tag::CLEAN)
+    }
+
+    /// Check whether the track can be played on stream without worry, that
+    /// is, it is creator friendly and not explicit.
+    pub fn is_stream_safe(&self) -> bool {
+        self.creator_friendly && !self.explicit
+    }
+
+    /// Check whether the track's version has some word, like "instrumental".
+    fn version_has(&self, word: &str) -> bool {
+        normalize(&self.version)
+            .split(' ')
+            .any(|found| found == word)
+    }
+}
+
+/// A track along with other versions of it found in the catalog, like its
+/// instrumental or clean edit.
+#[derive(Clone, Debug, PartialEq)]
+pub struct VersionSet {
+    /// The track the versions were looked up for.
+    pub track: Track,
+    /// Other versions of the track, without duplicates.
+    pub versions: Vec<Track>,
+}
+
+impl VersionSet {
+    /// Create a version set from a track and candidates, keeping the
+    /// candidates that are other versions of the track.
+    pub fn from_candidates(track: Track, candidates: impl IntoIterator<Item = Track>) -> Self {
+        let mut versions: Vec<Track> = Vec::new();
+        for candidate in candidates {
+            if candidate.id != track.id
+                && candidate.is_version_of(&track)
+                && !versions.iter().any(|version| version.id == candidate.id)
+            {
+                versions.push(candidate);
+            }
+        }
+
+        VersionSet { track, versions }
+    }
+
+    /// Get the instrumental versions.
+    pub fn instrumentals(&self) -> impl Iterator<Item = &Track> {
+        self.versions
+            .iter()
+            .filter(|version| version.is_instrumental())
+    }
+
+    /// Get the clean versions.
+    pub fn cleans(&self) -> impl Iterator<Item = &Track> {
+        self.versions.iter().filter(|version| version.is_clean())
+    }
+
+    /// Get the creator friendly versions.
+    pub fn creator_friendly(&self) -> impl Iterator<Item = &Track> {
+        self.versions
+            .iter()
+            .filter(|version| version.creator_friendly)
+    }
+
+    /// Get the version to play on stream in place of the track, which is the
+    /// track itself if it is safe already.
+    ///
+    /// Clean versions are preferred over instrumentals, as they are closer to
+    /// the track. Other versions, like remixes, are never picked.
+    pub fn stream_safe(&self) -> Option<&Track> {
+        if self.track.is_stream_safe() {
+            return Some(&self.track);
+        }
+
+        self.cleans()
+            .find(|version| version.is_stream_safe())
+            .or_else(|| {
+                self.instrumentals()
+                    .find(|version| version.is_stream_safe())
+            })
+    }
+}
+
+/// Audio features of a track, the same values moods filter on.
+///
+/// Values are only present if the API provides them.
+#[derive(Clone, Debug, Default, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct TrackFeatures {
+    #[serde(alias = "acousticness", skip_serializing_if = "Option::is_none")]
+    pub acousticness: Option<f32>,
+    #[serde(alias = "danceability", skip_serializing_if = "Option::is_none")]
+    pub danceability: Option<f32>,
+    #[serde(alias = "energy", skip_serializing_if = "Option::is_none")]
+    pub energy: Option<f32>,
+    #[serde(alias = "instrumentalness", skip_serializing_if = "Option::is_none")]
+    pub instrumentalness: Option<f32>,
+    #[serde(alias = "liveness", skip_serializing_if = "Option::is_none")]
+    pub liveness: Option<f32>,
+    #[serde(alias = "loudness", skip_serializing_if = "Option::is_none")]
+    pub loudness: Option<f32>,
+    #[serde(alias = "speechiness", skip_serializing_if = "Option::is_none")]
+    pub speechiness: Option<f32>,
+    #[serde(alias = "valence", skip_serializing_if = "Option::is_none")]
+    pub valence: Option<f32>,
+}
+
+impl TrackFeatures {
+    /// Get the value of a feature by mood parameter.
+    pub fn get(&self, param: &MoodParam) -> Option<f32> {
+        match param {
+            MoodParam::Acousticness => self.acousticness,
+            MoodParam::Danceability => self.danceability,
+            MoodParam::Energy => self.energy,
+            MoodParam::Instrumentalness => self.instrumentalness,
+            MoodParam::Liveness => self.liveness,
+            MoodParam::Loudness => self.loudness,
+            MoodParam::Speechiness => self.speechiness,
+            MoodParam::Valence => self.valence,
+            MoodParam::Other(_) => None,
+        }
+    }
+}
@@ -0,0 +1,95 @@
+use serde::{Deserialize, Serialize};
+use url::Url;
+
+/// Base URL of the Monstercat web player, which share and embed links point
+/// to by default. Clients may use another, see `Client::player_url`.
+pub const PLAYER_URL: &str = "https://player.monstercat.app";
+
+/// Get the base URL of the Monstercat web player, see [`PLAYER_URL`].
+pub fn default_player_url() -> Url {
+    Url::parse(PLAYER_URL).expect("Player URL is valid.")
+}
+
+/// Variants of embed and streaming widget themes.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum EmbedTheme {
+    #[default]
+    Dark,
+    Light,
+}
+
+impl std::fmt::Display for EmbedTheme {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            EmbedTheme::Dark => write!(f, "dark"),
+            EmbedTheme::Light => write!(f, "light"),
+        }
+    }
+}
+
+/// Options of an embed widget.
+#[derive(Clone, Debug, PartialEq)]
+pub struct EmbedOptions {
+    pub theme: EmbedTheme,
+    pub autoplay: bool,
+    pub width: u32,
+    pub height: u32,
+}
+
+impl Default for EmbedOptions {
+    fn default() -> Self {
+        EmbedOptions {
+            theme: EmbedTheme::default(),
+            autoplay: false,
+            width: 560,
+            height: 400,
+        }
+    }
+}
+
+impl EmbedOptions {
+    /// Set the widget theme.
+    pub fn set_theme(mut self, theme: EmbedTheme) -> Self {
+        self.theme = theme;
+        self
+    }
+
+    /// Set whether the widget starts playing on load.
+    pub fn set_autoplay(mut self, autoplay: bool) -> Self {
+        self.autoplay = autoplay;
+        self
+    }
+
+    /// Set the widget size, in pixels.
+    pub fn set_size(mut self, width: u32, height: u32) -> Self {
+        self.width = width;
+        self.height = height;
+        self
+    }
+}
+
+/// Build a web player URL from its base URL and path segments, escaping
+/// each of them.
+pub(crate) fn player_url(player: &Url, segments: &[&str]) -> Url {
+    let mut url = player.clone();
+    if let Ok(mut path) = url.path_segments_mut() {
+        path.pop_if_empty().extend(segments);
+    }
+    url
+}
+
+/// Build the HTML of an embed widget showing the web player page at the given path.
+pub(crate) fn embed_html(player: &Url, segments: &[&str], options: &EmbedOptions) -> String {
+    let mut url = player_url(player, &[&["embed"], segments].concat());
+    url.query_pairs_mut()
+        .append_pair("theme", &options.theme.to_string())
+        .append_pair("autoplay", if options.autoplay { "1" } else { "0" });
+
+    format!(
+        "<iframe src=\"{}\" width=\"{}\" height=\"{}\" frameborder=\"0\" allow=\"autoplay; encrypted-media\"></iframe>",
+        url.as_str().replace('&', "&amp;").replace('"', "&quot;"),
+        options.width,
+        options.height,
+    )
+}
@@ -0,0 +1,74 @@
+use serde::{Deserialize, Serialize};
+use std::fmt::Display;
+use std::ops::Deref;
+
+/// Tag of instrumental versions.
+pub const INSTRUMENTAL: &str = "instrumental";
+/// Tag of remixes.
+pub const REMIX: &str = "remix";
+/// Tag of VIP versions.
+pub const VIP: &str = "vip";
+/// Tag of acoustic versions.
+pub const ACOUSTIC: &str = "acoustic";
+/// Tag of extended mixes.
+pub const EXTENDED: &str = "extended";
+/// Tag of clean versions.
+pub const CLEAN: &str = "clean";
+/// Tag of live recordings.
+pub const LIVE: &str = "live";
+
+/// NewType for tags found on artists, releases, and tracks.
+///
+/// Tags are kept as the API returns them, but compared normalized, so
+/// "Instrumental" and " instrumental" are the same tag.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(transparent)]
+pub struct Tags(pub Vec<String>);
+
+impl Deref for Tags {
+    type Target = Vec<String>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl Display for Tags {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0.join(","))
+    }
+}
+
+impl From<Vec<String>> for Tags {
+    fn from(tags: Vec<String>) -> Self {
+        Tags(tags)
+    }
+}
+
+impl Tags {
+    /// Normalize a tag: trimmed, lowercase, and words joined with hyphens.
+    pub fn normalize(tag: &str) -> String {
+        tag.split(|c: char| c.is_whitespace() || c == '_' || c == '-')
+            .filter(|word| !word.is_empty())
+            .collect::<Vec<_>>()
+            .join("-")
+            .to_lowercase()
+    }
+
+    /// Check whether a tag is present, comparing normalized tags.
+    pub fn has_tag(&self, tag: &str) -> bool {
+        let tag = Tags::normalize(tag);
+        self.0.iter().any(|other| Tags::normalize(other) == tag)
+    }
+
+    /// Get the tags normalized and without duplicates, in their original order.
+    pub fn normalized(&self) -> Tags {
+        let mut tags: Vec<String> = Vec::new();
+        for tag in self.0.iter().map(|tag| Tags::normalize(tag)) {
+            if !tag.is_empty() && !tags.contains(&tag) {
+                tags.push(tag);
+            }
+        }
+        Tags(tags)
+    }
+}
@@ -0,0 +1,42 @@
+use crate::mc::playlist::PlaylistID;
+use crate::mc::release::CatalogID;
+use crate::mc::share;
+use url::Url;
+
+/// Get the canonical URL of a release, on the Monstercat web player, like
+/// [`Release::share_url`](crate::mc::release::Release::share_url).
+///
+/// Example
+/// ```rust
+/// use bombay::mc::release::CatalogID;
+/// use bombay::mc::urls;
+///
+/// let url = urls::release_url(&CatalogID("MCS1186".to_owned()));
+/// assert_eq!(url.as_str(), "https://player.monstercat.app/release/MCS1186");
+/// ```
+pub fn release_url(catalog_id: &CatalogID) -> Url {
+    share::player_url(&share::default_player_url(), &["release", catalog_id])
+}
+
+/// Get the canonical URL of an artist, on the Monstercat web player, from
+/// their URI like "whales".
+///
+/// Example
+/// ```rust
+/// use bombay::mc::urls;
+///
+/// let url = urls::artist_url("whales");
+/// assert_eq!(url.as_str(), "https://player.monstercat.app/artist/whales");
+/// ```
+pub fn artist_url(uri: &str) -> Url {
+    share::player_url(&share::default_player_url(), &["artist", uri])
+}
+
+/// Get the canonical URL of a playlist, on the Monstercat web player, like
+/// [`Playlist::share_url`](crate::mc::playlist::Playlist::share_url).
+pub fn playlist_url(playlist_id: &PlaylistID) -> Url {
+    share::player_url(
+        &share::default_player_url(),
+        &["playlist", &playlist_id.to_string()],
+    )
+}
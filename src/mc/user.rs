@@ -1,13 +1,18 @@
-use crate::mc::util::Codec;
+use crate::compat;
+use crate::mc::connect::{ConnectService, Connection};
+use crate::mc::playlist::{Playlist, PlaylistID, PlaylistItem};
+use crate::mc::share::EmbedTheme;
+use crate::mc::util::{Codec, License};
 use iso8601_timestamp::Timestamp;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
-use serde_with::skip_serializing_none;
-use std::{fmt::Display, ops::Deref};
+use serde_with::{skip_serializing_none, DeserializeFromStr, SerializeDisplay};
+use std::{fmt::Display, ops::Deref, str::FromStr};
+use url::Url;
 use uuid::Uuid;
 
 /// NewType for user identifier, wraps a UUID and adds type safety.
-#[derive(Clone, Copy, Debug, PartialEq, Deserialize, Serialize)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Deserialize, Serialize)]
 pub struct UserID(pub Uuid);
 
 impl Deref for UserID {
@@ -123,19 +128,95 @@ pub struct EditableSettings {
     pub auto_say_song: Option<bool>,
 }
 
+impl EditableSettings {
+    /// Check that enabled settings have what they depend on: auto_say_song
+    /// requires say_song, and both require a connected Twitch account.
+    ///
+    /// The API would reject or silently ignore such settings. Settings that
+    /// are `None` are left as they are on the server, so they are unknown
+    /// rather than disabled: only auto_say_song with say_song explicitly
+    /// disabled conflicts.
+    pub fn validate(&self, connections: &[Connection]) -> Result<(), SettingsConflict> {
+        let auto_say_song = self.auto_say_song == Some(true);
+        let say_song = self.say_song == Some(true);
+
+        if auto_say_song && self.say_song == Some(false) {
+            Err(SettingsConflict::AutoSaySongWithoutSaySong)
+        } else if (say_song || auto_say_song)
+            && !connections
+                .iter()
+                .any(|connection| connection.service == ConnectService::Twitch)
+        {
+            Err(SettingsConflict::TwitchNotConnected)
+        } else {
+            Ok(())
+        }
+    }
+}
+
+/// Variants of settings that can't be set together, see
+/// [`EditableSettings::validate`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum SettingsConflict {
+    /// auto_say_song is enabled, but say_song is not.
+    AutoSaySongWithoutSaySong,
+    /// say_song or auto_say_song is enabled, without a connected Twitch account.
+    TwitchNotConnected,
+}
+
+impl Display for SettingsConflict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SettingsConflict::AutoSaySongWithoutSaySong => {
+                write!(f, "Saying songs automatically requires saying songs")
+            }
+            SettingsConflict::TwitchNotConnected => {
+                write!(f, "Saying songs requires a connected Twitch account")
+            }
+        }
+    }
+}
+
+impl std::error::Error for SettingsConflict {}
+
+impl From<&Settings> for EditableSettings {
+    fn from(settings: &Settings) -> Self {
+        EditableSettings {
+            playlist_public_default: Some(settings.playlist_public_default),
+            preferred_format: settings.preferred_format.parse().ok(),
+            say_song: settings.say_song,
+            auto_say_song: settings.auto_say_song,
+        }
+    }
+}
+
 /// Simple type to capture the streaming width (player code) response.
 ///
 /// These sorts of simple wrappers are made to maintain the call patterns
 /// and to leave room for future expansion, such as additional fields or
 /// letter case changes.
+#[cfg(feature = "client")]
 #[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
 #[serde(rename_all = "PascalCase")]
 pub(crate) struct PlayerCode {
     pub player_code: String,
 }
 
+/// Streaming widget configuration, used with the player code.
+#[skip_serializing_none]
+#[derive(Clone, Debug, Default, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct PlayerWidget {
+    pub theme: Option<EmbedTheme>,
+    /// Playlist shown in the widget, if any.
+    pub playlist_id: Option<PlaylistID>,
+    pub show_cover_art: Option<bool>,
+    pub show_queue: Option<bool>,
+}
+
 /// NewType for shop code identifier, wraps a UUID and adds type safety.
-#[derive(Clone, Copy, Debug, PartialEq, Deserialize, Serialize)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Deserialize, Serialize)]
 pub struct ShopCodeID(pub Uuid);
 
 impl Deref for ShopCodeID {
@@ -167,11 +248,25 @@ pub struct ShopCode {
     pub value_type: String,
 }
 
+/// Simple type to capture the user information and settings response.
+///
+/// These sorts of simple wrappers are made to maintain the call patterns
+/// and to leave room for future expansion, such as additional fields or
+/// letter case changes.
+#[cfg(feature = "client")]
+#[derive(Clone, Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub(crate) struct UserInfo {
+    pub settings: Option<Settings>,
+    pub user: Option<User>,
+}
+
 /// Simple type to capture the new email request.
 ///
 /// These sorts of simple wrappers are made to maintain the call patterns
 /// and to leave room for future expansion, such as additional fields or
 /// letter case changes.
+#[cfg(feature = "client")]
 #[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
 #[serde(rename_all = "PascalCase")]
 pub(crate) struct NewEmail {
@@ -183,6 +278,7 @@ pub(crate) struct NewEmail {
 /// These sorts of simple wrappers are made to maintain the call patterns
 /// and to leave room for future expansion, such as additional fields or
 /// letter case changes.
+#[cfg(feature = "client")]
 #[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
 #[serde(rename_all = "PascalCase")]
 pub(crate) struct NewPassword {
@@ -190,13 +286,165 @@ pub(crate) struct NewPassword {
     pub new_password: String,
 }
 
-/// Variants of platforms.
+/// Simple type to capture the 2FA confirmation token request.
+///
+/// These sorts of simple wrappers are made to maintain the call patterns
+/// and to leave room for future expansion, such as additional fields or
+/// letter case changes.
+#[cfg(feature = "client")]
 #[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
-#[serde(rename_all = "camelCase")]
+#[serde(rename_all = "PascalCase")]
+pub(crate) struct TwoFactorToken {
+    pub token: String,
+}
+
+/// Simple type to capture the account deletion request.
+///
+/// These sorts of simple wrappers are made to maintain the call patterns
+/// and to leave room for future expansion, such as additional fields or
+/// letter case changes.
+#[cfg(feature = "client")]
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "PascalCase")]
+pub(crate) struct AccountDeletion {
+    pub password: String,
+}
+
+/// Status of a request to export the user's account data.
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct DataExport {
+    pub status: DataExportStatus,
+    pub requested_at: Option<Timestamp>,
+    /// Link to download the export, once ready.
+    #[serde(alias = "DownloadURL")]
+    pub download_url: Option<Url>,
+    pub expires_at: Option<Timestamp>,
+}
+
+/// Variants of data export statuses.
+///
+/// Unknown statuses are kept as [`DataExportStatus::Other`].
+#[derive(Clone, Debug, PartialEq, DeserializeFromStr, SerializeDisplay)]
+#[non_exhaustive]
+pub enum DataExportStatus {
+    Pending,
+    Processing,
+    Ready,
+    Expired,
+    Failed,
+    Other(String),
+}
+
+impl Display for DataExportStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                DataExportStatus::Pending => "pending",
+                DataExportStatus::Processing => "processing",
+                DataExportStatus::Ready => "ready",
+                DataExportStatus::Expired => "expired",
+                DataExportStatus::Failed => "failed",
+                DataExportStatus::Other(unk) => unk,
+            }
+        )
+    }
+}
+
+impl FromStr for DataExportStatus {
+    type Err = serde_json::Error;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s.trim().to_lowercase().as_str() {
+            "pending" => DataExportStatus::Pending,
+            "processing" => DataExportStatus::Processing,
+            "ready" | "complete" | "completed" => DataExportStatus::Ready,
+            "expired" => DataExportStatus::Expired,
+            "failed" => DataExportStatus::Failed,
+            _ => compat::unknown_variant(
+                "DataExportStatus",
+                s,
+                DataExportStatus::Other(s.trim().to_owned()),
+            )?,
+        })
+    }
+}
+
+/// 2FA backup codes, each usable once in place of a TOTP code or email confirmation.
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct BackupCodes {
+    pub codes: Vec<String>,
+}
+
+/// Variants of email notification interests.
+///
+/// Unknown interests are kept as [`NotificationInterests::Other`].
+#[derive(Clone, Debug, PartialEq, DeserializeFromStr, SerializeDisplay)]
+#[non_exhaustive]
 pub enum NotificationInterests {
     News,
     Events,
     Merch,
     GoldPerks,
     Relics,
+    Other(String),
+}
+
+impl Display for NotificationInterests {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                NotificationInterests::News => "news",
+                NotificationInterests::Events => "events",
+                NotificationInterests::Merch => "merch",
+                NotificationInterests::GoldPerks => "goldPerks",
+                NotificationInterests::Relics => "relics",
+                NotificationInterests::Other(unk) => unk,
+            }
+        )
+    }
+}
+
+impl FromStr for NotificationInterests {
+    type Err = serde_json::Error;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s.trim() {
+            "news" => NotificationInterests::News,
+            "events" => NotificationInterests::Events,
+            "merch" => NotificationInterests::Merch,
+            "goldPerks" => NotificationInterests::GoldPerks,
+            "relics" => NotificationInterests::Relics,
+            unk => compat::unknown_variant(
+                "NotificationInterests",
+                unk,
+                NotificationInterests::Other(unk.to_owned()),
+            )?,
+        })
+    }
+}
+
+/// Backup of a user's data, to keep as JSON and restore playlists from.
+///
+/// The library is exported as the playlist marked `my_library`.
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct UserExport {
+    pub exported_at: Timestamp,
+    pub user: User,
+    pub settings: Settings,
+    pub playlists: Vec<PlaylistExport>,
+    pub licenses: Vec<License>,
+}
+
+/// Playlist in a user data backup, along with its tracks.
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct PlaylistExport {
+    pub playlist: Playlist,
+    /// Tracks of the playlist, in order.
+    pub items: Vec<PlaylistItem>,
 }
@@ -1,3 +1,4 @@
+use crate::compat;
 use crate::mc::user::UserID;
 use iso8601_timestamp::Timestamp;
 use serde::{Deserialize, Serialize};
@@ -10,7 +11,7 @@ use uuid::Uuid;
 
 /// Values related to some sort of cache.
 /// These are found (flattened) in various objects.
-#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
 #[serde(rename_all = "PascalCase")]
 pub struct CacheDetails {
     pub cache_time: Timestamp,
@@ -18,8 +19,18 @@ pub struct CacheDetails {
     pub cache_status_detail: String,
 }
 
+impl CacheDetails {
+    /// Check whether the cached object was refreshed after some time.
+    pub fn updated_since(&self, since: &Timestamp) -> bool {
+        self.cache_time > *since
+    }
+}
+
 /// Supported audio codecs for downloading songs.
+///
+/// Unknown codecs fail to parse, since there's no telling which one was meant.
 #[derive(Clone, Debug, Default, PartialEq, DeserializeFromStr, SerializeDisplay)]
+#[non_exhaustive]
 pub enum Codec {
     #[default]
     MP3,
@@ -41,22 +52,33 @@ impl Display for Codec {
     }
 }
 
+impl Codec {
+    /// Get the file extension used for this codec.
+    pub fn extension(&self) -> &str {
+        match self {
+            Codec::MP3 => "mp3",
+            Codec::FLAC => "flac",
+            Codec::WAV => "wav",
+        }
+    }
+}
+
 impl FromStr for Codec {
     type Err = serde_json::Error;
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let mut norm = s.to_lowercase();
         norm.retain(|c| !c.is_whitespace());
         Ok(match norm.as_str() {
-            "mp3_320" => Codec::MP3,
+            "mp3" | "mp3_320" => Codec::MP3,
             "flac" => Codec::FLAC,
             "wav" => Codec::WAV,
-            _ => Codec::MP3,
+            _ => return Err(compat::unknown_variant_error("Codec", s)),
         })
     }
 }
 
 /// Represents a link to a particular platform resource.
-#[derive(Clone, Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
 #[serde(rename_all = "PascalCase")]
 pub struct Link {
     /// Name of the platform.
@@ -66,8 +88,11 @@ pub struct Link {
 }
 
 /// Variants of platforms.
-#[derive(Clone, Debug, PartialEq, DeserializeFromStr, SerializeDisplay)]
+///
+/// Unknown platforms are kept as [`Platform::Other`].
+#[derive(Clone, Debug, PartialEq, Eq, DeserializeFromStr, SerializeDisplay)]
 #[serde_with()]
+#[non_exhaustive]
 pub enum Platform {
     Amazon,
     AppleMusic,
@@ -145,13 +170,74 @@ impl FromStr for Platform {
             "twitter" => Platform::Twitter,
             "website" => Platform::Website,
             "youtube" => Platform::YouTube,
-            _ => Platform::Other(norm),
+            _ => compat::unknown_variant("Platform", s, Platform::Other(norm))?,
+        })
+    }
+}
+
+/// Variants of regions, by ISO 3166-1 alpha-2 country code.
+///
+/// Unknown regions are kept as [`Region::Other`].
+#[derive(Clone, Debug, PartialEq, Eq, Hash, DeserializeFromStr, SerializeDisplay)]
+#[non_exhaustive]
+pub enum Region {
+    Australia,
+    Brazil,
+    Canada,
+    France,
+    Germany,
+    Japan,
+    Mexico,
+    Netherlands,
+    UnitedKingdom,
+    UnitedStates,
+    Other(String),
+}
+
+impl Display for Region {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Region::Australia => "AU",
+                Region::Brazil => "BR",
+                Region::Canada => "CA",
+                Region::France => "FR",
+                Region::Germany => "DE",
+                Region::Japan => "JP",
+                Region::Mexico => "MX",
+                Region::Netherlands => "NL",
+                Region::UnitedKingdom => "GB",
+                Region::UnitedStates => "US",
+                Region::Other(unk) => unk,
+            }
+        )
+    }
+}
+
+impl FromStr for Region {
+    type Err = serde_json::Error;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let norm = s.trim().to_uppercase();
+        Ok(match norm.as_str() {
+            "AU" => Region::Australia,
+            "BR" => Region::Brazil,
+            "CA" => Region::Canada,
+            "FR" => Region::France,
+            "DE" => Region::Germany,
+            "JP" => Region::Japan,
+            "MX" => Region::Mexico,
+            "NL" => Region::Netherlands,
+            "GB" | "UK" => Region::UnitedKingdom,
+            "US" => Region::UnitedStates,
+            _ => compat::unknown_variant("Region", s, Region::Other(norm))?,
         })
     }
 }
 
 /// NewType for license identifier, wraps a UUID and adds type safety.
-#[derive(Clone, Copy, Debug, PartialEq, Deserialize, Serialize)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Deserialize, Serialize)]
 pub struct LicenseID(pub Uuid);
 
 impl Deref for LicenseID {
@@ -210,6 +296,13 @@ pub struct License {
     youtube_views: usize,
 }
 
+impl License {
+    /// Get the license's identifier.
+    pub fn get_id(&self) -> &LicenseID {
+        &self.id
+    }
+}
+
 /// Times during which license is active.
 #[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
 #[serde(rename_all = "PascalCase")]
@@ -223,6 +316,43 @@ pub struct LicenseActiveTimes {
     start: Timestamp,
 }
 
+/// Range of time, from its start up to its end.
+#[derive(Clone, Copy, Debug, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct DateRange {
+    pub start: Timestamp,
+    pub end: Timestamp,
+}
+
+/// Usage statistics of a license over time, like views of the licensed channel.
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct LicenseStats {
+    pub license_id: LicenseID,
+    pub points: Vec<LicenseStatsPoint>,
+}
+
+impl LicenseStats {
+    /// Get the views gained over the whole range, from the first to the last point.
+    pub fn views_gained(&self) -> Option<u64> {
+        let first = self.points.iter().find_map(|point| point.views)?;
+        let last = self.points.iter().rev().find_map(|point| point.views)?;
+        Some(last.saturating_sub(first))
+    }
+}
+
+/// Statistics of a license at some point in time.
+///
+/// Values are only present if the API provides them for the license's platform.
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct LicenseStatsPoint {
+    pub date: Timestamp,
+    pub claims: Option<u64>,
+    pub subscribers: Option<u64>,
+    pub views: Option<u64>,
+}
+
 /// Simple wrapper for call to remove copyright claim on a video.
 #[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
 #[serde(rename_all = "PascalCase")]
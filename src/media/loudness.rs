@@ -0,0 +1,364 @@
+use crate::mc::util::Codec;
+use crate::media::{self, Transcoder};
+use std::f64::consts::PI;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+/// Loudness ReplayGain 2.0 normalizes tracks to, in LUFS.
+pub const REFERENCE_LOUDNESS: f64 = -18.0;
+
+/// Length of the blocks loudness is gated by, in seconds.
+const BLOCK_SECONDS: f64 = 0.4;
+
+/// Blocks overlap by 75%, so a new one starts every quarter block.
+const BLOCK_STEPS: usize = 4;
+
+/// Blocks quieter than this are silence, in LUFS.
+const ABSOLUTE_GATE: f64 = -70.0;
+
+/// Blocks this much quieter than the ungated loudness are left out, in LU.
+const RELATIVE_GATE: f64 = -10.0;
+
+/// Loudness of audio, measured as in ITU-R BS.1770 and EBU R128.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Loudness {
+    /// Integrated loudness, in LUFS, or `None` for silence and audio shorter
+    /// than a block (400 ms).
+    pub integrated: Option<f64>,
+    /// Highest absolute sample value, where 1.0 is full scale.
+    pub sample_peak: f64,
+}
+
+impl Loudness {
+    /// Measure the loudness of interleaved samples, in the range -1.0 to 1.0.
+    ///
+    /// Channels are weighted as in BS.1770: the LFE channel of 5.1 audio is
+    /// left out and its surround channels weigh more.
+    ///
+    /// Example
+    /// ```rust
+    /// use bombay::media::loudness::Loudness;
+    ///
+    /// // A stereo 1 kHz sine at -23 dBFS measures -23 LUFS.
+    /// let amplitude = 10f32.powf(-23.0 / 20.0);
+    /// let samples: Vec<f32> = (0..48000 * 5)
+    ///     .flat_map(|i| {
+    ///         let sample = amplitude * (2.0 * std::f32::consts::PI * 1000.0 * i as f32 / 48000.0).sin();
+    ///         [sample, sample]
+    ///     })
+    ///     .collect();
+    ///
+    /// let loudness = Loudness::measure(&samples, 2, 48000);
+    /// assert!((loudness.integrated.unwrap() + 23.0).abs() < 0.1);
+    /// ```
+    pub fn measure(samples: &[f32], channels: usize, sample_rate: u32) -> Self {
+        let sample_peak = samples
+            .iter()
+            .fold(0.0_f64, |peak, sample| peak.max(f64::from(sample.abs())));
+        if channels == 0 || sample_rate == 0 {
+            return Loudness {
+                integrated: None,
+                sample_peak,
+            };
+        }
+
+        // Energy of the K-weighted signal per channel, per quarter block.
+        let step =
+            ((f64::from(sample_rate) * BLOCK_SECONDS / BLOCK_STEPS as f64).round() as usize).max(1);
+        let mut steps: Vec<Vec<f64>> = Vec::new();
+        let mut filters: Vec<KWeighting> = (0..channels)
+            .map(|_| KWeighting::new(f64::from(sample_rate)))
+            .collect();
+        for (index, frame) in samples.chunks_exact(channels).enumerate() {
+            if index % step == 0 {
+                steps.push(vec![0.0; channels]);
+            }
+            let energy = steps.last_mut().expect("A step was just added.");
+            for (channel, sample) in frame.iter().enumerate() {
+                let weighted = filters[channel].process(f64::from(*sample));
+                energy[channel] += weighted * weighted;
+            }
+        }
+
+        // Weighted mean square of each full block.
+        let block_length = (step * BLOCK_STEPS) as f64;
+        let full_steps = samples.len() / channels / step;
+        let blocks: Vec<f64> = (BLOCK_STEPS..=full_steps)
+            .map(|end| {
+                (0..channels)
+                    .map(|channel| {
+                        let energy: f64 = steps[end - BLOCK_STEPS..end]
+                            .iter()
+                            .map(|step| step[channel])
+                            .sum();
+                        channel_weight(channel, channels) * energy / block_length
+                    })
+                    .sum()
+            })
+            .collect();
+
+        let gated = |threshold: f64| -> Option<f64> {
+            let kept: Vec<f64> = blocks
+                .iter()
+                .copied()
+                .filter(|block| loudness(*block) > threshold)
+                .collect();
+            match kept.is_empty() {
+                true => None,
+                false => Some(kept.iter().sum::<f64>() / kept.len() as f64),
+            }
+        };
+
+        let integrated = gated(ABSOLUTE_GATE)
+            .map(|ungated| loudness(ungated) + RELATIVE_GATE)
+            .and_then(|relative_gate| gated(relative_gate.max(ABSOLUTE_GATE)))
+            .map(loudness);
+
+        Loudness {
+            integrated,
+            sample_peak,
+        }
+    }
+
+    /// Get the ReplayGain 2.0 values of the loudness, or `None` for silence.
+    pub fn replay_gain(&self) -> Option<ReplayGain> {
+        self.integrated.map(|integrated| ReplayGain {
+            track_gain: REFERENCE_LOUDNESS - integrated,
+            track_peak: self.sample_peak,
+        })
+    }
+}
+
+/// ReplayGain 2.0 values of a track, for players to play tracks at a
+/// consistent volume.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ReplayGain {
+    /// Gain to apply to reach the reference loudness, in dB.
+    pub track_gain: f64,
+    /// Highest absolute sample value, where 1.0 is full scale.
+    pub track_peak: f64,
+}
+
+impl ReplayGain {
+    /// Get the values as standard tags, as written to Vorbis comments, APE,
+    /// and ID3 user text frames.
+    pub fn tags(&self) -> Vec<(&'static str, String)> {
+        vec![
+            (
+                "REPLAYGAIN_TRACK_GAIN",
+                format!("{:.2} dB", self.track_gain),
+            ),
+            ("REPLAYGAIN_TRACK_PEAK", format!("{:.6}", self.track_peak)),
+            (
+                "REPLAYGAIN_REFERENCE_LOUDNESS",
+                format!("{:.2} LUFS", REFERENCE_LOUDNESS),
+            ),
+        ]
+    }
+}
+
+/// Measure the loudness of an audio file.
+///
+/// WAV files are read directly. Other codecs are first converted to WAV with
+/// the transcoder, so fail without one.
+pub fn measure_file(
+    path: impl AsRef<Path>,
+    transcoder: Option<&dyn Transcoder>,
+) -> io::Result<Loudness> {
+    let path = path.as_ref();
+    if media::sniff_file(path)? == Some(Codec::WAV) {
+        return read_wav(&fs::read(path)?).map(|wav| wav.loudness());
+    }
+
+    let transcoder = transcoder.ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::Unsupported,
+            "Only WAV files can be measured without a transcoder.",
+        )
+    })?;
+    let wav_path = path.with_extension("loudness.wav");
+    let transcoded = transcoder.transcode(path, &wav_path, &Codec::WAV);
+    let wav = transcoded.and_then(|_| fs::read(&wav_path));
+    let _ = fs::remove_file(&wav_path);
+    read_wav(&wav?).map(|wav| wav.loudness())
+}
+
+/// Get the loudness of a mean square, in LUFS.
+fn loudness(mean_square: f64) -> f64 {
+    -0.691 + 10.0 * mean_square.log10()
+}
+
+/// Get the weight of a channel, by its position in the usual 5.1 order:
+/// left, right, center, LFE, left surround, right surround.
+fn channel_weight(channel: usize, channels: usize) -> f64 {
+    match (channels, channel) {
+        (6, 3) => 0.0,
+        (6, 4 | 5) => 1.41,
+        _ => 1.0,
+    }
+}
+
+/// K-weighting filter of BS.1770: a high shelf modelling the head, then a
+/// high pass.
+struct KWeighting {
+    stages: [Biquad; 2],
+}
+
+impl KWeighting {
+    fn new(sample_rate: f64) -> Self {
+        // Pre-filter (high shelf), as derived in libebur128 for any sample rate.
+        let k = (PI * 1681.974450955533 / sample_rate).tan();
+        let q = 0.7071752369554196;
+        let vh = 10f64.powf(3.999843853973347 / 20.0);
+        let vb = vh.powf(0.4996667741545416);
+        let a0 = 1.0 + k / q + k * k;
+        let shelf = Biquad::new(
+            [
+                (vh + vb * k / q + k * k) / a0,
+                2.0 * (k * k - vh) / a0,
+                (vh - vb * k / q + k * k) / a0,
+            ],
+            [2.0 * (k * k - 1.0) / a0, (1.0 - k / q + k * k) / a0],
+        );
+
+        // RLB filter (high pass).
+        let k = (PI * 38.13547087602444 / sample_rate).tan();
+        let q = 0.5003270373238773;
+        let a0 = 1.0 + k / q + k * k;
+        let high_pass = Biquad::new(
+            [1.0, -2.0, 1.0],
+            [2.0 * (k * k - 1.0) / a0, (1.0 - k / q + k * k) / a0],
+        );
+
+        KWeighting {
+            stages: [shelf, high_pass],
+        }
+    }
+
+    fn process(&mut self, sample: f64) -> f64 {
+        self.stages
+            .iter_mut()
+            .fold(sample, |sample, stage| stage.process(sample))
+    }
+}
+
+/// Second order IIR filter, in direct form II.
+struct Biquad {
+    b: [f64; 3],
+    a: [f64; 2],
+    state: [f64; 2],
+}
+
+impl Biquad {
+    fn new(b: [f64; 3], a: [f64; 2]) -> Self {
+        Biquad {
+            b,
+            a,
+            state: [0.0; 2],
+        }
+    }
+
+    fn process(&mut self, sample: f64) -> f64 {
+        let w = sample - self.a[0] * self.state[0] - self.a[1] * self.state[1];
+        let out = self.b[0] * w + self.b[1] * self.state[0] + self.b[2] * self.state[1];
+        self.state = [w, self.state[0]];
+        out
+    }
+}
+
+/// Decoded PCM audio of a WAV file.
+struct Wav {
+    channels: usize,
+    sample_rate: u32,
+    samples: Vec<f32>,
+}
+
+impl Wav {
+    fn loudness(&self) -> Loudness {
+        Loudness::measure(&self.samples, self.channels, self.sample_rate)
+    }
+}
+
+/// Decode a WAV file of integer (8 to 32 bit) or float (32 bit) PCM.
+fn read_wav(bytes: &[u8]) -> io::Result<Wav> {
+    let invalid = |message: &str| io::Error::new(io::ErrorKind::InvalidData, message.to_owned());
+    let u16_at = |at: usize| u16::from_le_bytes([bytes[at], bytes[at + 1]]);
+    let u32_at =
+        |at: usize| u32::from_le_bytes([bytes[at], bytes[at + 1], bytes[at + 2], bytes[at + 3]]);
+
+    if bytes.len() < 12 || &bytes[0..4] != b"RIFF" || &bytes[8..12] != b"WAVE" {
+        return Err(invalid("Not a WAV file."));
+    }
+
+    let mut format = None;
+    let mut offset = 12;
+    while offset + 8 <= bytes.len() {
+        let id = &bytes[offset..offset + 4];
+        let size = u32_at(offset + 4) as usize;
+        let body = offset + 8;
+        let end = body.saturating_add(size).min(bytes.len());
+
+        match id {
+            b"fmt " if size >= 16 && end - body >= 16 => {
+                let mut tag = u16_at(body);
+                // Extensible format keeps the actual format in its sub-format.
+                if tag == 0xFFFE && end - body >= 26 {
+                    tag = u16_at(body + 24);
+                }
+                format = Some((
+                    tag,
+                    usize::from(u16_at(body + 2)),
+                    u32_at(body + 4),
+                    usize::from(u16_at(body + 14)),
+                ));
+            }
+            b"data" => {
+                let (tag, channels, sample_rate, bits) =
+                    format.ok_or_else(|| invalid("WAV data comes before its format."))?;
+                let width = bits / 8;
+                if channels == 0 || width == 0 {
+                    return Err(invalid("WAV format is invalid."));
+                }
+
+                let samples = bytes[body..end]
+                    .chunks_exact(width)
+                    .map(|sample| decode_sample(tag, sample))
+                    .collect::<Option<Vec<f32>>>()
+                    .ok_or_else(|| invalid("WAV sample format is not supported."))?;
+
+                return Ok(Wav {
+                    channels,
+                    sample_rate,
+                    samples,
+                });
+            }
+            _ => {}
+        }
+
+        // Chunks are padded to an even size.
+        offset = body.saturating_add(size + size % 2);
+    }
+
+    Err(invalid("WAV file has no data."))
+}
+
+/// Decode a little-endian sample to the range -1.0 to 1.0.
+fn decode_sample(tag: u16, sample: &[u8]) -> Option<f32> {
+    match (tag, sample.len()) {
+        (1, 1) => Some((f32::from(sample[0]) - 128.0) / 128.0),
+        (1, 2) => Some(f32::from(i16::from_le_bytes([sample[0], sample[1]])) / 32768.0),
+        (1, 3) => {
+            let value = i32::from_le_bytes([0, sample[0], sample[1], sample[2]]) >> 8;
+            Some(value as f32 / 8388608.0)
+        }
+        (1, 4) => {
+            let value = i32::from_le_bytes([sample[0], sample[1], sample[2], sample[3]]);
+            Some((f64::from(value) / 2147483648.0) as f32)
+        }
+        (3, 4) => Some(f32::from_le_bytes([
+            sample[0], sample[1], sample[2], sample[3],
+        ])),
+        _ => None,
+    }
+}
@@ -0,0 +1,94 @@
+pub mod loudness;
+
+use crate::mc::util::Codec;
+use std::fs;
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Detect the codec of audio from its first bytes, by their magic numbers.
+///
+/// At least 12 bytes are needed to tell WAV files apart. Returns nothing for
+/// other formats.
+///
+/// Example
+/// ```rust
+/// use bombay::media;
+/// use bombay::mc::util::Codec;
+///
+/// assert_eq!(media::sniff(b"fLaC\0\0\0\x22"), Some(Codec::FLAC));
+/// assert_eq!(media::sniff(b"ID3\x04\0\0\0\0\0\0"), Some(Codec::MP3));
+/// assert_eq!(media::sniff(b"{\"Data\": []}"), None);
+/// ```
+pub fn sniff(bytes: &[u8]) -> Option<Codec> {
+    match bytes {
+        [b'f', b'L', b'a', b'C', ..] => Some(Codec::FLAC),
+        [b'R', b'I', b'F', b'F', _, _, _, _, b'W', b'A', b'V', b'E', ..] => Some(Codec::WAV),
+        [b'I', b'D', b'3', ..] => Some(Codec::MP3),
+        // MPEG audio frame sync, for MP3s without ID3 tags.
+        [0xFF, second, ..] if second & 0xE0 == 0xE0 => Some(Codec::MP3),
+        _ => None,
+    }
+}
+
+/// Detect the codec of an audio file, see [`sniff`].
+pub fn sniff_file(path: impl AsRef<Path>) -> io::Result<Option<Codec>> {
+    let mut header = Vec::with_capacity(12);
+    fs::File::open(path)?.take(12).read_to_end(&mut header)?;
+    Ok(sniff(&header))
+}
+
+/// Converts audio files from one codec to another, for when the MC API
+/// doesn't serve a track in the wanted codec.
+pub trait Transcoder: Send + Sync {
+    /// Convert the input file into the output file, with the codec.
+    fn transcode(&self, input: &Path, output: &Path, codec: &Codec) -> io::Result<()>;
+}
+
+/// Transcoder running the ffmpeg command line tool, which must be installed.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FfmpegTranscoder {
+    /// Path of the ffmpeg program.
+    pub program: PathBuf,
+}
+
+impl Default for FfmpegTranscoder {
+    /// Run the ffmpeg found in the `PATH`.
+    fn default() -> Self {
+        FfmpegTranscoder {
+            program: PathBuf::from("ffmpeg"),
+        }
+    }
+}
+
+impl FfmpegTranscoder {
+    /// Get the ffmpeg arguments encoding with a codec, in the quality the MC
+    /// API serves it.
+    fn codec_arguments(codec: &Codec) -> &'static [&'static str] {
+        match codec {
+            Codec::MP3 => &["-codec:a", "libmp3lame", "-b:a", "320k"],
+            Codec::FLAC => &["-codec:a", "flac"],
+            Codec::WAV => &["-codec:a", "pcm_s16le"],
+        }
+    }
+}
+
+impl Transcoder for FfmpegTranscoder {
+    fn transcode(&self, input: &Path, output: &Path, codec: &Codec) -> io::Result<()> {
+        let status = Command::new(&self.program)
+            .args(["-y", "-loglevel", "error", "-i"])
+            .arg(input)
+            .args(["-vn"])
+            .args(Self::codec_arguments(codec))
+            .arg(output)
+            .status()?;
+
+        match status.success() {
+            true => Ok(()),
+            false => Err(io::Error::new(
+                io::ErrorKind::Other,
+                format!("ffmpeg failed to transcode to {}: {}", codec, status),
+            )),
+        }
+    }
+}
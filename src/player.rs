@@ -0,0 +1,437 @@
+#[cfg(feature = "client")]
+use crate::client::{Client, Error};
+#[cfg(feature = "client")]
+use crate::mc::release::{Track, TrackID};
+#[cfg(feature = "client")]
+use std::io::{self, Read};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Variants of repeat behavior once the end of the queue is reached.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum RepeatMode {
+    /// Stop after the last item.
+    #[default]
+    Off,
+    /// Keep repeating the current item.
+    One,
+    /// Start over from the first item after the last.
+    All,
+}
+
+/// Variants of shuffle behavior.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ShuffleMode {
+    /// Play items in the order they were added.
+    #[default]
+    Off,
+    /// Play upcoming items in random order.
+    On,
+}
+
+/// Play queue of tracks (or anything else), decoupled from audio output.
+///
+/// The queue keeps the items in the order they were added, and a separate
+/// play order. Shuffling only rearranges the items that are still up next,
+/// so the current item and history are never disturbed.
+///
+/// Example
+/// ```rust
+/// use bombay::player::{Queue, RepeatMode};
+///
+/// let mut queue = Queue::from(vec!["Rogue", "Grant", "Bishu"]);
+/// queue.set_repeat(RepeatMode::All);
+///
+/// assert_eq!(queue.next(), Some(&"Rogue"));
+/// assert_eq!(queue.next(), Some(&"Grant"));
+/// assert_eq!(queue.next(), Some(&"Bishu"));
+/// assert_eq!(queue.next(), Some(&"Rogue"));
+/// ```
+#[derive(Clone, Debug)]
+pub struct Queue<T> {
+    items: Vec<T>,
+    order: Vec<usize>,
+    position: Option<usize>,
+    history: Vec<usize>,
+    repeat: RepeatMode,
+    shuffle: ShuffleMode,
+    rng: u64,
+}
+
+impl<T> Default for Queue<T> {
+    fn default() -> Self {
+        Queue {
+            items: Vec::new(),
+            order: Vec::new(),
+            position: None,
+            history: Vec::new(),
+            repeat: RepeatMode::default(),
+            shuffle: ShuffleMode::default(),
+            rng: seed_rng(),
+        }
+    }
+}
+
+impl<T> From<Vec<T>> for Queue<T> {
+    fn from(items: Vec<T>) -> Self {
+        Queue {
+            order: (0..items.len()).collect(),
+            items,
+            ..Queue::default()
+        }
+    }
+}
+
+impl<T> Queue<T> {
+    /// Create an empty queue.
+    pub fn new() -> Self {
+        Queue::default()
+    }
+
+    /// Get the number of items in the queue, including played ones.
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    /// Check whether the queue has no items.
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    /// Get the current item, if playback started and hasn't finished.
+    pub fn current(&self) -> Option<&T> {
+        self.position
+            .and_then(|position| self.order.get(position))
+            .map(|&index| &self.items[index])
+    }
+
+    /// Get the items that will play after the current one, in play order.
+    pub fn up_next(&self) -> impl Iterator<Item = &T> {
+        let start = self.position.map_or(0, |position| position + 1);
+        self.order
+            .iter()
+            .skip(start)
+            .map(move |&index| &self.items[index])
+    }
+
+    /// Get the items that were played before the current one, oldest first.
+    pub fn history(&self) -> impl Iterator<Item = &T> {
+        self.history.iter().map(move |&index| &self.items[index])
+    }
+
+    /// Get the repeat mode.
+    pub fn repeat(&self) -> RepeatMode {
+        self.repeat
+    }
+
+    /// Set the repeat mode.
+    pub fn set_repeat(&mut self, repeat: RepeatMode) {
+        self.repeat = repeat;
+    }
+
+    /// Get the shuffle mode.
+    pub fn shuffle(&self) -> ShuffleMode {
+        self.shuffle
+    }
+
+    /// Set the shuffle mode.
+    ///
+    /// Turning shuffle on shuffles the items up next. Turning it off restores
+    /// the order the items were added in, continuing after the current item.
+    pub fn set_shuffle(&mut self, mode: ShuffleMode) {
+        self.shuffle = mode;
+        match mode {
+            ShuffleMode::On => self.shuffle_up_next(),
+            ShuffleMode::Off => {
+                let current = self
+                    .position
+                    .and_then(|position| self.order.get(position).copied());
+                self.order = (0..self.items.len()).collect();
+                if let Some(current) = current {
+                    self.position = Some(current);
+                }
+            }
+        }
+    }
+
+    /// Add an item to the end of the queue (or somewhere up next, when shuffling).
+    ///
+    /// Once the queue finished, the last item becomes current again, so the
+    /// pushed item plays next.
+    pub fn push(&mut self, item: T) {
+        self.resume_if_finished();
+        self.items.push(item);
+        let index = self.items.len() - 1;
+
+        match self.shuffle {
+            ShuffleMode::Off => self.order.push(index),
+            ShuffleMode::On => {
+                let start = self.up_next_start();
+                let slots = (self.order.len() - start + 1) as u64;
+                let at = start + (next_rng(&mut self.rng) % slots) as usize;
+                self.order.insert(at, index);
+            }
+        }
+    }
+
+    /// Add an item to play right after the current one.
+    pub fn push_next(&mut self, item: T) {
+        self.resume_if_finished();
+        self.items.push(item);
+        let at = self.up_next_start();
+        self.order.insert(at, self.items.len() - 1);
+    }
+
+    /// Remove every item and reset playback, keeping the modes.
+    pub fn clear(&mut self) {
+        self.items.clear();
+        self.order.clear();
+        self.history.clear();
+        self.position = None;
+    }
+
+    /// Advance to and get the next item once the current one finished
+    /// playing, honoring the repeat mode.
+    ///
+    /// With repeat one, this keeps getting the current item, see
+    /// [`Queue::skip`] to move on anyway. Returns `None` once the end of the
+    /// queue is reached without repeat.
+    #[allow(clippy::should_implement_trait)]
+    pub fn next(&mut self) -> Option<&T> {
+        if self.repeat == RepeatMode::One && self.current().is_some() {
+            return self.current();
+        }
+
+        self.skip()
+    }
+
+    /// Advance to and get the next item because the user skipped the current
+    /// one, even with repeat one.
+    ///
+    /// With repeat all, skipping past the last item starts over. Returns
+    /// `None` once the end of the queue is reached otherwise.
+    pub fn skip(&mut self) -> Option<&T> {
+        if let Some(index) = self
+            .position
+            .and_then(|position| self.order.get(position).copied())
+        {
+            self.history.push(index);
+        }
+
+        let next = self.position.map_or(0, |position| position + 1);
+        if next < self.order.len() {
+            self.position = Some(next);
+        } else if self.repeat == RepeatMode::All && !self.order.is_empty() {
+            if self.shuffle == ShuffleMode::On {
+                shuffle(&mut self.order, &mut self.rng);
+            }
+            self.position = Some(0);
+        } else {
+            self.position = Some(self.order.len());
+        }
+
+        self.current()
+    }
+
+    /// Go back to and get the previous item in play order.
+    ///
+    /// With repeat all, going back from the first item wraps to the last.
+    pub fn previous(&mut self) -> Option<&T> {
+        match self.position {
+            Some(position) if position > 0 => {
+                self.position = Some(position.min(self.order.len()) - 1);
+                self.history.pop();
+            }
+            Some(_) if self.repeat == RepeatMode::All && !self.order.is_empty() => {
+                self.position = Some(self.order.len() - 1);
+            }
+            _ => {}
+        }
+
+        self.current()
+    }
+
+    /// Make the last item current again if the queue finished, so items
+    /// added after it play next instead of being skipped.
+    fn resume_if_finished(&mut self) {
+        if self.position == Some(self.order.len()) {
+            self.position = self.order.len().checked_sub(1);
+            self.history.pop();
+        }
+    }
+
+    /// Index in the play order where items up next start.
+    fn up_next_start(&self) -> usize {
+        self.position
+            .map_or(0, |position| (position + 1).min(self.order.len()))
+    }
+
+    /// Shuffle the items up next, leaving the current item and history alone.
+    fn shuffle_up_next(&mut self) {
+        let start = self.up_next_start();
+        shuffle(&mut self.order[start..], &mut self.rng);
+    }
+}
+
+#[cfg(feature = "client")]
+/// Track stream with some bytes already buffered ahead of playback.
+pub struct PrefetchedStream {
+    track_id: TrackID,
+    preroll: io::Cursor<Vec<u8>>,
+    rest: Box<dyn Read + Send + Sync>,
+}
+
+#[cfg(feature = "client")]
+impl PrefetchedStream {
+    /// Get the identifier of the track this stream plays.
+    pub fn track_id(&self) -> &TrackID {
+        &self.track_id
+    }
+
+    /// Get the number of bytes buffered ahead, which can be read without waiting on the network.
+    pub fn preroll_bytes(&self) -> usize {
+        self.preroll.get_ref().len() - self.preroll.position() as usize
+    }
+}
+
+#[cfg(feature = "client")]
+impl Read for PrefetchedStream {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        match self.preroll.read(buf)? {
+            0 => self.rest.read(buf),
+            read => Ok(read),
+        }
+    }
+}
+
+#[cfg(feature = "client")]
+/// Opens and buffers the stream of the next track in a queue ahead of time,
+/// so players can transition between tracks without a gap.
+///
+/// Memory is bounded: at most one stream is buffered, up to a maximum number
+/// of bytes. The rest of the track is read from the network as usual.
+///
+/// Example
+/// ```rust,no_run
+/// use bombay::client::Client;
+/// use bombay::player::{Prefetcher, Queue};
+/// use bombay::mc::release::Track;
+///
+/// # fn example(mc: &Client, mut queue: Queue<Track>) -> Result<(), bombay::client::Error> {
+/// let mut prefetcher = Prefetcher::new(512 * 1024);
+///
+/// // While the current track plays, buffer the next one.
+/// prefetcher.prefetch(mc, &queue)?;
+///
+/// // When it's time to switch, take the buffered stream instead of opening a new one.
+/// if let Some(next) = queue.next() {
+///     if let Some(stream) = prefetcher.take(next) {
+///         println!("{} bytes ready to play.", stream.preroll_bytes());
+///     }
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub struct Prefetcher {
+    max_bytes: usize,
+    buffered: Option<PrefetchedStream>,
+}
+
+#[cfg(feature = "client")]
+impl Prefetcher {
+    /// Create a prefetcher buffering at most `max_bytes` of the next track.
+    pub fn new(max_bytes: usize) -> Self {
+        Prefetcher {
+            max_bytes,
+            buffered: None,
+        }
+    }
+
+    /// Open and buffer the stream of the next track in the queue, unless it already is.
+    ///
+    /// This blocks while buffering, so call it from a worker thread or while
+    /// the current track still has plenty left to play.
+    pub fn prefetch<ClientAuthState>(
+        &mut self,
+        client: &Client<ClientAuthState>,
+        queue: &Queue<Track>,
+    ) -> Result<(), Error> {
+        match queue.up_next().next() {
+            Some(track) => self.prefetch_track(client, track),
+            None => {
+                self.buffered = None;
+                Ok(())
+            }
+        }
+    }
+
+    /// Open and buffer the stream of a track, unless it already is.
+    pub fn prefetch_track<ClientAuthState>(
+        &mut self,
+        client: &Client<ClientAuthState>,
+        track: &Track,
+    ) -> Result<(), Error> {
+        if self.is_buffered(track) {
+            return Ok(());
+        }
+
+        let mut stream = client
+            .release()
+            .stream_by_ids(&track.release.id, &track.id)?;
+        let mut preroll = Vec::new();
+        stream
+            .by_ref()
+            .take(self.max_bytes as u64)
+            .read_to_end(&mut preroll)
+            .map_err(Error::IO)?;
+
+        self.buffered = Some(PrefetchedStream {
+            track_id: track.id,
+            preroll: io::Cursor::new(preroll),
+            rest: Box::new(stream),
+        });
+
+        Ok(())
+    }
+
+    /// Check whether a track's stream is buffered.
+    pub fn is_buffered(&self, track: &Track) -> bool {
+        self.buffered
+            .as_ref()
+            .map_or(false, |buffered| buffered.track_id == track.id)
+    }
+
+    /// Take the buffered stream if it belongs to the given track.
+    pub fn take(&mut self, track: &Track) -> Option<PrefetchedStream> {
+        if self.is_buffered(track) {
+            self.buffered.take()
+        } else {
+            None
+        }
+    }
+}
+
+/// Seed a random number generator from the clock.
+pub(crate) fn seed_rng() -> u64 {
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |duration| duration.as_nanos() as u64);
+
+    // Xorshift state must never be zero.
+    nanos | 1
+}
+
+/// Step a xorshift generator and get its next number.
+pub(crate) fn next_rng(rng: &mut u64) -> u64 {
+    *rng ^= *rng << 13;
+    *rng ^= *rng >> 7;
+    *rng ^= *rng << 17;
+    *rng
+}
+
+/// Shuffle items in place (Fisher-Yates, with a xorshift generator).
+pub(crate) fn shuffle<T>(items: &mut [T], rng: &mut u64) {
+    for i in (1..items.len()).rev() {
+        let j = (next_rng(rng) % (i as u64 + 1)) as usize;
+        items.swap(i, j);
+    }
+}
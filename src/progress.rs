@@ -0,0 +1,170 @@
+use std::fmt::Display;
+use std::io::{self, Read};
+use std::sync::mpsc::Sender;
+
+/// Bytes transferred between two [`ProgressEvent::Bytes`] of an item.
+const BYTES_INTERVAL: u64 = 256 * 1024;
+
+/// Long operations reporting progress.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Operation {
+    /// Downloading a single track.
+    DownloadTrack,
+    /// Downloading the tracks of a release.
+    DownloadRelease,
+    /// Fetching pages of releases, like to find a smart playlist's tracks.
+    FetchReleases,
+    /// Syncing a playlist's items.
+    SyncPlaylist,
+}
+
+/// Progress of a long operation, sent to a caller-provided channel so
+/// every subsystem reports progress the same way, like to a GUI's progress
+/// bar.
+///
+/// An operation sends [`ProgressEvent::Started`] first, then events for its
+/// items, then [`ProgressEvent::Finished`] or [`ProgressEvent::Failed`] last.
+/// Operations made of other operations, like syncing a smart playlist which
+/// fetches releases first, send the events of each.
+///
+/// Example
+/// ```rust,no_run
+/// use bombay::client::{Client, SignedIn};
+/// use bombay::download::Downloader;
+/// use bombay::mc::release::CatalogID;
+/// use bombay::mc::util::Codec;
+/// use bombay::progress::ProgressEvent;
+/// use std::sync::mpsc;
+///
+/// # fn example(mc: &Client<SignedIn>) -> Result<(), bombay::client::Error> {
+/// let (sender, receiver) = mpsc::channel();
+/// let downloader = Downloader::new(mc, Codec::FLAC)
+///     .set_directory("downloads")
+///     .set_progress(Some(sender));
+///
+/// std::thread::scope(|scope| {
+///     scope.spawn(|| {
+///         for event in receiver {
+///             if let ProgressEvent::Bytes { item, done, .. } = event {
+///                 println!("{item}: {done} bytes");
+///             }
+///         }
+///     });
+///     downloader.download_release(&CatalogID("MCS1186".to_owned()))
+/// })?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ProgressEvent {
+    /// Operation started, with its number of items, if known.
+    Started {
+        operation: Operation,
+        items: Option<usize>,
+    },
+    /// Bytes of an item were transferred so far, out of its size, if known.
+    Bytes {
+        item: String,
+        done: u64,
+        total: Option<u64>,
+    },
+    /// Item is done, like a downloaded track or a fetched page.
+    ItemDone { item: String },
+    /// Item is tried again, after failing.
+    Retry {
+        item: String,
+        attempt: u32,
+        error: String,
+    },
+    /// Operation finished.
+    Finished { operation: Operation },
+    /// Operation failed, and stopped.
+    Failed { operation: Operation, error: String },
+}
+
+/// Channel progress events are sent to, if any.
+///
+/// Events are dropped once the receiver hangs up, so the operation keeps
+/// going.
+#[derive(Clone, Default)]
+pub(crate) struct Reporter(Option<Sender<ProgressEvent>>);
+
+impl Reporter {
+    pub fn new(sender: Option<Sender<ProgressEvent>>) -> Self {
+        Reporter(sender)
+    }
+
+    /// Check whether there is a channel to send events to.
+    pub fn is_set(&self) -> bool {
+        self.0.is_some()
+    }
+
+    /// Send an event, if there is a channel.
+    pub fn send(&self, event: ProgressEvent) {
+        if let Some(sender) = &self.0 {
+            let _ = sender.send(event);
+        }
+    }
+
+    /// Run an operation, sending its start, and its end with how it went.
+    pub fn run<T, E: Display>(
+        &self,
+        operation: Operation,
+        items: Option<usize>,
+        run: impl FnOnce() -> Result<T, E>,
+    ) -> Result<T, E> {
+        self.send(ProgressEvent::Started { operation, items });
+        let result = run();
+        self.send(match &result {
+            Ok(_) => ProgressEvent::Finished { operation },
+            Err(err) => ProgressEvent::Failed {
+                operation,
+                error: err.to_string(),
+            },
+        });
+        result
+    }
+
+    /// Wrap a reader, sending the bytes of an item read through it.
+    pub fn reader<R: Read>(&self, item: String, total: Option<u64>, inner: R) -> ProgressReader<R> {
+        ProgressReader {
+            reporter: self.clone(),
+            item,
+            inner,
+            done: 0,
+            reported: 0,
+            total,
+        }
+    }
+}
+
+/// Reader sending the bytes read through it as progress events, every so
+/// often and at the end.
+pub(crate) struct ProgressReader<R> {
+    reporter: Reporter,
+    item: String,
+    inner: R,
+    done: u64,
+    reported: u64,
+    total: Option<u64>,
+}
+
+impl<R: Read> Read for ProgressReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let read = self.inner.read(buf)?;
+        self.done += read as u64;
+
+        if (read == 0 && self.done > self.reported) || self.done - self.reported >= BYTES_INTERVAL {
+            self.reported = self.done;
+            self.reporter.send(ProgressEvent::Bytes {
+                item: self.item.clone(),
+                done: self.done,
+                total: self.total,
+            });
+        }
+
+        Ok(read)
+    }
+}
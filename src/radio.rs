@@ -0,0 +1,193 @@
+use crate::client::{
+    Client, Error, Paginated, PaginationAnomaly, PaginationParameters, RequestParameters,
+};
+use crate::mc::mood::Mood;
+use crate::mc::release::{AnyRelease, ReleaseID, Track, TrackID};
+use crate::player::{seed_rng, shuffle};
+use std::collections::VecDeque;
+
+/// Where a radio gets its tracks from.
+#[derive(Clone, Debug, PartialEq)]
+pub enum RadioSeed {
+    /// Tracks matching a mood, by the mood's name uri.
+    Mood(String),
+    /// Tracks from releases related to a release.
+    Release(ReleaseID),
+    /// Tracks similar to a track.
+    Track(TrackID),
+}
+
+/// Endless, shuffled queue of tracks matching a mood or related to a seed track.
+///
+/// The radio fetches a page of tracks at a time, shuffles it, and fetches the
+/// next page once the queue runs dry. After the last page it starts over from
+/// the first, so iteration only ends if there are no tracks at all.
+///
+/// Example
+/// ```rust,no_run
+/// use bombay::client::Client;
+/// use bombay::radio::MoodRadio;
+///
+/// let mc = Client::default(); // Without authentication.
+/// let chill = mc.mood().get_by_name_uri("chill").expect("Could not find mood.");
+///
+/// for track in MoodRadio::from_mood(&mc, &chill).take(5).flatten() {
+///   println!("Up next: {} by {}", track.title, track.artists_title);
+/// }
+/// ```
+pub struct MoodRadio<'a, ClientAuthState> {
+    client: &'a Client<ClientAuthState>,
+    seed: RadioSeed,
+    queue: VecDeque<Track>,
+    offset: usize,
+    page_size: usize,
+    rng: u64,
+    anomalies: Vec<PaginationAnomaly>,
+}
+
+impl<'a, ClientAuthState> MoodRadio<'a, ClientAuthState> {
+    /// Create a radio playing tracks matching a mood.
+    ///
+    /// Unverified, mood tracks come from a guessed route, see
+    /// [`EndpointMood::get_tracks_by_name_uri`](crate::client::endpoints::EndpointMood::get_tracks_by_name_uri).
+    pub fn from_mood(client: &'a Client<ClientAuthState>, mood: &Mood) -> Self {
+        Self::new(client, RadioSeed::Mood(mood.uri.clone()))
+    }
+
+    /// Create a radio playing tracks from releases related to a track.
+    pub fn from_track(client: &'a Client<ClientAuthState>, track: &Track) -> Self {
+        Self::new(client, RadioSeed::Release(track.release.id))
+    }
+
+    /// Create a radio playing tracks similar to a track, rather than tracks
+    /// of related releases.
+    ///
+    /// Unverified, similar tracks come from a guessed route, see
+    /// [`EndpointRelease::get_similar_tracks`](crate::client::endpoints::EndpointRelease::get_similar_tracks).
+    pub fn from_similar(client: &'a Client<ClientAuthState>, track: &Track) -> Self {
+        Self::new(client, RadioSeed::Track(track.id))
+    }
+
+    /// Create a radio from any seed.
+    pub fn new(client: &'a Client<ClientAuthState>, seed: RadioSeed) -> Self {
+        MoodRadio {
+            client,
+            seed,
+            queue: VecDeque::new(),
+            offset: 0,
+            page_size: 25,
+            rng: seed_rng(),
+            anomalies: Vec::new(),
+        }
+    }
+
+    /// Set how many releases are fetched at a time.
+    pub fn set_page_size(mut self, page_size: usize) -> Self {
+        self.page_size = page_size.max(1);
+        self
+    }
+
+    /// Get the radio's seed.
+    pub fn seed(&self) -> &RadioSeed {
+        &self.seed
+    }
+
+    /// Get anything off about the pagination of the most recently fetched page.
+    pub fn anomalies(&self) -> &[PaginationAnomaly] {
+        &self.anomalies
+    }
+
+    /// Fetch the page at the given pagination.
+    fn fetch_page(&self, pagination: PaginationParameters) -> Result<Paginated<AnyRelease>, Error> {
+        let parameters = RequestParameters::from_pagination(pagination);
+
+        match &self.seed {
+            RadioSeed::Mood(uri) => self
+                .client
+                .mood()
+                .get_tracks_by_name_uri(uri, Some(parameters)),
+            RadioSeed::Release(id) => self
+                .client
+                .release()
+                .get_related_by_id(id, Some(parameters)),
+            RadioSeed::Track(id) => {
+                let page = self
+                    .client
+                    .release()
+                    .get_similar_tracks(id, Some(parameters))?;
+                Ok(Paginated {
+                    data: page
+                        .data
+                        .map(|tracks| tracks.into_iter().map(AnyRelease::Track).collect()),
+                    not_found: page.not_found,
+                    total: page.total,
+                    limit: page.limit,
+                    offset: page.offset,
+                })
+            }
+        }
+    }
+
+    /// Get the tracks of some release, fetching them if the release doesn't include them.
+    fn tracks_of(&self, release: AnyRelease) -> Result<Vec<Track>, Error> {
+        match release {
+            AnyRelease::Track(track) => Ok(vec![track]),
+            AnyRelease::Release(release) => match release.tracks {
+                Some(tracks) => Ok(tracks),
+                None => Ok(self
+                    .client
+                    .release()
+                    .get_by_catalog_id(&release.catalog_id)?
+                    .1),
+            },
+        }
+    }
+
+    /// Fetch the next page of tracks into the queue, starting over after the last page.
+    fn refill(&mut self) -> Result<(), Error> {
+        // Two attempts: the current page, then the first page if we ran off the end.
+        for _ in 0..2 {
+            let started_at = self.offset;
+            let requested = PaginationParameters {
+                limit: self.page_size,
+                offset: started_at,
+            };
+            let page = self.fetch_page(requested.clone())?;
+
+            self.anomalies = page.anomalies(&requested);
+            self.offset = page.next_offset().unwrap_or(0);
+
+            let mut tracks = Vec::new();
+            for release in page.data.unwrap_or_default() {
+                tracks.extend(self.tracks_of(release)?);
+            }
+
+            if !tracks.is_empty() {
+                shuffle(&mut tracks, &mut self.rng);
+                self.queue.extend(tracks);
+                return Ok(());
+            }
+
+            if started_at == 0 {
+                break;
+            }
+            self.offset = 0;
+        }
+
+        Ok(())
+    }
+}
+
+impl<ClientAuthState> Iterator for MoodRadio<'_, ClientAuthState> {
+    type Item = Result<Track, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.queue.is_empty() {
+            if let Err(err) = self.refill() {
+                return Some(Err(err));
+            }
+        }
+
+        self.queue.pop_front().map(Ok)
+    }
+}
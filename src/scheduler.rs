@@ -0,0 +1,226 @@
+use crate::client::{Client, Error, SignedIn};
+use crate::watch::PresaveTracker;
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
+use std::sync::mpsc::{self, RecvTimeoutError, Sender};
+use std::sync::{Arc, Mutex};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+/// Type for a job run periodically.
+type JobFn = dyn FnMut() -> Result<(), Error> + Send;
+
+/// Type for callback function provided to be told a job failed.
+type JobFailedFn = dyn Fn(&str, &Error) + Send + Sync;
+
+/// Job the scheduler runs periodically.
+struct Job {
+    name: String,
+    interval: Duration,
+    run: Box<JobFn>,
+    next_run: Instant,
+}
+
+/// Runs periodic jobs, like watching releases or keeping a session alive, on
+/// one background thread, so applications don't need a timer around each.
+///
+/// Jobs run right away when the scheduler is spawned, then every interval.
+/// Intervals are jittered, by 10% by default, so jobs of many processes
+/// started together don't hit the API in lockstep. A job failing is reported
+/// to a callback, and runs again at its next interval.
+///
+/// Example
+/// ```rust,no_run
+/// use bombay::client::{Client, SignedIn};
+/// use bombay::scheduler::Scheduler;
+/// use bombay::watch::PresaveTracker;
+/// use std::sync::{Arc, Mutex};
+/// use std::time::Duration;
+///
+/// # fn example(mc: Client<SignedIn>) {
+/// let mc = Arc::new(mc);
+/// let tracker = Arc::new(Mutex::new(
+///     PresaveTracker::new().set_on_release(|release| println!("{} is out!", release.title)),
+/// ));
+///
+/// let running = Scheduler::new()
+///     .set_on_job_failed(|name, err| eprintln!("{name} failed: {err}"))
+///     .add_keep_alive(mc.clone(), Duration::from_secs(30 * 60))
+///     .add_presave_tracker(tracker, mc, Duration::from_secs(60))
+///     .spawn();
+///
+/// // Later, when shutting down.
+/// let _scheduler = running.stop();
+/// # }
+/// ```
+pub struct Scheduler {
+    jobs: Vec<Job>,
+    jitter: f64,
+    on_job_failed: Option<Arc<JobFailedFn>>,
+    random: RandomState,
+    draws: u64,
+}
+
+impl std::fmt::Debug for Scheduler {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Scheduler")
+            .field("jobs", &self.job_names())
+            .field("jitter", &self.jitter)
+            .finish()
+    }
+}
+
+impl Default for Scheduler {
+    fn default() -> Self {
+        Scheduler {
+            jobs: Vec::new(),
+            jitter: 0.1,
+            on_job_failed: None,
+            random: RandomState::new(),
+            draws: 0,
+        }
+    }
+}
+
+impl Scheduler {
+    /// Create a scheduler without jobs.
+    pub fn new() -> Self {
+        Scheduler::default()
+    }
+
+    /// Set the most an interval is made shorter or longer, as a fraction of
+    /// it, 0.1 by default. Clamped between 0 and 1.
+    pub fn set_jitter(mut self, jitter: f64) -> Self {
+        self.jitter = jitter.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Set the callback called with the name of a job and its error, when it
+    /// fails.
+    pub fn set_on_job_failed(
+        mut self,
+        callback: impl Fn(&str, &Error) + Send + Sync + 'static,
+    ) -> Self {
+        self.on_job_failed = Some(Arc::new(callback));
+        self
+    }
+
+    /// Add a job run every interval.
+    pub fn add(
+        mut self,
+        name: impl Into<String>,
+        interval: Duration,
+        job: impl FnMut() -> Result<(), Error> + Send + 'static,
+    ) -> Self {
+        self.jobs.push(Job {
+            name: name.into(),
+            interval,
+            run: Box::new(job),
+            next_run: Instant::now(),
+        });
+        self
+    }
+
+    /// Add a job keeping a session alive, by getting the user's info every
+    /// interval.
+    pub fn add_keep_alive(self, client: Arc<Client<SignedIn>>, interval: Duration) -> Self {
+        self.add("keep-alive", interval, move || {
+            client.user().get_info().map(|_| ())
+        })
+    }
+
+    /// Add a job watching tracked releases, refreshing their release dates
+    /// then telling which came out, every interval.
+    pub fn add_presave_tracker<ClientAuthState: Send + Sync + 'static>(
+        self,
+        tracker: Arc<Mutex<PresaveTracker>>,
+        client: Arc<Client<ClientAuthState>>,
+        interval: Duration,
+    ) -> Self {
+        self.add("presave-tracker", interval, move || {
+            let mut tracker = tracker.lock().unwrap_or_else(|err| err.into_inner());
+            tracker.refresh(&client)?;
+            tracker.tick();
+            Ok(())
+        })
+    }
+
+    /// Get the names of the jobs, in the order they were added.
+    pub fn job_names(&self) -> Vec<&str> {
+        self.jobs.iter().map(|job| job.name.as_str()).collect()
+    }
+
+    /// Run the jobs due by some time, and schedule their next run.
+    ///
+    /// Returns the names of the jobs run, in order.
+    pub fn run_due(&mut self, now: Instant) -> Vec<String> {
+        let mut ran = Vec::new();
+        for index in 0..self.jobs.len() {
+            if self.jobs[index].next_run > now {
+                continue;
+            }
+
+            let delay = self.jittered(self.jobs[index].interval);
+            let job = &mut self.jobs[index];
+            if let Err(err) = (job.run)() {
+                if let Some(callback) = &self.on_job_failed {
+                    callback(&job.name, &err);
+                }
+            }
+            job.next_run = now + delay;
+            ran.push(job.name.clone());
+        }
+        ran
+    }
+
+    /// Get when the next job is due, if there are any.
+    pub fn next_run(&self) -> Option<Instant> {
+        self.jobs.iter().map(|job| job.next_run).min()
+    }
+
+    /// Run the jobs on a background thread, until stopped.
+    pub fn spawn(mut self) -> RunningScheduler {
+        let (stop, stopped) = mpsc::channel::<()>();
+
+        let thread = thread::spawn(move || loop {
+            let wait = self.next_run().map_or(Duration::MAX, |next| {
+                next.saturating_duration_since(Instant::now())
+            });
+            match stopped.recv_timeout(wait) {
+                Err(RecvTimeoutError::Timeout) => self.run_due(Instant::now()),
+                _ => return self,
+            };
+        });
+
+        RunningScheduler { stop, thread }
+    }
+
+    /// Get an interval made up to the jitter shorter or longer.
+    fn jittered(&mut self, interval: Duration) -> Duration {
+        self.draws += 1;
+        let mut hasher = self.random.build_hasher();
+        hasher.write_u64(self.draws);
+        let unit = hasher.finish() as f64 / u64::MAX as f64;
+        interval.mul_f64(1.0 + self.jitter * (unit * 2.0 - 1.0))
+    }
+}
+
+/// Scheduler running its jobs on a background thread, see
+/// [`Scheduler::spawn`].
+#[derive(Debug)]
+pub struct RunningScheduler {
+    stop: Sender<()>,
+    thread: JoinHandle<Scheduler>,
+}
+
+impl RunningScheduler {
+    /// Stop the background thread, and get the scheduler back.
+    ///
+    /// Jobs already running are finished first, and none are started after.
+    pub fn stop(self) -> Scheduler {
+        let _ = self.stop.send(());
+        self.thread
+            .join()
+            .unwrap_or_else(|panic| std::panic::resume_unwind(panic))
+    }
+}
@@ -0,0 +1,180 @@
+use crate::client::{Client, Error, PaginationParameters, RequestParameters, SignedIn, SignedOut};
+use crate::matching::{normalize, similarity, DEFAULT_THRESHOLD};
+use crate::mc::artist::Artist;
+use crate::mc::playlist::Playlist;
+use crate::mc::release::AnyRelease;
+use std::cmp::Ordering;
+use std::thread;
+
+/// Number of results fetched per entity type.
+const RESULTS_PER_KIND: usize = 10;
+
+/// Entity found by a search, tagged with its type.
+#[derive(Clone, Debug)]
+pub enum SearchEntity {
+    Artist(Box<Artist>),
+    Release(Box<AnyRelease>),
+    Playlist(Box<Playlist>),
+}
+
+impl SearchEntity {
+    /// Get the title of the entity, or name for artists.
+    pub fn title(&self) -> &str {
+        match self {
+            SearchEntity::Artist(artist) => &artist.name,
+            SearchEntity::Release(release) => release.get_title(),
+            SearchEntity::Playlist(playlist) => &playlist.title,
+        }
+    }
+
+    /// Get the URI-like identifier of the entity, if it has one worth matching exactly.
+    fn uri(&self) -> Option<&str> {
+        match self {
+            SearchEntity::Artist(artist) => Some(&artist.uri),
+            SearchEntity::Release(release) => match release.as_ref() {
+                AnyRelease::Release(release) => Some(&release.catalog_id),
+                AnyRelease::Track(_) => None,
+            },
+            SearchEntity::Playlist(_) => None,
+        }
+    }
+}
+
+/// Search result with a score between 0 and 1, higher being a better match.
+#[derive(Clone, Debug)]
+pub struct SearchResult {
+    pub score: f32,
+    pub entity: SearchEntity,
+}
+
+/// Source of playlists for a search, depending on the client's auth state.
+///
+/// Signed-out clients have no playlists to search. Signed-in clients search
+/// the user's own playlists.
+pub trait PlaylistSearch {
+    /// Get playlists that could match the query.
+    fn search_playlists(&self, query: &str) -> Result<Vec<Playlist>, Error>;
+}
+
+impl PlaylistSearch for Client<SignedOut> {
+    fn search_playlists(&self, _query: &str) -> Result<Vec<Playlist>, Error> {
+        Ok(Vec::new())
+    }
+}
+
+impl PlaylistSearch for Client<SignedIn> {
+    fn search_playlists(&self, _query: &str) -> Result<Vec<Playlist>, Error> {
+        // Playlists can't be searched remotely, they're ranked locally instead.
+        Ok(self.playlist().get_all()?.data.unwrap_or_default())
+    }
+}
+
+/// Search artists, releases, and playlists at once and get a single list
+/// of results, best match first.
+///
+/// The entity types are queried concurrently. Results are scored by exact
+/// URI (or catalog ID) match, then exact title, prefix, containment, and
+/// finally fuzzy similarity with the query.
+///
+/// Example
+/// ```rust
+/// use bombay::client::Client;
+/// use bombay::search::smart_search;
+///
+/// let mc = Client::default(); // Without authentication.
+///
+/// if let Ok(results) = smart_search(&mc, "ace aura") {
+///   for result in results.iter().take(3) {
+///     println!("{:.2} {}", result.score, result.entity.title());
+///   }
+/// }
+/// ```
+pub fn smart_search<ClientAuthState>(
+    client: &Client<ClientAuthState>,
+    query: &str,
+) -> Result<Vec<SearchResult>, Error>
+where
+    Client<ClientAuthState>: PlaylistSearch + Sync,
+{
+    let parameters =
+        RequestParameters::from_search(query.to_owned()).set_pagination(PaginationParameters {
+            limit: RESULTS_PER_KIND,
+            offset: 0,
+        });
+
+    let (artists, releases, playlists) = thread::scope(|scope| {
+        let artists = scope.spawn(|| client.artist().get_all(Some(parameters.clone())));
+        let releases = scope.spawn(|| client.release().get_all(Some(parameters.clone())));
+        let playlists = client.search_playlists(query);
+
+        (
+            artists
+                .join()
+                .unwrap_or(Err(Error::Message("Artist search panicked."))),
+            releases
+                .join()
+                .unwrap_or(Err(Error::Message("Release search panicked."))),
+            playlists,
+        )
+    });
+
+    let entities = artists?
+        .data
+        .unwrap_or_default()
+        .into_iter()
+        .map(|artist| SearchEntity::Artist(Box::new(artist)))
+        .chain(
+            releases?
+                .data
+                .unwrap_or_default()
+                .into_iter()
+                .map(|release| SearchEntity::Release(Box::new(release))),
+        )
+        .chain(
+            playlists?
+                .into_iter()
+                .map(|playlist| SearchEntity::Playlist(Box::new(playlist))),
+        );
+
+    Ok(rank(query, entities))
+}
+
+/// Score and sort entities against a query, dropping those that don't match at all.
+pub fn rank(query: &str, entities: impl IntoIterator<Item = SearchEntity>) -> Vec<SearchResult> {
+    let mut results: Vec<SearchResult> = entities
+        .into_iter()
+        .map(|entity| SearchResult {
+            score: score(query, entity.title(), entity.uri()),
+            entity,
+        })
+        .filter(|result| result.score > 0.0)
+        .collect();
+
+    results.sort_by(|a, b| b.score.partial_cmp(&a.score).unwrap_or(Ordering::Equal));
+    results
+}
+
+/// Score how well a title (and optional URI) matches a query, between 0 and 1.
+pub fn score(query: &str, title: &str, uri: Option<&str>) -> f32 {
+    let query = normalize(query);
+    let title = normalize(title);
+
+    if query.is_empty() {
+        return 0.0;
+    }
+
+    if uri.map_or(false, |uri| normalize(uri) == query) {
+        1.0
+    } else if title == query {
+        0.9
+    } else if title.starts_with(&query) {
+        0.75
+    } else if title.contains(&query) {
+        0.6
+    } else {
+        match similarity(&query, &title) {
+            fuzzy if fuzzy >= DEFAULT_THRESHOLD => 0.5 * fuzzy,
+            _ => 0.0,
+        }
+    }
+}
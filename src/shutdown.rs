@@ -0,0 +1,126 @@
+use crate::client::{Client, Error};
+use crate::scheduler::RunningScheduler;
+use crate::watch::RunningPresaveTracker;
+use std::sync::Arc;
+
+/// Type for a step of shutting down.
+type ShutdownStep = Box<dyn FnOnce() -> Result<(), Error> + Send>;
+
+/// Shuts down what a long-running daemon started, like schedulers and
+/// clients, when dropped or when told to.
+///
+/// Steps run in the reverse order they were added, like variables are
+/// dropped, so add clients first and what uses them after. That way,
+/// schedulers stop before the clients their jobs use are shut down.
+///
+/// Example
+/// ```rust,no_run
+/// use bombay::client::{Client, SessionFile, SignedIn};
+/// use bombay::scheduler::Scheduler;
+/// use bombay::shutdown::ShutdownGuard;
+/// use std::sync::Arc;
+/// use std::time::Duration;
+///
+/// # fn example(mc: Client<SignedIn>) {
+/// let mc = Arc::new(mc.set_on_shutdown(|snapshot| {
+///     SessionFile::new(snapshot).save("session.json")
+/// }));
+/// let scheduler = Scheduler::new()
+///     .add_keep_alive(mc.clone(), Duration::from_secs(30 * 60))
+///     .spawn();
+///
+/// let guard = ShutdownGuard::new().add_client(mc).add_scheduler(scheduler);
+/// // Serve until told to stop, then shut down, or just drop the guard.
+/// for err in guard.shutdown() {
+///     eprintln!("Could not shut down cleanly: {err}");
+/// }
+/// # }
+/// ```
+#[derive(Default)]
+pub struct ShutdownGuard {
+    steps: Vec<(String, ShutdownStep)>,
+}
+
+impl std::fmt::Debug for ShutdownGuard {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ShutdownGuard")
+            .field(
+                "steps",
+                &self.steps.iter().map(|(name, _)| name).collect::<Vec<_>>(),
+            )
+            .finish()
+    }
+}
+
+impl ShutdownGuard {
+    /// Create a guard with nothing to shut down.
+    pub fn new() -> Self {
+        ShutdownGuard::default()
+    }
+
+    /// Add a step of shutting down, like flushing a file.
+    pub fn add(
+        mut self,
+        name: impl Into<String>,
+        step: impl FnOnce() -> Result<(), Error> + Send + 'static,
+    ) -> Self {
+        self.steps.push((name.into(), Box::new(step)));
+        self
+    }
+
+    /// Add a client to shut down, see [`Client::shutdown`].
+    pub fn add_client<ClientAuthState: Send + Sync + 'static>(
+        self,
+        client: Arc<Client<ClientAuthState>>,
+    ) -> Self {
+        self.add("client", move || client.shutdown())
+    }
+
+    /// Add a running scheduler to stop, letting its running jobs finish.
+    pub fn add_scheduler(self, scheduler: RunningScheduler) -> Self {
+        self.add("scheduler", move || {
+            scheduler.stop();
+            Ok(())
+        })
+    }
+
+    /// Add a running presave tracker to stop.
+    pub fn add_presave_tracker(self, tracker: RunningPresaveTracker) -> Self {
+        self.add("presave-tracker", move || {
+            tracker.stop();
+            Ok(())
+        })
+    }
+
+    /// Get the names of the steps, in the order they run.
+    pub fn step_names(&self) -> Vec<&str> {
+        self.steps
+            .iter()
+            .rev()
+            .map(|(name, _)| name.as_str())
+            .collect()
+    }
+
+    /// Shut everything down now, getting the errors of steps that failed.
+    ///
+    /// Every step runs, even after one fails.
+    pub fn shutdown(mut self) -> Vec<Error> {
+        self.run()
+    }
+
+    fn run(&mut self) -> Vec<Error> {
+        let mut errors = Vec::new();
+        while let Some((_, step)) = self.steps.pop() {
+            if let Err(err) = step() {
+                errors.push(err);
+            }
+        }
+        errors
+    }
+}
+
+impl Drop for ShutdownGuard {
+    fn drop(&mut self) {
+        self.run();
+    }
+}
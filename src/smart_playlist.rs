@@ -0,0 +1,314 @@
+use crate::client::{
+    Client, Error, PaginationParameters, PlaylistItemsMod, PlaylistItemsOperations,
+    RequestParameters, SignedIn,
+};
+use crate::diff::{self, Diff};
+use crate::matching::normalize;
+use crate::mc::playlist::{PlaylistID, PlaylistItem};
+use crate::mc::release::{AnyRelease, Genre, Track};
+use crate::progress::{Operation, ProgressEvent, Reporter};
+use iso8601_timestamp::Timestamp;
+use std::collections::{BTreeMap, HashSet};
+use std::sync::mpsc::Sender;
+
+/// Releases requested per page while materializing a smart playlist.
+const PAGE_SIZE: usize = 50;
+
+/// Condition a track must meet to be in a smart playlist, which rules
+/// combine into, like "genre is DnB and BPM is 170-180 and released after 2022".
+///
+/// Example
+/// ```rust
+/// use bombay::mc::release::{Genre, Track};
+/// use bombay::smart_playlist::Rule;
+///
+/// fn fits_the_set(track: &Track) -> bool {
+///     let rule = Rule::Genre(Genre::DrumAndBass).and(Rule::Bpm { min: 170, max: 180 });
+///     rule.matches(track)
+/// }
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+#[non_exhaustive]
+pub enum Rule {
+    /// Primary or secondary genre is the genre.
+    Genre(Genre),
+    /// BPM is within the range, inclusive.
+    Bpm { min: usize, max: usize },
+    /// Release date is after the timestamp.
+    ReleasedAfter(Timestamp),
+    /// Release date is before the timestamp.
+    ReleasedBefore(Timestamp),
+    /// Artists title contains the name, compared normalized.
+    Artist(String),
+    /// Track has the tag, see [`Track::has_tag`].
+    Tag(String),
+    /// Track is creator friendly.
+    CreatorFriendly,
+    /// Track is explicit.
+    Explicit,
+    /// Every rule matches.
+    All(Vec<Rule>),
+    /// At least one rule matches.
+    Any(Vec<Rule>),
+    /// The rule doesn't match.
+    Not(Box<Rule>),
+}
+
+impl Rule {
+    /// Combine with another rule that must also match.
+    pub fn and(self, other: Rule) -> Rule {
+        match self {
+            Rule::All(mut rules) => {
+                rules.push(other);
+                Rule::All(rules)
+            }
+            rule => Rule::All(vec![rule, other]),
+        }
+    }
+
+    /// Combine with another rule that may match instead.
+    pub fn or(self, other: Rule) -> Rule {
+        match self {
+            Rule::Any(mut rules) => {
+                rules.push(other);
+                Rule::Any(rules)
+            }
+            rule => Rule::Any(vec![rule, other]),
+        }
+    }
+
+    /// Get the opposite rule.
+    pub fn negate(self) -> Rule {
+        match self {
+            Rule::Not(rule) => *rule,
+            rule => Rule::Not(Box::new(rule)),
+        }
+    }
+
+    /// Check whether a track matches the rule.
+    pub fn matches(&self, track: &Track) -> bool {
+        match self {
+            Rule::Genre(genre) => track.has_genre(genre),
+            Rule::Bpm { min, max } => (*min..=*max).contains(&track.bpm),
+            Rule::ReleasedAfter(after) => track.release.release_date > *after,
+            Rule::ReleasedBefore(before) => track.release.release_date < *before,
+            Rule::Artist(name) => normalize(&track.artists_title).contains(&normalize(name)),
+            Rule::Tag(tag) => track.has_tag(tag),
+            Rule::CreatorFriendly => track.creator_friendly,
+            Rule::Explicit => track.explicit,
+            Rule::All(rules) => rules.iter().all(|rule| rule.matches(track)),
+            Rule::Any(rules) => rules.iter().any(|rule| rule.matches(track)),
+            Rule::Not(rule) => !rule.matches(track),
+        }
+    }
+
+    /// Get a genre every matching track must have, to narrow catalog queries.
+    fn required_genre(&self) -> Option<&Genre> {
+        match self {
+            Rule::Genre(genre) => Some(genre),
+            Rule::All(rules) => rules.iter().find_map(Rule::required_genre),
+            _ => None,
+        }
+    }
+}
+
+/// Playlist kept in sync with the catalog tracks matching a rule, like an
+/// iTunes smart playlist.
+///
+/// Example
+/// ```rust,no_run
+/// use bombay::client::{Client, SignedIn};
+/// use bombay::mc::playlist::PlaylistID;
+/// use bombay::mc::release::Genre;
+/// use bombay::smart_playlist::{Rule, SmartPlaylist};
+/// use iso8601_timestamp::Timestamp;
+///
+/// # fn example(mc: &Client<SignedIn>, playlist_id: PlaylistID) -> Result<(), bombay::client::Error> {
+/// let rule = Rule::Genre(Genre::DrumAndBass)
+///     .and(Rule::Bpm { min: 170, max: 180 })
+///     .and(Rule::ReleasedAfter(Timestamp::parse("2023-01-01T00:00:00Z").unwrap()));
+///
+/// let changes = SmartPlaylist::new(rule, playlist_id).sync(mc)?;
+/// println!("{}", changes);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct SmartPlaylist {
+    pub rule: Rule,
+    pub playlist_id: PlaylistID,
+    /// Most releases to scan in the catalog, newest first.
+    pub max_releases: usize,
+    progress: Reporter,
+}
+
+impl std::fmt::Debug for SmartPlaylist {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SmartPlaylist")
+            .field("rule", &self.rule)
+            .field("playlist_id", &self.playlist_id)
+            .field("max_releases", &self.max_releases)
+            .field("progress", &self.progress.is_set())
+            .finish()
+    }
+}
+
+/// Smart playlists are equal if they keep the same playlist in sync with the
+/// same rule, wherever their progress goes.
+impl PartialEq for SmartPlaylist {
+    fn eq(&self, other: &Self) -> bool {
+        self.rule == other.rule
+            && self.playlist_id == other.playlist_id
+            && self.max_releases == other.max_releases
+    }
+}
+
+impl SmartPlaylist {
+    /// Create a smart playlist keeping a playlist in sync with a rule,
+    /// scanning up to 500 releases.
+    pub fn new(rule: Rule, playlist_id: PlaylistID) -> Self {
+        SmartPlaylist {
+            rule,
+            playlist_id,
+            max_releases: 500,
+            progress: Reporter::default(),
+        }
+    }
+
+    /// Set the most releases to scan in the catalog, newest first.
+    pub fn set_max_releases(mut self, max_releases: usize) -> Self {
+        self.max_releases = max_releases;
+        self
+    }
+
+    /// Set a channel to send progress to, see [`ProgressEvent`]. Unset by
+    /// default.
+    ///
+    /// Each release scanned is an item, named by its catalog ID.
+    pub fn set_progress(mut self, sender: Option<Sender<ProgressEvent>>) -> Self {
+        self.progress = Reporter::new(sender);
+        self
+    }
+
+    /// Get the catalog tracks matching the rule, newest first.
+    ///
+    /// Releases are queried by genre when the rule requires one, and their
+    /// tracks are then fetched release by release.
+    pub fn materialize<ClientAuthState>(
+        &self,
+        client: &Client<ClientAuthState>,
+    ) -> Result<Vec<Track>, Error> {
+        self.progress.run(Operation::FetchReleases, None, || {
+            let mut tracks = Vec::new();
+            let mut seen = HashSet::new();
+            let mut offset = 0;
+
+            while offset < self.max_releases {
+                let parameters = RequestParameters::from_pagination(PaginationParameters {
+                    limit: PAGE_SIZE.min(self.max_releases - offset),
+                    offset,
+                });
+                let page = match self.rule.required_genre() {
+                    Some(genre) => client
+                        .release()
+                        .get_by_genre(genre.clone(), Some(parameters))?,
+                    None => client.release().get_all(Some(parameters))?,
+                };
+
+                for release in page.data.iter().flatten() {
+                    let candidates = match release {
+                        AnyRelease::Release(release) => {
+                            client.release().get_by_catalog_id(&release.catalog_id)?.1
+                        }
+                        AnyRelease::Track(track) => vec![track.clone()],
+                    };
+
+                    tracks.extend(
+                        candidates
+                            .into_iter()
+                            .filter(|track| self.rule.matches(track) && seen.insert(track.id)),
+                    );
+                    self.progress.send(ProgressEvent::ItemDone {
+                        item: release.get_catalog_id().to_owned(),
+                    });
+                }
+
+                offset = match page.next_offset() {
+                    Some(next) if next > offset => next,
+                    _ => break,
+                };
+            }
+
+            Ok(tracks)
+        })
+    }
+
+    /// Make the playlist contain exactly the tracks matching the rule, adding
+    /// and removing as needed.
+    ///
+    /// Every page of the playlist is read, so tracks already in it are never
+    /// added again.
+    ///
+    /// Returns the changes to the playlist, where each field is a track ID
+    /// with the track title as value, so added tracks have no value before
+    /// and removed tracks have none after.
+    pub fn sync(&self, client: &Client<SignedIn>) -> Result<Diff, Error> {
+        self.progress.run(Operation::SyncPlaylist, None, || {
+            let wanted = self.materialize(client)?;
+            let current = client.playlist().get_all_tracks(self.playlist_id)?;
+
+            let changes = diff::diff(&membership(&current), &membership(&wanted))
+                .map_err(Error::Deserialization)?;
+            let changed = |tracks: &[Track], added: bool| -> Vec<PlaylistItem> {
+                tracks
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, track)| {
+                        let path = format!("/{}", track.id);
+                        changes.changes.iter().any(|change| {
+                            change.path == path
+                                && match added {
+                                    true => change.before.is_none(),
+                                    false => change.after.is_none(),
+                                }
+                        })
+                    })
+                    .map(|(sort, track)| PlaylistItem {
+                        playlist_id: self.playlist_id,
+                        release_id: track.release.id,
+                        sort,
+                        track_id: track.id,
+                    })
+                    .collect()
+            };
+
+            let removals = changed(&current, false);
+            if !removals.is_empty() {
+                client.playlist().modify_items(
+                    self.playlist_id,
+                    PlaylistItemsOperations::Remove,
+                    PlaylistItemsMod { records: removals },
+                )?;
+            }
+
+            let additions = changed(&wanted, true);
+            if !additions.is_empty() {
+                client.playlist().modify_items(
+                    self.playlist_id,
+                    PlaylistItemsOperations::Add,
+                    PlaylistItemsMod { records: additions },
+                )?;
+            }
+
+            Ok(changes)
+        })
+    }
+}
+
+/// Get the tracks of a playlist as a map of track ID to title, to diff.
+fn membership(tracks: &[Track]) -> BTreeMap<String, &str> {
+    tracks
+        .iter()
+        .map(|track| (track.id.to_string(), track.title.as_str()))
+        .collect()
+}
@@ -0,0 +1,195 @@
+use crate::mc::artist::{AnyArtist, Artist, ArtistDetails, ArtistID, ReleaseArtist, Role};
+use crate::mc::label::Brand;
+use crate::mc::playlist::{Playlist, PlaylistID};
+use crate::mc::release::{CatalogID, Release, ReleaseID, ReleaseSummary, Track, TrackID};
+use crate::mc::tag::Tags;
+use iso8601_timestamp::Timestamp;
+use uuid::{uuid, Uuid};
+
+/// Date all factory models are released on.
+fn release_date() -> Timestamp {
+    Timestamp::parse("2022-09-22T16:00:00Z").expect("Factory date is valid.")
+}
+
+/// Get an artist, with only the mandatory details filled in.
+pub fn artist() -> Artist {
+    Artist {
+        about: None,
+        active_years: None,
+        cache_details: None,
+        details: ArtistDetails {
+            about: None,
+            bookings: None,
+            management: None,
+            management_details: None,
+            show_events: None,
+        },
+        featured_release_cover_file_id: None,
+        featured_release_id: None,
+        featured_video_url: None,
+        id: ArtistID(uuid!("0f1c3a6e-1f5b-4e9f-8a61-7f4e2b9c1d01")),
+        landscape_file_id: None,
+        links: None,
+        logo_file_id: None,
+        name: "Whales".to_owned(),
+        portrait_file_id: None,
+        profile_file_id: None,
+        public: true,
+        show_event: false,
+        square_file_id: None,
+        tags: None,
+        uri: "whales".to_owned(),
+    }
+}
+
+/// Get the primary artist of the factory release, as the release lists it.
+pub fn release_artist() -> AnyArtist {
+    AnyArtist::ReleaseArtist(Box::new(ReleaseArtist {
+        catalog_record_id: "MCS1186".to_owned(),
+        id: ArtistID(uuid!("0f1c3a6e-1f5b-4e9f-8a61-7f4e2b9c1d01")),
+        name: "Whales".to_owned(),
+        profile_file_id: None,
+        public: true,
+        role: Role::Primary,
+        uri: "whales".to_owned(),
+    }))
+}
+
+/// Get a single release, without its tracks.
+pub fn release() -> Release {
+    Release {
+        album_notes: None,
+        artists: Some(vec![release_artist()]),
+        artists_title: "Whales".to_owned(),
+        brand_id: Some(Brand::Uncaged),
+        brand_title: Some("Monstercat Uncaged".to_owned()),
+        cache_details: None,
+        catalog_id: CatalogID("MCS1186".to_owned()),
+        copyright_p_line: None,
+        cover_file_id: None,
+        description: String::new(),
+        downloadable: Some(true),
+        featured_artists_title: String::new(),
+        grid: None,
+        genre_primary: Some("Electronic".to_owned()),
+        genre_secondary: Some("Drum & Bass".to_owned()),
+        id: ReleaseID(uuid!("6a58b6d2-bbec-4847-8dcf-45023a930968")),
+        in_early_access: Some(false),
+        links: None,
+        prerelease_date: None,
+        presave_date: None,
+        release_date: release_date(),
+        release_date_timezone: "America/Vancouver".to_owned(),
+        spotify_id: None,
+        streamable: Some(true),
+        tags: None,
+        title: "Souvenir".to_owned(),
+        tracks: None,
+        kind: "Single".to_owned(),
+        upc: None,
+        version: String::new(),
+        youtube_url: None,
+    }
+}
+
+/// Get the summary of the factory release, as tracks include it.
+pub fn release_summary() -> ReleaseSummary {
+    let release = release();
+
+    ReleaseSummary {
+        artists_title: release.artists_title,
+        catalog_id: release.catalog_id.0,
+        copyright_p_line: release.copyright_p_line,
+        description: release.description,
+        id: release.id,
+        release_date: release.release_date,
+        release_date_timezone: release.release_date_timezone,
+        tags: release.tags,
+        title: release.title,
+        kind: release.kind,
+        upc: release.upc,
+        version: release.version,
+    }
+}
+
+/// Get the first track of the factory release.
+///
+/// Example
+/// ```rust
+/// use bombay::mc::release::Track;
+/// use bombay::testing::factories;
+///
+/// let remix = Track {
+///     version: "Remix".to_owned(),
+///     ..factories::track()
+/// };
+///
+/// assert!(remix.is_remix());
+/// assert!(remix.is_version_of(&factories::track()));
+/// ```
+pub fn track() -> Track {
+    Track {
+        artists: Some(vec![release_artist()]),
+        artists_title: "Whales".to_owned(),
+        bpm: 174,
+        brand: "Monstercat Uncaged".to_owned(),
+        brand_id: 1,
+        creator_friendly: true,
+        debut_date: Some(release_date()),
+        downloadable: true,
+        duration: 201,
+        explicit: false,
+        acousticness: None,
+        danceability: None,
+        energy: None,
+        instrumentalness: None,
+        liveness: None,
+        loudness: None,
+        speechiness: None,
+        valence: None,
+        genre_primary: "Electronic".to_owned(),
+        genre_secondary: "Drum & Bass".to_owned(),
+        isrc: "CA6D22100326".to_owned(),
+        id: TrackID(uuid!("3c1c5bd2-9f5e-4d0b-a7a7-2bb3d1c4a0e1")),
+        in_early_access: false,
+        lock_status: String::new(),
+        public: true,
+        playlist_sort: None,
+        release: release_summary(),
+        streamable: true,
+        tags: Some(Tags(vec!["drum-and-bass".to_owned()])),
+        title: "Souvenir".to_owned(),
+        track_number: 1,
+        version: String::new(),
+    }
+}
+
+/// Get some tracks of the factory release, each with its own ID and track number.
+pub fn tracks(count: usize) -> Vec<Track> {
+    (0..count)
+        .map(|i| Track {
+            id: TrackID(Uuid::from_u128(i as u128 + 1)),
+            track_number: i + 1,
+            ..track()
+        })
+        .collect()
+}
+
+/// Get an empty public playlist.
+pub fn playlist() -> Playlist {
+    Playlist {
+        archived: false,
+        background_file_id: None,
+        created_at: "2024-01-01T00:00:00Z".to_owned(),
+        description: String::new(),
+        id: PlaylistID(uuid!("f4a8b5a4-4c2c-4b5e-9fd1-6a6a3a37d1a2")),
+        is_public: true,
+        items: None,
+        my_library: false,
+        num_records: 0,
+        tile_file_id: None,
+        title: "Favorites".to_owned(),
+        updated_at: "2024-01-01T00:00:00Z".to_owned(),
+        user_id: None,
+    }
+}
@@ -0,0 +1,230 @@
+/// Recorded response of an MC API endpoint.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Fixture {
+    /// Short name of the fixture.
+    pub name: &'static str,
+    /// Path of the endpoint, relative to the player API, or starting with
+    /// `/api/` for the WWW API. It may end with a query the request must
+    /// have the parameters of, like `?offset=2`.
+    pub path: &'static str,
+    /// JSON response body.
+    pub body: &'static str,
+}
+
+/// Response of a release and its tracks by catalog ID.
+pub const RELEASE: Fixture = Fixture {
+    name: "release",
+    path: "/catalog/release/MCS1186",
+    body: include_str!("release.json"),
+};
+
+/// Response of the latest releases, with a release and a track.
+pub const LATEST_RELEASES: Fixture = Fixture {
+    name: "latest_releases",
+    path: "/catalog/latest-releases",
+    body: include_str!("latest_releases.json"),
+};
+
+/// Response of the releases of all artists.
+pub const RELEASES: Fixture = Fixture {
+    name: "releases",
+    path: "/releases",
+    body: include_str!("releases.json"),
+};
+
+/// Response of the releases of an artist.
+pub const ARTIST_RELEASES: Fixture = Fixture {
+    name: "artist_releases",
+    path: "/artist/whales/releases",
+    body: include_str!("releases.json"),
+};
+
+/// Response of the releases related to a release.
+pub const RELATED_RELEASES: Fixture = Fixture {
+    name: "related_releases",
+    path: "/related-releases/6a58b6d2-bbec-4847-8dcf-45023a930968",
+    body: include_str!("latest_releases.json"),
+};
+
+/// Response of the tracks similar to a track.
+pub const SIMILAR_TRACKS: Fixture = Fixture {
+    name: "similar_tracks",
+    path: "/similar-tracks/3c1c5bd2-9f5e-4d0b-a7a7-2bb3d1c4a0e1",
+    body: include_str!("similar_tracks.json"),
+};
+
+/// Response of the audio features of a track, which come with lowercase keys.
+pub const TRACK_FEATURES: Fixture = Fixture {
+    name: "track_features",
+    path: "/track/3c1c5bd2-9f5e-4d0b-a7a7-2bb3d1c4a0e1/features",
+    body: include_str!("track_features.json"),
+};
+
+/// Response of a release page, from the WWW API.
+pub const RELEASE_PAGE: Fixture = Fixture {
+    name: "release_page",
+    path: "/api/release/MCS1186",
+    body: include_str!("release_page.json"),
+};
+
+/// Response of all artists, with PascalCase details.
+pub const ARTISTS: Fixture = Fixture {
+    name: "artists",
+    path: "/artists",
+    body: include_str!("artists.json"),
+};
+
+/// Response of an artist by name URI, with camelCase details.
+pub const ARTIST: Fixture = Fixture {
+    name: "artist",
+    path: "/artist/whales",
+    body: include_str!("artist.json"),
+};
+
+/// Response of an artist by ID.
+pub const ARTIST_BY_ID: Fixture = Fixture {
+    name: "artist_by_id",
+    path: "/artist/0f1c3a6e-1f5b-4e9f-8a61-7f4e2b9c1d01",
+    body: include_str!("artist.json"),
+};
+
+/// Response of the latest artists.
+pub const LATEST_ARTISTS: Fixture = Fixture {
+    name: "latest_artists",
+    path: "/latest-artists",
+    body: include_str!("latest_artists.json"),
+};
+
+/// Response of all moods.
+pub const MOODS: Fixture = Fixture {
+    name: "moods",
+    path: "/moods",
+    body: include_str!("moods.json"),
+};
+
+/// Response of a mood by name URI.
+pub const MOOD: Fixture = Fixture {
+    name: "mood",
+    path: "/mood/chill",
+    body: include_str!("mood.json"),
+};
+
+/// Response of a mood by ID.
+pub const MOOD_BY_ID: Fixture = Fixture {
+    name: "mood_by_id",
+    path: "/mood/5f0e4a4e-7b5d-4c55-9f0c-0d3c6b0b9a11",
+    body: include_str!("mood.json"),
+};
+
+/// Response of the releases matching a mood.
+pub const MOOD_CATALOG: Fixture = Fixture {
+    name: "mood_catalog",
+    path: "/mood/chill/catalog",
+    body: include_str!("latest_releases.json"),
+};
+
+/// Response of upcoming events.
+pub const EVENTS: Fixture = Fixture {
+    name: "events",
+    path: "/events",
+    body: include_str!("events.json"),
+};
+
+/// Response of a playlist by ID.
+pub const PLAYLIST: Fixture = Fixture {
+    name: "playlist",
+    path: "/playlist/f4a8b5a4-4c2c-4b5e-9fd1-6a6a3a37d1a2",
+    body: include_str!("playlist.json"),
+};
+
+/// Response of the releases of a playlist.
+pub const PLAYLIST_CATALOG: Fixture = Fixture {
+    name: "playlist_catalog",
+    path: "/playlist/f4a8b5a4-4c2c-4b5e-9fd1-6a6a3a37d1a2/catalog",
+    body: include_str!("latest_releases.json"),
+};
+
+/// Response of the signed-in user's playlists.
+pub const PLAYLISTS: Fixture = Fixture {
+    name: "playlists",
+    path: "/playlists",
+    body: include_str!("playlists.json"),
+};
+
+/// Response of creating a playlist.
+pub const PLAYLIST_CREATED: Fixture = Fixture {
+    name: "playlist_created",
+    path: "/playlist",
+    body: r#"{"Id": "b7e1c2d4-5a6f-4e3b-9c8d-1f2a3b4c5d6e"}"#,
+};
+
+/// Response of adding items to the created playlist.
+pub const PLAYLIST_ITEMS_ADDED: Fixture = Fixture {
+    name: "playlist_items_added",
+    path: "/playlist/b7e1c2d4-5a6f-4e3b-9c8d-1f2a3b4c5d6e/modify-items",
+    body: "{}",
+};
+
+/// Response of the signed-in user's information and settings.
+pub const ME: Fixture = Fixture {
+    name: "me",
+    path: "/me",
+    body: include_str!("me.json"),
+};
+
+/// Response of the signed-in user's licenses.
+pub const LICENSES: Fixture = Fixture {
+    name: "licenses",
+    path: "/self/licenses",
+    body: include_str!("licenses.json"),
+};
+
+/// Response of the identifiers of all charts.
+pub const CHARTS: Fixture = Fixture {
+    name: "charts",
+    path: "/charts",
+    body: include_str!("charts.json"),
+};
+
+/// Response of the weekly chart.
+pub const CHART: Fixture = Fixture {
+    name: "chart",
+    path: "/chart/weekly",
+    body: include_str!("chart.json"),
+};
+
+/// All fixtures.
+pub const ALL: &[Fixture] = &[
+    RELEASE,
+    LATEST_RELEASES,
+    RELEASES,
+    ARTIST_RELEASES,
+    RELATED_RELEASES,
+    SIMILAR_TRACKS,
+    TRACK_FEATURES,
+    RELEASE_PAGE,
+    ARTISTS,
+    ARTIST,
+    ARTIST_BY_ID,
+    LATEST_ARTISTS,
+    MOODS,
+    MOOD,
+    MOOD_BY_ID,
+    MOOD_CATALOG,
+    EVENTS,
+    PLAYLIST,
+    PLAYLIST_CATALOG,
+    PLAYLISTS,
+    PLAYLIST_CREATED,
+    PLAYLIST_ITEMS_ADDED,
+    ME,
+    LICENSES,
+    CHARTS,
+    CHART,
+];
+
+/// Get a fixture by the path of its endpoint, ignoring any query.
+pub fn by_path(path: &str) -> Option<&'static Fixture> {
+    let path = path.split('?').next().unwrap_or_default();
+    ALL.iter().find(|fixture| fixture.path == path)
+}
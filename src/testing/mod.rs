@@ -0,0 +1,10 @@
+/// Module containing valid model instances, to adjust with struct update
+/// syntax rather than filling every field by hand.
+pub mod factories;
+
+/// Module containing recorded MC API responses, to test and benchmark
+/// without hitting the MC API.
+pub mod fixtures;
+
+/// Module containing a local HTTP server of fixtures, to point a client at.
+pub mod server;
@@ -0,0 +1,298 @@
+use crate::testing::fixtures::Fixture;
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+/// Fault injected into the responses of an endpoint, to test how an
+/// application handles a flaky network, like its retries and backoff.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Fault {
+    /// Answer after a delay, like a slow network.
+    Delay(Duration),
+    /// Answer with an HTTP status, like 500 or 503, and an empty JSON object.
+    Status(u16),
+    /// Answer with a body that isn't valid JSON.
+    MalformedJson,
+    /// Close the connection without answering.
+    ///
+    /// HTTP clients may quietly try a request again on a new connection when
+    /// a reused one closes, so a reset injected only once may go unnoticed.
+    ConnectionReset,
+    /// Answer with a permanent redirect to a URL, or a path of the server,
+    /// like a link that moved.
+    Redirect(String),
+    /// Send the first bytes of the body, then close the connection, like a
+    /// download dropped midway.
+    Truncate(usize),
+    /// Answer requests for part of the body with all of it, like servers
+    /// without range support.
+    IgnoreRange,
+    /// Answer requests for part of the body with the part from another byte
+    /// offset, like a misbehaving server.
+    RangeFrom(usize),
+}
+
+/// Fault of some requests to a path.
+#[derive(Clone, Debug)]
+struct FaultRule {
+    path: String,
+    fault: Fault,
+    /// Requests left to inject the fault into, or `None` for all of them.
+    remaining: Option<usize>,
+}
+
+/// Faults injected into the responses of a fixture server, by endpoint path.
+///
+/// Paths are matched like fixtures, ignoring any query, and `*` matches
+/// every path. A request gets the delays of every matching fault, then the
+/// first matching fault of another kind, if any.
+///
+/// Example
+/// ```rust
+/// # #[cfg(feature = "client")] {
+/// use bombay::client::Client;
+/// use bombay::testing::server::{self, Fault, Faults};
+/// use bombay::testing::fixtures;
+///
+/// let faults = Faults::new()
+///     .add_times("/catalog/latest-releases", Fault::Status(503), 1)
+///     .add("/moods", Fault::MalformedJson);
+/// let url = server::serve_with_faults(fixtures::ALL, faults);
+/// let mc = Client::new(url.clone(), format!("{url}/"));
+///
+/// // The first request fails, and trying again works.
+/// assert_eq!(mc.release().get_latest(None).unwrap_err().status(), Some(503));
+/// assert!(mc.release().get_latest(None).is_ok());
+/// assert!(mc.mood().get_all(None).is_err());
+/// # }
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct Faults {
+    rules: Vec<FaultRule>,
+}
+
+impl Faults {
+    /// Create faults of no endpoints.
+    pub fn new() -> Self {
+        Faults::default()
+    }
+
+    /// Inject a fault into every request to a path.
+    pub fn add(mut self, path: impl Into<String>, fault: Fault) -> Self {
+        self.rules.push(FaultRule {
+            path: path.into(),
+            fault,
+            remaining: None,
+        });
+        self
+    }
+
+    /// Inject a fault into the first few requests to a path, then answer
+    /// normally, like an endpoint that recovers.
+    pub fn add_times(mut self, path: impl Into<String>, fault: Fault, times: usize) -> Self {
+        self.rules.push(FaultRule {
+            path: path.into(),
+            fault,
+            remaining: Some(times),
+        });
+        self
+    }
+
+    /// Get the faults to inject into a request to a path, counting it.
+    fn take(&mut self, path: &str) -> Vec<Fault> {
+        let mut faults = Vec::new();
+        for rule in &mut self.rules {
+            if rule.path != "*" && rule.path != path {
+                continue;
+            }
+            match &mut rule.remaining {
+                Some(0) => continue,
+                Some(remaining) => *remaining -= 1,
+                None => {}
+            }
+            faults.push(rule.fault.clone());
+        }
+        faults
+    }
+}
+
+/// Serve fixtures over HTTP on a local port, until the process exits.
+///
+/// Requests are answered with the fixture of the same path, or 404 if there
+/// is none. Bodies are ignored, and so are queries, unless a fixture's path
+/// has one, like `/playlist/{id}/catalog?offset=2` for a later page. Returns the base URL, to create a client
+/// with, where the WWW API is the base URL with a trailing slash.
+///
+/// Fixtures starting with `#EXTM3U` are served as HLS playlists, and others
+/// as JSON. Requests for part of a fixture, like `Range: bytes=4-` when a
+/// stream resumes, get that part.
+///
+/// Example
+/// ```rust
+/// # #[cfg(feature = "client")] {
+/// use bombay::client::Client;
+/// use bombay::testing::{fixtures, server};
+///
+/// let url = server::serve(fixtures::ALL);
+/// let mc = Client::new(url.clone(), format!("{url}/"));
+///
+/// let latest = mc.release().get_latest(None).unwrap();
+/// assert_eq!(latest.total, 2);
+/// # }
+/// ```
+pub fn serve(fixtures: &'static [Fixture]) -> String {
+    serve_with_faults(fixtures, Faults::default())
+}
+
+/// Serve fixtures like [`serve`], injecting faults into the responses of
+/// some endpoints, see [`Faults`].
+pub fn serve_with_faults(fixtures: &'static [Fixture], faults: Faults) -> String {
+    let faults = Arc::new(Mutex::new(faults));
+    let listener = TcpListener::bind("127.0.0.1:0").expect("Could not bind a local port.");
+    let url = format!(
+        "http://{}",
+        listener.local_addr().expect("Could not get local address.")
+    );
+
+    thread::spawn(move || {
+        for stream in listener.incoming().flatten() {
+            let faults = faults.clone();
+            thread::spawn(move || serve_connection(fixtures, &faults, stream));
+        }
+    });
+
+    url
+}
+
+/// Find the fixture of a request, preferring fixtures with a query the
+/// request's query has every parameter of, like pages by offset.
+fn find_fixture<'a>(fixtures: &'a [Fixture], path: &str, query: &str) -> Option<&'a Fixture> {
+    let parameters: Vec<&str> = query.split('&').collect();
+    let mut candidates = fixtures.iter().filter_map(|fixture| {
+        let (fixture_path, fixture_query) =
+            fixture.path.split_once('?').unwrap_or((fixture.path, ""));
+        (fixture_path == path).then_some((fixture, fixture_query))
+    });
+    candidates
+        .clone()
+        .find(|(_, fixture_query)| {
+            !fixture_query.is_empty()
+                && fixture_query
+                    .split('&')
+                    .all(|parameter| parameters.contains(&parameter))
+        })
+        .or_else(|| candidates.find(|(_, fixture_query)| fixture_query.is_empty()))
+        .map(|(fixture, _)| fixture)
+}
+
+/// Answer requests on a kept-alive connection until the client closes it.
+fn serve_connection(fixtures: &[Fixture], faults: &Mutex<Faults>, stream: TcpStream) {
+    let mut reader = match stream.try_clone() {
+        Ok(stream) => BufReader::new(stream),
+        Err(_) => return,
+    };
+    let mut writer = stream;
+
+    loop {
+        let mut request_line = String::new();
+        if reader.read_line(&mut request_line).unwrap_or(0) == 0 {
+            return;
+        }
+
+        // Skip the headers, and the body if there is one.
+        let mut header = String::new();
+        let mut body_len = 0;
+        let mut range = None;
+        while reader.read_line(&mut header).unwrap_or(0) > 2 {
+            if let Some((name, value)) = header.split_once(':') {
+                if name.eq_ignore_ascii_case("content-length") {
+                    body_len = value.trim().parse().unwrap_or(0);
+                } else if name.eq_ignore_ascii_case("range") {
+                    range = value
+                        .trim()
+                        .strip_prefix("bytes=")
+                        .and_then(|value| value.strip_suffix('-'))
+                        .and_then(|from| from.parse::<usize>().ok());
+                }
+            }
+            header.clear();
+        }
+        if io::copy(&mut (&mut reader).take(body_len), &mut io::sink()).is_err() {
+            return;
+        }
+
+        let target = request_line.split(' ').nth(1).unwrap_or_default();
+        let (path, query) = target.split_once('?').unwrap_or((target, ""));
+        let (mut status, mut body) = match find_fixture(fixtures, path, query) {
+            Some(fixture) => ("200 OK".to_owned(), fixture.body.as_bytes()),
+            None => ("404 Not Found".to_owned(), b"{}".as_slice()),
+        };
+        let content_type = match body.starts_with(b"#EXTM3U") {
+            true => "application/vnd.apple.mpegurl",
+            false => "application/json",
+        };
+        let mut headers = format!("Content-Type: {content_type}\r\n");
+        let mut sent = None;
+
+        let faults = faults
+            .lock()
+            .unwrap_or_else(|err| err.into_inner())
+            .take(path);
+        for fault in &faults {
+            if let Fault::Delay(delay) = fault {
+                thread::sleep(*delay);
+            }
+        }
+        let fault = faults
+            .iter()
+            .find(|fault| !matches!(fault, Fault::Delay(_)));
+        let range = match fault {
+            Some(Fault::IgnoreRange) => None,
+            Some(Fault::RangeFrom(from)) => range.map(|_| *from),
+            _ => range,
+        };
+        match fault {
+            Some(Fault::Status(code)) => {
+                status = format!("{code} Injected Fault");
+                body = b"{}";
+            }
+            Some(Fault::MalformedJson) => body = br#"{"Data": [{"Id": "#,
+            Some(Fault::ConnectionReset) => return,
+            Some(Fault::Redirect(location)) => {
+                status = "301 Moved Permanently".to_owned();
+                headers = format!("Location: {location}\r\n");
+                body = b"";
+            }
+            Some(Fault::Truncate(len)) => sent = Some(*len),
+            _ => {}
+        }
+        if let Some(from) = range.filter(|from| status.starts_with("200") && *from < body.len()) {
+            status = "206 Partial Content".to_owned();
+            headers += &format!(
+                "Content-Range: bytes {from}-{}/{}\r\n",
+                body.len() - 1,
+                body.len()
+            );
+            body = &body[from..];
+        }
+
+        let head = format!(
+            "HTTP/1.1 {status}\r\n{headers}Content-Length: {}\r\n\r\n",
+            body.len()
+        );
+        // Answers to HEAD requests only have the headers of the body.
+        if request_line.starts_with("HEAD ") {
+            body = b"";
+        }
+        let sent = &body[..sent.unwrap_or(body.len()).min(body.len())];
+        if writer.write_all(head.as_bytes()).is_err() || writer.write_all(sent).is_err() {
+            return;
+        }
+        if sent.len() < body.len() {
+            return;
+        }
+    }
+}
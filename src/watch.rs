@@ -0,0 +1,346 @@
+use crate::client::{Client, Error};
+use crate::diff;
+use crate::mc::artist::Artist;
+use crate::mc::release::{AnyRelease, Release, ReleaseID};
+use crate::mc::util::Link;
+use iso8601_timestamp::Timestamp;
+use std::collections::HashMap;
+use std::sync::mpsc::{self, RecvTimeoutError, Sender};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+/// Change noticed on an artist's page.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ArtistChange {
+    /// Name URI of the artist that changed.
+    pub uri: String,
+    pub kind: ArtistChangeKind,
+}
+
+/// Kinds of changes on an artist's page.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ArtistChangeKind {
+    About {
+        before: Option<String>,
+        after: Option<String>,
+    },
+    FeaturedRelease {
+        before: Option<String>,
+        after: Option<String>,
+    },
+    LinkAdded(Link),
+    LinkRemoved(Link),
+    Name {
+        before: String,
+        after: String,
+    },
+}
+
+impl ArtistChange {
+    /// Get the changes between two versions of an artist's page.
+    pub fn between(before: &Artist, after: &Artist) -> Result<Vec<ArtistChange>, Error> {
+        let diff = diff::diff(before, after).map_err(Error::Deserialization)?;
+        let mut kinds = Vec::new();
+
+        if diff.changed("/Name") {
+            kinds.push(ArtistChangeKind::Name {
+                before: before.name.clone(),
+                after: after.name.clone(),
+            });
+        }
+
+        if diff.changed("/About") {
+            kinds.push(ArtistChangeKind::About {
+                before: before.about.clone(),
+                after: after.about.clone(),
+            });
+        }
+
+        if diff.changed("/FeaturedReleaseId") {
+            kinds.push(ArtistChangeKind::FeaturedRelease {
+                before: before.featured_release_id.clone(),
+                after: after.featured_release_id.clone(),
+            });
+        }
+
+        // Links are compared as a set, so reordering them is no change.
+        let links_before = before.links.as_deref().unwrap_or_default();
+        let links_after = after.links.as_deref().unwrap_or_default();
+        kinds.extend(
+            links_after
+                .iter()
+                .filter(|link| !links_before.contains(link))
+                .map(|link| ArtistChangeKind::LinkAdded(link.clone())),
+        );
+        kinds.extend(
+            links_before
+                .iter()
+                .filter(|link| !links_after.contains(link))
+                .map(|link| ArtistChangeKind::LinkRemoved(link.clone())),
+        );
+
+        Ok(kinds
+            .into_iter()
+            .map(|kind| ArtistChange {
+                uri: after.uri.clone(),
+                kind,
+            })
+            .collect())
+    }
+}
+
+/// Polls artist pages and reports what changed since the last poll, like a
+/// new link or an updated about section.
+///
+/// The first poll of an artist only records their page, so changes are
+/// reported from the second poll on.
+///
+/// Example
+/// ```rust,no_run
+/// use bombay::client::Client;
+/// use bombay::watch::ArtistWatcher;
+/// use std::{thread, time::Duration};
+///
+/// let mc = Client::default(); // Without authentication.
+/// let mut watcher = ArtistWatcher::new(&mc, ["rogue", "ace-aura"]);
+///
+/// loop {
+///   for change in watcher.poll().expect("Could not poll artists.") {
+///     println!("{} changed: {:?}", change.uri, change.kind);
+///   }
+///   thread::sleep(Duration::from_secs(600));
+/// }
+/// ```
+pub struct ArtistWatcher<'a, ClientAuthState> {
+    client: &'a Client<ClientAuthState>,
+    uris: Vec<String>,
+    known: HashMap<String, Artist>,
+}
+
+impl<'a, ClientAuthState> ArtistWatcher<'a, ClientAuthState> {
+    /// Create a watcher of some artists, by name URI.
+    pub fn new(
+        client: &'a Client<ClientAuthState>,
+        uris: impl IntoIterator<Item = impl Into<String>>,
+    ) -> Self {
+        ArtistWatcher {
+            client,
+            uris: uris.into_iter().map(Into::into).collect(),
+            known: HashMap::new(),
+        }
+    }
+
+    /// Start watching another artist, by name URI.
+    pub fn watch(&mut self, uri: impl Into<String>) {
+        let uri = uri.into();
+        if !self.uris.contains(&uri) {
+            self.uris.push(uri);
+        }
+    }
+
+    /// Stop watching an artist, by name URI.
+    pub fn unwatch(&mut self, uri: &str) {
+        self.uris.retain(|watched| watched != uri);
+        self.known.remove(uri);
+    }
+
+    /// Get the last seen page of an artist, by name URI.
+    pub fn last_seen(&self, uri: &str) -> Option<&Artist> {
+        self.known.get(uri)
+    }
+
+    /// Fetch every watched artist's page and get the changes since the last poll.
+    ///
+    /// Stops at the first artist that can't be fetched, keeping what was
+    /// seen of the artists before it.
+    pub fn poll(&mut self) -> Result<Vec<ArtistChange>, Error> {
+        let mut changes = Vec::new();
+
+        for uri in &self.uris {
+            let artist = self.client.artist().get_by_name_uri(uri)?;
+            if let Some(before) = self.known.get(uri) {
+                changes.extend(ArtistChange::between(before, &artist)?);
+            }
+            self.known.insert(uri.clone(), artist);
+        }
+
+        Ok(changes)
+    }
+}
+
+/// Type for callback function provided to be told a tracked release is out.
+type ReleasedFn = dyn Fn(&Release) + Send + Sync;
+
+/// Keeps track of announced releases the user is interested in, like ones
+/// they presaved, and tells when they come out.
+///
+/// Time is driven by calling [`PresaveTracker::tick`], or by a background
+/// thread with [`PresaveTracker::spawn`].
+///
+/// Example
+/// ```rust,no_run
+/// use bombay::client::Client;
+/// use bombay::mc::release::{AnyRelease, CatalogID};
+/// use bombay::watch::PresaveTracker;
+/// use std::time::Duration;
+///
+/// let mc = Client::default(); // Without authentication.
+/// let (release, _) = mc
+///     .release()
+///     .get_by_catalog_id(&CatalogID("MCS1186".to_owned()))
+///     .expect("Could not get release.");
+///
+/// let mut tracker = PresaveTracker::new()
+///     .set_on_release(|release| println!("{} is out!", release.title));
+/// if let AnyRelease::Release(release) = release {
+///     tracker.track(release);
+/// }
+///
+/// let running = tracker.spawn(Duration::from_secs(60));
+/// // Later, when shutting down.
+/// let _tracker = running.stop();
+/// ```
+#[derive(Default)]
+pub struct PresaveTracker {
+    pending: Vec<Release>,
+    on_release: Option<Arc<ReleasedFn>>,
+}
+
+impl PresaveTracker {
+    /// Create a tracker of no releases.
+    pub fn new() -> Self {
+        PresaveTracker::default()
+    }
+
+    /// Set the callback called with each tracked release when it comes out.
+    pub fn set_on_release(mut self, callback: impl Fn(&Release) + Send + Sync + 'static) -> Self {
+        self.on_release = Some(Arc::new(callback));
+        self
+    }
+
+    /// Start tracking a release, unless it is already out or tracked.
+    ///
+    /// Returns whether the release is now tracked.
+    pub fn track(&mut self, release: Release) -> bool {
+        if release.release_date <= Timestamp::now_utc()
+            || self.pending.iter().any(|pending| pending.id == release.id)
+        {
+            return false;
+        }
+
+        let index = self
+            .pending
+            .partition_point(|pending| pending.release_date <= release.release_date);
+        self.pending.insert(index, release);
+        true
+    }
+
+    /// Stop tracking a release.
+    pub fn untrack(&mut self, release_id: &ReleaseID) {
+        self.pending.retain(|pending| pending.id != *release_id);
+    }
+
+    /// Get the tracked releases not out yet, soonest first.
+    pub fn pending(&self) -> &[Release] {
+        &self.pending
+    }
+
+    /// Get when the next tracked release comes out.
+    pub fn next_release_at(&self) -> Option<&Timestamp> {
+        self.pending.first().map(|release| &release.release_date)
+    }
+
+    /// Fetch the tracked releases again, in case their release date moved.
+    ///
+    /// Stops at the first release that can't be fetched, keeping the
+    /// releases as they were.
+    pub fn refresh<ClientAuthState>(
+        &mut self,
+        client: &Client<ClientAuthState>,
+    ) -> Result<(), Error> {
+        let mut refreshed = Vec::new();
+        for pending in &self.pending {
+            match client.release().get_by_catalog_id(&pending.catalog_id)?.0 {
+                AnyRelease::Release(release) => refreshed.push(release),
+                AnyRelease::Track(_) => refreshed.push(pending.clone()),
+            }
+        }
+
+        refreshed.sort_by_key(|release| release.release_date);
+        self.pending = refreshed;
+        Ok(())
+    }
+
+    /// Stop tracking the releases that are out by now, calling the callback
+    /// with each of them.
+    ///
+    /// Returns the releases that came out since the last tick.
+    pub fn tick(&mut self) -> Vec<Release> {
+        self.tick_at(Timestamp::now_utc())
+    }
+
+    /// Same as [`PresaveTracker::tick`], as if it were some time.
+    pub fn tick_at(&mut self, now: Timestamp) -> Vec<Release> {
+        let out = self
+            .pending
+            .partition_point(|pending| pending.release_date <= now);
+        let released: Vec<Release> = self.pending.drain(..out).collect();
+
+        if let Some(callback) = &self.on_release {
+            released.iter().for_each(|release| callback(release));
+        }
+
+        released
+    }
+
+    /// Tick on a background thread at some interval, until stopped.
+    pub fn spawn(self, interval: Duration) -> RunningPresaveTracker {
+        let tracker = Arc::new(Mutex::new(self));
+        let (stop, stopped) = mpsc::channel::<()>();
+
+        let ticked = tracker.clone();
+        let thread = thread::spawn(move || {
+            while let Err(RecvTimeoutError::Timeout) = stopped.recv_timeout(interval) {
+                lock(&ticked).tick();
+            }
+        });
+
+        RunningPresaveTracker {
+            tracker,
+            stop,
+            thread,
+        }
+    }
+}
+
+/// Presave tracker ticking on a background thread, see
+/// [`PresaveTracker::spawn`].
+pub struct RunningPresaveTracker {
+    tracker: Arc<Mutex<PresaveTracker>>,
+    stop: Sender<()>,
+    thread: JoinHandle<()>,
+}
+
+impl RunningPresaveTracker {
+    /// Use the tracker in between ticks, like to track another release.
+    pub fn with<T>(&self, use_tracker: impl FnOnce(&mut PresaveTracker) -> T) -> T {
+        use_tracker(&mut lock(&self.tracker))
+    }
+
+    /// Stop the background thread, and get the tracker back.
+    pub fn stop(self) -> PresaveTracker {
+        let _ = self.stop.send(());
+        let _ = self.thread.join();
+
+        match Arc::try_unwrap(self.tracker) {
+            Ok(tracker) => tracker.into_inner().unwrap_or_else(|err| err.into_inner()),
+            Err(tracker) => std::mem::take(&mut *lock(&tracker)),
+        }
+    }
+}
+
+fn lock(tracker: &Mutex<PresaveTracker>) -> MutexGuard<'_, PresaveTracker> {
+    tracker.lock().unwrap_or_else(|err| err.into_inner())
+}
@@ -0,0 +1,25 @@
+use bombay::accounts::AccountManager;
+use bombay::client::Error;
+
+#[test]
+fn restore_sessions() -> Result<(), Error> {
+    let mut accounts = AccountManager::new();
+
+    accounts.restore("first@example.com".to_owned(), "first-session".to_owned())?;
+    accounts.restore("second@example.com".to_owned(), "second-session".to_owned())?;
+
+    assert_eq!(accounts.selected_email(), Some("first@example.com"));
+    accounts.select("second@example.com")?;
+    assert_eq!(accounts.selected_email(), Some("second@example.com"));
+    assert!(accounts.select("third@example.com").is_err());
+
+    // Cookie stores are isolated between accounts.
+    let sessions = accounts.sessions();
+    assert_eq!(sessions["first@example.com"], "first-session");
+    assert_eq!(sessions["second@example.com"], "second-session");
+
+    accounts.remove("second@example.com");
+    assert!(accounts.selected().is_none());
+
+    Ok(())
+}
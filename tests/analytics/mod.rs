@@ -0,0 +1,97 @@
+use bombay::analytics::{self, Period, Report, Trend};
+use bombay::mc::release::Release;
+use bombay::testing::factories;
+use iso8601_timestamp::Timestamp;
+
+fn release(date: &str, genre: Option<&str>, label: &str, artists_title: &str) -> Release {
+    let mut release = factories::release();
+    release.release_date = Timestamp::parse(date).unwrap();
+    release.genre_primary = genre.map(str::to_owned);
+    release.brand_title = Some(label.to_owned());
+    release.artists = None;
+    release.artists_title = artists_title.to_owned();
+    release
+}
+
+#[test]
+fn report_of_releases() {
+    let releases = [
+        release(
+            "2019-03-01T16:00:00Z",
+            Some("Drum & Bass"),
+            "Uncaged",
+            "Whales",
+        ),
+        release("2020-05-01T16:00:00Z", Some("Dubstep"), "Uncaged", "Whales"),
+        release("2020-06-01T16:00:00Z", Some("Dubstep"), "Uncaged", "Rogue"),
+        release("2022-01-01T16:00:00Z", Some("Dubstep"), "Instinct", "Rogue"),
+        release("2022-02-01T16:00:00Z", Some("Dubstep"), "Instinct", "Rogue"),
+        release("2022-02-02T16:00:00Z", Some("House"), "Instinct", "Rogue"),
+        // Just after midnight UTC, still 2020 in Vancouver.
+        release("2021-01-01T00:30:00Z", None, "Uncaged", "Whales"),
+    ];
+
+    let shares = analytics::genre_share(&releases, Period::Year);
+    let shares: Vec<(&str, &str, usize)> = shares
+        .iter()
+        .map(|share| (share.period.as_str(), share.genre.as_str(), share.releases))
+        .collect();
+    assert_eq!(
+        shares,
+        vec![
+            ("2019", "Drum & Bass", 1),
+            ("2020", "Dubstep", 2),
+            ("2022", "Dubstep", 2),
+            ("2022", "House", 1),
+        ]
+    );
+    let monthly = analytics::genre_share(&releases, Period::Month);
+    assert_eq!(monthly[3].period, "2022-01");
+    assert!((analytics::genre_share(&releases, Period::Year)[3].share - 1.0 / 3.0).abs() < 1e-9);
+
+    let labels = analytics::releases_per_label(&releases);
+    let labels: Vec<(&str, i32, usize)> = labels
+        .iter()
+        .map(|label| (label.label.as_str(), label.year, label.releases))
+        .collect();
+    assert_eq!(
+        labels,
+        vec![
+            ("Instinct", 2022, 3),
+            ("Uncaged", 2019, 1),
+            ("Uncaged", 2020, 3)
+        ]
+    );
+
+    let activity = analytics::artist_activity(&releases);
+    assert_eq!(activity[0].artist, "Rogue");
+    assert_eq!(activity[0].releases, 4);
+    assert_eq!(
+        (activity[0].first_year, activity[0].last_year),
+        (2020, 2022)
+    );
+    assert_eq!(activity[0].trend, Trend::Rising);
+    assert_eq!(activity[1].artist, "Whales");
+    assert_eq!(activity[1].trend, Trend::Falling);
+
+    // Artists who stopped releasing long ago aren't steady.
+    let mut older = releases.to_vec();
+    older.push(release(
+        "2015-03-01T16:00:00Z",
+        Some("Trap"),
+        "Uncaged",
+        "Stonebank",
+    ));
+    let activity = analytics::artist_activity(&older);
+    let stonebank = activity
+        .iter()
+        .find(|activity| activity.artist == "Stonebank")
+        .unwrap();
+    assert_eq!(stonebank.trend, Trend::Inactive);
+
+    let report = Report::new(&releases, Period::Year);
+    assert_eq!(report.releases, 7);
+    let json = serde_json::to_string(&report).unwrap();
+    assert_eq!(serde_json::from_str::<Report>(&json).unwrap(), report);
+    assert!(Report::new(&[], Period::Month).artist_activity.is_empty());
+}
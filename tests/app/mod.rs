@@ -0,0 +1,33 @@
+use bombay::app::Monstercat;
+use bombay::client::{Client, Error};
+use bombay::mc::release::AnyRelease;
+use std::fs;
+
+#[test]
+fn play_top_30_track() -> Result<(), Error> {
+    let mc = Monstercat::new(Client::default());
+
+    let playlist_endpoint = mc.client.playlist();
+    let top_30_tracks =
+        playlist_endpoint.get_tracks_by_playlist_id(playlist_endpoint.get_top_30_playlist_id())?;
+
+    let track = top_30_tracks
+        .data
+        .unwrap_or_default()
+        .into_iter()
+        .find_map(|release| match release {
+            AnyRelease::Track(track) => Some(track),
+            _ => None,
+        })
+        .ok_or(Error::Message("Expected to find a track in the top 30."))?;
+
+    let mut reader = mc.play(&track)?;
+
+    fs::create_dir_all("downloads").unwrap();
+    let mut file_out = fs::File::create("downloads/top_30_track.mp3").unwrap();
+
+    match std::io::copy(&mut reader, &mut file_out) {
+        Ok(_) => Ok(()),
+        Err(err) => Err(Error::IO(err)),
+    }
+}
@@ -19,7 +19,7 @@ fn find_latest() -> Result<(), Error> {
         .data
         .ok_or(Error::Message("Expected to find latest artists."))?;
 
-    let latest_artist = artists.get(0).ok_or(Error::Message(
+    let latest_artist = artists.first().ok_or(Error::Message(
         "Expected to find at least one artist in latest artists.",
     ))?;
 
@@ -131,3 +131,12 @@ fn get_lani_daye_photo() -> Result<(), Error> {
         Err(err) => Err(Error::IO(err)),
     }
 }
+
+#[test]
+fn resolve_ace_aura() -> Result<(), Error> {
+    let artist = CLIENT.artist().resolve("Ace Aura")?;
+
+    assert_eq!(artist.name, "Ace Aura");
+
+    Ok(())
+}
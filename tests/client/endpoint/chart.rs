@@ -0,0 +1,20 @@
+use crate::CLIENT;
+use bombay::client::Error;
+use bombay::mc::chart::ChartID;
+
+#[test]
+fn find_weekly_chart() -> Result<(), Error> {
+    let weekly = CLIENT.chart().get(&ChartID::Weekly)?;
+
+    for entry in weekly.entries {
+        println!(
+            "{}. {} by {} ({:?})",
+            entry.rank,
+            entry.track.title,
+            entry.track.artists_title,
+            entry.movement()
+        );
+    }
+
+    Ok(())
+}
@@ -0,0 +1,14 @@
+use crate::AUTHED_CLIENT;
+use bombay::client::Error;
+
+#[test_with::env(MC_EMAIL, MC_PASSWORD, MC_TOTP_SECRET)]
+#[test]
+fn get_connections() -> Result<(), Error> {
+    let connections = AUTHED_CLIENT.connect().get_connections()?;
+
+    for connection in connections {
+        println!("Connected to {}.", connection.service);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,23 @@
+use crate::{AUTHED_CLIENT, CLIENT};
+use bombay::client::Error;
+
+#[test]
+fn find_upcoming() -> Result<(), Error> {
+    let events_resp = CLIENT.event().get_upcoming(None)?;
+
+    println!("There are {} upcoming events.", events_resp.total);
+
+    Ok(())
+}
+
+#[test_with::env(MC_EMAIL, MC_PASSWORD, MC_TOTP_SECRET)]
+#[test]
+fn find_reminded() -> Result<(), Error> {
+    let events_resp = AUTHED_CLIENT.event().get_reminded(None)?;
+
+    for event in events_resp.data.unwrap_or_default() {
+        println!("Reminded of {} on {}.", event.title, event.start_date);
+    }
+
+    Ok(())
+}
@@ -1,4 +1,7 @@
 mod artist;
+mod chart;
+mod connect;
+mod event;
 mod mood;
 mod playlist;
 mod release;
@@ -35,3 +35,12 @@ fn find_and_fetch_all() -> Result<(), Error> {
 
     Ok(())
 }
+
+#[test]
+fn find_chill_tracks() -> Result<(), Error> {
+    let chill_tracks_resp = CLIENT.mood().get_tracks_by_name_uri("chill", None)?;
+
+    println!("There are {} chill tracks.", chill_tracks_resp.total);
+
+    Ok(())
+}
@@ -5,6 +5,7 @@ use bombay::client::PlaylistItemOperations;
 use bombay::client::{Error, PlaylistItemsMod, PlaylistItemsOperations};
 use bombay::mc::playlist::PlaylistID;
 use bombay::mc::playlist::PlaylistItem;
+use bombay::mc::playlist::PlaylistPermission;
 use bombay::mc::release::AnyRelease;
 use uuid::uuid;
 
@@ -36,7 +37,7 @@ fn get_top_30_tracks() -> Result<(), Error> {
     );
 
     let hottest_track = tracks
-        .get(0)
+        .first()
         .ok_or(Error::Message("Expected to find latest releases."))?;
 
     println!("{} is really hot right now!", hottest_track.get_title());
@@ -66,6 +67,20 @@ fn get_test_playlist() -> Result<(), Error> {
     Ok(())
 }
 
+#[test_with::env(MC_EMAIL, MC_PASSWORD, MC_TOTP_SECRET)]
+#[test]
+fn get_test_playlist_members() -> Result<(), Error> {
+    let members = AUTHED_CLIENT
+        .playlist()
+        .get_members(PlaylistID(uuid!("f6cbaba8-5a86-4fb8-bd4a-1cc3d6ad22e0")))?;
+
+    assert!(members
+        .iter()
+        .any(|member| member.permission == PlaylistPermission::Owner));
+
+    Ok(())
+}
+
 #[test_with::env(MC_EMAIL, MC_PASSWORD, MC_TOTP_SECRET)]
 #[test]
 fn get_test_playlist_items() -> Result<(), Error> {
@@ -1,7 +1,10 @@
 use crate::{AUTHED_CLIENT, CLIENT};
-use bombay::client::Error;
-use bombay::mc::release::{AnyRelease, CatalogID, ReleaseID, TrackID};
+use bombay::client::endpoints::TargetAPI;
+use bombay::client::{Error, IdType, Paginated};
+use bombay::mc::artist::Role;
+use bombay::mc::release::{AnyRelease, CatalogID, Genre, ReleaseID, TrackID};
 use bombay::mc::util::Codec;
+use std::collections::HashMap;
 use std::fs;
 use uuid::uuid;
 
@@ -13,7 +16,7 @@ fn find_latest() -> Result<(), Error> {
         .data
         .ok_or(Error::Message("Expected to find latest releases."))?;
 
-    let release = releases.get(0).ok_or(Error::Message(
+    let release = releases.first().ok_or(Error::Message(
         "Expected to find at least one release in latest releases",
     ))?;
 
@@ -26,6 +29,39 @@ fn find_latest() -> Result<(), Error> {
     Ok(())
 }
 
+#[test]
+fn find_latest_with_meta() -> Result<(), Error> {
+    let latest = CLIENT.get_with_meta::<Paginated<AnyRelease>>(
+        TargetAPI::Player,
+        "/catalog/latest-releases",
+        None::<HashMap<String, String>>,
+    )?;
+
+    assert_eq!(latest.status, 200);
+    assert!(latest.header("Content-Type").is_some());
+    println!(
+        "Got {} latest releases in {:?}.",
+        latest.value.total, latest.duration
+    );
+
+    Ok(())
+}
+
+#[test]
+fn find_drum_and_bass() -> Result<(), Error> {
+    let releases = CLIENT
+        .release()
+        .get_by_genre(Genre::DrumAndBass, None)?
+        .data
+        .ok_or(Error::Message("Expected to find Drum & Bass releases."))?;
+
+    for release in releases {
+        println!("{} - {}", release.get_artists(), release.get_title());
+    }
+
+    Ok(())
+}
+
 #[test]
 fn find_latest_from_rogue() -> Result<(), Error> {
     let releases_resp = CLIENT.release().get_by_artist_name_uri("rogue", None)?;
@@ -34,7 +70,7 @@ fn find_latest_from_rogue() -> Result<(), Error> {
         "Expected to find latest releases from Rogue.",
     ))?;
 
-    let release = releases.get(0).ok_or(Error::Message(
+    let release = releases.first().ok_or(Error::Message(
         "Expected to find at least one release in latest releases from Rogue",
     ))?;
 
@@ -48,6 +84,41 @@ fn find_latest_from_rogue() -> Result<(), Error> {
     Ok(())
 }
 
+#[test]
+fn find_primary_from_rogue() -> Result<(), Error> {
+    let releases_resp =
+        CLIENT
+            .release()
+            .get_by_artist_name_uri_with_role("rogue", Role::Primary, None)?;
+
+    for release in releases_resp.data.unwrap_or_default() {
+        assert!(release.has_artist_role("rogue", &Role::Primary));
+    }
+
+    Ok(())
+}
+
+#[test]
+fn find_souvenir_remixes() -> Result<(), Error> {
+    let (_, tracks) = CLIENT
+        .release()
+        .get_by_catalog_id(&CatalogID("MCS1186".to_owned()))?;
+
+    let original = tracks
+        .iter()
+        .find(|track| track.is_original())
+        .ok_or(Error::Message("Expected to find the original mix."))?;
+
+    for remix in CLIENT.release().get_remixes(original, None)? {
+        println!(
+            "{} ({}) by {}",
+            remix.title, remix.version, remix.artists_title
+        );
+    }
+
+    Ok(())
+}
+
 #[test]
 fn get_souvenir_details() -> Result<(), Error> {
     let release_tracks = CLIENT
@@ -83,7 +154,7 @@ fn get_related_to_oxygen() -> Result<(), Error> {
         "Expected to find release Oxygen (6a58b6d2-bbec-4847-8dcf-45023a930968).",
     ))?;
 
-    let release = releases.get(0).ok_or(Error::Message(
+    let release = releases.first().ok_or(Error::Message(
         "Expected to find at least one release related to Oxygen (6a58b6d2-bbec-4847-8dcf-45023a930968).",
     ))?;
 
@@ -100,7 +171,7 @@ fn find_latest_related() -> Result<(), Error> {
         .data
         .ok_or(Error::Message("Expected to find latest releases."))?;
 
-    let latest_release = latest_releases.get(0).ok_or(Error::Message(
+    let latest_release = latest_releases.first().ok_or(Error::Message(
         "Expected to find at least one release in latest releases",
     ))?;
 
@@ -112,7 +183,7 @@ fn find_latest_related() -> Result<(), Error> {
         .data
         .ok_or(Error::Message("Expected to find related releases."))?;
 
-    let related_release_opt = related_releases.get(0);
+    let related_release_opt = related_releases.first();
 
     match related_release_opt {
         Some(related_release) => println!(
@@ -127,6 +198,40 @@ fn find_latest_related() -> Result<(), Error> {
     Ok(())
 }
 
+#[test]
+fn find_similar_to_far_out() -> Result<(), Error> {
+    let similar = CLIENT.release().get_similar_tracks(
+        &TrackID(uuid!("6b5401bc-06d0-41e8-ab16-7742f2aa40bf")),
+        None,
+    )?;
+
+    for track in similar.data.unwrap_or_default() {
+        println!(
+            "Similar to 'Far Out': {} by {}",
+            track.title, track.artists_title
+        );
+    }
+
+    Ok(())
+}
+
+#[test]
+fn find_versions_of_souvenir() -> Result<(), Error> {
+    let (_, tracks) = CLIENT
+        .release()
+        .get_by_catalog_id(&CatalogID("MCS1186".to_owned()))?;
+    let track = tracks
+        .first()
+        .ok_or(Error::Message("Expected release to have tracks."))?;
+
+    let versions = CLIENT.release().get_versions(track, None)?;
+    for version in &versions.versions {
+        println!("{} ({})", version.title, version.version);
+    }
+
+    Ok(())
+}
+
 #[test]
 fn stream_no_service() -> Result<(), Error> {
     let mut reader = CLIENT.release().stream_by_ids(
@@ -175,3 +280,83 @@ fn download_feelings_cover_art() -> Result<(), Error> {
         Err(err) => Err(Error::IO(err)),
     }
 }
+
+#[test_with::env(MC_EMAIL, MC_PASSWORD, MC_TOTP_SECRET)]
+#[test]
+fn find_early_access() -> Result<(), Error> {
+    let releases_resp = AUTHED_CLIENT.release().get_early_access(None)?;
+
+    for release in releases_resp.data.unwrap_or_default() {
+        if let AnyRelease::Release(release) = release {
+            println!(
+                "{} is in early access until {:?}.",
+                release.title,
+                release.early_access_until()
+            );
+        }
+    }
+
+    Ok(())
+}
+
+#[test]
+fn get_souvenir_page() -> Result<(), Error> {
+    let page = CLIENT
+        .release()
+        .get_www_page(&CatalogID("MCS1186".to_owned()))?;
+
+    assert_eq!(page.release.title, "Souvenir");
+
+    for credit in page.credits.unwrap_or_default() {
+        println!("{}: {}", credit.role, credit.name);
+    }
+
+    Ok(())
+}
+
+#[test]
+fn get_souvenir_by_lowercase_id_and_upc() -> Result<(), Error> {
+    let (by_catalog_id, _) = CLIENT
+        .release()
+        .get_by_catalog_id(&CatalogID("mcs1186".to_owned()))?;
+
+    let upc = match &by_catalog_id {
+        AnyRelease::Release(release) => release.upc.clone(),
+        AnyRelease::Track(_) => None,
+    }
+    .ok_or(Error::Message("Expected Souvenir to have a UPC."))?;
+
+    let (by_upc, _) = CLIENT.release().get_by_catalog_id(&CatalogID(upc))?;
+
+    assert_eq!(by_catalog_id.get_release_id(), by_upc.get_release_id());
+
+    Ok(())
+}
+
+#[test]
+fn get_souvenir_by_release_id() -> Result<(), Error> {
+    let (release, _) = CLIENT
+        .release()
+        .get_by_id_type("6a58b6d2-bbec-4847-8dcf-45023a930968", IdType::Id)?;
+
+    println!("Found {}!", release.get_title());
+
+    Ok(())
+}
+
+#[test_with::env(MC_EMAIL, MC_PASSWORD, MC_TOTP_SECRET)]
+#[test]
+fn estimate_every_little_thing_size() -> Result<(), Error> {
+    let size = AUTHED_CLIENT.release().estimate_download_size(
+        &ReleaseID(uuid!("e7c6a280-6af3-4101-af9f-5c809afb6541")),
+        Codec::WAV,
+    )?;
+
+    println!(
+        "Downloading as WAV takes {} bytes (exact: {}).",
+        size.bytes(),
+        size.is_exact()
+    );
+
+    Ok(())
+}
@@ -1,5 +1,7 @@
 use crate::{AUTHED_CLIENT, MC_EMAIL, MC_PASSWORD, MC_TOTP_GEN};
 use bombay::client::{auth::SignInOutcome, Client, Error};
+use bombay::mc::util::DateRange;
+use iso8601_timestamp::{Duration as TimestampDuration, Timestamp};
 use std::fs;
 use std::thread;
 use std::time::Duration;
@@ -36,6 +38,7 @@ fn get_user_info_any_login() -> Result<(), Error> {
             let token = MC_TOTP_GEN.generate_current().unwrap();
             totp_callback(&mut client_unauth, token)
         }
+        _ => Err(Error::Message("Test failed, unsupported sign-in outcome.")),
     }?;
 
     let user_info = client.user().get_info()?;
@@ -95,6 +98,36 @@ fn get_totp_qr_code_image() -> Result<(), Error> {
     }
 }
 
+#[test_with::env(MC_EMAIL, MC_PASSWORD, MC_TOTP_SECRET)]
+#[test]
+fn get_backup_codes() -> Result<(), Error> {
+    let backup_codes = AUTHED_CLIENT.user().get_backup_codes()?;
+
+    println!("Found {} backup codes.", backup_codes.codes.len());
+
+    Ok(())
+}
+
+#[test_with::env(MC_EMAIL, MC_PASSWORD, MC_TOTP_SECRET)]
+#[test]
+fn get_player_widget() -> Result<(), Error> {
+    let widget = AUTHED_CLIENT.user().get_player_widget()?;
+
+    println!("Streaming widget theme is {:?}.", widget.theme);
+
+    Ok(())
+}
+
+#[test_with::env(MC_EMAIL, MC_PASSWORD, MC_TOTP_SECRET)]
+#[test]
+fn get_export_status() -> Result<(), Error> {
+    let export = AUTHED_CLIENT.user().get_export_status()?;
+
+    println!("Data export is {}.", export.status);
+
+    Ok(())
+}
+
 #[test_with::env(MC_EMAIL, MC_PASSWORD, MC_TOTP_SECRET)]
 #[test]
 fn get_licenses() -> Result<(), Error> {
@@ -105,7 +138,7 @@ fn get_licenses() -> Result<(), Error> {
         .ok_or(Error::Message("Expected to find licenses."))?;
 
     let license = licenses
-        .get(0)
+        .first()
         .ok_or(Error::Message("Expected to find at least one license."))?;
 
     dbg!(license);
@@ -113,6 +146,31 @@ fn get_licenses() -> Result<(), Error> {
     Ok(())
 }
 
+#[test_with::env(MC_EMAIL, MC_PASSWORD, MC_TOTP_SECRET)]
+#[test]
+fn get_license_stats() -> Result<(), Error> {
+    let licenses = AUTHED_CLIENT
+        .user()
+        .get_licenses(None)?
+        .data
+        .ok_or(Error::Message("Expected to find licenses."))?;
+
+    let license = licenses
+        .first()
+        .ok_or(Error::Message("Expected to find at least one license."))?;
+
+    let end = Timestamp::now_utc();
+    let start = end.saturating_sub(TimestampDuration::days(30));
+
+    let stats = AUTHED_CLIENT
+        .user()
+        .get_license_stats(*license.get_id(), DateRange { start, end })?;
+
+    println!("Views gained in 30 days: {:?}", stats.views_gained());
+
+    Ok(())
+}
+
 #[test_with::env(MC_EMAIL, MC_PASSWORD, MC_TOTP_SECRET)]
 #[test]
 fn get_shop_discount_code() -> Result<(), Error> {
@@ -1,7 +1,21 @@
 mod endpoint;
 
-use bombay::client::{Client, Error, PaginationParameters, RequestParameters};
+use bombay::client::auth::SignInFailure;
+use bombay::client::endpoints::TargetAPI;
+use bombay::client::{
+    ArtistField, Client, Error, Field, Paginated, PaginationAnomaly, PaginationParameters,
+    ReleaseField, RequestParameters,
+};
+use bombay::mc::artist::ArtistID;
+use bombay::mc::release::{AnyRelease, ReleaseID, TrackID};
+use bombay::testing::fixtures::Fixture;
+use bombay::testing::server::{self, Fault, Faults};
+use std::collections::HashMap;
 use std::error;
+use std::io::Read;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+use std::time::Duration;
 use uuid::uuid;
 
 #[test]
@@ -21,7 +35,7 @@ fn about_grant() -> Result<(), Box<dyn error::Error>> {
         .ok_or(Error::Message("Expected to find artists."))?;
 
     // And Grant should be in there.
-    let grant_id = uuid!("27063fd3-4fba-4119-9af0-5001e925b0d2");
+    let grant_id = ArtistID(uuid!("27063fd3-4fba-4119-9af0-5001e925b0d2"));
     let grant = artists
         .iter()
         .find(|artist| artist.id == grant_id)
@@ -92,3 +106,533 @@ fn about_grant() -> Result<(), Box<dyn error::Error>> {
 
     Ok(())
 }
+
+#[test]
+fn join_all_caps_concurrency() -> Result<(), Error> {
+    let mc = Client::default();
+    let running = AtomicUsize::new(0);
+    let peak = AtomicUsize::new(0);
+
+    let request = |i: usize| {
+        let (running, peak) = (&running, &peak);
+        move || {
+            let now = running.fetch_add(1, Ordering::SeqCst) + 1;
+            peak.fetch_max(now, Ordering::SeqCst);
+            thread::sleep(Duration::from_millis(20));
+            running.fetch_sub(1, Ordering::SeqCst);
+            Ok::<_, Error>(i)
+        }
+    };
+
+    let results = mc.join_all(2, (0..6).map(request).collect::<Vec<_>>());
+    assert_eq!(
+        results.into_iter().collect::<Result<Vec<_>, _>>()?,
+        vec![0, 1, 2, 3, 4, 5]
+    );
+    assert!(peak.load(Ordering::SeqCst) <= 2);
+
+    let (number, text) = mc.join_all(2, (|| Ok::<_, Error>(1), || Ok::<_, Error>("one")));
+    assert_eq!((number?, text?), (1, "one"));
+
+    // A request panicking fails alone, instead of taking the batch down.
+    let (panicked, fine): (Result<(), Error>, _) =
+        mc.join_all(2, (|| panic!("Request panicked."), || Ok::<_, Error>(2)));
+    assert!(matches!(panicked, Err(Error::Message(_))));
+    assert_eq!(fine?, 2);
+    Ok(())
+}
+
+#[test]
+fn probe_capabilities() -> Result<(), Error> {
+    let capabilities = Client::default().probe_capabilities()?;
+
+    assert!(capabilities.supports_artists);
+    assert!(capabilities.supports_latest_releases);
+    println!("{:?}", capabilities);
+
+    Ok(())
+}
+
+#[test]
+fn classify_errors() {
+    let status_error = |response: &str| {
+        let response: ureq::Response = response.parse().unwrap();
+        let status = response.status();
+        Error::Request(Box::new(ureq::Error::Status(status, response)))
+    };
+
+    let rate_limited = status_error("HTTP/1.1 429 Too Many Requests\r\nRetry-After: 5\r\n\r\n");
+    assert!(rate_limited.is_retryable());
+    assert!(!rate_limited.is_auth_error());
+    assert_eq!(rate_limited.retry_after(), Some(Duration::from_secs(5)));
+
+    let unauthorized = status_error("HTTP/1.1 401 Unauthorized\r\n\r\n");
+    assert!(!unauthorized.is_retryable());
+    assert!(unauthorized.is_auth_error());
+    assert_eq!(unauthorized.retry_after(), None);
+
+    assert!(!Error::NotFound("release").is_retryable());
+
+    let rate_limited_sign_in = Error::SignInDetailed(SignInFailure::RateLimited(Some(30)));
+    assert!(rate_limited_sign_in.is_retryable());
+    assert_eq!(
+        rate_limited_sign_in.retry_after(),
+        Some(Duration::from_secs(30))
+    );
+
+    // Bodies that don't decode are the same the next time.
+    let undecodable = serde_json::from_str::<u32>("{")
+        .map_err(|err| Error::IO(err.into()))
+        .unwrap_err();
+    assert!(!undecodable.is_retryable());
+    let reset = Error::IO(std::io::ErrorKind::ConnectionReset.into());
+    assert!(reset.is_retryable());
+}
+
+#[test]
+fn pagination_guardrails() {
+    let page = |offset: usize, received: usize, total: usize| Paginated {
+        data: Some(vec![(); received]),
+        not_found: None,
+        total,
+        limit: 10,
+        offset,
+    };
+    let requested = |offset: usize| PaginationParameters { limit: 10, offset };
+
+    // A full page in the middle advances by what was received.
+    let middle = page(10, 10, 25);
+    assert_eq!(middle.next_offset(), Some(20));
+    assert!(middle.anomalies(&requested(10)).is_empty());
+
+    // The last page may be short, that's fine.
+    let last = page(20, 5, 25);
+    assert_eq!(last.next_offset(), None);
+    assert!(last.anomalies(&requested(20)).is_empty());
+
+    // A short page before the end doesn't skip the missing items.
+    let short = page(0, 7, 25);
+    assert_eq!(short.next_offset(), Some(7));
+    assert_eq!(
+        short.anomalies(&requested(0)),
+        vec![PaginationAnomaly::ShortPage {
+            expected: 10,
+            received: 7
+        }]
+    );
+
+    // Huge offsets neither overflow nor go past the total.
+    let past = page(usize::MAX, 0, 25);
+    assert_eq!(past.next_offset(), None);
+    assert!(past
+        .anomalies(&requested(usize::MAX))
+        .contains(&PaginationAnomaly::OffsetPastTotal {
+            offset: usize::MAX,
+            total: 25
+        }));
+    assert_eq!(requested(usize::MAX).clamped(25).offset, 25);
+}
+
+#[test]
+fn filter_explicit_tracks() -> Result<(), Error> {
+    let mc = Client::default().set_filter_explicit(true);
+    let chill = mc.mood().get_by_name_uri("chill")?;
+
+    let releases = mc
+        .mood()
+        .get_tracks_by_name_uri(&chill.uri, None)?
+        .data
+        .unwrap_or_default();
+    for release in releases {
+        if let AnyRelease::Track(track) = release {
+            assert!(!track.explicit);
+        }
+    }
+
+    Ok(())
+}
+
+#[test]
+fn head_artist() -> Result<(), Error> {
+    let head = Client::default().head(
+        TargetAPI::Player,
+        "/artist/rogue",
+        None::<HashMap<String, String>>,
+    )?;
+
+    assert_eq!(head.status, 200);
+    println!("Content-Type: {:?}", head.header("content-type"));
+
+    Ok(())
+}
+
+/// Path of the track streamed in stream tests.
+const STREAM_PATH: &str =
+    "/release/4c6b9486-7644-4f3f-b9ae-0fa4d27a4259/track-stream/00164f5c-3a1e-44ad-8b73-bfdde22b8b6e";
+
+/// Stream the track of [`STREAM_PATH`] to the end.
+fn read_stream(url: &str) -> Result<(String, u64), Error> {
+    let mc = Client::new(url.to_owned(), format!("{url}/"));
+    let mut reader = mc.release().stream_by_ids(
+        &ReleaseID(uuid!("4c6b9486-7644-4f3f-b9ae-0fa4d27a4259")),
+        &TrackID(uuid!("00164f5c-3a1e-44ad-8b73-bfdde22b8b6e")),
+    )?;
+    let mut audio = String::new();
+    reader.read_to_string(&mut audio).map_err(Error::IO)?;
+    Ok((audio, reader.position()))
+}
+
+#[test]
+fn stream_hls() -> Result<(), Error> {
+    // A master playlist with one variant of two segments.
+    static HLS: [Fixture; 4] = [
+        Fixture {
+            name: "master",
+            path: STREAM_PATH,
+            body: "#EXTM3U\n#EXT-X-STREAM-INF:BANDWIDTH=64000\n/lo/index.m3u8\n#EXT-X-STREAM-INF:BANDWIDTH=256000\n/hi/index.m3u8\n",
+        },
+        Fixture {
+            name: "variant",
+            path: "/hi/index.m3u8",
+            body: "#EXTM3U\n#EXT-X-TARGETDURATION:10\n#EXTINF:10.0,\n0.aac\n#EXTINF:2.5,\n1.aac\n#EXT-X-ENDLIST\n",
+        },
+        Fixture {
+            name: "first_segment",
+            path: "/hi/0.aac",
+            body: "first,",
+        },
+        Fixture {
+            name: "second_segment",
+            path: "/hi/1.aac",
+            body: "second",
+        },
+    ];
+
+    // The highest bandwidth variant is streamed, segment after segment.
+    let (audio, _) = read_stream(&server::serve(&HLS))?;
+    assert_eq!(audio, "first,second");
+    Ok(())
+}
+
+#[test]
+fn stream_resumes_after_drop() -> Result<(), Error> {
+    static STREAM: [Fixture; 1] = [Fixture {
+        name: "stream",
+        path: STREAM_PATH,
+        body: "0123456789",
+    }];
+
+    // The connection drops after a few bytes the first time, like when the
+    // signed URL expires mid-playback.
+    let dropping = || Faults::new().add_times(STREAM_PATH, Fault::Truncate(4), 1);
+
+    // The rest is asked for from where it dropped, or skipped up to there.
+    for faults in [dropping(), dropping().add(STREAM_PATH, Fault::IgnoreRange)] {
+        let url = server::serve_with_faults(&STREAM, faults);
+        assert_eq!(read_stream(&url)?, ("0123456789".to_owned(), 10));
+    }
+
+    // A part starting anywhere else would garble the track.
+    let url = server::serve_with_faults(&STREAM, dropping().add(STREAM_PATH, Fault::RangeFrom(2)));
+    assert!(read_stream(&url).is_err());
+    Ok(())
+}
+
+/// Cipher for tests only, flipping bits behind a tag.
+struct FlipCipher;
+
+impl bombay::client::SnapshotCipher for FlipCipher {
+    fn seal(&self, plaintext: &[u8]) -> Result<Vec<u8>, Error> {
+        Ok(b"flip:"
+            .iter()
+            .copied()
+            .chain(plaintext.iter().map(|byte| !byte))
+            .collect())
+    }
+
+    fn open(&self, sealed: &[u8]) -> Result<Vec<u8>, Error> {
+        match sealed.strip_prefix(b"flip:") {
+            Some(ciphertext) => Ok(ciphertext.iter().map(|byte| !byte).collect()),
+            None => Err(Error::Message("Snapshot was not sealed with this cipher.")),
+        }
+    }
+}
+
+#[test]
+fn snapshot_shares_session() -> Result<(), Error> {
+    use bombay::client::ClientSnapshot;
+
+    let url = "http://127.0.0.1:9/api".to_owned();
+    let mc = Client::new(url.clone(), "http://127.0.0.1:9/".to_owned())
+        .set_locale(Some("fr-CA".to_owned()))
+        .restore_session("s3cr3t-session".to_owned())?;
+
+    let snapshot = mc.snapshot();
+    assert!(snapshot.has_cookie("cid"));
+    assert!(!format!("{snapshot:?}").contains("s3cr3t-session"));
+
+    // Sealed bytes don't hold the session in plaintext, and need the cipher.
+    let sealed = snapshot.to_sealed_bytes(&FlipCipher)?;
+    assert!(!String::from_utf8_lossy(&sealed).contains("s3cr3t-session"));
+    assert!(ClientSnapshot::from_bytes(&sealed).is_err());
+
+    // A worker resumes the session with the same settings.
+    let restored = ClientSnapshot::from_sealed_bytes(&sealed, &FlipCipher)?;
+    let worker =
+        Client::new(url.clone(), "http://127.0.0.1:9/".to_owned()).restore_snapshot(restored)?;
+    assert_eq!(worker.session_cookie().as_deref(), Some("s3cr3t-session"));
+    assert_eq!(worker.snapshot().locale(), Some("fr-CA"));
+    assert_eq!(worker.snapshot().url_player_api(), url);
+
+    // Without a session cookie, there is no session to resume.
+    let signed_out = Client::new(url.clone(), "http://127.0.0.1:9/".to_owned()).snapshot();
+    assert!(Client::from_snapshot(signed_out.clone())?
+        .restore_snapshot(signed_out)
+        .is_err());
+    Ok(())
+}
+
+#[test]
+fn session_file_round_trip() -> Result<(), Error> {
+    use bombay::client::SessionFile;
+
+    let url = "http://127.0.0.1:9/api".to_owned();
+    let mc = Client::new(url.clone(), "http://127.0.0.1:9/".to_owned())
+        .restore_session("s3cr3t-session".to_owned())?;
+    let path = std::env::temp_dir().join(format!("bombay-session-{}.bin", std::process::id()));
+
+    SessionFile::new(mc.snapshot()).save_sealed(&path, &FlipCipher)?;
+    assert!(
+        !String::from_utf8_lossy(&std::fs::read(&path).map_err(Error::IO)?)
+            .contains("s3cr3t-session")
+    );
+    assert!(SessionFile::load(&path).is_err());
+
+    let session = SessionFile::load_sealed(&path, &FlipCipher)?;
+    let resumed =
+        Client::new(url, "http://127.0.0.1:9/".to_owned()).restore_snapshot(session.snapshot)?;
+    assert_eq!(resumed.session_cookie().as_deref(), Some("s3cr3t-session"));
+
+    std::fs::remove_file(&path).map_err(Error::IO)
+}
+
+#[cfg(unix)]
+#[test]
+fn session_file_replaces_readable_file_privately() -> Result<(), Error> {
+    use bombay::client::SessionFile;
+    use std::os::unix::fs::PermissionsExt;
+
+    let path =
+        std::env::temp_dir().join(format!("bombay-session-mode-{}.json", std::process::id()));
+    std::fs::write(&path, "{}").map_err(Error::IO)?;
+    std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o644)).map_err(Error::IO)?;
+
+    SessionFile::new(Client::default().snapshot()).save(&path)?;
+    let mode = std::fs::metadata(&path)
+        .map_err(Error::IO)?
+        .permissions()
+        .mode();
+    assert_eq!(mode & 0o777, 0o600);
+
+    std::fs::remove_file(&path).map_err(Error::IO)
+}
+
+#[cfg(feature = "encryption")]
+#[test]
+fn session_file_encrypted() -> Result<(), Error> {
+    use bombay::client::SessionFile;
+
+    let mc = Client::new(
+        "http://127.0.0.1:9/api".to_owned(),
+        "http://127.0.0.1:9/".to_owned(),
+    )
+    .restore_session("s3cr3t-session".to_owned())?;
+    let path = std::env::temp_dir().join(format!("bombay-session-enc-{}.bin", std::process::id()));
+
+    SessionFile::new(mc.snapshot()).save_encrypted(&path, "correct horse")?;
+    let bytes = std::fs::read(&path).map_err(Error::IO)?;
+    assert!(!String::from_utf8_lossy(&bytes).contains("s3cr3t-session"));
+
+    // A wrong passphrase or a tampered file fail to decrypt.
+    assert!(SessionFile::load_encrypted(&path, "battery staple").is_err());
+    let mut tampered = bytes.clone();
+    *tampered.last_mut().unwrap() ^= 1;
+    std::fs::write(&path, tampered).map_err(Error::IO)?;
+    assert!(SessionFile::load_encrypted(&path, "correct horse").is_err());
+
+    std::fs::write(&path, bytes).map_err(Error::IO)?;
+    let session = SessionFile::load_encrypted(&path, "correct horse")?;
+    assert!(session.snapshot.has_cookie("cid"));
+
+    std::fs::remove_file(&path).map_err(Error::IO)
+}
+
+#[test]
+fn shutdown_persists_session_and_goes_offline() -> Result<(), Error> {
+    use bombay::client::ClientSnapshot;
+    use bombay::testing::fixtures;
+    use std::sync::{Arc, Mutex};
+
+    let url = server::serve(fixtures::ALL);
+    let saved: Arc<Mutex<Vec<ClientSnapshot>>> = Arc::default();
+    let saving = saved.clone();
+    let mc = Client::new(url.clone(), format!("{url}/"))
+        .set_on_shutdown(move |snapshot| {
+            saving.lock().unwrap().push(snapshot);
+            Ok(())
+        })
+        .restore_session("cookie".to_owned())?;
+    mc.user().get_info()?;
+
+    // The session is persisted once, as it was before going offline.
+    mc.shutdown()?;
+    mc.shutdown()?;
+    let saved = saved.lock().unwrap();
+    assert_eq!(saved.len(), 1);
+    assert!(saved[0].has_cookie("cid"));
+    assert!(!Client::from_snapshot(saved[0].clone())?.is_offline());
+
+    assert!(mc.is_offline());
+    assert!(matches!(mc.user().get_info(), Err(Error::Offline)));
+    Ok(())
+}
+
+#[test]
+fn sign_in_keeps_password_secret() -> Result<(), Error> {
+    use bombay::client::auth::SignInOutcome;
+
+    static SIGN_IN: [Fixture; 1] = [Fixture {
+        name: "sign_in",
+        path: "/sign-in",
+        body: r#"{"Needs2FA": true, "DefaultAuthType": "TOTP", "AuthData": {"TOTP": {}}}"#,
+    }];
+
+    let url = server::serve(&SIGN_IN);
+    let mut mc = Client::new(url.clone(), format!("{url}/"));
+
+    // The password is kept for the TOTP callback, but never shown.
+    match mc.sign_in("label@example.com".to_owned(), "hunter2".to_owned())? {
+        SignInOutcome::TOTP(_) => {}
+        _ => panic!("Expected to be asked for a TOTP code."),
+    }
+    let debug = format!("{:?}", mc);
+    assert!(debug.contains("Secret([REDACTED])"));
+    assert!(!debug.contains("hunter2"));
+
+    Ok(())
+}
+
+#[test]
+fn sign_in_reports_unreadable_response() {
+    static SIGN_IN: [Fixture; 1] = [Fixture {
+        name: "sign_in",
+        path: "/sign-in",
+        body: r#"{"Needs2FA": "#,
+    }];
+
+    let url = server::serve(&SIGN_IN);
+    let mut mc = Client::new(url.clone(), format!("{url}/"));
+
+    // The response is at fault, not a missing session cookie.
+    let err = mc
+        .sign_in("label@example.com".to_owned(), "hunter2".to_owned())
+        .err()
+        .expect("Expected the sign-in to fail.");
+    assert!(matches!(err, Error::IO(_) | Error::Deserialization(_)));
+}
+
+#[test]
+fn debug_redacts_session() -> Result<(), Error> {
+    let mc = Client::default().restore_session("cookie-value".to_owned())?;
+    assert_eq!(mc.session_cookie().as_deref(), Some("cookie-value"));
+
+    let debug = format!("{:?}", mc);
+    assert!(debug.contains("agent: [REDACTED]"));
+    assert!(!debug.contains("cookie-value"));
+
+    Ok(())
+}
+
+#[test]
+fn session_cookie_of_api_host() -> Result<(), Error> {
+    use bombay::client::auth::CookieAuth;
+
+    // Sessions are looked up on the configured host, not the MC API's.
+    let url = "http://127.0.0.1:9/api";
+    let mc = Client::new(url.to_owned(), "http://127.0.0.1:9/".to_owned())
+        .restore_session("cookie-value".to_owned())?;
+    assert_eq!(mc.session_cookie().as_deref(), Some("cookie-value"));
+
+    let mc = Client::new(url.to_owned(), "http://127.0.0.1:9/".to_owned())
+        .set_auth_provider(CookieAuth::for_url(url).set_name("session"))
+        .restore_session("other-value".to_owned())?;
+    assert_eq!(mc.session_cookie().as_deref(), Some("other-value"));
+
+    assert_eq!(CookieAuth::for_url("not a url"), CookieAuth::default());
+    Ok(())
+}
+
+#[test]
+fn ping_apis() {
+    use bombay::testing::fixtures;
+
+    let url = server::serve(fixtures::ALL);
+    let ping = Client::new(url.clone(), "http://127.0.0.1:9/".to_owned()).ping();
+
+    // The fixture server answers, if only with a "not found".
+    assert_eq!(ping.player_api.url, url);
+    assert_eq!(ping.player_api.status, Some(404));
+    assert!(!ping.www_api.is_reachable());
+    assert!(!ping.is_online() && !ping.is_offline());
+}
+
+#[test]
+fn offline_requests_fail() -> Result<(), Error> {
+    use bombay::testing::fixtures;
+
+    let url = server::serve(fixtures::ALL);
+    let mc = Client::new(url.clone(), format!("{url}/")).set_offline(true);
+
+    assert!(mc.is_offline());
+    assert!(matches!(mc.release().get_latest(None), Err(Error::Offline)));
+    assert!(!mc.ping().player_api.is_reachable());
+
+    let mc = mc.set_offline(false);
+    assert_eq!(mc.release().get_latest(None)?.total, 2);
+    Ok(())
+}
+
+#[test]
+fn request_fields_selection() -> Result<(), Error> {
+    use bombay::testing::fixtures;
+
+    let parameters =
+        RequestParameters::default().set_fields(&[ArtistField::Tags, ArtistField::Name]);
+    let queries: HashMap<String, String> = parameters.into();
+    assert_eq!(
+        queries["fields"],
+        "Details,Id,Name,Public,ShowEvent,URI,Tags"
+    );
+
+    let parameters = RequestParameters::default().set_fields::<ReleaseField>(&[]);
+    assert_eq!(
+        parameters.fields.unwrap().len(),
+        ReleaseField::REQUIRED.len()
+    );
+    let parameters = RequestParameters::default().set_fields(&[
+        ReleaseField::Grid,
+        ReleaseField::PrereleaseDate,
+        ReleaseField::YouTubeUrl,
+    ]);
+    let queries: HashMap<String, String> = parameters.into();
+    assert!(queries["fields"].ends_with(",GRid,PrereleaseDate,YouTubeUrl"));
+
+    // The fixture server answers with every field, like the API may.
+    let url = server::serve(fixtures::ALL);
+    let mc = Client::new(url.clone(), format!("{url}/"));
+    let releases = mc.release().get_latest(Some(
+        RequestParameters::default().set_fields(&[ReleaseField::CoverFileId]),
+    ))?;
+    assert_eq!(releases.total, 2);
+    Ok(())
+}
@@ -0,0 +1,71 @@
+use bombay::app::Monstercat;
+use bombay::client::{Client, Error, SessionFile};
+use bombay::dirs::Dirs;
+use std::fs;
+
+#[test]
+fn dirs_under_root() -> Result<(), Error> {
+    let root = std::env::temp_dir().join(format!("bombay-dirs-{}", std::process::id()));
+    let dirs = Dirs::under(&root).set_cache(root.join("covers"));
+    assert_eq!(dirs.cache, root.join("covers"));
+    assert_eq!(dirs.downloads, root.join("downloads"));
+    assert_eq!(
+        dirs.snapshot_file("worker"),
+        root.join("snapshots").join("worker.json")
+    );
+
+    dirs.create_all().map_err(Error::IO)?;
+    assert!(dirs.cache.is_dir() && dirs.snapshots.is_dir());
+
+    // Persistence features default to the client's directories.
+    let mc = Client::default().set_dirs(dirs.clone());
+    assert_eq!(mc.dirs(), &dirs);
+    assert_eq!(Monstercat::new(mc).download_directory, dirs.downloads);
+
+    // Session files create their directory.
+    let nested = Dirs::under(root.join("nested"));
+    let path = nested.session_file("default");
+    SessionFile::new(Client::default().snapshot()).save(&path)?;
+    assert!(SessionFile::load(&path).is_ok());
+
+    fs::remove_dir_all(&root).map_err(Error::IO)
+}
+
+#[test]
+fn platform_dirs() -> Result<(), Error> {
+    let dirs = Dirs::platform();
+    assert_eq!(Dirs::default(), dirs);
+    assert!(dirs.sessions.ends_with("sessions"));
+    assert!(dirs.downloads.ends_with("Monstercat"));
+
+    let home = std::env::var_os("HOME")
+        .map(std::path::PathBuf::from)
+        .filter(|home| home.is_absolute());
+    let Some(home) = home.filter(|_| cfg!(unix)) else {
+        return Ok(());
+    };
+    assert!(dirs.cache.is_absolute());
+    assert!(dirs.cache.ends_with("bombay"));
+    if cfg!(target_os = "macos") {
+        return Ok(());
+    }
+
+    // Without the variable, the download directory comes from user-dirs.dirs.
+    let config = std::env::temp_dir().join(format!("bombay-user-dirs-{}", std::process::id()));
+    fs::create_dir_all(&config).map_err(Error::IO)?;
+    fs::write(
+        config.join("user-dirs.dirs"),
+        "# Written by xdg-user-dirs-update\n\
+         XDG_DESKTOP_DIR=\"$HOME/Desktop\"\n\
+         XDG_DOWNLOAD_DIR=\"$HOME/Telechargements\"\n",
+    )
+    .map_err(Error::IO)?;
+
+    std::env::remove_var("XDG_DOWNLOAD_DIR");
+    std::env::set_var("XDG_CONFIG_HOME", &config);
+    let downloads = Dirs::platform().downloads;
+    std::env::remove_var("XDG_CONFIG_HOME");
+    assert_eq!(downloads, home.join("Telechargements").join("Monstercat"));
+
+    fs::remove_dir_all(&config).map_err(Error::IO)
+}
@@ -0,0 +1,289 @@
+use bombay::client::Error;
+use bombay::download::{AuditFormat, Manifest, ManifestEntry};
+use bombay::mc::release::{CatalogID, TrackID};
+use bombay::mc::util::Codec;
+use std::fs;
+use std::path::PathBuf;
+use uuid::uuid;
+
+#[test]
+fn manifest_round_trip_and_verify() -> Result<(), Error> {
+    let directory = std::env::temp_dir().join("bombay-manifest-test");
+    fs::create_dir_all(&directory).map_err(Error::IO)?;
+    fs::write(directory.join("01 - Whales - Souvenir.flac"), b"souvenir").map_err(Error::IO)?;
+
+    let track_id = TrackID(uuid!("00164f5c-3a1e-44ad-8b73-bfdde22b8b6e"));
+    let mut manifest = Manifest::default();
+    manifest.insert(ManifestEntry {
+        catalog_id: CatalogID("MCS1186".to_owned()),
+        track_id,
+        isrc: "CA6D21800144".to_owned(),
+        codec: Codec::FLAC,
+        path: PathBuf::from("01 - Whales - Souvenir.flac"),
+        // FNV-1a of "souvenir".
+        hash: "8a484a4019478ff4".to_owned(),
+        size: 8,
+        creator_friendly: Some(true),
+    });
+
+    let manifest_path = directory.join("manifest.json");
+    manifest.save(&manifest_path)?;
+    let manifest = Manifest::load(&manifest_path)?;
+
+    let entry = manifest
+        .find(&track_id, &Codec::FLAC)
+        .ok_or(Error::Message("Expected to find the manifest entry."))?;
+    assert!(manifest.find(&track_id, &Codec::MP3).is_none());
+    assert!(entry.is_present(&directory));
+    assert!(manifest.verify(&directory)?.is_empty());
+
+    fs::write(directory.join("01 - Whales - Souvenir.flac"), b"changed!").map_err(Error::IO)?;
+    assert!(entry.is_present(&directory));
+    assert_eq!(manifest.verify(&directory)?.len(), 1);
+
+    fs::remove_dir_all(&directory).map_err(Error::IO)?;
+    Ok(())
+}
+
+#[test]
+fn download_progress_events() -> Result<(), Error> {
+    use bombay::client::Client;
+    use bombay::dirs::Dirs;
+    use bombay::download::Downloader;
+    use bombay::progress::{Operation, ProgressEvent};
+    use bombay::testing::fixtures::{self, Fixture};
+    use bombay::testing::server;
+    use std::sync::mpsc;
+
+    static DOWNLOADS: [Fixture; 2] = [
+        fixtures::RELEASE,
+        Fixture {
+            name: "track_download",
+            path: "/release/6a58b6d2-bbec-4847-8dcf-45023a930968/track-download/3c1c5bd2-9f5e-4d0b-a7a7-2bb3d1c4a0e1",
+            body: "souvenir audio",
+        },
+    ];
+
+    let url = server::serve(&DOWNLOADS);
+    let directory = std::env::temp_dir().join(format!("bombay-progress-{}", std::process::id()));
+    let mc = Client::new(url.clone(), format!("{url}/"))
+        .set_dirs(Dirs::under(&directory).set_downloads(&directory))
+        .restore_session("cookie".to_owned())?;
+    let (sender, receiver) = mpsc::channel();
+    // Downloads go to the client's downloads directory by default.
+    let downloader = Downloader::new(&mc, Codec::MP3).set_progress(Some(sender));
+    assert_eq!(downloader.directory, directory);
+
+    // The second time, the track is skipped, but still done.
+    let catalog_id = CatalogID("MCS1186".to_owned());
+    downloader.download_release(&catalog_id)?;
+    downloader.download_release(&catalog_id)?;
+    drop(downloader);
+
+    let item = "01 - Whales feat. Dutch Melrose - Souvenir.mp3".to_owned();
+    let started = ProgressEvent::Started {
+        operation: Operation::DownloadRelease,
+        items: Some(1),
+    };
+    let done = ProgressEvent::ItemDone { item: item.clone() };
+    let finished = ProgressEvent::Finished {
+        operation: Operation::DownloadRelease,
+    };
+    assert_eq!(
+        receiver.iter().collect::<Vec<_>>(),
+        vec![
+            started.clone(),
+            ProgressEvent::Bytes {
+                item,
+                done: 14,
+                total: Some(14)
+            },
+            done.clone(),
+            finished.clone(),
+            started,
+            done,
+            finished,
+        ]
+    );
+
+    fs::remove_dir_all(&directory).map_err(Error::IO)?;
+    Ok(())
+}
+
+#[cfg(feature = "media")]
+#[test]
+fn failed_transcode_keeps_download() -> Result<(), Error> {
+    use bombay::client::Client;
+    use bombay::download::Downloader;
+    use bombay::media::Transcoder;
+    use bombay::progress::ProgressEvent;
+    use bombay::testing::fixtures::{self, Fixture};
+    use bombay::testing::server::{self, Fault, Faults};
+    use std::io;
+    use std::path::Path;
+    use std::sync::mpsc;
+    use std::sync::Arc;
+
+    struct Failing;
+    impl Transcoder for Failing {
+        fn transcode(&self, _: &Path, output: &Path, _: &Codec) -> io::Result<()> {
+            fs::write(output, b"partial")?;
+            Err(io::Error::new(io::ErrorKind::Other, "ffmpeg failed"))
+        }
+    }
+
+    static DOWNLOADS: [Fixture; 2] = [
+        fixtures::RELEASE,
+        Fixture {
+            name: "track_download",
+            path: "/release/6a58b6d2-bbec-4847-8dcf-45023a930968/track-download/3c1c5bd2-9f5e-4d0b-a7a7-2bb3d1c4a0e1",
+            body: "fLaC souvenir audio",
+        },
+    ];
+
+    // MP3 isn't served, so the download falls back to FLAC.
+    let faults = Faults::new().add_times(DOWNLOADS[1].path, Fault::Status(415), 1);
+    let url = server::serve_with_faults(&DOWNLOADS, faults);
+    let mc = Client::new(url.clone(), format!("{url}/")).restore_session("cookie".to_owned())?;
+    let directory = std::env::temp_dir().join(format!("bombay-transcode-{}", std::process::id()));
+    let (sender, receiver) = mpsc::channel();
+    let downloader = Downloader::new(&mc, Codec::MP3)
+        .set_directory(&directory)
+        .set_transcoder(Some(Arc::new(Failing)))
+        .set_progress(Some(sender));
+
+    let result = downloader.download_release(&CatalogID("MCS1186".to_owned()));
+    assert!(matches!(result, Err(Error::IO(err)) if err.to_string() == "ffmpeg failed"));
+
+    // The fallback isn't a retry of the download.
+    drop(downloader);
+    assert!(!receiver
+        .iter()
+        .any(|event| matches!(event, ProgressEvent::Retry { .. })));
+
+    // The FLAC served instead is kept, and nothing is left beside it.
+    let path = directory.join("01 - Whales feat. Dutch Melrose - Souvenir.mp3");
+    assert_eq!(fs::read(&path).map_err(Error::IO)?, b"fLaC souvenir audio");
+    assert!(!path.with_extension("source").exists());
+
+    fs::remove_dir_all(&directory).map_err(Error::IO)?;
+    Ok(())
+}
+
+#[test]
+fn audit_manifest_formats() -> Result<(), Error> {
+    let mut manifest = Manifest::default();
+    manifest.insert(ManifestEntry {
+        catalog_id: CatalogID("MCS1186".to_owned()),
+        track_id: TrackID(uuid!("00164f5c-3a1e-44ad-8b73-bfdde22b8b6e")),
+        isrc: "CA6D21800144".to_owned(),
+        codec: Codec::FLAC,
+        path: PathBuf::from("01 - Whales, Rogue - Souvenir.flac"),
+        hash: "8a484a4019478ff4".to_owned(),
+        size: 8,
+        creator_friendly: Some(true),
+    });
+
+    // Entries from older manifests have no license status.
+    let mut old: ManifestEntry = serde_json::from_str(
+        r#"{
+            "CatalogId": "MCS1186",
+            "TrackId": "3c1c5bd2-9f5e-4d0b-a7a7-2bb3d1c4a0e1",
+            "Codec": "mp3_320",
+            "Path": "02 - Whales - Souvenir (Extended Mix).mp3",
+            "Hash": "0000000000000000",
+            "Size": 0
+        }"#,
+    )
+    .map_err(Error::Deserialization)?;
+    assert_eq!(old.creator_friendly, None);
+    old.isrc = String::new();
+    manifest.insert(old);
+
+    let mut csv = Vec::new();
+    manifest.write_audit(&mut csv, &AuditFormat::Csv)?;
+    assert_eq!(
+        String::from_utf8_lossy(&csv),
+        "catalog_id,track_id,isrc,codec,path,hash,creator_friendly\n\
+         MCS1186,00164f5c-3a1e-44ad-8b73-bfdde22b8b6e,CA6D21800144,flac,\"01 - Whales, Rogue - Souvenir.flac\",8a484a4019478ff4,true\n\
+         MCS1186,3c1c5bd2-9f5e-4d0b-a7a7-2bb3d1c4a0e1,,mp3_320,02 - Whales - Souvenir (Extended Mix).mp3,0000000000000000,\n"
+    );
+
+    let mut json = Vec::new();
+    manifest.write_audit(&mut json, &AuditFormat::Json)?;
+    let records: serde_json::Value =
+        serde_json::from_slice(&json).map_err(Error::Deserialization)?;
+    assert_eq!(records[0]["isrc"], "CA6D21800144");
+    assert_eq!(records[0]["creator_friendly"], true);
+    assert!(records[1]["creator_friendly"].is_null());
+
+    Ok(())
+}
+
+#[cfg(feature = "media")]
+#[test]
+fn sniff_audio_formats() -> Result<(), Error> {
+    use bombay::media;
+
+    assert_eq!(media::sniff(b"RIFF\x24\0\0\0WAVEfmt "), Some(Codec::WAV));
+    assert_eq!(media::sniff(b"RIFF\x24\0\0\0AVI LIST"), None);
+    assert_eq!(media::sniff(&[0xFF, 0xFB, 0x90, 0x64]), Some(Codec::MP3));
+    assert_eq!(media::sniff(b"fLa"), None);
+
+    let path = std::env::temp_dir().join("bombay-sniff-test.flac");
+    fs::write(&path, b"fLaC\0\0\0\x22 and the rest").map_err(Error::IO)?;
+    assert_eq!(
+        media::sniff_file(&path).map_err(Error::IO)?,
+        Some(Codec::FLAC)
+    );
+    fs::remove_file(&path).map_err(Error::IO)
+}
+
+#[cfg(feature = "media")]
+#[test]
+fn replay_gain_of_wav() -> Result<(), Error> {
+    use bombay::media::loudness;
+
+    // Five seconds of a stereo 1 kHz sine at -23 dBFS, as 16-bit PCM.
+    let amplitude = 10f64.powf(-23.0 / 20.0);
+    let data: Vec<u8> = (0..44100 * 5)
+        .flat_map(|i| {
+            let phase = 2.0 * std::f64::consts::PI * 1000.0 * f64::from(i) / 44100.0;
+            let sample = ((amplitude * phase.sin()) * 32767.0).round() as i16;
+            [sample.to_le_bytes(), sample.to_le_bytes()].concat()
+        })
+        .collect();
+
+    let mut wav = Vec::new();
+    wav.extend_from_slice(b"RIFF");
+    wav.extend_from_slice(&(36 + data.len() as u32).to_le_bytes());
+    wav.extend_from_slice(b"WAVEfmt ");
+    wav.extend_from_slice(&16u32.to_le_bytes());
+    wav.extend_from_slice(&1u16.to_le_bytes());
+    wav.extend_from_slice(&2u16.to_le_bytes());
+    wav.extend_from_slice(&44100u32.to_le_bytes());
+    wav.extend_from_slice(&(44100u32 * 4).to_le_bytes());
+    wav.extend_from_slice(&4u16.to_le_bytes());
+    wav.extend_from_slice(&16u16.to_le_bytes());
+    wav.extend_from_slice(b"data");
+    wav.extend_from_slice(&(data.len() as u32).to_le_bytes());
+    wav.extend_from_slice(&data);
+
+    let path = std::env::temp_dir().join("bombay-loudness-test.wav");
+    fs::write(&path, wav).map_err(Error::IO)?;
+    let measured = loudness::measure_file(&path, None).map_err(Error::IO)?;
+    fs::remove_file(&path).map_err(Error::IO)?;
+
+    let gain = measured
+        .replay_gain()
+        .ok_or(Error::Message("Expected a loudness."))?;
+    assert!((gain.track_gain - 5.0).abs() < 0.1);
+    assert!((gain.track_peak - amplitude).abs() < 0.001);
+    assert_eq!(gain.tags()[2].1, "-18.00 LUFS");
+
+    // Silence has no loudness to normalize.
+    let silence = loudness::Loudness::measure(&[0.0; 48000], 1, 48000);
+    assert_eq!(silence.replay_gain(), None);
+
+    Ok(())
+}
@@ -0,0 +1,922 @@
+use bombay::client::{Client, Error};
+use bombay::compat::{self, ApiVersion, UnknownEnumPolicy};
+use bombay::mc::artist::{Artist, ArtistID, ArtistListItem};
+use bombay::mc::chart::ChartID;
+use bombay::mc::mood::MoodID;
+use bombay::mc::playlist::PlaylistID;
+use bombay::mc::release::{CatalogID, Genre, ReleaseID, TrackID};
+use bombay::mc::util::{Platform, Region};
+use bombay::smart_playlist::{Rule, SmartPlaylist};
+use bombay::testing::server::{Fault, Faults};
+use bombay::testing::{fixtures, server};
+use iso8601_timestamp::Timestamp;
+use lazy_static::lazy_static;
+use std::collections::HashSet;
+use std::time::{Duration, Instant};
+use uuid::uuid;
+
+lazy_static! {
+    static ref FIXTURE_CLIENT: Client = {
+        let url = server::serve(fixtures::ALL);
+        Client::new(url.clone(), format!("{url}/"))
+    };
+}
+
+#[test]
+fn releases_from_fixtures() -> Result<(), Error> {
+    let release = FIXTURE_CLIENT.release();
+
+    assert_eq!(release.get_latest(None)?.total, 2);
+    assert_eq!(release.get_all(None)?.total, 2);
+    assert_eq!(release.get_by_artist_name_uri("whales", None)?.total, 2);
+
+    let (_, tracks) = release.get_by_catalog_id(&CatalogID("MCS1186".to_owned()))?;
+    assert!(!tracks.is_empty());
+
+    let related = release.get_related_by_id(
+        &ReleaseID(uuid!("6a58b6d2-bbec-4847-8dcf-45023a930968")),
+        None,
+    )?;
+    assert_eq!(related.total, 2);
+
+    let page = release.get_www_page(&CatalogID("MCS1186".to_owned()))?;
+    let track_id = TrackID(uuid!("3c1c5bd2-9f5e-4d0b-a7a7-2bb3d1c4a0e1"));
+    assert_eq!(page.track_credits(&track_id).len(), 1);
+
+    Ok(())
+}
+
+#[test]
+fn tracks_from_fixtures() -> Result<(), Error> {
+    let track_id = TrackID(uuid!("3c1c5bd2-9f5e-4d0b-a7a7-2bb3d1c4a0e1"));
+    let release = FIXTURE_CLIENT.release();
+
+    // Tracks spell some keys in capitals, like "ISRC" and "BPM".
+    let similar = release.get_similar_tracks(&track_id, None)?.data.unwrap();
+    assert_eq!(similar[0].isrc, "CA6D22100326");
+    assert_eq!(similar[0].bpm, 174);
+
+    // Track features come with lowercase keys.
+    let features = release.get_track_features(&track_id)?;
+    assert_eq!(features.energy, Some(0.89));
+
+    Ok(())
+}
+
+#[test]
+fn artists_from_fixtures() -> Result<(), Error> {
+    let artist = FIXTURE_CLIENT.artist();
+
+    assert!(artist.get_all(None)?.data.is_some());
+    assert_eq!(artist.get_latest(None)?.total, 1);
+    assert_eq!(artist.get_by_name_uri("whales")?.name, "Whales");
+
+    let whales = ArtistID(uuid!("0f1c3a6e-1f5b-4e9f-8a61-7f4e2b9c1d01"));
+    assert_eq!(artist.get_by_id(&whales)?.id, whales);
+
+    Ok(())
+}
+
+#[test]
+fn artist_by_id_without_an_id_route() -> Result<(), Error> {
+    let faults = Faults::new().add(
+        "/artist/0f1c3a6e-1f5b-4e9f-8a61-7f4e2b9c1d01",
+        Fault::Status(404),
+    );
+    let url = server::serve_with_faults(fixtures::ALL, faults);
+    let mc = Client::new(url.clone(), format!("{url}/"));
+
+    // Found among all artists instead.
+    let whales = ArtistID(uuid!("0f1c3a6e-1f5b-4e9f-8a61-7f4e2b9c1d01"));
+    assert_eq!(mc.artist().get_by_id(&whales)?.name, "Whales");
+
+    let unknown = ArtistID(uuid!("00000000-0000-4000-8000-000000000000"));
+    assert!(matches!(
+        mc.artist().get_by_id(&unknown),
+        Err(Error::NotFound("artist"))
+    ));
+
+    Ok(())
+}
+
+#[test]
+fn moods_from_fixtures() -> Result<(), Error> {
+    let mood = FIXTURE_CLIENT.mood();
+
+    assert_eq!(mood.get_all(None)?.total, 1);
+    assert_eq!(mood.get_by_name_uri("chill")?.name, "Chill");
+
+    let chill = MoodID(uuid!("5f0e4a4e-7b5d-4c55-9f0c-0d3c6b0b9a11"));
+    assert_eq!(mood.get_by_id(&chill)?.id, chill);
+    assert_eq!(mood.get_tracks_by_name_uri("chill", None)?.total, 2);
+
+    Ok(())
+}
+
+#[test]
+fn playlists_events_and_charts_from_fixtures() -> Result<(), Error> {
+    let playlist_id = PlaylistID(uuid!("f4a8b5a4-4c2c-4b5e-9fd1-6a6a3a37d1a2"));
+
+    assert_eq!(
+        FIXTURE_CLIENT.playlist().by_id(playlist_id)?.title,
+        "Favorites"
+    );
+    assert_eq!(
+        FIXTURE_CLIENT
+            .playlist()
+            .get_tracks_by_playlist_id(playlist_id)?
+            .total,
+        2
+    );
+
+    assert_eq!(FIXTURE_CLIENT.event().get_upcoming(None)?.total, 1);
+
+    assert!(FIXTURE_CLIENT.chart().get_all()?.contains(&ChartID::Weekly));
+    let weekly = FIXTURE_CLIENT.chart().get(&ChartID::Weekly)?;
+    assert_eq!(weekly.entries.len(), 2);
+
+    Ok(())
+}
+
+#[test]
+fn every_fixture_is_served() {
+    for fixture in fixtures::ALL {
+        assert_eq!(
+            fixtures::by_path(fixture.path).map(|found| found.path),
+            Some(fixture.path)
+        );
+        serde_json::from_str::<serde_json::Value>(fixture.body)
+            .unwrap_or_else(|err| panic!("Fixture {} is not JSON: {}", fixture.name, err));
+    }
+}
+
+#[test]
+fn renamed_fields_with_aliases() -> Result<(), Error> {
+    // Pretend MC renamed an artist's "Name" to "DisplayName".
+    let renamed = fixtures::ARTIST.body.replace("\"Name\"", "\"DisplayName\"");
+    assert!(compat::from_str::<Artist>(&renamed, ApiVersion::Current).is_err());
+
+    let aliases = vec![("DisplayName".to_owned(), "Name".to_owned())];
+    let artist: Artist = compat::with_aliases(aliases.clone(), || {
+        compat::from_str(&renamed, ApiVersion::Current)
+    })
+    .unwrap();
+    assert_eq!(artist.name, "Whales");
+
+    // Aliases end with their scope.
+    assert!(compat::from_str::<Artist>(&renamed, ApiVersion::Current).is_err());
+
+    // Clients take their own.
+    let url = server::serve(Box::leak(Box::new([fixtures::Fixture {
+        body: Box::leak(renamed.into_boxed_str()),
+        ..fixtures::ARTIST
+    }])));
+    let mc = Client::new(url.clone(), format!("{url}/"));
+    assert!(mc.artist().get_by_name_uri("whales").is_err());
+    let artist = mc.set_aliases(aliases).artist().get_by_name_uri("whales")?;
+    assert_eq!(artist.name, "Whales");
+
+    Ok(())
+}
+
+#[test]
+fn unknown_enum_policy_per_client() -> Result<(), Error> {
+    let body = fixtures::ARTIST.body.replace(
+        "\"Links\": null",
+        "\"Links\": [{ \"Platform\": \"myspace\", \"Url\": \"https://myspace.com/whales\" }]",
+    );
+    let url = server::serve(Box::leak(Box::new([fixtures::Fixture {
+        body: Box::leak(body.into_boxed_str()),
+        ..fixtures::ARTIST
+    }])));
+
+    let strict = Client::new(url.clone(), format!("{url}/"))
+        .set_unknown_enum_policy(Some(UnknownEnumPolicy::Error));
+    let lenient = Client::new(url.clone(), format!("{url}/"));
+    assert!(strict.artist().get_by_name_uri("whales").is_err());
+    let links = lenient.artist().get_by_name_uri("whales")?.links.unwrap();
+    assert_eq!(links[0].platform, Platform::Other("myspace".to_owned()));
+
+    Ok(())
+}
+
+#[test]
+fn region_sent_to_events() -> Result<(), Error> {
+    static REGIONAL: [fixtures::Fixture; 2] = [
+        fixtures::Fixture {
+            path: "/events?region=CA",
+            ..fixtures::EVENTS
+        },
+        fixtures::MOODS,
+    ];
+    let url = server::serve(&REGIONAL);
+
+    let canada = Client::new(url.clone(), format!("{url}/")).set_region(Some(Region::Canada));
+    assert!(canada.event().get_upcoming(None).is_ok());
+    assert!(canada.mood().get_all(None).is_ok());
+
+    let anywhere = Client::new(url.clone(), format!("{url}/"));
+    assert_eq!(
+        anywhere.event().get_upcoming(None).unwrap_err().status(),
+        Some(404)
+    );
+
+    Ok(())
+}
+
+#[test]
+fn artist_details_in_either_case() -> Result<(), Error> {
+    let artist = FIXTURE_CLIENT.artist().get_by_name_uri("whales")?;
+    assert_eq!(
+        artist.details.about.as_deref(),
+        Some("Whales is an electronic music producer.")
+    );
+    assert_eq!(
+        artist.details.bookings.as_deref(),
+        Some("bookings@example.com")
+    );
+    assert_eq!(
+        artist.details.show_events.as_deref(),
+        Some("Tour dates soon.")
+    );
+
+    let artists = FIXTURE_CLIENT.artist().get_all(None)?;
+    let details = &artists.data.unwrap()[0].details;
+    assert_eq!(
+        details.management_details.as_deref(),
+        Some("Managed by Monstercat.")
+    );
+    assert_eq!(details.show_events.as_deref(), Some("Tour dates soon."));
+
+    Ok(())
+}
+
+#[test]
+fn smart_playlist_from_fixtures() -> Result<(), Error> {
+    let playlist_id = PlaylistID(uuid!("f4a8b5a4-4c2c-4b5e-9fd1-6a6a3a37d1a2"));
+    let after_2022 = Timestamp::parse("2022-01-01T00:00:00Z").ok_or(Error::Message("Bad date."))?;
+
+    let rule = Rule::Genre(Genre::DrumAndBass)
+        .and(Rule::Bpm { min: 170, max: 180 })
+        .and(Rule::ReleasedAfter(after_2022));
+    let tracks = SmartPlaylist::new(rule.clone(), playlist_id).materialize(&*FIXTURE_CLIENT)?;
+    assert!(!tracks.is_empty());
+    assert!(tracks.iter().all(|track| rule.matches(track)));
+
+    // Tracks are only listed once, even when found in several releases.
+    let ids: HashSet<_> = tracks.iter().map(|track| track.id).collect();
+    assert_eq!(ids.len(), tracks.len());
+
+    let others = SmartPlaylist::new(rule.clone().negate(), playlist_id)
+        .set_max_releases(1)
+        .materialize(&*FIXTURE_CLIENT)?;
+    assert!(others.iter().all(|track| !rule.matches(track)));
+
+    Ok(())
+}
+
+#[test]
+fn smart_playlist_sync_reads_every_page() -> Result<(), Error> {
+    use bombay::testing::factories;
+
+    let playlist_id = PlaylistID(uuid!("f4a8b5a4-4c2c-4b5e-9fd1-6a6a3a37d1a2"));
+    let smart = SmartPlaylist::new(Rule::Genre(Genre::DrumAndBass), playlist_id);
+    let wanted = smart.materialize(&*FIXTURE_CLIENT)?;
+    assert!(!wanted.is_empty());
+
+    // Tracks not matching come first, so the wanted ones are on later pages.
+    let mut current = factories::tracks(2);
+    for (i, track) in current.iter_mut().enumerate() {
+        track.id = TrackID(uuid::Uuid::from_u128(0xffff_0000 + i as u128));
+        track.genre_secondary = "Dubstep".to_owned();
+    }
+    current.extend(wanted);
+    let mut served = paged_playlist(&current).to_vec();
+    served.extend(fixtures::ALL);
+    let url = server::serve(Box::leak(served.into_boxed_slice()));
+    let mc = Client::new(url.clone(), format!("{url}/")).restore_session("cookie".to_owned())?;
+
+    // Only the tracks not matching are removed, and nothing is added.
+    let changes = smart.sync(&mc)?;
+    assert_eq!(changes.changes.len(), 2);
+    assert!(changes.changes.iter().all(|change| change.after.is_none()));
+
+    Ok(())
+}
+
+#[test]
+fn app_sync_playlist_reads_every_page() -> Result<(), Error> {
+    use bombay::app::Monstercat;
+    use bombay::testing::factories;
+
+    let playlist_id = PlaylistID(uuid!("f4a8b5a4-4c2c-4b5e-9fd1-6a6a3a37d1a2"));
+    let tracks = factories::tracks(5);
+    // Any edit fails, so the playlist must be found already in sync.
+    let faults = Faults::new().add(
+        "/playlist/f4a8b5a4-4c2c-4b5e-9fd1-6a6a3a37d1a2/modify-items",
+        Fault::Status(500),
+    );
+    let url = server::serve_with_faults(paged_playlist(&tracks), faults);
+    let mc = Client::new(url.clone(), format!("{url}/")).restore_session("cookie".to_owned())?;
+
+    Monstercat::new(mc).sync_playlist(playlist_id, &tracks)
+}
+
+#[cfg(feature = "cassette")]
+#[test]
+fn cassette_record_and_replay() -> Result<(), Error> {
+    use bombay::client::cassette::{Cassette, CassetteMode};
+    use bombay::dirs::Dirs;
+
+    let url = server::serve(fixtures::ALL);
+    let root = std::env::temp_dir().join(format!("bombay-cassette-{}", std::process::id()));
+
+    // Cassettes go in the client's snapshots directory, created when recording.
+    let recorder = Client::new(url.clone(), format!("{url}/")).set_dirs(Dirs::under(&root));
+    let path = recorder.dirs().cassette_file("latest");
+    assert!(path.starts_with(root.join("snapshots")));
+    let recorder = recorder.set_cassette(CassetteMode::Record(path.clone()))?;
+    let recorded = recorder.release().get_latest(None)?;
+    assert_eq!(Cassette::load(&path)?.interactions.len(), 1);
+
+    let player = Client::new(url.clone(), format!("{url}/"))
+        .set_cassette(CassetteMode::Replay(path.clone()))?;
+    let replayed = player.release().get_latest(None)?;
+    assert_eq!(replayed.total, recorded.total);
+
+    // The only recorded response was used up, and nothing reaches the server.
+    assert!(player.release().get_latest(None).is_err());
+
+    // Recorded responses are still served offline.
+    let offline = Client::new(url.clone(), format!("{url}/"))
+        .set_offline(true)
+        .set_cassette(CassetteMode::Replay(path.clone()))?;
+    assert_eq!(offline.release().get_latest(None)?.total, recorded.total);
+
+    std::fs::remove_dir_all(root).map_err(Error::IO)
+}
+
+#[cfg(feature = "cassette")]
+#[test]
+fn cassette_redacts_credentials() {
+    use bombay::client::cassette::{Cassette, Interaction};
+
+    let header = |name: &str, value: &str| (name.to_owned(), value.to_owned());
+    let cassette = Cassette {
+        interactions: vec![Interaction {
+            method: "POST".to_owned(),
+            url: "https://player.monstercat.app/api/sign-in".to_owned(),
+            status: 200,
+            status_text: "OK".to_owned(),
+            headers: vec![
+                header("Content-Type", "application/json"),
+                header("Set-Cookie", "cid=s3cr3t; Path=/; HttpOnly"),
+                header("set-cookie", "flag"),
+                header("Cookie", "cid=s3cr3t; theme=dark"),
+                header("Authorization", "Bearer s3cr3t"),
+            ],
+            body: "{}".to_owned(),
+        }],
+    };
+
+    let redacted = cassette.redacted();
+    assert_eq!(
+        redacted.interactions[0].headers,
+        vec![
+            header("Content-Type", "application/json"),
+            header("Set-Cookie", "cid=REDACTED; Path=/; HttpOnly"),
+            header("set-cookie", "REDACTED"),
+            header("Cookie", "cid=REDACTED; theme=REDACTED"),
+            header("Authorization", "REDACTED"),
+        ]
+    );
+    assert!(!serde_json::to_string(&redacted).unwrap().contains("s3cr3t"));
+}
+
+#[test]
+fn cover_art_prefetch() -> Result<(), Error> {
+    use bombay::cover_art::CoverArtCache;
+    use bombay::dirs::Dirs;
+    use bombay::testing::fixtures::Fixture;
+
+    static COVERS: [Fixture; 1] = [Fixture {
+        name: "cover",
+        path: "/release/d3c3e8e6-0a5d-4b1b-9a36-6b0e5d4a8c1f/cover",
+        body: "not really a jpeg",
+    }];
+
+    let root = std::env::temp_dir().join(format!("bombay-covers-{}", std::process::id()));
+    let url = server::serve(&COVERS);
+    let client = Client::new(url.clone(), format!("{url}/")).set_dirs(Dirs::under(&root));
+    let covers = CoverArtCache::new(&client).set_max_concurrent(2);
+
+    let cover = ReleaseID(uuid!("d3c3e8e6-0a5d-4b1b-9a36-6b0e5d4a8c1f"));
+    let missing = ReleaseID(uuid!("00000000-0000-0000-0000-000000000000"));
+
+    let failed = covers.prefetch(&[cover, missing, cover, missing], 256);
+    assert_eq!(failed.len(), 1);
+    assert_eq!(failed[0].0, missing);
+
+    assert_eq!(
+        covers.cached(&cover, 256).unwrap().as_slice(),
+        b"not really a jpeg"
+    );
+    assert!(covers.cached(&cover, 512).is_none());
+
+    covers.clear();
+    assert!(covers.cached(&cover, 256).is_none());
+    assert_eq!(covers.get(&cover, 256)?.len(), 17);
+
+    // Cover art on disk is read without fetching it, even from another cache.
+    let offline = Client::new(url.clone(), format!("{url}/"))
+        .set_offline(true)
+        .set_dirs(Dirs::under(&root));
+    let covers = CoverArtCache::new(&offline);
+    assert_eq!(covers.get(&cover, 256)?.len(), 17);
+    assert!(covers.get(&cover, 512).is_err());
+    assert!(CoverArtCache::new(&offline)
+        .set_directory(None)
+        .get(&cover, 256)
+        .is_err());
+
+    std::fs::remove_dir_all(&root).map_err(Error::IO)
+}
+
+#[test]
+fn user_export_from_fixtures() -> Result<(), Error> {
+    use bombay::mc::user::UserExport;
+
+    let url = server::serve(fixtures::ALL);
+    let mc = Client::new(url.clone(), format!("{url}/")).restore_session("cookie".to_owned())?;
+
+    let export = mc.user().export_all()?;
+    assert_eq!(export.user.username, "label");
+    assert!(export.licenses.is_empty());
+
+    // The library is exported as a playlist, with its tracks in order.
+    let library = &export.playlists[0];
+    assert!(library.playlist.my_library);
+    assert!(!library.items.is_empty());
+    assert!(library
+        .items
+        .iter()
+        .enumerate()
+        .all(|(sort, item)| item.sort == sort));
+
+    let json = serde_json::to_string(&export).map_err(Error::Deserialization)?;
+    let restored: UserExport = serde_json::from_str(&json).map_err(Error::Deserialization)?;
+    assert_eq!(restored.user, export.user);
+    assert_eq!(restored.playlists, export.playlists);
+
+    Ok(())
+}
+
+/// Get fixtures of a playlist's catalog served in pages of two tracks, by
+/// offset, along with the playlist and its edits.
+fn paged_playlist(tracks: &[bombay::mc::release::Track]) -> &'static [fixtures::Fixture] {
+    use bombay::client::Paginated;
+    use bombay::testing::fixtures::Fixture;
+
+    let leak = |string: String| -> &'static str { Box::leak(string.into_boxed_str()) };
+    let mut pages: Vec<Fixture> = tracks
+        .chunks(2)
+        .enumerate()
+        .map(|(page, chunk)| Fixture {
+            name: "playlist_catalog_page",
+            path: leak(format!(
+                "/playlist/f4a8b5a4-4c2c-4b5e-9fd1-6a6a3a37d1a2/catalog?offset={}",
+                page * 2
+            )),
+            body: leak(
+                serde_json::to_string(&Paginated {
+                    data: Some(chunk.to_vec()),
+                    not_found: None,
+                    total: tracks.len(),
+                    limit: 2,
+                    offset: page * 2,
+                })
+                .unwrap(),
+            ),
+        })
+        .collect();
+    pages.extend([
+        fixtures::PLAYLIST,
+        fixtures::PLAYLIST_CREATED,
+        fixtures::PLAYLIST_ITEMS_ADDED,
+        Fixture {
+            name: "modify_item",
+            path: "/playlist/f4a8b5a4-4c2c-4b5e-9fd1-6a6a3a37d1a2/modify-item",
+            body: "{}",
+        },
+        Fixture {
+            name: "modify_items",
+            path: "/playlist/f4a8b5a4-4c2c-4b5e-9fd1-6a6a3a37d1a2/modify-items",
+            body: "{}",
+        },
+    ]);
+    Box::leak(pages.into_boxed_slice())
+}
+
+#[test]
+fn playlist_backup_reads_every_page() -> Result<(), Error> {
+    use bombay::testing::factories;
+
+    let tracks = factories::tracks(5);
+    let url = server::serve(paged_playlist(&tracks));
+    let mc = Client::new(url.clone(), format!("{url}/")).restore_session("cookie".to_owned())?;
+
+    let backup = mc
+        .playlist()
+        .backup(PlaylistID(uuid!("f4a8b5a4-4c2c-4b5e-9fd1-6a6a3a37d1a2")))?;
+    assert_eq!(
+        backup
+            .tracks
+            .iter()
+            .map(|track| track.track_id)
+            .collect::<Vec<_>>(),
+        tracks.iter().map(|track| track.id).collect::<Vec<_>>()
+    );
+
+    Ok(())
+}
+
+#[test]
+fn user_export_and_import_every_page() -> Result<(), Error> {
+    use bombay::mc::user::UserExport;
+    use bombay::testing::factories;
+
+    let tracks = factories::tracks(5);
+    let mut served = paged_playlist(&tracks).to_vec();
+    served.extend([fixtures::ME, fixtures::PLAYLISTS, fixtures::LICENSES]);
+    let url = server::serve(Box::leak(served.into_boxed_slice()));
+    let mc = Client::new(url.clone(), format!("{url}/")).restore_session("cookie".to_owned())?;
+
+    // Every page of the library is exported, in order.
+    let export = mc.user().export_all()?;
+    let library = &export.playlists[0];
+    assert_eq!(library.items.len(), 5);
+    assert!(library
+        .items
+        .iter()
+        .zip(&tracks)
+        .enumerate()
+        .all(|(sort, (item, track))| item.sort == sort && item.track_id == track.id));
+
+    // Round trip through JSON, with another playlist, which is created anew.
+    let json = serde_json::to_string(&export).map_err(Error::Deserialization)?;
+    let mut restored: UserExport = serde_json::from_str(&json).map_err(Error::Deserialization)?;
+    assert_eq!(restored.playlists, export.playlists);
+    let mut other = restored.playlists[0].clone();
+    other.playlist.id = PlaylistID(uuid!("00000000-0000-0000-0000-0000000000aa"));
+    other.playlist.my_library = false;
+    restored.playlists.push(other);
+
+    assert_eq!(
+        mc.user().import(restored)?,
+        vec![
+            (library.playlist.id, library.playlist.id),
+            (
+                PlaylistID(uuid!("00000000-0000-0000-0000-0000000000aa")),
+                PlaylistID(uuid!("b7e1c2d4-5a6f-4e3b-9c8d-1f2a3b4c5d6e"))
+            ),
+        ]
+    );
+
+    Ok(())
+}
+
+#[test]
+fn playlist_undo_removing_a_copy_on_a_later_page() -> Result<(), Error> {
+    use bombay::client::{
+        PlaylistItemMod, PlaylistItemOperations, PlaylistItemsMod, PlaylistItemsOperations,
+        UndoStep,
+    };
+    use bombay::mc::playlist::PlaylistItem;
+    use bombay::testing::factories;
+
+    // The second track is in the playlist twice, the last time on page three.
+    let mut tracks = factories::tracks(5);
+    tracks.push(tracks[1].clone());
+    let url = server::serve(paged_playlist(&tracks));
+    let mc = Client::new(url.clone(), format!("{url}/")).restore_session("cookie".to_owned())?;
+    let playlist_id = PlaylistID(uuid!("f4a8b5a4-4c2c-4b5e-9fd1-6a6a3a37d1a2"));
+    let copy = PlaylistItem {
+        playlist_id,
+        release_id: tracks[1].release.id,
+        sort: 5,
+        track_id: tracks[1].id,
+    };
+
+    // Only the removed copy is added back, then moved where it was.
+    let removed = PlaylistItemsMod {
+        records: vec![copy.clone()],
+    };
+    let undo = mc.playlist().modify_items_with_undo(
+        playlist_id,
+        PlaylistItemsOperations::Remove,
+        removed.clone(),
+    )?;
+    assert_eq!(
+        undo.steps,
+        vec![
+            UndoStep::Items(PlaylistItemsOperations::Add, removed),
+            UndoStep::Item(
+                PlaylistItemOperations::To,
+                PlaylistItemMod {
+                    move_to: Some(5),
+                    record: copy.clone(),
+                },
+            ),
+        ]
+    );
+
+    // Moving the copy up is undone by moving it back down to its own place.
+    let undo = mc.playlist().modify_item_with_undo(
+        playlist_id,
+        PlaylistItemOperations::Up,
+        PlaylistItemMod {
+            move_to: None,
+            record: copy,
+        },
+    )?;
+    assert!(matches!(
+        &undo.steps[..],
+        [UndoStep::Item(PlaylistItemOperations::To, item_mod)]
+            if item_mod.move_to == Some(5) && item_mod.record.sort == 4
+    ));
+
+    Ok(())
+}
+
+#[test]
+fn playlist_backup_and_restore() -> Result<(), Error> {
+    use bombay::mc::playlist::{BackedUpTrack, PlaylistBackup};
+
+    let url = server::serve(fixtures::ALL);
+    let mc = Client::new(url.clone(), format!("{url}/")).restore_session("cookie".to_owned())?;
+    let playlist_id = PlaylistID(uuid!("f4a8b5a4-4c2c-4b5e-9fd1-6a6a3a37d1a2"));
+
+    let backup = mc.playlist().backup(playlist_id)?;
+    assert_eq!(backup.playlist.title, "Favorites");
+    assert_eq!(backup.tracks.len(), 1);
+
+    let json = serde_json::to_string(&backup).map_err(Error::Deserialization)?;
+    let mut backup: PlaylistBackup = serde_json::from_str(&json).map_err(Error::Deserialization)?;
+
+    // Pretend the track was removed from its release and re-released, and
+    // that another track's release is gone.
+    let kept = backup.tracks[0].clone();
+    let removed = BackedUpTrack {
+        track_id: TrackID(uuid!("00000000-0000-0000-0000-000000000001")),
+        isrc: "CA-6D2-21-00326".to_owned(),
+        ..kept.clone()
+    };
+    let gone = BackedUpTrack {
+        catalog_id: CatalogID("MCS0000".to_owned()),
+        isrc: "XX0000000000".to_owned(),
+        ..kept.clone()
+    };
+    backup.tracks.extend([removed.clone(), gone.clone()]);
+
+    let restore = mc.playlist().restore(backup)?;
+    assert_eq!(
+        restore.playlist_id,
+        PlaylistID(uuid!("b7e1c2d4-5a6f-4e3b-9c8d-1f2a3b4c5d6e"))
+    );
+    assert_eq!(restore.items.len(), 2);
+    assert!(restore
+        .items
+        .iter()
+        .all(|item| item.track_id == kept.track_id));
+    assert_eq!(restore.replaced, vec![(removed, kept.track_id)]);
+    assert_eq!(restore.missing, vec![gone]);
+
+    Ok(())
+}
+
+#[test]
+fn playlist_mutations_undo() -> Result<(), Error> {
+    use bombay::client::{
+        PlaylistItemMod, PlaylistItemOperations, PlaylistItemsMod, PlaylistItemsOperations,
+        UndoStep,
+    };
+    use bombay::mc::playlist::PlaylistItem;
+    use bombay::testing::fixtures::Fixture;
+
+    static EDITS: [Fixture; 3] = [
+        fixtures::PLAYLIST_CATALOG,
+        Fixture {
+            name: "modify_item",
+            path: "/playlist/f4a8b5a4-4c2c-4b5e-9fd1-6a6a3a37d1a2/modify-item",
+            body: "{}",
+        },
+        Fixture {
+            name: "modify_items",
+            path: "/playlist/f4a8b5a4-4c2c-4b5e-9fd1-6a6a3a37d1a2/modify-items",
+            body: "{}",
+        },
+    ];
+
+    let url = server::serve(&EDITS);
+    let mc = Client::new(url.clone(), format!("{url}/")).restore_session("cookie".to_owned())?;
+    let playlist_id = PlaylistID(uuid!("f4a8b5a4-4c2c-4b5e-9fd1-6a6a3a37d1a2"));
+    let record = PlaylistItem {
+        playlist_id,
+        release_id: ReleaseID(uuid!("6a58b6d2-bbec-4847-8dcf-45023a930968")),
+        sort: 5,
+        track_id: TrackID(uuid!("3c1c5bd2-9f5e-4d0b-a7a7-2bb3d1c4a0e1")),
+    };
+
+    // Removing the only track is undone by adding it back, then moving it
+    // where it was, whatever sort the removal was given.
+    let undo = mc.playlist().modify_item_with_undo(
+        playlist_id,
+        PlaylistItemOperations::Remove,
+        PlaylistItemMod {
+            move_to: None,
+            record: record.clone(),
+        },
+    )?;
+    assert_eq!(undo.steps.len(), 2);
+    assert!(matches!(
+        &undo.steps[1],
+        UndoStep::Item(PlaylistItemOperations::To, item_mod) if item_mod.move_to == Some(0)
+    ));
+    assert_eq!(mc.playlist().undo(undo)?, playlist_id);
+
+    let added = PlaylistItemsMod {
+        records: vec![record],
+    };
+    let undo = mc.playlist().modify_items_with_undo(
+        playlist_id,
+        PlaylistItemsOperations::Add,
+        added.clone(),
+    )?;
+    assert_eq!(
+        undo.steps,
+        vec![UndoStep::Items(PlaylistItemsOperations::Remove, added)]
+    );
+    assert_eq!(mc.playlist().undo(undo)?, playlist_id);
+
+    Ok(())
+}
+
+#[test]
+fn settings_read_modify_write() -> Result<(), Error> {
+    use bombay::mc::user::SettingsConflict;
+    use bombay::mc::util::Codec;
+    use bombay::testing::fixtures::Fixture;
+
+    static SETTINGS: [Fixture; 3] = [
+        fixtures::ME,
+        Fixture {
+            name: "settings",
+            path: "/me/settings",
+            body: "{}",
+        },
+        Fixture {
+            name: "connections",
+            path: "/me/connections",
+            body: r#"{"Connections": [{"CreatedAt": null, "ExternalId": "42", "Name": "label", "Service": "twitch"}]}"#,
+        },
+    ];
+
+    let url = server::serve(&SETTINGS);
+    let mc = Client::new(url.clone(), format!("{url}/")).restore_session("cookie".to_owned())?;
+
+    // Settings left alone are posted as they were.
+    let posted = mc
+        .user()
+        .update_settings(|settings| settings.preferred_format = Some(Codec::FLAC))?;
+    assert_eq!(posted.preferred_format, Some(Codec::FLAC));
+    assert_eq!(posted.playlist_public_default, Some(true));
+    assert_eq!(posted.say_song, Some(false));
+
+    assert!(matches!(
+        mc.user()
+            .update_settings(|settings| settings.auto_say_song = Some(true)),
+        Err(Error::InvalidSettings(
+            SettingsConflict::AutoSaySongWithoutSaySong
+        ))
+    ));
+
+    // Saying songs is allowed with a connected Twitch account.
+    let posted = mc.user().update_settings(|settings| {
+        settings.say_song = Some(true);
+        settings.auto_say_song = Some(true);
+    })?;
+    assert_eq!(posted.auto_say_song, Some(true));
+
+    Ok(())
+}
+
+#[cfg(feature = "datetime")]
+#[test]
+fn releasing_on_a_day_in_a_time_zone() -> Result<(), Error> {
+    use bombay::mc::datetime;
+    use chrono::NaiveDate;
+
+    let release = FIXTURE_CLIENT.release();
+    let vancouver = datetime::parse_timezone("America/Vancouver").unwrap();
+    let tokyo = datetime::parse_timezone("Asia/Tokyo").unwrap();
+    let sept_22 = NaiveDate::from_ymd_opt(2022, 9, 22).unwrap();
+    let sept_23 = NaiveDate::from_ymd_opt(2022, 9, 23).unwrap();
+
+    // Released at 9 AM in Vancouver, which is already the next day in Tokyo.
+    assert_eq!(release.get_releasing_on(sept_22, vancouver)?.len(), 2);
+    assert!(release.get_releasing_on(sept_22, tokyo)?.is_empty());
+    assert_eq!(release.get_releasing_on(sept_23, tokyo)?.len(), 2);
+
+    // Fixture releases are all out already.
+    assert!(release.get_upcoming()?.is_empty());
+
+    let latest = release.get_all(None)?.data.unwrap();
+    assert_eq!(
+        latest[0].released_at().to_rfc3339(),
+        "2022-09-22T09:00:00-07:00"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn faults_injected_per_path() {
+    let faults = Faults::new()
+        .add_times("/catalog/latest-releases", Fault::Status(500), 2)
+        .add_times("/releases", Fault::MalformedJson, 1)
+        .add("/moods", Fault::ConnectionReset)
+        .add("/artists", Fault::Delay(Duration::from_millis(200)));
+    let url = server::serve_with_faults(fixtures::ALL, faults);
+    let mc = Client::new(url.clone(), format!("{url}/"));
+
+    for _ in 0..2 {
+        let err = mc.release().get_latest(None).unwrap_err();
+        assert_eq!(err.status(), Some(500));
+        assert!(err.is_retryable());
+    }
+    assert!(mc.release().get_latest(None).is_ok());
+
+    assert!(matches!(
+        mc.release().get_all(None),
+        Err(Error::IO(_) | Error::Deserialization(_))
+    ));
+    assert!(mc.release().get_all(None).is_ok());
+
+    let err = mc.mood().get_all(None).unwrap_err();
+    assert_eq!(err.status(), None);
+    assert!(err.is_retryable());
+
+    let start = Instant::now();
+    assert!(mc.artist().get_all(None).is_ok());
+    assert!(start.elapsed() >= Duration::from_millis(200));
+}
+
+#[test]
+fn slim_list_items_from_fixtures() -> Result<(), Error> {
+    let releases = FIXTURE_CLIENT.release().get_all_slim(None)?;
+    let full = FIXTURE_CLIENT.release().get_all(None)?;
+    assert_eq!(releases.total, full.total);
+    let items = releases.data.unwrap();
+    let release = &items[0];
+    assert_eq!(release.title, full.data.unwrap()[0].get_title());
+    // Tracks listed among releases are read as their release.
+    assert_eq!(items[1].catalog_id, release.catalog_id);
+    assert!(items[1].genre_primary.is_some());
+    assert_eq!(
+        Some(release.release_date),
+        Timestamp::parse("2022-09-22T16:00:00Z")
+    );
+    assert_eq!(release.release_date_timezone, "America/Vancouver");
+
+    let artists = FIXTURE_CLIENT.artist().get_all_slim(None)?.data.unwrap();
+    let full = FIXTURE_CLIENT.artist().get_all(None)?.data.unwrap();
+    assert_eq!(artists[0], ArtistListItem::from(&full[0]));
+    Ok(())
+}
+
+#[test]
+fn single_flight_followers_outlive_a_failed_leader() -> Result<(), Error> {
+    // The first request is slow and fails, so the ones waiting on it make
+    // their own request instead of waiting on it forever.
+    let faults = Faults::new()
+        .add_times("/moods", Fault::Delay(Duration::from_millis(200)), 1)
+        .add_times("/moods", Fault::Status(500), 1);
+    let url = server::serve_with_faults(fixtures::ALL, faults);
+    let client = Client::new(url.clone(), format!("{url}/")).set_single_flight(true);
+
+    let results: Vec<_> = std::thread::scope(|scope| {
+        let leader = scope.spawn(|| client.mood().get_all(None));
+        std::thread::sleep(Duration::from_millis(50));
+        let followers: Vec<_> = (0..3)
+            .map(|_| scope.spawn(|| client.mood().get_all(None)))
+            .collect();
+        std::iter::once(leader)
+            .chain(followers)
+            .map(|handle| handle.join().unwrap())
+            .collect()
+    });
+
+    assert_eq!(results[0].as_ref().unwrap_err().status(), Some(500));
+    assert!(results[1..].iter().all(Result::is_ok));
+    Ok(())
+}
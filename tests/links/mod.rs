@@ -0,0 +1,44 @@
+use bombay::links::{self, LinkStatus};
+use bombay::mc::util::{Link, Platform};
+use bombay::testing::fixtures::Fixture;
+use bombay::testing::server::{self, Fault, Faults};
+
+/// Serve a link at `/ok`, another at `/moved` redirecting to it, and nothing else.
+fn serve_links() -> String {
+    static LINKS: [Fixture; 1] = [Fixture {
+        name: "ok",
+        path: "/ok",
+        body: "",
+    }];
+    server::serve_with_faults(
+        &LINKS,
+        Faults::new().add("/moved", Fault::Redirect("/ok".to_owned())),
+    )
+}
+
+#[test]
+fn validate_links() {
+    let server = serve_links();
+    let link = |path: &str| Link {
+        platform: Platform::Website,
+        url: format!("{server}{path}").parse().unwrap(),
+    };
+
+    // Nothing listens on port 9 locally.
+    let unreachable = Link {
+        platform: Platform::Spotify,
+        url: "http://127.0.0.1:9/".parse().unwrap(),
+    };
+
+    let reports = links::validate(&[link("/ok"), link("/moved"), link("/gone"), unreachable]);
+
+    assert_eq!(reports[0].status, LinkStatus::Ok);
+    assert_eq!(
+        reports[1].status,
+        LinkStatus::Redirected(format!("{server}/ok").parse().unwrap())
+    );
+    assert_eq!(reports[2].status, LinkStatus::Dead(404));
+    assert!(matches!(reports[3].status, LinkStatus::Unreachable(_)));
+    assert!(!reports[0].needs_attention());
+    assert!(reports[1].needs_attention());
+}
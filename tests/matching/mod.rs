@@ -0,0 +1,9 @@
+use bombay::matching::similarity;
+
+#[test]
+fn similarity_forgives_case_typos_and_order() {
+    assert_eq!(similarity("Ace Aura", "ace  aura!"), 1.0);
+    assert!(similarity("Ace Aura", "Ace Auro") > 0.8);
+    assert!(similarity("Rogue & Grant", "Grant Rogue") >= 0.6);
+    assert!(similarity("Ace Aura", "Bishu") < 0.3);
+}
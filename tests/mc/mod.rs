@@ -0,0 +1,707 @@
+use bombay::client::Client;
+use bombay::compat::{self, ApiVersion, UnknownEnumPolicy, UnknownVariant};
+use bombay::dedupe::{self, CanonicalRelease};
+use bombay::diff::{self, FieldChange};
+use bombay::mc::artist::{AnyArtist, Artist, ArtistID, Role};
+use bombay::mc::chart::{ChartEntry, ChartID, RankMovement};
+use bombay::mc::copyright::{CopyrightLine, CopyrightSymbol};
+use bombay::mc::id::{parse_any_id, AnyID, ById, EntityId, EntityKind};
+use bombay::mc::label::Brand;
+use bombay::mc::notes::{self, Block, Inline};
+use bombay::mc::playlist::PlaylistID;
+use bombay::mc::release::{AnyRelease, CatalogID, Genre, ReleasePage, Track, TrackID, VersionSet};
+use bombay::mc::share::{self, EmbedOptions};
+use bombay::mc::tag::{self, Tags};
+use bombay::mc::urls;
+use bombay::mc::util::{Platform, Region};
+use bombay::testing::{factories, fixtures};
+use iso8601_timestamp::Timestamp;
+use serde_json::Value;
+use std::collections::HashSet;
+use std::sync::{Arc, Mutex};
+use uuid::Uuid;
+
+#[test]
+fn tags_compare_normalized() {
+    let tags = Tags(vec![
+        "Instrumental".to_owned(),
+        " drum_and bass ".to_owned(),
+        "instrumental".to_owned(),
+    ]);
+
+    assert!(tags.has_tag(tag::INSTRUMENTAL));
+    assert!(tags.has_tag("Drum and Bass"));
+    assert!(!tags.has_tag(tag::REMIX));
+    assert_eq!(tags.normalized().to_string(), "instrumental,drum-and-bass");
+}
+
+#[test]
+fn playlist_share_and_embed() {
+    let playlist = factories::playlist();
+    let player = share::default_player_url();
+
+    assert_eq!(
+        playlist.share_url(&player).as_str(),
+        "https://player.monstercat.app/playlist/f4a8b5a4-4c2c-4b5e-9fd1-6a6a3a37d1a2"
+    );
+
+    let html = playlist.embed_html(&player, &EmbedOptions::default().set_autoplay(true));
+    assert!(html.starts_with("<iframe src=\"https://player.monstercat.app/embed/playlist/"));
+    assert!(html.contains("?theme=dark&amp;autoplay=1\""));
+
+    // Links point to the web player of a client's player API.
+    let client = Client::new(
+        "http://localhost:8080/player/api/".to_owned(),
+        "http://localhost:8080/".to_owned(),
+    );
+    let player = client.player_url().unwrap();
+    assert_eq!(player.as_str(), "http://localhost:8080/player");
+    assert_eq!(
+        playlist.share_url(&player).as_str(),
+        "http://localhost:8080/player/playlist/f4a8b5a4-4c2c-4b5e-9fd1-6a6a3a37d1a2"
+    );
+}
+
+#[test]
+fn website_urls() {
+    // Website URLs are the same as share URLs.
+    let playlist = factories::playlist();
+    assert_eq!(
+        urls::playlist_url(&playlist.id),
+        playlist.share_url(&share::default_player_url())
+    );
+    assert_eq!(
+        urls::playlist_url(&playlist.id).as_str(),
+        "https://player.monstercat.app/playlist/f4a8b5a4-4c2c-4b5e-9fd1-6a6a3a37d1a2"
+    );
+
+    // Segments are escaped rather than changing the path.
+    assert_eq!(
+        urls::artist_url("a/b c").as_str(),
+        "https://player.monstercat.app/artist/a%2Fb%20c"
+    );
+}
+
+#[test]
+fn artist_roles_parse() {
+    let artist: AnyArtist = serde_json::from_str(
+        r#"{
+            "CatalogRecordId": "MCS1186",
+            "Id": "2f1bd1a8-5c7d-4a4e-9b3e-0c1b6d1c3a11",
+            "Name": "Dutch Melrose",
+            "ProfileFileId": null,
+            "Public": true,
+            "Role": "featured",
+            "URI": "dutchmelrose"
+        }"#,
+    )
+    .unwrap();
+
+    assert_eq!(artist.get_name(), "Dutch Melrose");
+    assert_eq!(artist.get_role(), Some(&Role::Featured));
+    assert_eq!("Remix".parse::<Role>().unwrap(), Role::Remixer);
+    assert_eq!(
+        "Mastering".parse::<Role>().unwrap(),
+        Role::Other("Mastering".to_owned())
+    );
+}
+
+#[test]
+fn regions_parse() {
+    assert_eq!("uk".parse::<Region>().unwrap(), Region::UnitedKingdom);
+    assert_eq!(Region::UnitedKingdom.to_string(), "GB");
+    assert_eq!(
+        " se ".parse::<Region>().unwrap(),
+        Region::Other("SE".to_owned())
+    );
+}
+
+#[test]
+fn tracks_compare_by_id() {
+    let mut release: Value = serde_json::from_str(fixtures::RELEASE.body).unwrap();
+    let track: Track = serde_json::from_value(release["Tracks"][0].take()).unwrap();
+
+    let mut renamed = track.clone();
+    renamed.title = "Souvenir (Radio Edit)".to_owned();
+
+    let unique: HashSet<ById<Track>> = [ById(track), ById(renamed)].into_iter().collect();
+    assert_eq!(unique.len(), 1);
+}
+
+#[test]
+fn entity_ids_parse() {
+    fn kinds<T: EntityId>(ids: &[&str]) -> Vec<EntityKind> {
+        ids.iter()
+            .filter_map(|id| T::parse(id).ok())
+            .map(|id| id.entity_kind())
+            .collect()
+    }
+
+    let uuid = "3c1c5bd2-9f5e-4d0b-a7a7-2bb3d1c4a0e1";
+    assert_eq!(kinds::<TrackID>(&[uuid, "nope"]), [EntityKind::Track]);
+    assert_eq!(kinds::<ArtistID>(&[uuid]), [EntityKind::Artist]);
+    assert_eq!(ArtistID::from_uuid(Uuid::nil()).uuid(), Uuid::nil());
+
+    let playlist = parse_any_id(&format!(
+        "https://player.monstercat.app/playlist/{uuid}?t=1"
+    ))
+    .unwrap();
+    assert_eq!(playlist, AnyID::Playlist(PlaylistID::parse(uuid).unwrap()));
+    assert_eq!(parse_any_id(&playlist.to_string()), Some(playlist));
+
+    assert_eq!(
+        parse_any_id(&format!(" Mood:{uuid} ")).map(|id| id.entity_kind()),
+        Some(EntityKind::Mood)
+    );
+    assert_eq!(parse_any_id(uuid), None);
+    assert_eq!(parse_any_id(&format!("song:{uuid}")), None);
+    assert_eq!(
+        parse_any_id("https://www.monstercat.com/release/MCS1186"),
+        None
+    );
+}
+
+#[test]
+fn releases_compare_equal() {
+    let mut first: Value = serde_json::from_str(fixtures::RELEASE.body).unwrap();
+    let mut second = first.clone();
+
+    let first: AnyRelease = serde_json::from_value(first["Release"].take()).unwrap();
+    let second: AnyRelease = serde_json::from_value(second["Release"].take()).unwrap();
+    assert_eq!(first, second);
+}
+
+#[test]
+fn factory_tracks_are_distinct() {
+    let tracks = factories::tracks(3);
+    let unique: HashSet<ById<Track>> = tracks.into_iter().map(ById).collect();
+    assert_eq!(unique.len(), 3);
+}
+
+#[test]
+fn artist_uris_from_names() {
+    assert_eq!(Artist::uri_from_name("Ace Aura"), "ace-aura");
+    assert_eq!(Artist::uri_from_name("  Case & Point "), "case-and-point");
+    assert_eq!(Artist::uri_from_name("Rogue's Mr. Fix"), "rogues-mr-fix");
+    assert_eq!(Artist::uri_from_name("ace-aura"), "ace-aura");
+}
+
+#[test]
+fn catalog_ids_normalize() {
+    assert_eq!(
+        CatalogID::new(" mcs1186 "),
+        Some(CatalogID("MCS1186".to_owned()))
+    );
+    assert_eq!(CatalogID::new("MCS 1186"), None);
+    assert_eq!(CatalogID::new(""), None);
+
+    assert!(CatalogID("742779546913".to_owned()).is_upc());
+    assert!(!CatalogID("MCS1186".to_owned()).is_upc());
+}
+
+#[test]
+fn legacy_data_upgrades() {
+    // Recreate older data: camelCase keys and `_id` identifiers.
+    fn downgrade(value: Value) -> Value {
+        match value {
+            Value::Object(object) => Value::Object(
+                object
+                    .into_iter()
+                    .map(|(key, value)| {
+                        let key = match key.as_str() {
+                            "Id" => "_id".to_owned(),
+                            _ => key[..1].to_lowercase() + &key[1..],
+                        };
+                        (key, downgrade(value))
+                    })
+                    .collect(),
+            ),
+            Value::Array(values) => Value::Array(values.into_iter().map(downgrade).collect()),
+            value => value,
+        }
+    }
+
+    let track = factories::track();
+    let legacy = downgrade(serde_json::to_value(&track).unwrap());
+    assert!(legacy.get("_id").is_some());
+
+    let upgraded: Track = compat::from_value(legacy.clone(), ApiVersion::Legacy).unwrap();
+    assert_eq!(upgraded, track);
+    assert!(compat::from_value::<Track>(legacy, ApiVersion::Current).is_err());
+}
+
+#[test]
+fn genres_parse_and_match() {
+    assert_eq!(
+        "drum-and-bass".parse::<Genre>().unwrap(),
+        Genre::DrumAndBass
+    );
+    assert_eq!("Drum & Bass".parse::<Genre>().unwrap(), Genre::DrumAndBass);
+    assert_eq!(Genre::NuDisco.to_string(), "Nu Disco");
+    assert_eq!(
+        "Hyperpop".parse::<Genre>().unwrap(),
+        Genre::Other("Hyperpop".to_owned())
+    );
+
+    let release = factories::release();
+    assert!(release.has_genre(&Genre::DrumAndBass));
+    assert!(!release.has_genre(&Genre::Dubstep));
+}
+
+#[test]
+fn chart_ids_and_movement() {
+    assert_eq!(
+        ChartID::Genre(Genre::DrumAndBass).to_string(),
+        "genre/drum-and-bass"
+    );
+    assert_eq!(
+        "genre/drum-and-bass".parse::<ChartID>().unwrap(),
+        ChartID::Genre(Genre::DrumAndBass)
+    );
+    assert_eq!("Top30".parse::<ChartID>().unwrap(), ChartID::Top30);
+
+    let entry = |rank: u32, previous_rank: Option<u32>| ChartEntry {
+        peak_rank: None,
+        previous_rank,
+        rank,
+        track: factories::track(),
+        weeks_on_chart: None,
+    };
+    assert_eq!(entry(3, None).movement(), RankMovement::New);
+    assert_eq!(entry(3, Some(7)).movement(), RankMovement::Up(4));
+    assert_eq!(entry(7, Some(3)).movement(), RankMovement::Down(4));
+    assert_eq!(entry(3, Some(3)).movement(), RankMovement::Same);
+}
+
+#[test]
+fn stream_safe_versions() {
+    let mut tracks = factories::tracks(5).into_iter();
+    let mut explicit = tracks.next().unwrap();
+    explicit.explicit = true;
+
+    let mut instrumental = tracks.next().unwrap();
+    instrumental.version = "Instrumental Mix".to_owned();
+    let mut clean = tracks.next().unwrap();
+    clean.version = "Clean".to_owned();
+    let mut remix = tracks.next().unwrap();
+    remix.version = "Grant Remix".to_owned();
+    let mut other_song = tracks.next().unwrap();
+    other_song.title = "Another Song".to_owned();
+
+    let versions = VersionSet::from_candidates(
+        explicit.clone(),
+        vec![
+            explicit.clone(),
+            instrumental.clone(),
+            remix,
+            clean.clone(),
+            clean.clone(),
+            other_song,
+        ],
+    );
+    assert_eq!(versions.versions.len(), 3);
+    assert_eq!(versions.instrumentals().count(), 1);
+    assert_eq!(versions.stream_safe(), Some(&clean));
+
+    // Without a clean edit, fall back to the instrumental.
+    let versions = VersionSet::from_candidates(explicit, vec![instrumental.clone()]);
+    assert_eq!(versions.stream_safe(), Some(&instrumental));
+
+    // Safe tracks need no replacement.
+    let safe = factories::track();
+    assert_eq!(
+        VersionSet::from_candidates(safe.clone(), vec![]).stream_safe(),
+        Some(&safe)
+    );
+}
+
+#[test]
+fn diff_playlists() {
+    let before = factories::playlist();
+    assert!(diff::diff(&before, &before).unwrap().is_empty());
+
+    let mut after = before.clone();
+    after.title = "Favourites".to_owned();
+    after.is_public = false;
+
+    let diff = diff::diff(&before, &after).unwrap();
+    assert_eq!(diff.changes.len(), 2);
+    assert!(diff.changed("/Title"));
+    assert!(diff.changed("/IsPublic"));
+    assert!(!diff.changed("/Is"));
+    assert_eq!(
+        diff.changes
+            .iter()
+            .find(|change| change.path == "/Title")
+            .map(FieldChange::to_string),
+        Some("/Title: \"Favorites\" -> \"Favourites\"".to_owned())
+    );
+
+    // Items within lists are compared by position.
+    let tracks = factories::tracks(2);
+    let mut reordered = tracks.clone();
+    reordered.swap(0, 1);
+    let diff = diff::diff(&tracks, &reordered).unwrap();
+    assert!(diff.changed("/0/Id"));
+    assert!(diff.changed("/1"));
+}
+
+#[test]
+fn mood_params_parse_older_spellings() {
+    use bombay::mc::mood::MoodParam;
+
+    for (older, param) in [
+        ("speechniess", MoodParam::Speechiness),
+        ("valance", MoodParam::Valence),
+    ] {
+        assert_eq!(older.parse::<MoodParam>().unwrap(), param);
+    }
+    assert_eq!(MoodParam::Valence.to_string(), "valence");
+    assert_eq!(
+        serde_json::from_str::<MoodParam>("\"Energy\"").unwrap(),
+        MoodParam::Energy
+    );
+}
+
+#[test]
+fn unknown_enum_policies() {
+    // Unknown values are kept by default.
+    let brand: Brand = serde_json::from_str("9").unwrap();
+    assert_eq!(brand, Brand::Other(9));
+    assert_eq!(serde_json::to_string(&brand).unwrap(), "9");
+    assert_eq!(serde_json::from_str::<Brand>("4").unwrap(), Brand::Silk);
+
+    let strict = compat::with_unknown_enum_policy(UnknownEnumPolicy::Error, || {
+        (
+            serde_json::from_str::<Brand>("9").is_err(),
+            serde_json::from_str::<Platform>("\"myspace\"").is_err(),
+            serde_json::from_str::<Platform>("\"spotify\"").is_ok(),
+        )
+    });
+    assert_eq!(strict, (true, true, true));
+
+    let seen = Arc::new(Mutex::new(Vec::new()));
+    let callback = {
+        let seen = seen.clone();
+        UnknownEnumPolicy::Callback(Arc::new(move |unknown: &UnknownVariant| {
+            seen.lock().unwrap().push(unknown.clone())
+        }))
+    };
+    let role = compat::with_unknown_enum_policy(callback, || "Narrator".parse::<Role>());
+    assert_eq!(role.unwrap(), Role::Other("Narrator".to_owned()));
+    assert_eq!(
+        *seen.lock().unwrap(),
+        vec![UnknownVariant {
+            enum_name: "Role",
+            value: "Narrator".to_owned()
+        }]
+    );
+
+    // The scoped policy doesn't outlive its closure.
+    assert!(serde_json::from_str::<Brand>("9").is_ok());
+}
+
+#[test]
+fn dedupe_tracks_by_isrc() {
+    let single = factories::track();
+
+    let mut album = single.clone();
+    album.id = TrackID(Uuid::from_u128(2));
+    album.isrc = "ca-6d2-21-00326".to_owned();
+    album.release.kind = "Album".to_owned();
+    album.release.release_date = Timestamp::parse("2023-03-01T16:00:00Z").unwrap();
+
+    let mut other = single.clone();
+    other.id = TrackID(Uuid::from_u128(3));
+    other.isrc = "CA6D22100999".to_owned();
+
+    let mut unknown = single.clone();
+    unknown.isrc = String::new();
+
+    let tracks = vec![
+        album.clone(),
+        other.clone(),
+        single.clone(),
+        unknown.clone(),
+        unknown,
+    ];
+
+    // The single came out first, and takes the album's place.
+    let deduped = dedupe::dedupe_tracks(tracks.clone());
+    let ids: Vec<_> = deduped.iter().map(|track| track.id).collect();
+    assert_eq!(ids, vec![single.id, other.id, single.id, single.id]);
+
+    let deduped = dedupe::dedupe_tracks_with(tracks.clone(), &CanonicalRelease::Latest);
+    assert_eq!(deduped[0].id, album.id);
+
+    let albums_first = CanonicalRelease::KindOrder(vec!["album".to_owned()]);
+    let deduped = dedupe::dedupe_tracks_with(tracks, &albums_first);
+    assert_eq!(deduped[0].id, album.id);
+    assert_eq!(deduped.len(), 4);
+}
+
+#[test]
+fn settings_dependencies() {
+    use bombay::mc::connect::{ConnectService, Connection};
+    use bombay::mc::user::{EditableSettings, SettingsConflict};
+
+    let mut settings = EditableSettings {
+        playlist_public_default: None,
+        preferred_format: None,
+        say_song: Some(false),
+        auto_say_song: Some(true),
+    };
+    assert_eq!(
+        settings.validate(&[]),
+        Err(SettingsConflict::AutoSaySongWithoutSaySong)
+    );
+
+    settings.say_song = Some(true);
+    assert_eq!(
+        settings.validate(&[]),
+        Err(SettingsConflict::TwitchNotConnected)
+    );
+
+    let twitch = Connection {
+        created_at: None,
+        external_id: None,
+        name: None,
+        service: ConnectService::Twitch,
+    };
+    assert_eq!(settings.validate(std::slice::from_ref(&twitch)), Ok(()));
+
+    // Settings left out may already be enabled on the server.
+    settings.say_song = None;
+    assert_eq!(settings.validate(&[twitch]), Ok(()));
+    assert_eq!(
+        settings.validate(&[]),
+        Err(SettingsConflict::TwitchNotConnected)
+    );
+
+    // Disabled settings don't depend on anything.
+    settings.say_song = None;
+    settings.auto_say_song = Some(false);
+    assert_eq!(settings.validate(&[]), Ok(()));
+}
+
+#[cfg(feature = "datetime")]
+#[test]
+fn release_display_dates() {
+    let mut release = factories::release();
+    // Just after midnight UTC, still the evening before in Vancouver.
+    release.release_date = Timestamp::parse("2022-09-23T00:30:00Z").unwrap();
+
+    assert_eq!(release.display_date("en-US", None), "09/22/2022");
+    assert_eq!(release.display_date("de", None), "22.09.2022");
+    assert_eq!(
+        release.display_date("xx", Some("Europe/Berlin")),
+        "2022-09-23"
+    );
+
+    let track = AnyRelease::Track(factories::track());
+    let release = AnyRelease::Release(release);
+    assert_eq!(
+        release.to_string(),
+        "Souvenir by Whales (MCS1186) - 2022-09-22"
+    );
+    assert!(track.to_string().contains("(MCS1186)"));
+}
+
+#[cfg(feature = "datetime")]
+#[test]
+fn release_calendar_by_day_and_week() {
+    use bombay::calendar::{CalendarEventKind, ReleaseCalendar};
+    use bombay::mc::datetime;
+    use chrono::NaiveDate;
+
+    let mut souvenir = factories::release();
+    souvenir.presave_date = Timestamp::parse("2022-09-15T16:00:00Z");
+    let mut single = factories::release();
+    single.title = "Earlier".to_owned();
+    single.release_date = Timestamp::parse("2022-09-20T23:30:00Z").unwrap();
+
+    let vancouver = datetime::parse_timezone("America/Vancouver").unwrap();
+    let calendar = ReleaseCalendar::new(
+        vancouver,
+        [AnyRelease::Release(souvenir), AnyRelease::Release(single)],
+    );
+    assert_eq!(calendar.len(), 3);
+
+    let days: Vec<NaiveDate> = calendar.days().map(|(date, _)| date).collect();
+    let date = |day| NaiveDate::from_ymd_opt(2022, 9, day).unwrap();
+    assert_eq!(days, vec![date(15), date(20), date(22)]);
+    assert_eq!(calendar.on(date(15))[0].kind, CalendarEventKind::Presave);
+
+    // The presave is the week before, the releases share a week from Monday.
+    let weeks: Vec<(NaiveDate, usize)> = calendar
+        .weeks()
+        .map(|(monday, entries)| (monday, entries.len()))
+        .collect();
+    assert_eq!(weeks, vec![(date(12), 1), (date(19), 2)]);
+
+    let titles: Vec<&str> = calendar
+        .iter()
+        .map(|entry| entry.release.get_title())
+        .collect();
+    assert_eq!(titles, vec!["Souvenir", "Earlier", "Souvenir"]);
+
+    // Half past eleven at night in UTC is the next day in Tokyo.
+    let tokyo = datetime::parse_timezone("Asia/Tokyo").unwrap();
+    let calendar = ReleaseCalendar::new(tokyo, calendar.iter().map(|entry| entry.release.clone()));
+    assert_eq!(calendar.on(date(21))[0].release.get_title(), "Earlier");
+}
+
+#[test]
+fn album_notes_parse() {
+    let mut release = factories::release();
+    assert!(release.notes().is_empty());
+
+    release.album_notes = Some(
+        "<h3>Tracklist</h3><ul><li>Souvenir</li><li>Souvenir (VIP)</li></ul>\
+         <p>Out now on <a href=\"https://www.monstercat.com\">Monstercat</a> &amp; \
+         <a href=\"javascript:alert(1)\">elsewhere</a>.<script>alert(1)</script></p>"
+            .to_owned(),
+    );
+    let blocks = release.notes();
+    assert_eq!(blocks.len(), 4);
+    assert_eq!(
+        blocks[0],
+        Block::Heading {
+            level: 3,
+            content: vec![Inline::Text("Tracklist".to_owned())]
+        }
+    );
+    assert_eq!(
+        blocks[2],
+        Block::ListItem(vec![Inline::Text("Souvenir (VIP)".to_owned())])
+    );
+    assert_eq!(
+        notes::to_plaintext(&blocks),
+        "Tracklist\n\n- Souvenir\n- Souvenir (VIP)\n\n\
+         Out now on Monstercat (https://www.monstercat.com/) & elsewhere."
+    );
+
+    let blocks = notes::parse(
+        "## Credits\nMixed by **Whales**,\nsee [the site](https://example.com/a_b).\n\n\
+         More at https://example.com. *Enjoy* <3",
+    );
+    assert_eq!(
+        notes::to_markdown(&blocks),
+        "## Credits\n\nMixed by Whales, see [the site](https://example.com/a_b).\n\n\
+         More at [https://example.com/](https://example.com/). \\*Enjoy\\* \\<3"
+    );
+    assert_eq!(
+        notes::to_plaintext(&blocks),
+        "Credits\n\nMixed by Whales, see the site (https://example.com/a_b).\n\n\
+         More at https://example.com/. *Enjoy* <3"
+    );
+}
+
+#[test]
+fn copyright_lines_parse() {
+    let line = CopyrightLine::parse("℗ 2022 Monstercat");
+    assert_eq!(line.year, Some(2022));
+    assert_eq!(line.owner.as_deref(), Some("Monstercat"));
+
+    let line = CopyrightLine::parse("Copyright © Monstercat Uncaged, 2021");
+    assert_eq!(line.year, Some(2021));
+    assert_eq!(line.owner.as_deref(), Some("Monstercat Uncaged"));
+    assert_eq!(line.symbol, CopyrightSymbol::Copyright);
+    assert_eq!(line.to_string(), "© 2021 Monstercat Uncaged");
+    assert_eq!(
+        CopyrightLine::parse("Copyright Monstercat 2020").to_string(),
+        "Copyright 2020 Monstercat"
+    );
+
+    let line = CopyrightLine::parse("℗2019 – 2020");
+    assert_eq!(line.year, Some(2019));
+    assert_eq!(line.owner, None);
+    assert_eq!(CopyrightLine::parse("").to_string(), "℗");
+
+    // Years within the owner's name are left in it.
+    let line = CopyrightLine::parse("℗ Studio 1999 Records");
+    assert_eq!(line.year, None);
+    assert_eq!(line.owner.as_deref(), Some("Studio 1999 Records"));
+    let line = CopyrightLine::parse("℗ 2022 Studio 1999 Records");
+    assert_eq!(line.year, Some(2022));
+    assert_eq!(line.owner.as_deref(), Some("Studio 1999 Records"));
+
+    let mut release = factories::release();
+    release.copyright_p_line = Some("℗ 2021 Whales".to_owned());
+    assert_eq!(release.copyright().to_string(), "℗ 2021 Whales");
+
+    // Release pages fall back to their release, keeping its symbol.
+    release.copyright_p_line = Some("© 2021 Whales".to_owned());
+    let mut page: ReleasePage = serde_json::from_str(fixtures::RELEASE_PAGE.body).unwrap();
+    page.release = release;
+    page.copyright_p_line = None;
+    assert_eq!(page.copyright().to_string(), "© 2021 Whales");
+    page.copyright_p_line = Some("℗ Monstercat".to_owned());
+    assert_eq!(page.copyright().to_string(), "℗ 2021 Monstercat");
+}
+
+#[cfg(feature = "datetime")]
+#[test]
+fn copyright_year_in_release_time_zone() {
+    let mut release = factories::release();
+    // Just after midnight UTC, still the year before in Vancouver.
+    release.release_date = Timestamp::parse("2023-01-01T00:30:00Z").unwrap();
+    assert_eq!(release.release_year(), 2022);
+    assert_eq!(
+        release.copyright(),
+        CopyrightLine {
+            year: Some(2022),
+            owner: Some("Monstercat Uncaged".to_owned()),
+            ..CopyrightLine::default()
+        }
+    );
+}
+
+#[test]
+fn track_features_in_either_case() {
+    use bombay::mc::mood::{MoodID, MoodParam, MoodParamConfig};
+
+    let mut json = serde_json::to_value(factories::track()).unwrap();
+    assert!(json.get("Energy").is_none());
+    json["Energy"] = 0.8.into();
+    json["valence"] = 0.25.into();
+
+    let track: Track = serde_json::from_value(json).unwrap();
+    assert_eq!((track.energy, track.valence), (Some(0.8), Some(0.25)));
+    assert_eq!(track.danceability, None);
+
+    // Features are matched against moods, leaving out those the track lacks.
+    let range = |param, min, max| MoodParamConfig {
+        mood_id: MoodID(Uuid::nil()),
+        param,
+        min,
+        max,
+    };
+    let features = track.features();
+    assert!(range(MoodParam::Energy, 0.5, 1.0).contains(&features));
+    assert!(!range(MoodParam::Valence, 0.5, 1.0).contains(&features));
+    assert!(range(MoodParam::Danceability, 0.5, 1.0).contains(&features));
+}
+
+#[test]
+fn parse_enums_without_losing_input() {
+    use bombay::mc::playlist::PlaylistPermission;
+    use bombay::mc::util::Codec;
+
+    assert_eq!("FLAC".parse::<Codec>().unwrap(), Codec::FLAC);
+    assert!("ogg".parse::<Codec>().is_err());
+    assert!(serde_json::from_str::<Codec>("\"ogg\"").is_err());
+
+    assert_eq!(
+        "Viewer".parse::<PlaylistPermission>().unwrap(),
+        PlaylistPermission::View
+    );
+    let unknown: PlaylistPermission = "CoOwner".parse().unwrap();
+    assert_eq!(unknown, PlaylistPermission::Other("CoOwner".to_owned()));
+    assert_eq!(unknown.to_string(), "CoOwner");
+
+    let strict = compat::with_unknown_enum_policy(UnknownEnumPolicy::Error, || {
+        "CoOwner".parse::<PlaylistPermission>()
+    });
+    assert!(strict.is_err());
+}
@@ -7,15 +7,15 @@ lazy_static! {
 }
 
 lazy_static! {
-    static ref MC_EMAIL: &'static str = option_env!("MC_EMAIL").unwrap();
+    static ref MC_EMAIL: String = std::env::var("MC_EMAIL").unwrap();
 }
 
 lazy_static! {
-    static ref MC_PASSWORD: &'static str =  option_env!("MC_PASSWORD").unwrap();
+    static ref MC_PASSWORD: String = std::env::var("MC_PASSWORD").unwrap();
 }
 
 lazy_static! {
-    static ref MC_TOTP_SECRET: Secret = Secret::Encoded( option_env!("MC_TOTP_SECRET").unwrap().to_owned());
+    static ref MC_TOTP_SECRET: Secret = Secret::Encoded(std::env::var("MC_TOTP_SECRET").unwrap());
 }
 
 lazy_static! {
@@ -38,4 +38,20 @@ lazy_static! {
         .expect("Failed to sign in");
 }
 
+mod accounts;
+#[cfg(feature = "datetime")]
+mod analytics;
+mod app;
 mod client;
+mod dirs;
+mod download;
+mod fixtures;
+mod links;
+mod matching;
+mod mc;
+mod player;
+mod radio;
+mod scheduler;
+mod search;
+mod testing;
+mod watch;
@@ -0,0 +1,109 @@
+use bombay::player::{Queue, RepeatMode, ShuffleMode};
+
+#[test]
+fn queue_plays_in_order_then_stops() {
+    let mut queue = Queue::from(vec![1, 2, 3]);
+
+    assert_eq!(queue.current(), None);
+    assert_eq!(queue.next(), Some(&1));
+    assert_eq!(queue.next(), Some(&2));
+    assert_eq!(queue.up_next().collect::<Vec<_>>(), vec![&3]);
+    assert_eq!(queue.next(), Some(&3));
+    assert_eq!(queue.next(), None);
+    assert_eq!(queue.history().collect::<Vec<_>>(), vec![&1, &2, &3]);
+}
+
+#[test]
+fn queue_plays_items_pushed_after_the_end() {
+    let mut queue = Queue::from(vec![1, 2]);
+    queue.next();
+    queue.next();
+    assert_eq!(queue.next(), None);
+
+    queue.push(3);
+    assert_eq!(queue.up_next().collect::<Vec<_>>(), vec![&3]);
+    assert_eq!(queue.next(), Some(&3));
+    assert_eq!(queue.next(), None);
+
+    queue.push_next(4);
+    assert_eq!(queue.next(), Some(&4));
+    assert_eq!(queue.history().collect::<Vec<_>>(), vec![&1, &2, &3]);
+
+    // An empty queue plays what is pushed first.
+    let mut empty = Queue::new();
+    assert_eq!(empty.next(), None);
+    empty.push(1);
+    assert_eq!(empty.next(), Some(&1));
+}
+
+#[test]
+fn queue_repeats() {
+    let mut queue = Queue::from(vec![1, 2]);
+
+    queue.set_repeat(RepeatMode::One);
+    assert_eq!(queue.next(), Some(&1));
+    assert_eq!(queue.next(), Some(&1));
+
+    queue.set_repeat(RepeatMode::All);
+    assert_eq!(queue.next(), Some(&2));
+    assert_eq!(queue.next(), Some(&1));
+    assert_eq!(queue.previous(), Some(&2));
+}
+
+#[test]
+fn queue_skips_with_repeat_one() {
+    let mut queue = Queue::from(vec![1, 2]);
+    queue.set_repeat(RepeatMode::One);
+    assert_eq!(queue.next(), Some(&1));
+
+    // Skipping moves on, and the skipped-to item repeats in turn.
+    assert_eq!(queue.skip(), Some(&2));
+    assert_eq!(queue.next(), Some(&2));
+    assert_eq!(queue.skip(), None);
+
+    queue.set_repeat(RepeatMode::All);
+    assert_eq!(queue.skip(), Some(&1));
+}
+
+#[test]
+fn queue_goes_back() {
+    let mut queue = Queue::from(vec![1, 2, 3]);
+
+    queue.next();
+    queue.next();
+    assert_eq!(queue.previous(), Some(&1));
+    assert_eq!(queue.previous(), Some(&1));
+    assert_eq!(queue.history().count(), 0);
+}
+
+#[test]
+fn queue_shuffle_keeps_current_and_items() {
+    let mut queue = Queue::from((0..50).collect::<Vec<_>>());
+
+    queue.next();
+    queue.set_shuffle(ShuffleMode::On);
+    assert_eq!(queue.current(), Some(&0));
+
+    let mut up_next: Vec<_> = queue.up_next().copied().collect();
+    up_next.sort();
+    assert_eq!(up_next, (1..50).collect::<Vec<_>>());
+
+    queue.next();
+    let current = *queue.current().unwrap();
+    queue.set_shuffle(ShuffleMode::Off);
+    assert_eq!(queue.current(), Some(&current));
+    assert_eq!(
+        queue.up_next().next(),
+        (current + 1 < 50).then_some(&(current + 1))
+    );
+}
+
+#[test]
+fn queue_push_next() {
+    let mut queue = Queue::from(vec![1, 2]);
+
+    queue.next();
+    queue.push_next(9);
+    queue.push(3);
+    assert_eq!(queue.up_next().collect::<Vec<_>>(), vec![&9, &2, &3]);
+}
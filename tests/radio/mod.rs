@@ -0,0 +1,19 @@
+use crate::CLIENT;
+use bombay::client::Error;
+use bombay::radio::MoodRadio;
+
+#[test]
+fn chill_radio() -> Result<(), Error> {
+    let chill = CLIENT.mood().get_by_name_uri("chill")?;
+
+    println!("Playing {} radio:", chill.name);
+    for track in MoodRadio::from_mood(&CLIENT, &chill)
+        .set_page_size(5)
+        .take(10)
+    {
+        let track = track?;
+        println!("  {} by {}", track.title, track.artists_title);
+    }
+
+    Ok(())
+}
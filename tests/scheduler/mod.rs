@@ -0,0 +1,113 @@
+use bombay::client::Error;
+use bombay::scheduler::Scheduler;
+use bombay::shutdown::ShutdownGuard;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+#[test]
+fn scheduler_runs_due_jobs() {
+    let failed = Arc::new(Mutex::new(Vec::new()));
+    let failures = failed.clone();
+    let mut scheduler = Scheduler::new()
+        .set_jitter(0.0)
+        .set_on_job_failed(move |name, _| failures.lock().unwrap().push(name.to_owned()))
+        .add("often", Duration::from_secs(10), || Ok(()))
+        .add("rarely", Duration::from_secs(60), || {
+            Err(Error::Message("Could not refresh."))
+        });
+    assert_eq!(scheduler.job_names(), vec!["often", "rarely"]);
+
+    // Jobs run right away, then every interval, and keep going after failing.
+    let start = Instant::now();
+    assert_eq!(scheduler.run_due(start), vec!["often", "rarely"]);
+    assert_eq!(scheduler.next_run(), Some(start + Duration::from_secs(10)));
+    assert!(scheduler.run_due(start + Duration::from_secs(5)).is_empty());
+    assert_eq!(
+        scheduler.run_due(start + Duration::from_secs(10)),
+        vec!["often"]
+    );
+    assert_eq!(
+        scheduler.run_due(start + Duration::from_secs(60)),
+        vec!["often", "rarely"]
+    );
+    assert_eq!(*failed.lock().unwrap(), vec!["rarely", "rarely"]);
+}
+
+#[test]
+fn scheduler_jitters_intervals() {
+    let mut scheduler =
+        Scheduler::new()
+            .set_jitter(0.5)
+            .add("job", Duration::from_secs(100), || Ok(()));
+
+    let mut now = Instant::now();
+    let mut delays = Vec::new();
+    for _ in 0..20 {
+        scheduler.run_due(now);
+        let next = scheduler.next_run().unwrap();
+        delays.push(next - now);
+        now = next;
+    }
+
+    assert!(delays
+        .iter()
+        .all(|delay| *delay >= Duration::from_secs(50) && *delay <= Duration::from_secs(150)));
+    assert!(delays.iter().any(|delay| *delay != delays[0]));
+}
+
+#[test]
+fn scheduler_stops_gracefully() {
+    let runs = Arc::new(AtomicUsize::new(0));
+    let counted = runs.clone();
+    let running = Scheduler::new()
+        .add("count", Duration::from_millis(5), move || {
+            counted.fetch_add(1, Ordering::SeqCst);
+            Ok(())
+        })
+        .spawn();
+
+    thread::sleep(Duration::from_millis(50));
+    let scheduler = running.stop();
+    let stopped_at = runs.load(Ordering::SeqCst);
+    assert!(stopped_at >= 2);
+
+    // Nothing runs after stopping, and the jobs are kept.
+    thread::sleep(Duration::from_millis(20));
+    assert_eq!(runs.load(Ordering::SeqCst), stopped_at);
+    assert_eq!(scheduler.job_names(), vec!["count"]);
+}
+
+#[test]
+fn shutdown_guard_runs_steps_in_reverse() {
+    let ran = Arc::new(Mutex::new(Vec::new()));
+    let step = |name: &'static str, fails: bool| {
+        let ran = ran.clone();
+        move || {
+            ran.lock().unwrap().push(name);
+            match fails {
+                true => Err(Error::Message("Could not flush.")),
+                false => Ok(()),
+            }
+        }
+    };
+
+    let running = Scheduler::new()
+        .add("idle", Duration::from_secs(60), || Ok(()))
+        .spawn();
+    let guard = ShutdownGuard::new()
+        .add("client", step("client", false))
+        .add("flush", step("flush", true))
+        .add_scheduler(running);
+    assert_eq!(guard.step_names(), vec!["scheduler", "flush", "client"]);
+
+    // Every step runs, even after one fails.
+    let errors = guard.shutdown();
+    assert_eq!(errors.len(), 1);
+    assert_eq!(*ran.lock().unwrap(), vec!["flush", "client"]);
+
+    // Dropping the guard shuts down too.
+    drop(ShutdownGuard::new().add("dropped", step("dropped", false)));
+    assert_eq!(*ran.lock().unwrap(), vec!["flush", "client", "dropped"]);
+}
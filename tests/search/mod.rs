@@ -0,0 +1,30 @@
+use crate::CLIENT;
+use bombay::client::Error;
+use bombay::search::{score, smart_search};
+
+#[test]
+fn score_prefers_closer_matches() {
+    let exact_uri = score("ace aura", "Ace Aura", Some("ace-aura"));
+    let exact = score("ace aura", "Ace Aura", None);
+    let prefix = score("ace", "Ace Aura", None);
+    let contains = score("aura", "Ace Aura", None);
+    let overlap = score("aura ace remix", "Ace Aura", None);
+
+    assert!(exact_uri > exact);
+    assert!(exact > prefix);
+    assert!(prefix > contains);
+    assert!(contains > overlap);
+    assert!(overlap > 0.0);
+    assert_eq!(score("rogue", "Ace Aura", None), 0.0);
+}
+
+#[test]
+fn search_ace_aura() -> Result<(), Error> {
+    let results = smart_search(&*CLIENT, "ace aura")?;
+
+    if let Some(best) = results.first() {
+        println!("Best match is {} ({:.2}).", best.entity.title(), best.score);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,20 @@
+use bombay::client::{Paginated, Wrapped};
+use bombay::mc::artist::Artist;
+use bombay::mc::release::AnyRelease;
+use bombay::testing::fixtures;
+
+#[test]
+fn fixtures_deserialize() {
+    let releases: Paginated<AnyRelease> =
+        serde_json::from_str(fixtures::LATEST_RELEASES.body).unwrap();
+    assert_eq!(releases.data.unwrap().len(), releases.total);
+
+    let mut artists: Wrapped<Paginated<Artist>> =
+        serde_json::from_str(fixtures::ARTISTS.body).unwrap();
+    assert!(artists.remove("Artists").is_some());
+
+    assert_eq!(
+        fixtures::by_path("/catalog/release/MCS1186?idType=catalogId"),
+        Some(&fixtures::RELEASE)
+    );
+}
@@ -0,0 +1,130 @@
+use crate::CLIENT;
+use bombay::client::Error;
+use bombay::mc::util::{Link, Platform};
+use bombay::testing::factories;
+use bombay::watch::{ArtistChange, ArtistChangeKind, ArtistWatcher};
+
+#[test]
+fn artist_changes() {
+    let before = factories::artist();
+    assert!(ArtistChange::between(&before, &before).unwrap().is_empty());
+
+    let link = Link {
+        platform: Platform::Spotify,
+        url: "https://open.spotify.com/artist/whales".parse().unwrap(),
+    };
+    let mut after = before.clone();
+    after.about = Some("Whales makes drum & bass.".to_owned());
+    after.links = Some(vec![link.clone()]);
+
+    let kinds: Vec<ArtistChangeKind> = ArtistChange::between(&before, &after)
+        .unwrap()
+        .into_iter()
+        .map(|change| change.kind)
+        .collect();
+    assert_eq!(
+        kinds,
+        vec![
+            ArtistChangeKind::About {
+                before: None,
+                after: Some("Whales makes drum & bass.".to_owned()),
+            },
+            ArtistChangeKind::LinkAdded(link.clone()),
+        ]
+    );
+
+    let removed = ArtistChange::between(&after, &before).unwrap();
+    assert!(removed.contains(&ArtistChange {
+        uri: "whales".to_owned(),
+        kind: ArtistChangeKind::LinkRemoved(link),
+    }));
+}
+
+#[test]
+fn artist_changes_with_details() {
+    let mut before = factories::artist();
+    before.details.about = Some("Whales makes bass music.".to_owned());
+    before.details.show_events = Some("Tour dates soon.".to_owned());
+
+    // Details survive serialization, so they don't hide other changes.
+    let json = serde_json::to_string(&before).unwrap();
+    assert_eq!(
+        serde_json::from_str::<bombay::mc::artist::Artist>(&json).unwrap(),
+        before
+    );
+
+    let mut after = before.clone();
+    after.name = "Whales & Friends".to_owned();
+    after.featured_release_id = Some("MCS1186".to_owned());
+
+    let kinds: Vec<ArtistChangeKind> = ArtistChange::between(&before, &after)
+        .unwrap()
+        .into_iter()
+        .map(|change| change.kind)
+        .collect();
+    assert_eq!(
+        kinds,
+        vec![
+            ArtistChangeKind::Name {
+                before: "Whales".to_owned(),
+                after: "Whales & Friends".to_owned(),
+            },
+            ArtistChangeKind::FeaturedRelease {
+                before: None,
+                after: Some("MCS1186".to_owned()),
+            },
+        ]
+    );
+}
+
+#[test]
+fn watch_rogue() -> Result<(), Error> {
+    let mut watcher = ArtistWatcher::new(&*CLIENT, ["rogue"]);
+
+    // The first poll only records the page, the second finds nothing new.
+    assert!(watcher.poll()?.is_empty());
+    assert!(watcher.last_seen("rogue").is_some());
+    println!("Changes: {:?}", watcher.poll()?);
+
+    Ok(())
+}
+
+#[test]
+fn presave_tracker_fires_on_release() {
+    use bombay::watch::PresaveTracker;
+    use iso8601_timestamp::Timestamp;
+    use std::sync::{Arc, Mutex};
+    use std::time::Duration;
+
+    let now = Timestamp::now_utc();
+    let in_hours =
+        |hours: u64| Timestamp::from_primitive_datetime(*now + Duration::from_secs(hours * 3600));
+
+    let mut soon = factories::release();
+    soon.release_date = in_hours(1);
+    let mut later = factories::release();
+    later.id.0 = uuid::uuid!("9b2f4c1e-6d3a-4f8b-8e5d-2c7a1b0f9e34");
+    later.release_date = in_hours(48);
+
+    let released = Arc::new(Mutex::new(Vec::new()));
+    let told = released.clone();
+    let mut tracker =
+        PresaveTracker::new().set_on_release(move |release| told.lock().unwrap().push(release.id));
+
+    // Releases already out, or already tracked, are not tracked again.
+    assert!(tracker.track(later.clone()));
+    assert!(tracker.track(soon.clone()));
+    assert!(!tracker.track(soon.clone()));
+    assert!(!tracker.track(factories::release()));
+    assert_eq!(tracker.next_release_at(), Some(&soon.release_date));
+
+    assert!(tracker.tick().is_empty());
+    assert_eq!(tracker.tick_at(in_hours(2)), vec![soon.clone()]);
+    assert_eq!(*released.lock().unwrap(), vec![soon.id]);
+
+    // The background thread keeps the tracker, and gives it back when stopped.
+    let running = tracker.spawn(Duration::from_millis(10));
+    assert_eq!(running.with(|tracker| tracker.pending().len()), 1);
+    let tracker = running.stop();
+    assert_eq!(tracker.pending(), &[later]);
+}